@@ -87,6 +87,8 @@ impl<T: Serialize + Type> ApiResponse for Bcs<T> {
                 content: vec![MetaMediaType {
                     content_type: Self::CONTENT_TYPE,
                     schema: Self::schema_ref(),
+                    examples: vec![],
+                    encoding: vec![],
                 }],
                 headers: vec![],
             }],
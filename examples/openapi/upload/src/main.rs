@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use poem::{error::BadRequest, listener::TcpListener, Result, Route, Server};
 use poem_openapi::{
     param::Path,
-    payload::{Attachment, AttachmentType, Json},
+    payload::{Attachment, Json},
     types::multipart::Upload,
     ApiResponse, Multipart, Object, OpenApi, OpenApiService,
 };
@@ -69,8 +69,7 @@ impl Api {
         let status = self.status.lock().await;
         match status.files.get(&id) {
             Some(file) => {
-                let mut attachment =
-                    Attachment::new(file.data.clone()).attachment_type(AttachmentType::Attachment);
+                let mut attachment = Attachment::new(file.data.clone());
                 if let Some(filename) = &file.filename {
                     attachment = attachment.filename(filename);
                 }
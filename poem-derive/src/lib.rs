@@ -11,7 +11,10 @@ mod utils;
 
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, FnArg, GenericParam, ItemFn, Member, Result};
+use syn::{
+    ext::IdentExt, parse_macro_input, parse_quote, visit::Visit, Attribute, Data, DeriveInput,
+    Fields, FnArg, GenericParam, ItemFn, Lifetime, LitInt, LitStr, Member, Result, TypeImplTrait,
+};
 
 /// Wrap an asynchronous function as an `Endpoint`.
 ///
@@ -22,25 +25,54 @@ use syn::{parse_macro_input, FnArg, GenericParam, ItemFn, Member, Result};
 /// async fn example() {
 /// }
 /// ```
+///
+/// Generic functions are also supported, the endpoint is created with
+/// `Default::default`:
+///
+/// ```ignore
+/// #[handler]
+/// async fn respond<T: Serialize + Clone + Send + Sync + 'static>(data: Data<&T>) -> Json<T> {
+///     Json(data.0.clone())
+/// }
+///
+/// let ep = respond::<Config>::default();
+/// ```
+///
+/// # Blocking handlers
+///
+/// With `#[handler(blocking)]`, the synchronous function is executed with
+/// `tokio::task::spawn_blocking` after the extractors have been executed, so
+/// that CPU-heavy or blocking-IO code does not stall the async runtime. The
+/// arguments and the return value must be `Send + 'static`.
+///
+/// ```ignore
+/// #[handler(blocking)]
+/// fn resize(data: Bytes) -> Result<Vec<u8>> {
+///     ...
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn handler(args: TokenStream, input: TokenStream) -> TokenStream {
     let mut internal = false;
+    let mut blocking = false;
 
     let arg_parser = syn::meta::parser(|meta| {
         if meta.path.is_ident("internal") {
             internal = true;
+        } else if meta.path.is_ident("blocking") {
+            blocking = true;
         }
         Ok(())
     });
     parse_macro_input!(args with arg_parser);
 
-    match generate_handler(internal, input) {
+    match generate_handler(internal, blocking, input) {
         Ok(stream) => stream,
         Err(err) => err.into_compile_error().into(),
     }
 }
 
-fn generate_handler(internal: bool, input: TokenStream) -> Result<TokenStream> {
+fn generate_handler(internal: bool, blocking: bool, input: TokenStream) -> Result<TokenStream> {
     let crate_name = utils::get_crate_name(internal);
     let item_fn = syn::parse::<ItemFn>(input)?;
     let (impl_generics, type_generics, where_clause) = item_fn.sig.generics.split_for_impl();
@@ -57,31 +89,69 @@ fn generate_handler(internal: bool, input: TokenStream) -> Result<TokenStream> {
     } else {
         None
     };
+    if blocking && item_fn.sig.asyncness.is_some() {
+        return Err(syn::Error::new_spanned(
+            item_fn.sig.asyncness,
+            "blocking handlers must not be async",
+        ));
+    }
 
     let def_struct = if !item_fn.sig.generics.params.is_empty() {
+        if let Some(lifetime) = item_fn.sig.generics.lifetimes().next() {
+            return Err(syn::Error::new_spanned(
+                lifetime,
+                "handlers with lifetime parameters are not supported",
+            ));
+        }
+
         let members = item_fn
             .sig
             .generics
-            .params
-            .iter()
-            .filter_map(|param| match param {
-                GenericParam::Type(ty) => Some(ty),
-                _ => None,
-            })
+            .type_params()
             .enumerate()
             .map(|(idx, ty)| {
                 let ty_ident = &ty.ident;
                 let ident = format_ident!("_mark{}", idx);
-                quote! { #ident: ::std::marker::PhantomData<#ty_ident> }
-            });
+                (
+                    quote! { #ident: ::std::marker::PhantomData<fn() -> #ty_ident> },
+                    quote! { #ident: ::std::marker::PhantomData },
+                )
+            })
+            .collect::<Vec<_>>();
+        let fields = members.iter().map(|(field, _)| field);
+        let inits = members.iter().map(|(_, init)| init);
         quote! {
-            #[derive(Default)]
-            #vis struct #ident #type_generics { #(#members),*}
+            #vis struct #ident #impl_generics #where_clause { #(#fields),* }
+
+            impl #impl_generics ::std::default::Default for #ident #type_generics #where_clause {
+                fn default() -> Self {
+                    Self { #(#inits),* }
+                }
+            }
         }
     } else {
         quote! { #vis struct #ident; }
     };
 
+    // explicitly specifies the generic arguments, since they can not always be
+    // inferred from the arguments
+    let generic_args = item_fn
+        .sig
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(ty) => Some(ty.ident.clone()),
+            GenericParam::Const(cp) => Some(cp.ident.clone()),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let turbofish = if !generic_args.is_empty() && !has_impl_trait_args(&item_fn) {
+        Some(quote! { ::<#(#generic_args),*> })
+    } else {
+        None
+    };
+
     let mut extractors = Vec::new();
     let mut args = Vec::new();
     for (idx, input) in item_fn.sig.inputs.clone().into_iter().enumerate() {
@@ -95,6 +165,28 @@ fn generate_handler(internal: bool, input: TokenStream) -> Result<TokenStream> {
         }
     }
 
+    let call = if blocking {
+        quote! {
+            let res = #crate_name::__private::tokio::task::spawn_blocking(move || #ident #turbofish(#(#args),*)).await;
+            let res = match res {
+                ::std::result::Result::Ok(res) => res,
+                ::std::result::Result::Err(err) => match err.try_into_panic() {
+                    ::std::result::Result::Ok(payload) => ::std::panic::resume_unwind(payload),
+                    ::std::result::Result::Err(err) => {
+                        return ::std::result::Result::Err(#crate_name::Error::new(
+                            err,
+                            #crate_name::http::StatusCode::INTERNAL_SERVER_ERROR,
+                        ));
+                    }
+                },
+            };
+        }
+    } else {
+        quote! {
+            let res = #ident #turbofish(#(#args),*)#call_await;
+        }
+    };
+
     let expanded = quote! {
         #(#docs)*
         #[allow(non_camel_case_types)]
@@ -103,14 +195,144 @@ fn generate_handler(internal: bool, input: TokenStream) -> Result<TokenStream> {
         impl #impl_generics #crate_name::Endpoint for #ident #type_generics #where_clause {
             type Output = #crate_name::Response;
 
-            #[allow(unused_mut)]
+            #[allow(unused_mut, clippy::result_large_err)]
             async fn call(&self, mut req: #crate_name::Request) -> #crate_name::Result<Self::Output> {
                 let (req, mut body) = req.split();
                 #(#extractors)*
                 #item_fn
-                let res = #ident(#(#args),*)#call_await;
+                #call
                 let res = #crate_name::error::IntoResult::into_result(res);
-                std::result::Result::map(res, #crate_name::IntoResponse::into_response)
+                let res = std::result::Result::map(res, #crate_name::IntoResponse::into_response);
+                #crate_name::__private::apply_response_data(&req, res)
+            }
+        }
+    };
+
+    Ok(expanded.into())
+}
+
+/// Derive `FromRequest` for a struct whose fields are extractors.
+///
+/// Each field is extracted in order with its own `FromRequest`
+/// implementation. When an extraction fails, its error is returned with the
+/// name of the field prepended to the error message, while the response of
+/// the error is unchanged. At most one field can extract the body.
+///
+/// If the struct has a lifetime parameter, the first one is used as the
+/// lifetime of the request.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(FromRequest)]
+/// struct ListUsers<'a> {
+///     org: Path<String>,
+///     params: Query<Pagination>,
+///     db: Data<&'a Db>,
+///     user_agent: TypedHeader<UserAgent>,
+/// }
+///
+/// #[handler]
+/// async fn list_users(req: ListUsers<'_>) -> Json<Vec<User>> {
+///     ...
+/// }
+/// ```
+#[proc_macro_derive(FromRequest, attributes(from_request))]
+pub fn derive_from_request(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match generate_from_request(input) {
+        Ok(stream) => stream,
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn generate_from_request(input: DeriveInput) -> Result<TokenStream> {
+    let mut internal = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("from_request"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("internal") {
+                internal = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute"))
+            }
+        })?;
+    }
+
+    let crate_name = utils::get_crate_name(internal);
+    let ident = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "FromRequest can only be derived for structs",
+        ));
+    };
+
+    let mut generics = input.generics.clone();
+    let lifetime = match input.generics.lifetimes().next() {
+        Some(param) => param.lifetime.clone(),
+        None => {
+            let lifetime = Lifetime::new("'__poem_req", proc_macro2::Span::call_site());
+            generics.params.insert(0, parse_quote!(#lifetime));
+            lifetime
+        }
+    };
+    if input.generics.type_params().next().is_some() {
+        let where_clause = generics.make_where_clause();
+        for field in &data.fields {
+            let ty = &field.ty;
+            where_clause
+                .predicates
+                .push(parse_quote!(#ty: #crate_name::FromRequest<#lifetime>));
+        }
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, type_generics, _) = input.generics.split_for_impl();
+
+    let extract = |ty: &syn::Type, name: String| {
+        quote! {
+            match <#ty as #crate_name::FromRequest<#lifetime>>::from_request(req, body).await {
+                ::std::result::Result::Ok(value) => value,
+                ::std::result::Result::Err(mut err) => {
+                    let msg = ::std::format!("failed to extract `{}`: {}", #name, err);
+                    err.set_error_message(msg);
+                    return ::std::result::Result::Err(err);
+                }
+            }
+        }
+    };
+    let construct = match &data.fields {
+        Fields::Named(fields) => {
+            let fields = fields.named.iter().map(|field| {
+                let field_ident = field.ident.as_ref().unwrap();
+                let value = extract(&field.ty, field_ident.to_string());
+                quote! { #field_ident: #value }
+            });
+            quote! { Self { #(#fields),* } }
+        }
+        Fields::Unnamed(fields) => {
+            let fields = fields
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(idx, field)| extract(&field.ty, idx.to_string()));
+            quote! { Self(#(#fields),*) }
+        }
+        Fields::Unit => quote! { Self },
+    };
+
+    let expanded = quote! {
+        impl #impl_generics #crate_name::FromRequest<#lifetime> for #ident #type_generics #where_clause {
+            #[allow(unused_variables)]
+            async fn from_request(
+                req: &#lifetime #crate_name::Request,
+                body: &mut #crate_name::RequestBody,
+            ) -> #crate_name::Result<Self> {
+                ::std::result::Result::Ok(#construct)
             }
         }
     };
@@ -118,6 +340,394 @@ fn generate_handler(internal: bool, input: TokenStream) -> Result<TokenStream> {
     Ok(expanded.into())
 }
 
+/// Derive `IntoResponse` for an enum whose variants are the possible
+/// responses.
+///
+/// # Variant attributes
+///
+/// - `#[response(status = 404)]` sets the status code of the response.
+/// - `#[response(content_type = "text/plain")]` sets the `Content-Type`
+///   header of the response.
+///
+/// # Field attributes
+///
+/// - `#[response(header = "X-Name")]` uses the field, which must implement
+///   `Display`, as the value of the header. The header is skipped if the value
+///   is not a valid header value.
+///
+/// The field without attribute, if any, is the body, which must implement
+/// `IntoResponse`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(IntoResponse)]
+/// enum CreateUserResponse {
+///     #[response(status = 201)]
+///     Created(Json<User>, #[response(header = "Location")] String),
+///     #[response(status = 409, content_type = "text/plain")]
+///     Conflict(String),
+///     #[response(status = 400)]
+///     BadRequest,
+/// }
+/// ```
+#[proc_macro_derive(IntoResponse, attributes(response))]
+pub fn derive_into_response(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match generate_into_response(input) {
+        Ok(stream) => stream,
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct ResponseArgs {
+    internal: bool,
+    status: Option<u16>,
+    content_type: Option<LitStr>,
+    header: Option<String>,
+}
+
+fn parse_response_args(attrs: &[Attribute]) -> Result<ResponseArgs> {
+    let mut args = ResponseArgs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("response")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("internal") {
+                args.internal = true;
+            } else if meta.path.is_ident("status") {
+                let lit: LitInt = meta.value()?.parse()?;
+                let status = lit.base10_parse::<u16>()?;
+                if !(100..1000).contains(&status) {
+                    return Err(syn::Error::new_spanned(lit, "invalid status code"));
+                }
+                args.status = Some(status);
+            } else if meta.path.is_ident("content_type") {
+                let lit: LitStr = meta.value()?.parse()?;
+                // the same check as `HeaderValue::from_static`
+                if !lit
+                    .value()
+                    .bytes()
+                    .all(|b| b == b'\t' || (0x20..0x7f).contains(&b))
+                {
+                    return Err(syn::Error::new_spanned(lit, "invalid content type"));
+                }
+                args.content_type = Some(lit);
+            } else if meta.path.is_ident("header") {
+                let lit: LitStr = meta.value()?.parse()?;
+                let name = lit.value().to_ascii_lowercase();
+                if name.is_empty()
+                    || !name
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+                {
+                    return Err(syn::Error::new_spanned(lit, "invalid header name"));
+                }
+                args.header = Some(name);
+            } else {
+                return Err(meta.error("unsupported attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(args)
+}
+
+fn generate_into_response(input: DeriveInput) -> Result<TokenStream> {
+    let crate_name = utils::get_crate_name(parse_response_args(&input.attrs)?.internal);
+    let ident = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "IntoResponse can only be derived for enums",
+        ));
+    };
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let variant_ident = &variant.ident;
+        let args = parse_response_args(&variant.attrs)?;
+        let mut bindings = Vec::new();
+        let mut body = None;
+        let mut headers = Vec::new();
+
+        for (idx, field) in variant.fields.iter().enumerate() {
+            let binding = format_ident!("f{}", idx);
+            match parse_response_args(&field.attrs)?.header {
+                Some(name) => headers.push(quote! {
+                    if let ::std::result::Result::Ok(value) = #crate_name::http::HeaderValue::from_str(&::std::string::ToString::to_string(&#binding)) {
+                        resp.headers_mut().insert(#crate_name::http::header::HeaderName::from_static(#name), value);
+                    }
+                }),
+                None if body.is_none() => body = Some(binding.clone()),
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        "only one field can be the body, use `#[response(header = \"...\")]` for headers",
+                    ))
+                }
+            }
+            bindings.push(match &field.ident {
+                Some(field_ident) => quote! { #field_ident: #binding },
+                None => quote! { #binding },
+            });
+        }
+
+        let pattern = match &variant.fields {
+            Fields::Named(_) => quote! { #ident::#variant_ident { #(#bindings),* } },
+            Fields::Unnamed(_) => quote! { #ident::#variant_ident(#(#bindings),*) },
+            Fields::Unit => quote! { #ident::#variant_ident },
+        };
+        let create_response = match body {
+            Some(body) => quote! { #crate_name::IntoResponse::into_response(#body) },
+            None => quote! { #crate_name::Response::default() },
+        };
+        let set_status = args.status.map(|status| {
+            quote! {
+                resp.set_status(#crate_name::http::StatusCode::from_u16(#status).unwrap());
+            }
+        });
+        let set_content_type = args.content_type.map(|content_type| {
+            quote! {
+                resp.headers_mut().insert(
+                    #crate_name::http::header::CONTENT_TYPE,
+                    #crate_name::http::HeaderValue::from_static(#content_type),
+                );
+            }
+        });
+
+        arms.push(quote! {
+            #pattern => {
+                let mut resp = #create_response;
+                #set_status
+                #set_content_type
+                #(#headers)*
+                resp
+            }
+        });
+    }
+
+    let expanded = quote! {
+        impl #impl_generics #crate_name::IntoResponse for #ident #type_generics #where_clause {
+            #[allow(unused_mut)]
+            fn into_response(self) -> #crate_name::Response {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    };
+
+    Ok(expanded.into())
+}
+
+/// Derive `FromRequest` for a struct parsed from a `multipart/form-data`
+/// body, each field of the struct is parsed from the multipart field with
+/// the same name.
+///
+/// The types of the fields must implement `poem::web::FromMultipartField`,
+/// such as `String`, `Bytes`, the primitive types and `UploadedFile`.
+/// `Option<T>` fields are optional, and `Vec<T>` fields collect the repeated
+/// fields. The unknown fields are ignored.
+///
+/// The whole multipart body is limited to 2 MiB by default, the request
+/// fails with `413 Payload Too Large` if it is larger.
+///
+/// # Struct attributes
+///
+/// - `#[multipart(deny_unknown_fields)]` rejects the unknown fields.
+/// - `#[multipart(limit = 10485760)]` sets the maximum size of the multipart
+///   body in bytes.
+///
+/// # Field attributes
+///
+/// - `#[multipart(rename = "name")]` sets the name of the multipart field.
+/// - `#[multipart(default)]` uses `Default::default()` if the field is
+///   missing.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(FromMultipart)]
+/// struct Upload {
+///     title: String,
+///     #[multipart(rename = "tag")]
+///     tags: Vec<String>,
+///     file: UploadedFile,
+/// }
+///
+/// #[handler]
+/// async fn upload(upload: Upload) -> String {
+///     format!("{}: {} bytes", upload.title, upload.file.size())
+/// }
+/// ```
+#[proc_macro_derive(FromMultipart, attributes(multipart))]
+pub fn derive_from_multipart(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match generate_from_multipart(input) {
+        Ok(stream) => stream,
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct MultipartArgs {
+    internal: bool,
+    deny_unknown_fields: bool,
+    rename: Option<String>,
+    default: bool,
+    limit: Option<u64>,
+}
+
+fn parse_multipart_args(attrs: &[Attribute]) -> Result<MultipartArgs> {
+    let mut args = MultipartArgs::default();
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("multipart"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("internal") {
+                args.internal = true;
+            } else if meta.path.is_ident("deny_unknown_fields") {
+                args.deny_unknown_fields = true;
+            } else if meta.path.is_ident("rename") {
+                let lit: LitStr = meta.value()?.parse()?;
+                args.rename = Some(lit.value());
+            } else if meta.path.is_ident("default") {
+                args.default = true;
+            } else if meta.path.is_ident("limit") {
+                let lit: LitInt = meta.value()?.parse()?;
+                args.limit = Some(lit.base10_parse()?);
+            } else {
+                return Err(meta.error("unsupported attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(args)
+}
+
+fn generate_from_multipart(input: DeriveInput) -> Result<TokenStream> {
+    let args = parse_multipart_args(&input.attrs)?;
+    let crate_name = utils::get_crate_name(args.internal);
+    let limit = args.limit.unwrap_or(2 * 1024 * 1024);
+    let ident = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "FromMultipart can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "FromMultipart can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut generics = input.generics.clone();
+    let lifetime = Lifetime::new("'__poem_req", proc_macro2::Span::call_site());
+    generics.params.insert(0, parse_quote!(#lifetime));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, type_generics, _) = input.generics.split_for_impl();
+
+    let mut values = Vec::new();
+    let mut parse_fields = Vec::new();
+    let mut finish_fields = Vec::new();
+
+    for field in &fields.named {
+        let field_args = parse_multipart_args(&field.attrs)?;
+        let field_ident = field.ident.as_ref().unwrap();
+        let field_ty = &field.ty;
+        let field_name = field_args
+            .rename
+            .unwrap_or_else(|| field_ident.unraw().to_string());
+        let value = format_ident!("__poem_value_{}", field_ident.unraw());
+
+        parse_fields.push(quote! {
+            ::std::option::Option::Some(#field_name) => {
+                #value = ::std::option::Option::Some(match #value.take() {
+                    ::std::option::Option::Some(value) => {
+                        <#field_ty as #crate_name::web::FromMultipartField>::merge_repeated_field(value, __poem_field).await?
+                    }
+                    ::std::option::Option::None => {
+                        <#field_ty as #crate_name::web::FromMultipartField>::from_field(__poem_field).await?
+                    }
+                });
+            }
+        });
+
+        let missing = if field_args.default {
+            quote!(<#field_ty as ::std::default::Default>::default())
+        } else {
+            quote!(<#field_ty as #crate_name::web::FromMultipartField>::from_missing(#field_name)?)
+        };
+        finish_fields.push(quote! {
+            #field_ident: match #value {
+                ::std::option::Option::Some(value) => value,
+                ::std::option::Option::None => #missing,
+            }
+        });
+        values.push(value);
+    }
+
+    let unknown_field = if args.deny_unknown_fields {
+        quote! {
+            ::std::option::Option::Some(name) => {
+                return ::std::result::Result::Err(::std::convert::Into::into(
+                    #crate_name::error::ParseMultipartError::InvalidField {
+                        name: ::std::string::ToString::to_string(name),
+                        reason: ::std::string::ToString::to_string("unknown field"),
+                    },
+                ));
+            }
+            ::std::option::Option::None => {}
+        }
+    } else {
+        quote!(_ => {})
+    };
+
+    let expanded = quote! {
+        impl #impl_generics #crate_name::FromRequest<#lifetime> for #ident #type_generics #where_clause {
+            async fn from_request(
+                req: &#lifetime #crate_name::Request,
+                body: &mut #crate_name::RequestBody,
+            ) -> #crate_name::Result<Self> {
+                let mut __poem_multipart = #crate_name::web::Multipart::from_request_limit(req, body, #limit).await?;
+                #(let mut #values = ::std::option::Option::None;)*
+                while let ::std::option::Option::Some(__poem_field) = __poem_multipart.next_field().await? {
+                    let __poem_name = __poem_field.name().map(::std::string::ToString::to_string);
+                    match __poem_name.as_deref() {
+                        #(#parse_fields)*
+                        #unknown_field
+                    }
+                }
+                ::std::result::Result::Ok(Self { #(#finish_fields),* })
+            }
+        }
+    };
+
+    Ok(expanded.into())
+}
+
+fn has_impl_trait_args(item_fn: &ItemFn) -> bool {
+    struct FindImplTrait(bool);
+
+    impl<'ast> Visit<'ast> for FindImplTrait {
+        fn visit_type_impl_trait(&mut self, _: &'ast TypeImplTrait) {
+            self.0 = true;
+        }
+    }
+
+    let mut find = FindImplTrait(false);
+    for input in &item_fn.sig.inputs {
+        if let FnArg::Typed(pat) = input {
+            find.visit_type(&pat.ty);
+        }
+    }
+    find.0
+}
+
 #[doc(hidden)]
 #[proc_macro]
 pub fn generate_implement_middlewares(_: TokenStream) -> TokenStream {
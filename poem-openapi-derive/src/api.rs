@@ -1,20 +1,23 @@
+use std::collections::HashSet;
+
 use darling::{util::SpannedValue, FromMeta};
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use syn::{
-    ext::IdentExt, visit_mut::VisitMut, Error, Expr, FnArg, ImplItem, ImplItemFn, ItemImpl, Pat,
-    Path, ReturnType, Type,
+    ext::IdentExt, parse_quote, visit_mut::VisitMut, Error, Expr, FnArg, ImplItem, ImplItemFn,
+    ItemImpl, Pat, Path, ReturnType, Type,
 };
 
 use crate::{
     common_args::{
-        APIMethod, CodeSample, DefaultValue, ExampleValue, ExternalDocument, ExtraHeader,
+        extensions_meta, APIMethod, Callback, CodeSample, DefaultValue, ExampleValue, Extension,
+        ExternalDocument, ExtraHeader, Server,
     },
     error::GeneratorResult,
     utils::{
-        convert_oai_path, get_crate_name, get_description, get_summary_and_description,
-        optional_literal, optional_literal_string, parse_oai_attrs, remove_description,
-        remove_oai_attrs, RemoveLifetime,
+        convert_oai_path, get_crate_name, get_description, get_option_inner_type,
+        get_summary_and_description, optional_literal, optional_literal_string, parse_oai_attrs,
+        remove_description, remove_oai_attrs, RemoveLifetime,
     },
     validators::Validators,
 };
@@ -43,7 +46,9 @@ struct APIOperation {
     #[darling(default, multiple, rename = "tag")]
     tags: Vec<Path>,
     #[darling(default)]
-    transform: Option<Ident>,
+    transform: Option<Path>,
+    #[darling(default, multiple, rename = "middleware")]
+    middlewares: Vec<Expr>,
     #[darling(default)]
     operation_id: Option<String>,
     #[darling(default)]
@@ -57,7 +62,15 @@ struct APIOperation {
     #[darling(default, multiple, rename = "code_sample")]
     code_samples: Vec<CodeSample>,
     #[darling(default)]
+    curl_example: bool,
+    #[darling(default)]
     hidden: bool,
+    #[darling(default, multiple, rename = "extension")]
+    extensions: Vec<Extension>,
+    #[darling(default, multiple, rename = "server")]
+    servers: Vec<Server>,
+    #[darling(default, multiple, rename = "callback")]
+    callbacks: Vec<Callback>,
 }
 
 #[derive(FromMeta, Default)]
@@ -69,12 +82,14 @@ struct APIOperationParam {
     deprecated: bool,
     #[darling(default)]
     default: Option<DefaultValue>,
-    #[darling(default)]
-    example: Option<ExampleValue>,
+    #[darling(default, multiple, rename = "example")]
+    examples: Vec<ExampleValue>,
     #[darling(default)]
     validator: Option<Validators>,
     #[darling(default)]
     explode: Option<bool>,
+    #[darling(default, multiple, rename = "extension")]
+    extensions: Vec<Extension>,
 
     // for oauth
     #[darling(multiple, default, rename = "scope")]
@@ -85,6 +100,8 @@ struct Context {
     add_routes: Vec<TokenStream>,
     operations: Vec<(TokenStream, TokenStream)>,
     register_items: Vec<TokenStream>,
+    operation_ids: HashSet<String>,
+    type_name: Option<String>,
 }
 
 pub(crate) fn generate(args: APIArgs, mut item_impl: ItemImpl) -> GeneratorResult<TokenStream> {
@@ -95,6 +112,15 @@ pub(crate) fn generate(args: APIArgs, mut item_impl: ItemImpl) -> GeneratorResul
         add_routes: Default::default(),
         operations: Default::default(),
         register_items: Default::default(),
+        operation_ids: Default::default(),
+        type_name: match &*item_impl.self_ty {
+            Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.unraw().to_string()),
+            _ => None,
+        },
     };
 
     for item in &mut item_impl.items {
@@ -116,6 +142,7 @@ pub(crate) fn generate(args: APIArgs, mut item_impl: ItemImpl) -> GeneratorResul
         add_routes,
         operations,
         register_items,
+        ..
     } = ctx;
 
     let paths = {
@@ -174,13 +201,18 @@ fn generate_operation(
         deprecated,
         tags,
         transform,
+        middlewares,
         operation_id,
         external_docs,
         response_headers,
         request_headers,
         actual_type,
         code_samples,
+        curl_example,
         hidden,
+        extensions,
+        servers,
+        callbacks,
     } = args;
     if methods.is_empty() {
         return Err(Error::new_spanned(
@@ -190,6 +222,44 @@ fn generate_operation(
         .into());
     }
     let fn_ident = &item_method.sig.ident;
+
+    // the operation id defaults to the type name and the function name, suffixed
+    // with the method if the operation has more than one method
+    let default_operation_id = match &ctx.type_name {
+        Some(type_name) => format!("{}_{}", type_name, fn_ident.unraw()),
+        None => fn_ident.unraw().to_string(),
+    };
+    let operation_ids = match &operation_id {
+        Some(operation_id) if methods.len() > 1 => {
+            return Err(Error::new_spanned(
+                fn_ident,
+                "The `operation_id` cannot be shared by multiple methods.",
+            )
+            .into());
+        }
+        Some(operation_id) => vec![operation_id.clone()],
+        None if methods.len() > 1 => methods
+            .iter()
+            .map(|method| {
+                format!(
+                    "{}_{}",
+                    default_operation_id,
+                    method.to_http_method().to_string().to_lowercase()
+                )
+            })
+            .collect(),
+        None => vec![default_operation_id],
+    };
+    for operation_id in &operation_ids {
+        if !hidden && !ctx.operation_ids.insert(operation_id.clone()) {
+            return Err(Error::new_spanned(
+                fn_ident,
+                format!("Duplicate operation id `{operation_id}`."),
+            )
+            .into());
+        }
+    }
+
     let (summary, description) = get_summary_and_description(&item_method.attrs)?;
     let summary = optional_literal(&summary);
     let description = optional_literal(&description);
@@ -280,7 +350,13 @@ fn generate_operation(
             .name
             .clone()
             .unwrap_or_else(|| arg_ident.unraw().to_string());
-        use_args.push(pname.clone());
+        match get_option_inner_type(&arg_ty).cloned() {
+            Some(inner_ty) => {
+                arg_ty = parse_quote!(#crate_name::__private::OptionalExtractor<#inner_ty>);
+                use_args.push(quote!(#pname.0));
+            }
+            None => use_args.push(quote!(#pname)),
+        }
 
         if !hidden {
             // register arg type
@@ -310,25 +386,44 @@ fn generate_operation(
             None => quote!(::std::option::Option::None),
         };
 
-        // example value for parameter
-        let example_value = match &operation_param.example {
+        // example value for parameter, the named examples are used for the request
+        // body
+        let mut param_example = None;
+        let mut body_examples = Vec::new();
+        for example in &operation_param.examples {
+            match example {
+                ExampleValue::Named(_) => {
+                    body_examples.extend(example.to_media_example(crate_name, body_examples.len()));
+                }
+                _ if param_example.is_none() => param_example = Some(example),
+                _ => {
+                    return Err(Error::new_spanned(
+                        &arg_ident,
+                        "Only one parameter example is allowed, use `example(name = \"...\", value = \"...\")` for the request body.",
+                    )
+                    .into())
+                }
+            }
+        }
+
+        let example_value = match param_example {
             Some(ExampleValue::Default) => {
                 quote!(::std::option::Option::Some(<<#arg_ty as #crate_name::ApiExtractor>::ParamType as std::default::Default>::default))
             }
             Some(ExampleValue::Function(func_name)) => {
                 quote!(::std::option::Option::Some(#func_name))
             }
-            None => quote!(::std::option::Option::None),
+            Some(ExampleValue::Named(_)) | None => quote!(::std::option::Option::None),
         };
 
-        let param_meta_example = match &operation_param.example {
+        let param_meta_example = match param_example {
             Some(ExampleValue::Default) => {
                 quote!(#crate_name::types::ToJSON::to_json(&<<#arg_ty as #crate_name::ApiExtractor>::ParamType as std::default::Default>::default()))
             }
             Some(ExampleValue::Function(func_name)) => {
                 quote!(#crate_name::types::ToJSON::to_json(&#func_name()))
             }
-            None => quote!(::std::option::Option::None),
+            Some(ExampleValue::Named(_)) | None => quote!(::std::option::Option::None),
         };
 
         // validator
@@ -346,6 +441,7 @@ fn generate_operation(
 
         // do extract
         let explode = operation_param.explode.unwrap_or(true);
+        let scopes = &operation_param.scopes;
 
         parse_args.push(quote! {
             let mut param_opts = #crate_name::ExtractParamOptions {
@@ -353,6 +449,7 @@ fn generate_operation(
                 default_value: #default_value,
                 example_value: #example_value,
                 explode: #explode,
+                scopes: ::std::vec![#(#crate_name::OAuthScopes::name(&#scopes)),*],
             };
 
             let #pname = match <#arg_ty as #crate_name::ApiExtractor>::from_request(&request, &mut body, param_opts).await {
@@ -370,6 +467,7 @@ fn generate_operation(
         // param meta
         let param_desc = optional_literal_string(&param_description);
         let deprecated = operation_param.deprecated;
+        let param_extensions = extensions_meta(crate_name, &operation_param.extensions)?;
         params_meta.push(quote! {
             if <#arg_ty as #crate_name::ApiExtractor>::TYPES.contains(&#crate_name::ApiExtractorType::Parameter) {
                 let mut original_schema = <#arg_ty as #crate_name::ApiExtractor>::param_schema_ref().unwrap();
@@ -390,27 +488,37 @@ fn generate_operation(
                     required: <#arg_ty as #crate_name::ApiExtractor>::PARAM_IS_REQUIRED && !#has_default,
                     deprecated: #deprecated,
                     explode: #explode,
+                    extensions: #param_extensions,
                 };
                 params.push(meta_param);
             }
         });
 
         // request object meta
+        let update_request_examples = (!body_examples.is_empty()).then(|| {
+            quote! {
+                if let ::std::option::Option::Some(request) = &mut request {
+                    for mt in &mut request.content {
+                        mt.examples.extend([#(#body_examples),*]);
+                    }
+                }
+            }
+        });
         request_meta.push(quote! {
             if <#arg_ty as #crate_name::ApiExtractor>::TYPES.contains(&#crate_name::ApiExtractorType::RequestObject) {
                 request = <#arg_ty as #crate_name::ApiExtractor>::request_meta();
+                #update_request_examples
             }
         });
 
         // security meta
-        let scopes = &operation_param.scopes;
         security.push(quote! {
             if <#arg_ty as #crate_name::ApiExtractor>::TYPES.contains(&#crate_name::ApiExtractorType::SecurityScheme) {
-                for security_name in <#arg_ty as #crate_name::ApiExtractor>::security_schemes() {
-                    security.push(<::std::collections::HashMap<&'static str, ::std::vec::Vec<&'static str>> as ::std::convert::From<_>>::from([
-                        (security_name, ::std::vec![#(#crate_name::OAuthScopes::name(&#scopes)),*])
-                    ]));
-                }
+                #crate_name::__private::add_security_requirements(
+                    &mut security,
+                    <#arg_ty as #crate_name::ApiExtractor>::security_requirements(),
+                    ::std::vec![#(#crate_name::OAuthScopes::name(&#scopes)),*],
+                );
             }
         });
     }
@@ -430,6 +538,16 @@ fn generate_operation(
             let ep = #crate_name::__private::poem::EndpointExt::map_to_response(#transform(ep));
         }
     });
+    let middlewares = middlewares
+        .iter()
+        .map(|middleware| {
+            quote! {
+                let ep = #crate_name::__private::poem::EndpointExt::map_to_response(
+                    #crate_name::__private::poem::EndpointExt::with(ep, #middleware)
+                );
+            }
+        })
+        .collect::<Vec<_>>();
     let update_content_type = match &actual_type {
         Some(actual_type) => quote!(
             resp.headers_mut().insert(#crate_name::__private::poem::http::header::CONTENT_TYPE,
@@ -439,24 +557,48 @@ fn generate_operation(
         None => quote!(),
     };
 
-    for method in &methods {
+    let mut tag_names = Vec::new();
+    for tag in tags {
+        ctx.register_items
+            .push(quote!(#crate_name::Tags::register(&#tag, registry);));
+        tag_names.push(quote!(#crate_name::Tags::name(&#tag)));
+    }
+
+    for (method, operation_id) in methods.iter().zip(&operation_ids) {
         let http_method = method.to_http_method();
-        let set_operation_id = operation_id.as_ref().map(|operation_id| {
-            quote! {
-                let ep = #crate_name::__private::poem::EndpointExt::after(ep, |mut res| async move {
-                    let operator_id = #crate_name::OperationId(#operation_id);
-                    match &mut res {
-                        ::std::result::Result::Ok(resp) => resp.set_data(operator_id),
-                        ::std::result::Result::Err(err) => err.set_data(operator_id),
+        let set_operation_id = quote! {
+            let ep = {
+                let operation_tags = #crate_name::OperationTags(::std::vec![#(#tag_names),*]);
+                #crate_name::__private::poem::EndpointExt::around(ep, move |ep, mut req| {
+                    let operation_id = #crate_name::OperationId(#operation_id);
+                    let operation_tags = ::std::clone::Clone::clone(&operation_tags);
+                    async move {
+                        req.set_data(operation_id);
+                        req.set_data(::std::clone::Clone::clone(&operation_tags));
+                        let mut res = #crate_name::__private::poem::Endpoint::call(&*ep, req).await;
+                        match &mut res {
+                            ::std::result::Result::Ok(resp) => {
+                                resp.set_data(operation_id);
+                                resp.set_data(operation_tags);
+                            }
+                            ::std::result::Result::Err(err) => {
+                                err.set_data(operation_id);
+                                err.set_data(operation_tags);
+                            }
+                        }
+                        res
                     }
-                    res
-                });
-            }
-        });
+                })
+            };
+        };
 
         ctx.add_routes.push(quote! {
-            route_table.entry(#new_path)
-                .or_default()
+            let path = #new_path;
+            let route_methods = route_table.entry(::std::clone::Clone::clone(&path)).or_default();
+            if route_methods.contains_key(&#crate_name::__private::poem::http::Method::#http_method) {
+                ::std::panic!("duplicate operation: {} {}", #crate_name::__private::poem::http::Method::#http_method, path);
+            }
+            route_methods
                 .insert(#crate_name::__private::poem::http::Method::#http_method, {
                     let api_obj = ::std::clone::Clone::clone(&api_obj);
                     let ep = #crate_name::__private::poem::endpoint::make(move |request| {
@@ -476,19 +618,13 @@ fn generate_operation(
                         }
                     });
                     #transform
+                    #(#middlewares)*
                     #set_operation_id
                     #crate_name::__private::poem::EndpointExt::boxed(ep)
                 });
         });
     }
 
-    let mut tag_names = Vec::new();
-    for tag in tags {
-        ctx.register_items
-            .push(quote!(#crate_name::Tags::register(&#tag, registry);));
-        tag_names.push(quote!(#crate_name::Tags::name(&#tag)));
-    }
-    let operation_id = optional_literal(&operation_id);
     let external_docs = match external_docs {
         Some(external_docs) => {
             let s = external_docs.to_token_stream(crate_name);
@@ -517,6 +653,7 @@ fn generate_operation(
                 required: <#ty as #crate_name::types::Type>::IS_REQUIRED,
                 deprecated: #deprecated,
                 explode: true,
+                extensions: ::std::collections::BTreeMap::new(),
             });
         });
     }
@@ -554,6 +691,12 @@ fn generate_operation(
         Some(actual_type) => quote!(<#actual_type as #crate_name::ApiResponse>::meta()),
         None => quote!(<#res_ty as #crate_name::ApiResponse>::meta()),
     };
+    let resp_extensions = match &actual_type {
+        Some(actual_type) => {
+            quote!(<#actual_type as #crate_name::ApiResponse>::operation_extensions())
+        }
+        None => quote!(<#res_ty as #crate_name::ApiResponse>::operation_extensions()),
+    };
 
     let code_samples = code_samples
         .iter()
@@ -568,14 +711,52 @@ fn generate_operation(
                 #crate_name::registry::MetaCodeSample {
                     lang: #lang,
                     label: #label,
-                    source: #source,
+                    source: ::std::borrow::Cow::Borrowed(#source),
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    let extensions = extensions_meta(crate_name, &extensions)?;
+
+    let servers = servers
+        .iter()
+        .map(|Server { url, description }| {
+            let description = optional_literal_string(description);
+            quote! {
+                #crate_name::registry::MetaServer {
+                    url: ::std::string::ToString::to_string(#url),
+                    description: #description,
+                    variables: ::std::collections::BTreeMap::new(),
                 }
             }
         })
         .collect::<Vec<_>>();
 
+    let mut meta_callbacks = Vec::new();
+    for Callback {
+        name,
+        expression,
+        webhook,
+    } in &callbacks
+    {
+        if !hidden {
+            ctx.register_items
+                .push(quote!(<#webhook as #crate_name::Webhook>::register(registry);));
+        }
+        meta_callbacks.push(quote! {
+            #crate_name::registry::MetaCallback {
+                name: #name,
+                expression: #expression,
+                operations: ::std::iter::Iterator::collect(::std::iter::Iterator::map(
+                    ::std::iter::IntoIterator::into_iter(<#webhook as #crate_name::Webhook>::meta()),
+                    |webhook| webhook.operation,
+                )),
+            }
+        });
+    }
+
     if !hidden {
-        for method in &methods {
+        for (method, operation_id) in methods.iter().zip(&operation_ids) {
             let http_method = method.to_http_method();
             let meta_operation = quote! {
                 #crate_name::registry::MetaOperation {
@@ -606,8 +787,16 @@ fn generate_operation(
                         #(#security)*
                         security
                     },
-                    operation_id: #operation_id,
+                    operation_id: ::std::option::Option::Some(#operation_id),
                     code_samples: ::std::vec![#(#code_samples),*],
+                    curl_example: #curl_example,
+                    servers: ::std::vec![#(#servers),*],
+                    callbacks: ::std::vec![#(#meta_callbacks),*],
+                    extensions: {
+                        let mut extensions = #resp_extensions;
+                        ::std::iter::Extend::extend(&mut extensions, #extensions);
+                        extensions
+                    },
                 }
             };
             ctx.operations.push((oai_path.clone(), meta_operation));
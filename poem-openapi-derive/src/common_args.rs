@@ -1,7 +1,9 @@
-use darling::{util::SpannedValue, FromMeta};
+use darling::{ast::NestedMeta, util::SpannedValue, FromMeta};
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Lit, Path};
+use syn::{Error, Lit, Path};
+
+use crate::{error::GeneratorResult, utils::optional_literal};
 
 #[derive(Debug, Copy, Clone, FromMeta)]
 #[allow(clippy::enum_variant_names)]
@@ -159,6 +161,7 @@ impl FromMeta for DefaultValue {
 pub(crate) enum ExampleValue {
     Default,
     Function(Path),
+    Named(MediaExample),
 }
 
 impl FromMeta for ExampleValue {
@@ -172,6 +175,77 @@ impl FromMeta for ExampleValue {
             _ => Err(darling::Error::unexpected_lit_type(value).with_span(value)),
         }
     }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        Ok(ExampleValue::Named(MediaExample::from_list(items)?))
+    }
+}
+
+#[derive(Debug, FromMeta)]
+pub(crate) struct MediaExample {
+    #[darling(default)]
+    name: Option<String>,
+    value: Path,
+    #[darling(default)]
+    summary: Option<String>,
+    #[darling(default)]
+    description: Option<String>,
+}
+
+impl ExampleValue {
+    /// Returns the example of a request or response body, the `Default` form
+    /// is only supported by parameters.
+    ///
+    /// An example without a name is named after its position in the list of
+    /// examples, starting from `example1`.
+    pub(crate) fn to_media_example(
+        &self,
+        crate_name: &TokenStream,
+        idx: usize,
+    ) -> Option<TokenStream> {
+        let default_name = format!("example{}", idx + 1);
+        match self {
+            ExampleValue::Default => None,
+            ExampleValue::Function(func) => Some(media_example(
+                crate_name,
+                &default_name,
+                func,
+                &None,
+                &None,
+            )),
+            ExampleValue::Named(MediaExample {
+                name,
+                value,
+                summary,
+                description,
+            }) => Some(media_example(
+                crate_name,
+                name.as_deref().unwrap_or(&default_name),
+                value,
+                summary,
+                description,
+            )),
+        }
+    }
+}
+
+fn media_example(
+    crate_name: &TokenStream,
+    name: &str,
+    value: &Path,
+    summary: &Option<String>,
+    description: &Option<String>,
+) -> TokenStream {
+    let summary = optional_literal(summary);
+    let description = optional_literal(description);
+    quote! {
+        #crate_name::registry::MetaExample {
+            name: #name,
+            summary: #summary,
+            description: #description,
+            value: #crate_name::types::ToJSON::to_json(&#value()),
+        }
+    }
 }
 
 #[derive(FromMeta, Clone)]
@@ -222,6 +296,68 @@ pub(crate) struct ExtraHeader {
     pub(crate) deprecated: bool,
 }
 
+#[derive(FromMeta)]
+pub(crate) struct Extension {
+    name: SpannedValue<String>,
+    value: SpannedValue<String>,
+}
+
+/// Generates a `BTreeMap<String, Value>` with the specification extensions.
+pub(crate) fn extensions_meta(
+    crate_name: &TokenStream,
+    extensions: &[Extension],
+) -> GeneratorResult<TokenStream> {
+    let mut items = Vec::new();
+
+    for Extension { name, value } in extensions {
+        if !name.starts_with("x-") {
+            return Err(Error::new(
+                name.span(),
+                "The name of the specification extension must begin with `x-`.",
+            )
+            .into());
+        }
+        if let Err(err) = serde_json::from_str::<serde_json::Value>(value) {
+            return Err(Error::new(value.span(), format!("Invalid JSON value: {err}")).into());
+        }
+
+        let name = &**name;
+        let value = &**value;
+        items.push(quote! {
+            extensions.insert(
+                ::std::string::ToString::to_string(#name),
+                #crate_name::__private::serde_json::from_str(#value).unwrap(),
+            );
+        });
+    }
+
+    if items.is_empty() {
+        return Ok(quote!(::std::collections::BTreeMap::new()));
+    }
+
+    Ok(quote! {
+        {
+            let mut extensions = ::std::collections::BTreeMap::new();
+            #(#items)*
+            extensions
+        }
+    })
+}
+
+#[derive(FromMeta)]
+pub(crate) struct Server {
+    pub(crate) url: String,
+    #[darling(default)]
+    pub(crate) description: Option<String>,
+}
+
+#[derive(FromMeta)]
+pub(crate) struct Callback {
+    pub(crate) name: String,
+    pub(crate) expression: String,
+    pub(crate) webhook: syn::Type,
+}
+
 #[derive(FromMeta)]
 pub(crate) struct CodeSample {
     pub(crate) lang: String,
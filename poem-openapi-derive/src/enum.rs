@@ -8,9 +8,11 @@ use quote::quote;
 use syn::{ext::IdentExt, Attribute, DeriveInput, Error, Path};
 
 use crate::{
-    common_args::{apply_rename_rule_variant, ExternalDocument, RenameRule},
+    common_args::{
+        apply_rename_rule_variant, extensions_meta, Extension, ExternalDocument, RenameRule,
+    },
     error::GeneratorResult,
-    utils::{get_crate_name, get_description, optional_literal},
+    utils::{get_crate_name, get_description, get_oai_typename, optional_literal},
 };
 
 #[derive(FromVariant)]
@@ -18,6 +20,7 @@ use crate::{
 struct EnumItem {
     ident: Ident,
     fields: Fields<Ignored>,
+    attrs: Vec<Attribute>,
 
     #[darling(default)]
     rename: Option<String>,
@@ -39,16 +42,20 @@ struct EnumArgs {
     #[darling(default)]
     remote: Option<Path>,
     #[darling(default)]
+    namespace: Option<String>,
+    #[darling(default)]
     deprecated: bool,
     #[darling(default)]
     external_docs: Option<ExternalDocument>,
+    #[darling(default, multiple, rename = "extension")]
+    extensions: Vec<Extension>,
 }
 
 pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
     let args: EnumArgs = EnumArgs::from_derive_input(&args)?;
     let crate_name = get_crate_name(args.internal);
     let ident = &args.ident;
-    let oai_typename = args.rename.clone().unwrap_or_else(|| ident.to_string());
+    let oai_typename = get_oai_typename(ident, &args.rename, &args.namespace);
     let description = get_description(&args.attrs)?;
     let e = match &args.data {
         Data::Enum(e) => e,
@@ -58,6 +65,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
     let mut enum_items = Vec::new();
     let mut ident_to_item = Vec::new();
     let mut item_to_ident = Vec::new();
+    let mut item_descriptions = Vec::new();
 
     for variant in e {
         if !variant.fields.is_empty() {
@@ -77,6 +85,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         });
 
         enum_items.push(quote!(#crate_name::types::ToJSON::to_json(&#ident::#item_ident).unwrap()));
+        item_descriptions.push(get_description(&variant.attrs)?);
         ident_to_item.push(quote!(#ident::#item_ident => #oai_item_name));
         item_to_ident
             .push(quote!(#oai_item_name => ::std::result::Result::Ok(#ident::#item_ident)));
@@ -125,6 +134,21 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         }
         None => quote!(::std::option::Option::None),
     };
+    let mut extensions = extensions_meta(&crate_name, &args.extensions)?;
+    if item_descriptions.iter().any(Option::is_some) {
+        let item_descriptions = item_descriptions
+            .iter()
+            .map(|description| description.as_deref().unwrap_or_default());
+        extensions = quote! {{
+            let mut extensions = #extensions;
+            extensions
+                .entry(::std::string::ToString::to_string("x-enum-descriptions"))
+                .or_insert_with(|| #crate_name::__private::serde_json::Value::Array(::std::vec![
+                    #(#crate_name::__private::serde_json::Value::String(::std::string::ToString::to_string(#item_descriptions))),*
+                ]));
+            extensions
+        }};
+    }
 
     let expanded = quote! {
         impl #crate_name::types::Type for #ident {
@@ -152,6 +176,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     external_docs: #external_docs,
                     deprecated: #deprecated,
                     enum_items: ::std::vec![#(#enum_items),*],
+                    extensions: #extensions,
                     ..#crate_name::registry::MetaSchema::new("string")
                 });
             }
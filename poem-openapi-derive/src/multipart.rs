@@ -28,6 +28,8 @@ struct MultipartField {
     default: Option<DefaultValue>,
     #[darling(default)]
     validator: Option<Validators>,
+    #[darling(default)]
+    content_type: Option<String>,
 }
 
 #[derive(FromDeriveInput)]
@@ -68,6 +70,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
     let mut meta_fields = Vec::new();
     let mut register_fields = Vec::new();
     let mut required_fields = Vec::new();
+    let mut encoding_fields = Vec::new();
 
     for field in &s.fields {
         let field_ident = field.ident.as_ref().unwrap();
@@ -99,8 +102,20 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             }
         }};
 
+        let check_content_type = field.content_type.as_ref().map(|content_type| {
+            quote! {
+                if !#crate_name::__private::multipart_content_type_matches(field.content_type(), #content_type) {
+                    return ::std::result::Result::Err(::std::convert::Into::into(#crate_name::error::MultipartFieldContentTypeError {
+                        name: #field_name,
+                        content_type: ::std::string::ToString::to_string(field.content_type().unwrap_or_default()),
+                    }));
+                }
+            }
+        });
+
         deserialize_fields.push(quote! {
             if field.name() == ::std::option::Option::Some(#field_name) {
+                #check_content_type
                 #field_ident = match #field_ident {
                     ::std::option::Option::Some(value) => {
                         ::std::option::Option::Some(<#field_ty as #crate_name::types::ParseFromMultipartField>::parse_from_repeated_field(value, field).await.map_err(|err| #parse_err )?)
@@ -188,6 +203,21 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 fields.push(#field_name);
             }
         });
+
+        let field_content_type = match &field.content_type {
+            Some(content_type) => quote!(::std::option::Option::Some(#content_type)),
+            None => {
+                quote!(<#field_ty as #crate_name::types::ParseFromMultipartField>::content_type())
+            }
+        };
+        encoding_fields.push(quote! {
+            if let ::std::option::Option::Some(content_type) = #field_content_type {
+                encoding.push(#crate_name::registry::MetaEncoding {
+                    name: #field_name,
+                    content_type,
+                });
+            }
+        });
     }
 
     let extractor_impl_generics = {
@@ -274,6 +304,12 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     content: ::std::vec![#crate_name::registry::MetaMediaType {
                         content_type: <Self as #crate_name::payload::Payload>::CONTENT_TYPE,
                         schema: <Self as #crate_name::payload::Payload>::schema_ref(),
+                        examples: ::std::vec![],
+                        encoding: {
+                            let mut encoding = ::std::vec::Vec::new();
+                            #(#encoding_fields)*
+                            encoding
+                        },
                     }],
                     required: <Self as #crate_name::payload::ParsePayload>::IS_REQUIRED,
                 })
@@ -10,9 +10,7 @@ use syn::{Attribute, DeriveInput, Error, Generics, Type};
 use crate::{
     common_args::ExternalDocument,
     error::GeneratorResult,
-    utils::{
-        get_crate_name, get_summary_and_description, optional_literal, optional_literal_string,
-    },
+    utils::{get_crate_name, get_title_and_description, optional_literal, optional_literal_string},
 };
 
 #[derive(FromDeriveInput)]
@@ -39,6 +37,12 @@ struct NewTypeArgs {
     external_docs: Option<ExternalDocument>,
     #[darling(default)]
     example: bool,
+    #[darling(default)]
+    const_value: Option<String>,
+    #[darling(default)]
+    title: Option<String>,
+    #[darling(default)]
+    title_from_docs: bool,
 }
 
 const fn default_true() -> bool {
@@ -50,8 +54,9 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
     let crate_name = get_crate_name(args.internal);
     let (impl_generics, ty_generics, where_clause) = args.generics.split_for_impl();
     let ident = &args.ident;
-    let (summary, description) = get_summary_and_description(&args.attrs)?;
-    let summary = optional_literal_string(&summary);
+    let (title, description) =
+        get_title_and_description(&args.attrs, &args.title, args.title_from_docs)?;
+    let title = optional_literal_string(&title);
     let description = optional_literal(&description);
 
     let fields = match &args.data {
@@ -90,12 +95,28 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         quote!(None)
     };
 
+    let enum_items = match &args.const_value {
+        Some(value) => quote! {
+            ::std::vec![#crate_name::__private::serde_json::Value::String(::std::string::ToString::to_string(#value))]
+        },
+        None => quote!(::std::vec::Vec::new()),
+    };
+    let check_const = args.const_value.as_ref().map(|value| {
+        quote! {
+            let validator = #crate_name::validation::ConstValue::new(#value);
+            if !#crate_name::validation::Validator::check(&validator, &value) {
+                return ::std::result::Result::Err(#crate_name::types::ParseError::custom(::std::format!("verification failed. {}", validator)));
+            }
+        }
+    });
+
     let schema_ref = quote! {
         <#inner_ty as #crate_name::types::Type>::schema_ref().merge(#crate_name::registry::MetaSchema {
-            title: #summary,
+            title: #title,
             description: #description,
             external_docs: #external_docs,
             example: #example,
+            enum_items: #enum_items,
             ..#crate_name::registry::MetaSchema::ANY
         })
     };
@@ -105,6 +126,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             impl #impl_generics #crate_name::types::ParseFromJSON for #ident #ty_generics #where_clause {
                 fn parse_from_json(value: ::std::option::Option<#crate_name::__private::serde_json::Value>) -> #crate_name::types::ParseResult<Self> {
                     let value = ::std::result::Result::map_err(<#inner_ty as #crate_name::types::ParseFromJSON>::parse_from_json(value), poem_openapi::types::ParseError::propagate)?;
+                    #check_const
                     ::std::result::Result::Ok(#ident(value))
                 }
             }
@@ -118,6 +140,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             impl #impl_generics #crate_name::types::ParseFromParameter for #ident #ty_generics #where_clause {
                 fn parse_from_parameter(value: &str) -> #crate_name::types::ParseResult<Self> {
                     let value = ::std::result::Result::map_err(<#inner_ty as #crate_name::types::ParseFromParameter>::parse_from_parameter(value), poem_openapi::types::ParseError::propagate)?;
+                    #check_const
                     ::std::result::Result::Ok(#ident(value))
                 }
 
@@ -125,6 +148,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     iter: I,
                 ) -> #crate_name::types::ParseResult<Self> {
                     let value = ::std::result::Result::map_err(<#inner_ty as #crate_name::types::ParseFromParameter>::parse_from_parameters(iter), poem_openapi::types::ParseError::propagate)?;
+                    #check_const
                     ::std::result::Result::Ok(#ident(value))
                 }
             }
@@ -138,11 +162,13 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             impl #impl_generics #crate_name::types::ParseFromMultipartField for #ident #ty_generics #where_clause {
                 async fn parse_from_multipart(field: ::std::option::Option<#crate_name::__private::poem::web::Field>) -> #crate_name::types::ParseResult<Self> {
                     let value = ::std::result::Result::map_err(<#inner_ty as #crate_name::types::ParseFromMultipartField>::parse_from_multipart(field).await, poem_openapi::types::ParseError::propagate)?;
+                    #check_const
                     ::std::result::Result::Ok(#ident(value))
                 }
 
                 async fn parse_from_repeated_field(self, field: #crate_name::__private::poem::web::Field) -> #crate_name::types::ParseResult<Self> {
                     let value = ::std::result::Result::map_err(<#inner_ty as #crate_name::types::ParseFromMultipartField>::parse_from_repeated_field(self.0, field).await, poem_openapi::types::ParseError::propagate)?;
+                    #check_const
                     ::std::result::Result::Ok(#ident(value))
                 }
             }
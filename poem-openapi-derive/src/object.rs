@@ -8,9 +8,15 @@ use quote::quote;
 use syn::{ext::IdentExt, Attribute, DeriveInput, Error, Generics, Path, Type};
 
 use crate::{
-    common_args::{apply_rename_rule_field, DefaultValue, ExternalDocument, RenameRule},
+    common_args::{
+        apply_rename_rule_field, extensions_meta, DefaultValue, Extension, ExternalDocument,
+        RenameRule,
+    },
     error::GeneratorResult,
-    utils::{create_object_name, get_crate_name, get_description, optional_literal},
+    utils::{
+        create_object_name, get_crate_name, get_description, get_oai_typename,
+        get_title_and_description, optional_literal, optional_literal_string,
+    },
     validators::Validators,
 };
 
@@ -46,6 +52,8 @@ struct ObjectField {
     serialize_with: Option<Path>,
     #[darling(default)]
     deserialize_with: Option<Path>,
+    #[darling(default)]
+    as_string: bool,
 }
 
 #[derive(FromDeriveInput)]
@@ -63,6 +71,8 @@ struct ObjectArgs {
     #[darling(default)]
     rename_all: Option<RenameRule>,
     #[darling(default)]
+    namespace: Option<String>,
+    #[darling(default)]
     deprecated: bool,
     #[darling(default)]
     read_only_all: bool,
@@ -76,12 +86,20 @@ struct ObjectArgs {
     external_docs: Option<ExternalDocument>,
     #[darling(default)]
     remote: Option<Path>,
+    #[darling(default, multiple, rename = "extension")]
+    extensions: Vec<Extension>,
     #[darling(default)]
     skip_serializing_if_is_none: bool,
     #[darling(default)]
     skip_serializing_if_is_empty: bool,
     #[darling(default)]
     default: Option<DefaultValue>,
+    #[darling(default)]
+    title: Option<String>,
+    #[darling(default)]
+    title_from_docs: bool,
+    #[darling(default)]
+    hidden: bool,
 }
 
 pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
@@ -97,8 +115,9 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             );
         }
     };
-    let oai_typename = args.rename.clone().unwrap_or_else(|| ident.to_string());
-    let description = get_description(&args.attrs)?;
+    let oai_typename = get_oai_typename(ident, &args.rename, &args.namespace);
+    let (title, description) =
+        get_title_and_description(&args.attrs, &args.title, args.title_from_docs)?;
     let mut deserialize_fields = Vec::new();
     let mut serialize_fields = Vec::new();
     let mut register_types = Vec::new();
@@ -137,6 +156,14 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             .into());
         }
 
+        if field.as_string && (*field.flatten || field.serialize_with.is_some()) {
+            return Err(Error::new_spanned(
+                field_ident,
+                "The `as_string` attribute cannot be used with `flatten` or `serialize_with`.",
+            )
+            .into());
+        }
+
         let field_name = field.rename.clone().unwrap_or_else(|| {
             apply_rename_rule_field(args.rename_all, field_ident.unraw().to_string())
         });
@@ -182,12 +209,23 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 };
             });
         } else if !*field.flatten {
+            let remove_value = if field.as_string && field.deserialize_with.is_none() {
+                quote! {
+                    ::std::option::Option::map(
+                        obj.remove(#field_name),
+                        #crate_name::__private::string_to_number,
+                    )
+                }
+            } else {
+                quote!(obj.remove(#field_name))
+            };
+
             match &create_default_value {
                 Some(create_default_value) => {
                     deserialize_fields.push(quote! {
                         #[allow(non_snake_case)]
                         let #field_ident: #field_ty = {
-                            match obj.remove(#field_name) {
+                            match #remove_value {
                                 ::std::option::Option::Some(#crate_name::__private::serde_json::Value::Null) | ::std::option::Option::None => #create_default_value,
                                 value => {
                                     let value = #crate_name::types::ParseFromJSON::parse_from_json(value).map_err(#crate_name::types::ParseError::propagate)?;
@@ -207,7 +245,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     deserialize_fields.push(quote! {
                         #[allow(non_snake_case)]
                         let #field_ident: #field_ty = {
-                            let value = #deserialize_function(obj.remove(#field_name))
+                            let value = #deserialize_function(#remove_value)
                                 .map_err(#crate_name::types::ParseError::propagate)?;
                             #validators_checker
                             value
@@ -250,14 +288,20 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     quote!(true)
                 };
 
-                let serialize_function = match field.serialize_with {
-                    Some(ref function) => quote! { #function },
-                    None => quote! { #crate_name::types::ToJSON::to_json },
+                let serialize_value = match field.serialize_with {
+                    Some(ref function) => quote! { #function(&self.#field_ident) },
+                    None if field.as_string => quote! {
+                        ::std::option::Option::map(
+                            #crate_name::types::ToJSON::to_json(&self.#field_ident),
+                            #crate_name::__private::number_to_string,
+                        )
+                    },
+                    None => quote! { #crate_name::types::ToJSON::to_json(&self.#field_ident) },
                 };
 
                 serialize_fields.push(quote! {
                     if #check_is_none && #check_is_empty && #check_if {
-                        if let ::std::option::Option::Some(value) = #serialize_function(&self.#field_ident) {
+                        if let ::std::option::Option::Some(value) = #serialize_value {
                             object.insert(::std::string::ToString::to_string(#field_name), value);
                         }
                     }
@@ -272,18 +316,31 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         }
 
         let field_meta_default = match &create_default_value {
+            Some(create_default_value) if !read_only && field.as_string => quote! {
+                ::std::option::Option::map(
+                    #crate_name::types::ToJSON::to_json(&#create_default_value),
+                    #crate_name::__private::number_to_string,
+                )
+            },
             Some(create_default_value) if !read_only => {
                 quote!(#crate_name::types::ToJSON::to_json(&#create_default_value))
             }
             _ => quote!(::std::option::Option::None),
         };
 
+        let convert_schema = field.as_string.then(|| {
+            quote! {
+                let original_schema = #crate_name::__private::schema_as_string(original_schema);
+            }
+        });
+
         if !*field.flatten {
             register_types
                 .push(quote!(<#field_ty as #crate_name::types::Type>::register(registry);));
 
             meta_fields.push(quote! {{
                 let original_schema = <#field_ty as #crate_name::types::Type>::schema_ref();
+                #convert_schema
                 let patch_schema = {
                     let mut schema = #crate_name::registry::MetaSchema::ANY;
                     schema.default = #field_meta_default;
@@ -316,8 +373,10 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         }
     }
 
+    let title = optional_literal_string(&title);
     let description = optional_literal(&description);
     let deprecated = args.deprecated;
+    let deny_additional_properties = args.deny_unknown_fields;
     let external_docs = match &args.external_docs {
         Some(external_docs) => {
             let s = external_docs.to_token_stream(&crate_name);
@@ -325,8 +384,10 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         }
         None => quote!(::std::option::Option::None),
     };
+    let extensions = extensions_meta(&crate_name, &args.extensions)?;
     let meta = quote! {
         #crate_name::registry::MetaSchema {
+            title: #title,
             description: #description,
             external_docs: #external_docs,
             required: {
@@ -341,6 +402,8 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 fields
             },
             deprecated: #deprecated,
+            deny_additional_properties: #deny_additional_properties,
+            extensions: #extensions,
             ..#crate_name::registry::MetaSchema::new("object")
         }
     };
@@ -378,18 +441,18 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         )
     };
 
-    let define_obj = quote! {
-        impl #impl_generics #crate_name::types::Type for #ident #ty_generics #where_clause {
-            const IS_REQUIRED: bool = true;
-
-            type RawValueType = Self;
-
-            type RawElementValueType = Self;
-
-            fn name() -> ::std::borrow::Cow<'static, str> {
-                ::std::convert::Into::into(#object_name)
+    // a hidden object is neither registered nor described, so it does not
+    // appear in the document
+    let schema_ref_and_register = if args.hidden {
+        quote! {
+            fn schema_ref() -> #crate_name::registry::MetaSchemaRef {
+                #crate_name::registry::MetaSchemaRef::Inline(::std::boxed::Box::new(#crate_name::registry::MetaSchema::new("object")))
             }
 
+            fn register(_registry: &mut #crate_name::registry::Registry) {}
+        }
+    } else {
+        quote! {
             fn schema_ref() -> #crate_name::registry::MetaSchemaRef {
                 #crate_name::registry::MetaSchemaRef::Reference(<Self as #crate_name::types::Type>::name().into_owned())
             }
@@ -402,6 +465,22 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     meta
                 })
             }
+        }
+    };
+
+    let define_obj = quote! {
+        impl #impl_generics #crate_name::types::Type for #ident #ty_generics #where_clause {
+            const IS_REQUIRED: bool = true;
+
+            type RawValueType = Self;
+
+            type RawElementValueType = Self;
+
+            fn name() -> ::std::borrow::Cow<'static, str> {
+                ::std::convert::Into::into(#object_name)
+            }
+
+            #schema_ref_and_register
 
             fn as_raw_value(&self) -> ::std::option::Option<&Self::RawValueType> {
                 ::std::option::Option::Some(self)
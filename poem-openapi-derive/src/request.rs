@@ -10,6 +10,7 @@ use quote::quote;
 use syn::{Attribute, DeriveInput, Error, Generics, Type};
 
 use crate::{
+    common_args::ExampleValue,
     error::GeneratorResult,
     utils::{get_crate_name, get_description, optional_literal},
 };
@@ -22,6 +23,8 @@ struct RequestItem {
 
     #[darling(default)]
     content_type: Option<SpannedValue<String>>,
+    #[darling(default, multiple, rename = "example")]
+    examples: Vec<ExampleValue>,
 }
 
 #[derive(FromDeriveInput)]
@@ -51,6 +54,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
     let description = optional_literal(&description);
 
     let mut from_requests = Vec::new();
+    let mut parse_default = None;
     let mut content = Vec::new();
     let mut schemas = Vec::new();
 
@@ -90,17 +94,38 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                         quote!(<#payload_ty as #crate_name::payload::Payload>::check_content_type(content_type))
                     }
                 };
+                let mut examples = Vec::new();
+                for (idx, example) in variant.examples.iter().enumerate() {
+                    match example.to_media_example(&crate_name, idx) {
+                        Some(example) => examples.push(example),
+                        None => {
+                            return Err(Error::new_spanned(
+                                &variant.ident,
+                                "The example of a request must be a function.",
+                            )
+                            .into())
+                        }
+                    }
+                }
+                let parse_payload = quote! {
+                    return ::std::result::Result::Ok(#ident::#item_ident(
+                        <#payload_ty as #crate_name::payload::ParsePayload>::from_request(request, body).await?
+                    ));
+                };
+                if from_requests.is_empty() {
+                    parse_default = Some(parse_payload.clone());
+                }
                 from_requests.push(quote! {
                     if #check_content_type {
-                        return ::std::result::Result::Ok(#ident::#item_ident(
-                            <#payload_ty as #crate_name::payload::ParsePayload>::from_request(request, body).await?
-                        ));
+                        #parse_payload
                     }
                 });
                 content.push(quote! {
                     #crate_name::registry::MetaMediaType {
                         content_type: #content_type,
                         schema: <#payload_ty as #crate_name::payload::Payload>::schema_ref(),
+                        examples: ::std::vec![#(#examples),*],
+                        encoding: ::std::vec![],
                     }
                 });
                 schemas.push(payload_ty);
@@ -149,6 +174,9 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                             }))
                         }
                         ::std::option::Option::None => {
+                            if #crate_name::__private::poem::web::ContentTypeMode::from_request(request) == #crate_name::__private::poem::web::ContentTypeMode::Lenient {
+                                #parse_default
+                            }
                             ::std::result::Result::Err(::std::convert::Into::into(#crate_name::error::ContentTypeError::ExpectContentType))
                         }
                     }
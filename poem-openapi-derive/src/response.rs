@@ -8,7 +8,7 @@ use quote::quote;
 use syn::{Attribute, DeriveInput, Error, Generics, Path, Type};
 
 use crate::{
-    common_args::ExtraHeader,
+    common_args::{ExampleValue, ExtraHeader},
     error::GeneratorResult,
     utils::{get_crate_name, get_description, optional_literal, optional_literal_string},
 };
@@ -40,6 +40,8 @@ struct ResponseItem {
     headers: Vec<ExtraHeader>,
     #[darling(default)]
     actual_type: Option<Type>,
+    #[darling(default, multiple, rename = "example")]
+    examples: Vec<ExampleValue>,
 }
 
 #[derive(FromDeriveInput)]
@@ -134,6 +136,35 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             });
         }
 
+        // examples
+        let mut examples = Vec::new();
+        for (idx, example) in variant.examples.iter().enumerate() {
+            match example.to_media_example(&crate_name, idx) {
+                Some(example) => examples.push(example),
+                None => {
+                    return Err(Error::new_spanned(
+                        &variant.ident,
+                        "The example of a response must be a function.",
+                    )
+                    .into())
+                }
+            }
+        }
+        let update_meta_examples = (!examples.is_empty()).then(|| {
+            quote! {
+                for mt in &mut content {
+                    mt.examples.extend([#(#examples),*]);
+                }
+            }
+        });
+        if values.is_empty() && !examples.is_empty() {
+            return Err(Error::new_spanned(
+                &variant.ident,
+                "Examples can only be specified for the response with a body.",
+            )
+            .into());
+        }
+
         fn update_content_type(
             crate_name: &TokenStream,
             content_type: Option<&str>,
@@ -203,6 +234,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                         content: {
                             let mut content = <#media_ty as #crate_name::ResponseContent>::media_types();
                             #update_meta_content_type
+                            #update_meta_examples
                             content
                         },
                         headers: ::std::vec![#(#meta_headers),*],
@@ -243,6 +275,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                         content: {
                             let mut content = <#media_ty as #crate_name::ResponseContent>::media_types();
                             #update_meta_content_type
+                            #update_meta_examples
                             content
                         },
                         headers: ::std::vec![#(#meta_headers),*],
@@ -104,6 +104,8 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     #crate_name::registry::MetaMediaType {
                         content_type: #content_type,
                         schema: #schema_ref,
+                        examples: ::std::vec![],
+                        encoding: ::std::vec![],
                     }
                 });
                 if let Some(actual_type) = &variant.actual_type {
@@ -5,7 +5,7 @@ use darling::{
 };
 use http::header::HeaderName;
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{Attribute, DeriveInput, Error, Path};
 
 use crate::{
@@ -219,6 +219,8 @@ struct SecuritySchemeArgs {
     openid_connect_url: Option<String>,
     #[darling(default)]
     checker: Option<Path>,
+    #[darling(default)]
+    check_scopes: bool,
 }
 
 impl SecuritySchemeArgs {
@@ -448,16 +450,34 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             let register_security_scheme =
                 args.generate_register_security_scheme(&crate_name, &oai_typename)?;
             let from_request = args.generate_from_request(&crate_name)?;
+            let from_request =
+                quote!(#from_request.map_err(#crate_name::__private::missing_credentials));
             let path = args.checker.as_ref();
 
-            let output = match path {
-                Some(_) => quote! {
+            let output = match (path, args.check_scopes) {
+                (Some(_), false) => quote! {
                     let output = #crate_name::__private::CheckerReturn::from(#path(&req, #from_request?).await).into_result()?;
                 },
-                None => quote! {
+                (Some(_), true) => quote! {
+                    let output = #crate_name::__private::CheckerReturn::from(#path(&req, #from_request?, &param_opts.scopes).await).into_result()?;
+                    if !param_opts.scopes.iter().all(|scope| #crate_name::auth::GrantedScopes::has_scope(&output, scope)) {
+                        return ::std::result::Result::Err(#crate_name::error::InsufficientScopeError.into());
+                    }
+                },
+                (None, true) => {
+                    return Err(
+                        Error::new_spanned(ident, "`check_scopes` requires a checker.").into(),
+                    );
+                }
+                (None, false) => quote! {
                     let output = #from_request?;
                 },
             };
+            let param_opts = if args.check_scopes {
+                quote!(param_opts)
+            } else {
+                quote!(_param_opts)
+            };
 
             let expanded = quote! {
                 impl<'a> #crate_name::ApiExtractor<'a> for #ident {
@@ -477,7 +497,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     async fn from_request(
                         req: &'a #crate_name::__private::poem::Request,
                         body: &mut #crate_name::__private::poem::RequestBody,
-                        _param_opts: #crate_name::ExtractParamOptions<Self::ParamType>,
+                        #param_opts: #crate_name::ExtractParamOptions<Self::ParamType>,
                     ) -> #crate_name::__private::poem::Result<Self> {
                         let query = req.extensions().get::<#crate_name::__private::UrlQuery>().unwrap();
                         #output
@@ -495,6 +515,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
 
             let mut registers = Vec::new();
             let mut security_schemes = Vec::new();
+            let mut security_requirements = Vec::new();
             let mut from_requests = Vec::new();
 
             if items.is_empty() {
@@ -506,30 +527,59 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     continue;
                 }
 
-                if item.fields.style != Style::Tuple || item.fields.fields.len() != 1 {
-                    return Err(
-                        Error::new_spanned(&item.ident, "Must be a tuple of length 1.").into(),
-                    );
+                if item.fields.style != Style::Tuple || item.fields.fields.is_empty() {
+                    return Err(Error::new_spanned(
+                        &item.ident,
+                        "Must be a tuple with at least one field.",
+                    )
+                    .into());
                 }
 
                 let item_ident = &item.ident;
-                let item_type = &item.fields.fields[0];
+                let item_types = &item.fields.fields;
+                let item_vars = (0..item_types.len())
+                    .map(|idx| format_ident!("item{}", idx))
+                    .collect::<Vec<_>>();
 
-                registers.push(
-                    quote! { <#item_type as #crate_name::ApiExtractor>::register(registry); },
-                );
+                registers.push(quote! {
+                    #(<#item_types as #crate_name::ApiExtractor>::register(registry);)*
+                });
                 security_schemes.push(quote! {
-                    security_schemes.extend(<#item_type as #crate_name::ApiExtractor>::security_schemes());
+                    #(security_schemes.extend(<#item_types as #crate_name::ApiExtractor>::security_schemes());)*
+                });
+                security_requirements.push(quote! {
+                    let mut requirements = ::std::vec![::std::vec![]];
+                    #(requirements = #crate_name::__private::and_security_requirements(
+                        requirements,
+                        <#item_types as #crate_name::ApiExtractor>::security_requirements(),
+                    );)*
+                    security_requirements.extend(requirements);
                 });
                 from_requests.push(quote! {
-                    match <#item_type as #crate_name::ApiExtractor>::from_request(req, body, param_opts.clone()).await {
-                        ::std::result::Result::Ok(item) => return Ok(#ident::#item_ident(item)),
-                        ::std::result::Result::Err(err) => last_err = ::std::option::Option::Some(err),
+                    'variant: {
+                        #(
+                        let #item_vars = match <#item_types as #crate_name::ApiExtractor>::from_request(req, body, param_opts.clone()).await {
+                            ::std::result::Result::Ok(item) => item,
+                            ::std::result::Result::Err(err) => {
+                                // prefers the errors which are not caused by the missing credentials
+                                if last_err.as_ref().filter(|err| !#crate_name::__private::is_missing_credentials(err)).is_none() {
+                                    last_err = ::std::option::Option::Some(err);
+                                }
+                                break 'variant;
+                            }
+                        };
+                        )*
+                        return ::std::result::Result::Ok(#ident::#item_ident(#(#item_vars),*));
                     }
                 })
             }
 
             let fallback = items.iter().find(|item| item.fallback);
+            if fallback.is_some() {
+                security_requirements.push(quote! {
+                    security_requirements.push(::std::vec![]);
+                });
+            }
             let fallback = match fallback {
                 Some(item) => {
                     if item.fields.style != Style::Unit {
@@ -560,6 +610,12 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                         security_schemes
                     }
 
+                    fn security_requirements() -> ::std::vec::Vec<::std::vec::Vec<&'static str>> {
+                        let mut security_requirements = ::std::vec![];
+                        #({ #security_requirements })*
+                        security_requirements
+                    }
+
                     async fn from_request(
                         req: &'a #crate_name::__private::poem::Request,
                         body: &mut #crate_name::__private::poem::RequestBody,
@@ -8,9 +8,13 @@ use quote::quote;
 use syn::{ext::IdentExt, Attribute, DeriveInput, Error, Generics, Type};
 
 use crate::{
-    common_args::{apply_rename_rule_variant, ExternalDocument, RenameRule},
+    common_args::{
+        apply_rename_rule_variant, extensions_meta, Extension, ExternalDocument, RenameRule,
+    },
     error::GeneratorResult,
-    utils::{create_object_name, get_crate_name, get_description, optional_literal},
+    utils::{
+        create_object_name, get_crate_name, get_description, get_oai_typename, optional_literal,
+    },
 };
 
 #[derive(FromVariant)]
@@ -18,6 +22,7 @@ use crate::{
 struct UnionItem {
     ident: Ident,
     fields: Fields<Type>,
+    attrs: Vec<Attribute>,
 
     #[darling(default)]
     mapping: Option<String>,
@@ -38,11 +43,15 @@ struct UnionArgs {
     #[darling(default)]
     one_of: bool,
     #[darling(default)]
+    namespace: Option<String>,
+    #[darling(default)]
     discriminator_name: Option<String>,
     #[darling(default)]
     external_docs: Option<ExternalDocument>,
     #[darling(default)]
     rename_all: Option<RenameRule>,
+    #[darling(default, multiple, rename = "extension")]
+    extensions: Vec<Extension>,
 }
 
 pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
@@ -50,7 +59,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
     let crate_name = get_crate_name(args.internal);
     let ident = &args.ident;
     let (impl_generics, ty_generics, where_clause) = args.generics.split_for_impl();
-    let oai_typename = args.rename.clone().unwrap_or_else(|| ident.to_string());
+    let oai_typename = get_oai_typename(ident, &args.rename, &args.namespace);
     let description = get_description(&args.attrs)?;
     let description = optional_literal(&description);
     let discriminator_name = &args.discriminator_name;
@@ -75,6 +84,8 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
 
     for variant in e {
         let item_ident = &variant.ident;
+        let item_description = get_description(&variant.attrs)?;
+        let item_description_literal = optional_literal(&item_description);
 
         match variant.fields.len() {
             1 => {
@@ -171,15 +182,25 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                                 })),
                                 <#object_ty as #crate_name::types::Type>::schema_ref(),
                             ],
+                            description: #item_description_literal,
                             ..#crate_name::registry::MetaSchema::ANY
                         };
 
-                        registry.schemas.insert(#schema_name, schema);
+                        registry.create_schema::<(Self, #object_ty), _>(#schema_name, |_| schema);
                     });
 
                     schemas.push(quote! {
                         #crate_name::registry::MetaSchemaRef::Reference(#schema_name)
                     });
+                } else if let Some(item_description) = &item_description {
+                    // a reference cannot have a description, so wraps it with `allOf`
+                    schemas.push(quote! {
+                        #crate_name::registry::MetaSchemaRef::Inline(::std::boxed::Box::new(#crate_name::registry::MetaSchema {
+                            description: ::std::option::Option::Some(#item_description),
+                            all_of: ::std::vec![<#object_ty as #crate_name::types::Type>::schema_ref()],
+                            ..#crate_name::registry::MetaSchema::ANY
+                        }))
+                    });
                 } else {
                     schemas.push(quote! {
                         <#object_ty as #crate_name::types::Type>::schema_ref()
@@ -246,6 +267,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         quote!(::std::vec![])
     };
 
+    let extensions = extensions_meta(&crate_name, &args.extensions)?;
     let meta = quote! {
         #crate_name::registry::MetaSchema {
             ty: "object",
@@ -254,6 +276,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             one_of: #one_of,
             any_of: #any_of,
             discriminator: #discriminator,
+            extensions: #extensions,
             ..#crate_name::registry::MetaSchema::ANY
         }
     };
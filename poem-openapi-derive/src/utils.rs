@@ -5,8 +5,8 @@ use proc_macro2::{Ident, Span, TokenStream};
 use proc_macro_crate::{crate_name, FoundCrate};
 use quote::quote;
 use syn::{
-    visit_mut, visit_mut::VisitMut, Attribute, Error, Expr, ExprLit, GenericParam, Generics,
-    Lifetime, Lit, Meta, Result,
+    visit_mut, visit_mut::VisitMut, Attribute, Error, Expr, ExprLit, GenericArgument, GenericParam,
+    Generics, Lifetime, Lit, Meta, PathArguments, Result, Type,
 };
 
 use crate::error::GeneratorResult;
@@ -70,6 +70,36 @@ pub(crate) fn get_summary_and_description(
     }
 }
 
+/// Returns the title and the description of a schema. If `title_from_docs` is
+/// enabled, the first paragraph of the doc comments is used as the title,
+/// an explicit `title` always takes precedence.
+pub(crate) fn get_title_and_description(
+    attrs: &[Attribute],
+    title: &Option<String>,
+    title_from_docs: bool,
+) -> Result<(Option<String>, Option<String>)> {
+    let (summary, description) = if title_from_docs {
+        get_summary_and_description(attrs)?
+    } else {
+        (None, get_description(attrs)?)
+    };
+    Ok((title.clone().or(summary), description))
+}
+
+/// Returns the OpenAPI name of a type, which is prefixed with the namespace
+/// if it is specified, e.g. `billing.Invoice`.
+pub(crate) fn get_oai_typename(
+    ident: &Ident,
+    rename: &Option<String>,
+    namespace: &Option<String>,
+) -> String {
+    let name = rename.clone().unwrap_or_else(|| ident.to_string());
+    match namespace {
+        Some(namespace) => format!("{namespace}.{name}"),
+        None => name,
+    }
+}
+
 pub(crate) fn optional_literal(s: &Option<impl AsRef<str>>) -> TokenStream {
     match s {
         Some(s) => {
@@ -166,6 +196,27 @@ impl VisitMut for RemoveLifetime {
     }
 }
 
+/// Returns the type `T` if the type is `Option<T>`.
+pub(crate) fn get_option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    if type_path.qself.is_some() {
+        return None;
+    }
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(ty)) if args.args.len() == 1 => Some(ty),
+        _ => None,
+    }
+}
+
 pub(crate) fn create_object_name(
     crate_name: &TokenStream,
     name: &str,
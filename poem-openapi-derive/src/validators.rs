@@ -30,6 +30,8 @@ pub(crate) struct Validators {
     min_length: Option<SpannedValue<usize>>,
     #[darling(default)]
     pattern: Option<SpannedValue<String>>,
+    #[darling(default)]
+    const_value: Option<SpannedValue<String>>,
 
     // for containers
     #[darling(default)]
@@ -107,6 +109,12 @@ impl Validators {
             elem_validators.push(quote!(#crate_name::validation::Pattern::new(#value)));
         }
 
+        if let Some(value) = &self.const_value {
+            // https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-5.20
+            let value = &**value;
+            elem_validators.push(quote!(#crate_name::validation::ConstValue::new(#value)));
+        }
+
         //////////////////////////////////////////////////////////////////////////////
         // custom validators
         //////////////////////////////////////////////////////////////////////////////
@@ -290,29 +298,41 @@ impl Validators {
         let ValidatorsTokenStream {
             container_validators,
             elem_validators,
-            ..
+            custom_validators,
         } = self.create_validators(crate_name)?;
 
+        let update_elem_meta = |target: TokenStream| {
+            let builtin = elem_validators.iter().map(|validator| {
+                quote!(#crate_name::validation::ValidatorMeta::update_meta(&#validator, &mut #target);)
+            });
+            let custom = custom_validators.iter().map(|validator| {
+                quote!({
+                    #[allow(unused_imports)]
+                    use #crate_name::validation::{CustomValidatorMeta as _, NoCustomValidatorMeta as _};
+                    (&#validator).update_custom_meta(&mut #target);
+                })
+            });
+            builtin.chain(custom).collect::<Vec<_>>()
+        };
+        let update_items_meta = update_elem_meta(quote!(items_schema));
+        let update_additional_properties_meta =
+            update_elem_meta(quote!(additional_properties_schema));
+        let update_schema_meta = update_elem_meta(quote!(schema));
+
         let update_elem_meta = quote! {
             if original_schema.is_array() {
                 let mut items_schema = #crate_name::registry::MetaSchema::ANY;
-                #(
-                #crate_name::validation::ValidatorMeta::update_meta(&#elem_validators, &mut items_schema);
-                )*
+                #(#update_items_meta)*
                 schema.items = ::std::option::Option::Some(::std::boxed::Box::new(#crate_name::registry::MetaSchemaRef::Inline(::std::boxed::Box::new(items_schema))));
             } else if original_schema.is_object() {
                 let mut additional_properties_schema = #crate_name::registry::MetaSchema::ANY;
-                #(
-                #crate_name::validation::ValidatorMeta::update_meta(&#elem_validators, &mut additional_properties_schema);
-                )*
+                #(#update_additional_properties_meta)*
                 schema.additional_properties = ::std::option::Option::Some(::std::boxed::Box::new(#crate_name::registry::MetaSchemaRef::Inline(::std::boxed::Box::new(additional_properties_schema))));
             } else {
-                #(
-                #crate_name::validation::ValidatorMeta::update_meta(&#elem_validators, &mut schema);
-                )*
+                #(#update_schema_meta)*
             }
         };
-        let update_elem_meta = if !elem_validators.is_empty() {
+        let update_elem_meta = if !elem_validators.is_empty() || !custom_validators.is_empty() {
             Some(update_elem_meta)
         } else {
             None
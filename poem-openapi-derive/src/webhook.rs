@@ -10,7 +10,7 @@ use syn::{
 };
 
 use crate::{
-    common_args::{APIMethod, DefaultValue, ExternalDocument},
+    common_args::{extensions_meta, APIMethod, DefaultValue, Extension, ExternalDocument},
     error::GeneratorResult,
     utils::{
         get_crate_name, get_description, get_summary_and_description, optional_literal,
@@ -41,6 +41,10 @@ struct WebhookOperation {
     operation_id: Option<String>,
     #[darling(default)]
     external_docs: Option<ExternalDocument>,
+    #[darling(default, multiple, rename = "extension")]
+    extensions: Vec<Extension>,
+    #[darling(default)]
+    hidden: bool,
 }
 
 #[derive(FromMeta, Default)]
@@ -55,6 +59,8 @@ struct WebHookOperationParam {
     validator: Option<Validators>,
     #[darling(default)]
     explode: Option<bool>,
+    #[darling(default, multiple, rename = "extension")]
+    extensions: Vec<Extension>,
 }
 
 struct Context {
@@ -123,6 +129,8 @@ fn generate_operation(
         tags,
         operation_id,
         external_docs,
+        extensions,
+        hidden,
     } = args;
     let name = name.unwrap_or_else(|| trait_method.sig.ident.to_string());
     let http_method = method.to_http_method();
@@ -186,10 +194,12 @@ fn generate_operation(
 
         RemoveLifetime.visit_type_mut(&mut arg_ty);
 
-        // register
-        ctx.register_items.push(quote! {
-            <#arg_ty as #crate_name::ApiExtractor>::register(registry);
-        });
+        if !hidden {
+            // register
+            ctx.register_items.push(quote! {
+                <#arg_ty as #crate_name::ApiExtractor>::register(registry);
+            });
+        }
 
         // default value for parameter
         let param_meta_default = match &operation_param.default {
@@ -214,6 +224,7 @@ fn generate_operation(
         let param_desc = optional_literal_string(&param_description);
         let deprecated = operation_param.deprecated;
         let explode = operation_param.explode.unwrap_or(true);
+        let param_extensions = extensions_meta(crate_name, &operation_param.extensions)?;
 
         params_meta.push(quote! {
             if <#arg_ty as #crate_name::ApiExtractor>::TYPES.contains(&#crate_name::ApiExtractorType::Parameter) {
@@ -234,6 +245,7 @@ fn generate_operation(
                     required: <#arg_ty as #crate_name::ApiExtractor>::PARAM_IS_REQUIRED,
                     deprecated: #deprecated,
                     explode: #explode,
+                    extensions: #param_extensions,
                 };
                 params.push(meta_param);
             }
@@ -247,6 +259,11 @@ fn generate_operation(
         });
     }
 
+    let extensions = extensions_meta(crate_name, &extensions)?;
+    if hidden {
+        return Ok(());
+    }
+
     ctx.register_items
         .push(quote!(<#res_ty as #crate_name::ApiResponse>::register(registry);));
 
@@ -290,6 +307,10 @@ fn generate_operation(
                         security: ::std::vec![],
                         operation_id: #operation_id,
                         code_samples: ::std::vec![],
+                        curl_example: false,
+                        servers: ::std::vec![],
+                        callbacks: ::std::vec![],
+                        extensions: #extensions,
                     }
                 }
             },
@@ -1,4 +1,4 @@
-use poem::{Request, Result};
+use poem::{http::header, web::cookie::Cookie, Request, Result};
 
 use crate::{
     auth::ApiKeyAuthorization, base::UrlQuery, error::AuthorizationError, registry::MetaParamIn,
@@ -33,9 +33,13 @@ impl ApiKeyAuthorization for ApiKey {
                 })
                 .ok_or_else(|| AuthorizationError.into()),
             MetaParamIn::Cookie => req
-                .cookie()
-                .get(name)
-                .as_ref()
+                .headers()
+                .get_all(header::COOKIE)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|value| value.split(';'))
+                .filter_map(|cookie| Cookie::parse(cookie.trim()).ok())
+                .find(|cookie| cookie.name() == name)
                 .map(|cookie| Self {
                     key: cookie.value_str().to_string(),
                 })
@@ -44,3 +48,23 @@ impl ApiKeyAuthorization for ApiKey {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_key_in_cookie() {
+        let req = Request::builder()
+            .header(header::COOKIE, "a=1; key=abcdef; b=2")
+            .finish();
+        let api_key =
+            ApiKey::from_request(&req, &UrlQuery(vec![]), "key", MetaParamIn::Cookie).unwrap();
+        assert_eq!(api_key.key, "abcdef");
+
+        let req = Request::builder()
+            .header(header::COOKIE, "a=1; b=2")
+            .finish();
+        assert!(ApiKey::from_request(&req, &UrlQuery(vec![]), "key", MetaParamIn::Cookie).is_err());
+    }
+}
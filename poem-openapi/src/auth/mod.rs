@@ -4,6 +4,8 @@ mod api_key;
 mod basic;
 mod bearer;
 
+use std::collections::HashMap;
+
 use poem::{Request, Result};
 
 pub use self::{api_key::ApiKey, basic::Basic, bearer::Bearer};
@@ -32,6 +34,29 @@ pub trait ApiKeyAuthorization: Sized {
     ) -> Result<Self>;
 }
 
+/// Represents an authorization that has been granted some OAuth scopes.
+pub trait GrantedScopes {
+    /// Returns `true` if the scope has been granted.
+    fn has_scope(&self, scope: &str) -> bool;
+}
+
+/// Marks the errors of the security schemes which are caused by the missing
+/// credentials, an optional security scheme is `None` for these errors.
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct MissingCredentials;
+
+#[doc(hidden)]
+pub fn missing_credentials(mut err: poem::Error) -> poem::Error {
+    err.set_data(MissingCredentials);
+    err
+}
+
+#[doc(hidden)]
+pub fn is_missing_credentials(err: &poem::Error) -> bool {
+    err.data::<MissingCredentials>().is_some()
+}
+
 /// Facilitates the conversion of `Option` into `Results`, for `SecuritySchema` checker.
 #[doc(hidden)]
 pub enum CheckerReturn<T> {
@@ -61,3 +86,54 @@ impl<T> From<Option<T>> for CheckerReturn<T> {
         Self::Option(option)
     }
 }
+
+/// Combines two lists of security requirements, all of the security schemes
+/// of a requirement of `a` and a requirement of `b` are required at the same
+/// time.
+#[doc(hidden)]
+pub fn and_security_requirements(
+    a: Vec<Vec<&'static str>>,
+    b: Vec<Vec<&'static str>>,
+) -> Vec<Vec<&'static str>> {
+    a.iter()
+        .flat_map(|a| {
+            b.iter().map(move |b| {
+                let mut names = a.clone();
+                names.extend(b.iter().filter(|name| !a.contains(name)));
+                names
+            })
+        })
+        .collect()
+}
+
+/// Adds the security requirements of a security scheme parameter with the
+/// scopes to the security requirements of an operation.
+#[doc(hidden)]
+pub fn add_security_requirements(
+    security: &mut Vec<HashMap<&'static str, Vec<&'static str>>>,
+    requirements: Vec<Vec<&'static str>>,
+    scopes: Vec<&'static str>,
+) {
+    let requirements = requirements.into_iter().map(|names| {
+        names
+            .into_iter()
+            .map(|name| (name, scopes.clone()))
+            .collect::<HashMap<_, _>>()
+    });
+
+    if security.is_empty() {
+        security.extend(requirements);
+    } else {
+        let requirements = requirements.collect::<Vec<_>>();
+        *security = security
+            .iter()
+            .flat_map(|a| {
+                requirements.iter().map(move |b| {
+                    let mut requirement = a.clone();
+                    requirement.extend(b.iter().map(|(name, scopes)| (*name, scopes.clone())));
+                    requirement
+                })
+            })
+            .collect();
+    }
+}
@@ -1,12 +1,16 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fmt::{self, Debug, Display},
     future::Future,
     ops::Deref,
 };
 
 use futures_util::FutureExt;
-use poem::{endpoint::BoxEndpoint, http::Method, Error, FromRequest, Request, RequestBody, Result};
+use poem::{
+    endpoint::BoxEndpoint,
+    http::{header, Method},
+    Error, FromRequest, Request, RequestBody, Result,
+};
 
 use crate::{
     payload::Payload,
@@ -75,6 +79,9 @@ pub struct ExtractParamOptions<T> {
     /// separate parameters for each value of the array or key-value pair of the
     /// map.
     pub explode: bool,
+
+    /// The OAuth scopes required by the operation for this security scheme.
+    pub scopes: Vec<&'static str>,
 }
 
 impl<T> Default for ExtractParamOptions<T> {
@@ -84,6 +91,7 @@ impl<T> Default for ExtractParamOptions<T> {
             default_value: None,
             example_value: None,
             explode: true,
+            scopes: Vec::new(),
         }
     }
 }
@@ -132,9 +140,9 @@ impl<T> Default for ExtractParamOptions<T> {
 ///     Parse the request body in `JSON` format into
 /// [`Json`](crate::payload::Json).
 ///
-/// - **PlainText&lt;T>**
+/// - **PlainText&lt;T: FromStr + Type>**
 ///
-///     Extract the request body as utf8 string into
+///     Extract the request body as utf8 string and parse it into
 /// [`PlainText`](crate::payload::PlainText).
 ///
 /// - **Any type derived from the [`ApiRequest`](crate::ApiRequest) macro**
@@ -176,6 +184,18 @@ pub trait ApiExtractor<'a>: Sized {
         vec![]
     }
 
+    /// Returns the security requirements if this extractor is security
+    /// scheme.
+    ///
+    /// Each item is an alternative, and all the security schemes in it must be
+    /// satisfied at the same time.
+    fn security_requirements() -> Vec<Vec<&'static str>> {
+        Self::security_schemes()
+            .into_iter()
+            .map(|name| vec![name])
+            .collect()
+    }
+
     /// Returns the location of the parameter if this extractor is parameter.
     fn param_in() -> Option<MetaParamIn> {
         None
@@ -221,6 +241,104 @@ impl<'a, T: FromRequest<'a>> ApiExtractor<'a> for T {
     }
 }
 
+/// An optional extractor, the `OpenApi` macro uses it for parameters of type
+/// `Option<T>`.
+///
+/// It is `None` if the request does not contain the value, such as a missing
+/// parameter, an empty body or no credentials, other errors are returned.
+/// Poem extractors keep the behavior of `Option<T>` in poem, which ignores all
+/// errors.
+///
+/// If `T` is a security scheme, an empty security requirement is added so that
+/// the operation can also be called anonymously.
+#[doc(hidden)]
+pub struct OptionalExtractor<T>(pub Option<T>);
+
+impl<'a, T: ApiExtractor<'a>> ApiExtractor<'a> for OptionalExtractor<T> {
+    const TYPES: &'static [ApiExtractorType] = T::TYPES;
+
+    type ParamType = T::ParamType;
+    type ParamRawType = T::ParamRawType;
+
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
+
+    fn security_schemes() -> Vec<&'static str> {
+        T::security_schemes()
+    }
+
+    fn security_requirements() -> Vec<Vec<&'static str>> {
+        let mut requirements = T::security_requirements();
+        if !requirements.is_empty() && !requirements.contains(&vec![]) {
+            requirements.push(vec![]);
+        }
+        requirements
+    }
+
+    fn param_in() -> Option<MetaParamIn> {
+        T::param_in()
+    }
+
+    fn param_schema_ref() -> Option<MetaSchemaRef> {
+        T::param_schema_ref()
+    }
+
+    fn request_meta() -> Option<MetaRequest> {
+        T::request_meta().map(|meta| MetaRequest {
+            required: false,
+            ..meta
+        })
+    }
+
+    fn param_raw_type(&self) -> Option<&Self::ParamRawType> {
+        self.0.as_ref().and_then(ApiExtractor::param_raw_type)
+    }
+
+    async fn from_request(
+        request: &'a Request,
+        body: &mut RequestBody,
+        param_opts: ExtractParamOptions<Self::ParamType>,
+    ) -> Result<Self> {
+        let name = param_opts.name;
+        // FIXME: remove the unnecessary boxed
+        // https://github.com/rust-lang/rust/issues/100013
+        match T::from_request(request, body, param_opts).boxed().await {
+            Ok(value) => Ok(Self(Some(value))),
+            Err(err) if Self::is_missing(request, name, &err) => Ok(Self(None)),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<'a, T: ApiExtractor<'a>> OptionalExtractor<T> {
+    fn is_missing(request: &Request, name: &str, err: &Error) -> bool {
+        T::TYPES.iter().all(|ty| match ty {
+            ApiExtractorType::RequestObject => {
+                let headers = request.headers();
+                !headers.contains_key(header::CONTENT_TYPE)
+                    && !headers.contains_key(header::TRANSFER_ENCODING)
+                    && headers
+                        .get(header::CONTENT_LENGTH)
+                        .filter(|len| *len != "0")
+                        .is_none()
+            }
+            ApiExtractorType::Parameter => match T::param_in() {
+                Some(MetaParamIn::Query) => request
+                    .extensions()
+                    .get::<UrlQuery>()
+                    .and_then(|query| query.get(name))
+                    .is_none(),
+                Some(MetaParamIn::Header) => !request.headers().contains_key(name),
+                Some(MetaParamIn::Cookie) => request.cookie().get(name).is_none(),
+                _ => false,
+            },
+            ApiExtractorType::SecurityScheme => crate::auth::is_missing_credentials(err),
+            ApiExtractorType::PoemExtractor => true,
+        })
+    }
+}
+
 /// Represents a OpenAPI response content object.
 pub trait ResponseContent {
     /// Returns the media types in this content.
@@ -236,6 +354,8 @@ impl<T: Payload> ResponseContent for T {
         vec![MetaMediaType {
             content_type: T::CONTENT_TYPE,
             schema: T::schema_ref(),
+            examples: vec![],
+            encoding: vec![],
         }]
     }
 
@@ -291,6 +411,15 @@ pub trait ApiResponse: Sized {
     /// Register the schema contained in this response object to the registry.
     fn register(registry: &mut Registry);
 
+    /// Returns the specification extensions (`x-*`) added to the operations
+    /// returning this response.
+    ///
+    /// The extensions specified with the `extension` attribute of the
+    /// operation take precedence.
+    fn operation_extensions() -> BTreeMap<String, serde_json::Value> {
+        BTreeMap::new()
+    }
+
     /// Convert [`poem::Error`] to this response object.
     #[allow(unused_variables)]
     fn from_parse_request_error(err: Error) -> Self {
@@ -341,6 +470,12 @@ where
         E::register(registry);
     }
 
+    fn operation_extensions() -> BTreeMap<String, serde_json::Value> {
+        let mut extensions = E::operation_extensions();
+        extensions.extend(T::operation_extensions());
+        extensions
+    }
+
     fn from_parse_request_error(err: Error) -> Self {
         Ok(T::from_parse_request_error(err))
     }
@@ -384,10 +519,21 @@ pub trait OAuthScopes {
     fn name(&self) -> &'static str;
 }
 
-/// A operation id that can be obtained from the response
+/// A operation id that can be obtained from the request extensions and the
+/// response
+///
+/// It is inserted into the request extensions before the operation is called,
+/// so the middlewares of the operation can label the logs and the metrics
+/// with it rather than the raw path, and the middlewares of the whole service
+/// can get it from the response.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct OperationId(pub &'static str);
 
+/// The tags of an operation that can be obtained from the request extensions
+/// and the response, like [`OperationId`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct OperationTags(pub Vec<&'static str>);
+
 impl Display for OperationId {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -0,0 +1,818 @@
+//! Compare two OpenAPI documents.
+//!
+//! [`diff`] compares two documents, as returned by
+//! [`OpenApiService::spec_value`](crate::OpenApiService::spec_value) or
+//! loaded from a previously saved specification file, and classifies every
+//! change as breaking or non-breaking. This can be used in CI to fail a build
+//! that would break existing clients.
+//!
+//! ```
+//! use poem_openapi::{diff::diff, payload::PlainText, OpenApi, OpenApiService};
+//!
+//! struct ApiV1;
+//!
+//! #[OpenApi]
+//! impl ApiV1 {
+//!     #[oai(path = "/hello", method = "get")]
+//!     async fn hello(&self) -> PlainText<String> {
+//!         PlainText("hello".to_string())
+//!     }
+//! }
+//!
+//! struct ApiV2;
+//!
+//! #[OpenApi]
+//! impl ApiV2 {}
+//!
+//! let old = OpenApiService::new(ApiV1, "Demo", "1.0").spec_value();
+//! let new = OpenApiService::new(ApiV2, "Demo", "2.0").spec_value();
+//! let changes = diff(&old, &new);
+//! assert!(changes.is_breaking());
+//! ```
+
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+};
+
+use serde_json::{Map, Value};
+
+/// The kind of a change.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ChangeKind {
+    /// The change may break existing clients.
+    Breaking,
+    /// The change is backward compatible.
+    NonBreaking,
+}
+
+/// A change between two OpenAPI documents.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Change {
+    /// The kind of the change.
+    pub kind: ChangeKind,
+    /// The location of the change, such as `paths./users.post.requestBody`.
+    pub location: String,
+    /// The description of the change.
+    pub description: String,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            ChangeKind::Breaking => "breaking",
+            ChangeKind::NonBreaking => "non-breaking",
+        };
+        write!(f, "[{}] {}: {}", kind, self.location, self.description)
+    }
+}
+
+/// The changes between two OpenAPI documents.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Changes {
+    /// All changes.
+    pub changes: Vec<Change>,
+}
+
+impl Changes {
+    /// Returns `true` if there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns `true` if any of the changes is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.breaking().next().is_some()
+    }
+
+    /// Returns the breaking changes.
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|change| change.kind == ChangeKind::Breaking)
+    }
+
+    /// Returns the non-breaking changes.
+    pub fn non_breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|change| change.kind == ChangeKind::NonBreaking)
+    }
+
+    fn push(&mut self, kind: ChangeKind, location: &str, description: impl Into<String>) {
+        self.changes.push(Change {
+            kind,
+            location: location.to_string(),
+            description: description.into(),
+        });
+    }
+
+    fn breaking_change(&mut self, location: &str, description: impl Into<String>) {
+        self.push(ChangeKind::Breaking, location, description);
+    }
+
+    fn non_breaking_change(&mut self, location: &str, description: impl Into<String>) {
+        self.push(ChangeKind::NonBreaking, location, description);
+    }
+
+    /// Records a change that makes a schema accept fewer values, which breaks
+    /// the clients sending them.
+    fn tightened(&mut self, direction: Direction, location: &str, description: impl Into<String>) {
+        match direction {
+            Direction::Response => self.non_breaking_change(location, description),
+            Direction::Request | Direction::Both => self.breaking_change(location, description),
+        }
+    }
+
+    /// Records a change that makes a schema accept more values, which breaks
+    /// the clients receiving them.
+    fn loosened(&mut self, direction: Direction, location: &str, description: impl Into<String>) {
+        match direction {
+            Direction::Request => self.non_breaking_change(location, description),
+            Direction::Response | Direction::Both => self.breaking_change(location, description),
+        }
+    }
+}
+
+/// Where a schema is used.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Direction {
+    /// The schema describes values sent by the clients.
+    Request,
+    /// The schema describes values received by the clients.
+    Response,
+    /// The schema is used in both directions, or not used at all.
+    Both,
+}
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+
+/// Compares two OpenAPI documents.
+///
+/// Whether a change to a schema is breaking depends on where the schema is
+/// used. Accepting fewer values breaks the clients sending requests, and
+/// accepting more values breaks the clients reading responses. The schemas
+/// in `components` are classified by the operations referencing them, and
+/// are treated as used in both directions if they are not referenced.
+///
+/// The following changes are considered breaking:
+///
+/// - Removing a path, an operation, a parameter, a response or a content
+///   type.
+/// - Adding a required parameter or making a parameter or request body
+///   required.
+/// - Removing a property.
+/// - Changing the type or format of a schema.
+/// - In requests, adding a required property, making a property required,
+///   removing enum values or tightening a validator, such as decreasing
+///   `maxLength` or making `minimum` exclusive.
+/// - In responses, making a property optional, adding enum values or
+///   loosening a validator.
+pub fn diff(old: &Value, new: &Value) -> Changes {
+    let mut changes = Changes::default();
+    let empty = Map::new();
+
+    let old_paths = old["paths"].as_object().unwrap_or(&empty);
+    let new_paths = new["paths"].as_object().unwrap_or(&empty);
+    for (path, old_item) in sorted_entries(old_paths) {
+        let location = format!("paths.{path}");
+        match new_paths.get(path) {
+            Some(new_item) => diff_path_item(&location, old_item, new_item, &mut changes),
+            None => changes.breaking_change(&location, "path removed"),
+        }
+    }
+    for (path, _) in sorted_entries(new_paths) {
+        if !old_paths.contains_key(path) {
+            changes.non_breaking_change(&format!("paths.{path}"), "path added");
+        }
+    }
+
+    let request_schemas = referenced_schemas(old, new, &["parameters", "requestBody"]);
+    let response_schemas = referenced_schemas(old, new, &["responses"]);
+    let old_schemas = old["components"]["schemas"].as_object().unwrap_or(&empty);
+    let new_schemas = new["components"]["schemas"].as_object().unwrap_or(&empty);
+    for (name, old_schema) in sorted_entries(old_schemas) {
+        let location = format!("components.schemas.{name}");
+        match new_schemas.get(name) {
+            Some(new_schema) => {
+                let direction = match (
+                    request_schemas.contains(name.as_str()),
+                    response_schemas.contains(name.as_str()),
+                ) {
+                    (true, false) => Direction::Request,
+                    (false, true) => Direction::Response,
+                    _ => Direction::Both,
+                };
+                diff_schema(&location, direction, old_schema, new_schema, &mut changes)
+            }
+            None => changes.breaking_change(&location, "schema removed"),
+        }
+    }
+    for (name, _) in sorted_entries(new_schemas) {
+        if !old_schemas.contains_key(name) {
+            changes.non_breaking_change(&format!("components.schemas.{name}"), "schema added");
+        }
+    }
+
+    changes
+}
+
+fn sorted_entries(map: &Map<String, Value>) -> Vec<(&String, &Value)> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+fn collect_refs<'a>(value: &'a Value, refs: &mut BTreeSet<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value.as_str() {
+                    Some(reference) if key == "$ref" => {
+                        if let Some(name) = reference.strip_prefix(SCHEMA_REF_PREFIX) {
+                            refs.insert(name);
+                        }
+                    }
+                    _ => collect_refs(value, refs),
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_refs(item, refs)),
+        _ => {}
+    }
+}
+
+/// Returns the names of the schemas used by the given parts of the operations
+/// of either document, including the schemas they reference.
+fn referenced_schemas<'a>(old: &'a Value, new: &'a Value, keys: &[&str]) -> BTreeSet<&'a str> {
+    let mut refs = BTreeSet::new();
+    for doc in [old, new] {
+        for item in doc["paths"].as_object().into_iter().flat_map(Map::values) {
+            for method in METHODS {
+                for key in keys {
+                    collect_refs(&item[*method][*key], &mut refs);
+                }
+            }
+        }
+    }
+
+    let mut pending = refs.iter().copied().collect::<Vec<_>>();
+    while let Some(name) = pending.pop() {
+        let mut nested = BTreeSet::new();
+        for doc in [old, new] {
+            collect_refs(&doc["components"]["schemas"][name], &mut nested);
+        }
+        for name in nested {
+            if refs.insert(name) {
+                pending.push(name);
+            }
+        }
+    }
+    refs
+}
+
+fn diff_path_item(location: &str, old: &Value, new: &Value, changes: &mut Changes) {
+    for method in METHODS {
+        let location = format!("{location}.{method}");
+        match (old.get(*method), new.get(*method)) {
+            (Some(old), Some(new)) => diff_operation(&location, old, new, changes),
+            (Some(_), None) => changes.breaking_change(&location, "operation removed"),
+            (None, Some(_)) => changes.non_breaking_change(&location, "operation added"),
+            (None, None) => {}
+        }
+    }
+}
+
+fn find_param<'a>(params: &'a [Value], param: &Value) -> Option<&'a Value> {
+    params
+        .iter()
+        .find(|item| item["name"] == param["name"] && item["in"] == param["in"])
+}
+
+fn is_true(value: &Value) -> bool {
+    value.as_bool().unwrap_or(false)
+}
+
+fn diff_operation(location: &str, old: &Value, new: &Value, changes: &mut Changes) {
+    let empty = Vec::new();
+    let old_params = old["parameters"].as_array().unwrap_or(&empty);
+    let new_params = new["parameters"].as_array().unwrap_or(&empty);
+    for old_param in old_params {
+        let location = format!(
+            "{location}.parameters.{}",
+            old_param["name"].as_str().unwrap_or_default()
+        );
+        match find_param(new_params, old_param) {
+            Some(new_param) => {
+                if !is_true(&old_param["required"]) && is_true(&new_param["required"]) {
+                    changes.breaking_change(&location, "parameter became required");
+                } else if is_true(&old_param["required"]) && !is_true(&new_param["required"]) {
+                    changes.non_breaking_change(&location, "parameter became optional");
+                }
+                diff_schema(
+                    &location,
+                    Direction::Request,
+                    &old_param["schema"],
+                    &new_param["schema"],
+                    changes,
+                );
+            }
+            None => changes.breaking_change(&location, "parameter removed"),
+        }
+    }
+    for new_param in new_params
+        .iter()
+        .filter(|param| find_param(old_params, param).is_none())
+    {
+        let location = format!(
+            "{location}.parameters.{}",
+            new_param["name"].as_str().unwrap_or_default()
+        );
+        if is_true(&new_param["required"]) {
+            changes.breaking_change(&location, "required parameter added");
+        } else {
+            changes.non_breaking_change(&location, "optional parameter added");
+        }
+    }
+
+    let location_body = format!("{location}.requestBody");
+    match (old.get("requestBody"), new.get("requestBody")) {
+        (Some(old_body), Some(new_body)) => {
+            if !is_true(&old_body["required"]) && is_true(&new_body["required"]) {
+                changes.breaking_change(&location_body, "request body became required");
+            }
+            diff_content(
+                &location_body,
+                Direction::Request,
+                old_body,
+                new_body,
+                changes,
+            );
+        }
+        (Some(_), None) => changes.breaking_change(&location_body, "request body removed"),
+        (None, Some(new_body)) => {
+            if is_true(&new_body["required"]) {
+                changes.breaking_change(&location_body, "required request body added");
+            } else {
+                changes.non_breaking_change(&location_body, "request body added");
+            }
+        }
+        (None, None) => {}
+    }
+
+    let empty = Map::new();
+    let old_responses = old["responses"].as_object().unwrap_or(&empty);
+    let new_responses = new["responses"].as_object().unwrap_or(&empty);
+    for (status, old_resp) in sorted_entries(old_responses) {
+        let location = format!("{location}.responses.{status}");
+        match new_responses.get(status) {
+            Some(new_resp) => {
+                diff_content(&location, Direction::Response, old_resp, new_resp, changes)
+            }
+            None => changes.breaking_change(&location, "response removed"),
+        }
+    }
+    for (status, _) in sorted_entries(new_responses) {
+        if !old_responses.contains_key(status) {
+            changes
+                .non_breaking_change(&format!("{location}.responses.{status}"), "response added");
+        }
+    }
+}
+
+fn diff_content(
+    location: &str,
+    direction: Direction,
+    old: &Value,
+    new: &Value,
+    changes: &mut Changes,
+) {
+    let empty = Map::new();
+    let old_content = old["content"].as_object().unwrap_or(&empty);
+    let new_content = new["content"].as_object().unwrap_or(&empty);
+    for (content_type, old_media) in sorted_entries(old_content) {
+        let location = format!("{location}.content.{content_type}");
+        match new_content.get(content_type) {
+            Some(new_media) => diff_schema(
+                &location,
+                direction,
+                &old_media["schema"],
+                &new_media["schema"],
+                changes,
+            ),
+            None => changes.breaking_change(&location, "content type removed"),
+        }
+    }
+    for (content_type, _) in sorted_entries(new_content) {
+        if !old_content.contains_key(content_type) {
+            changes.non_breaking_change(
+                &format!("{location}.content.{content_type}"),
+                "content type added",
+            );
+        }
+    }
+}
+
+fn required_names(schema: &Value) -> Vec<&str> {
+    schema["required"]
+        .as_array()
+        .map(|items| items.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Compares a bound, returns `Some(true)` if it is tightened, `Some(false)` if
+/// it is loosened and `None` if it is unchanged.
+fn compare_bound(old: &Value, new: &Value, key: &str, exclusive_key: &str) -> Option<bool> {
+    let tighten_when_larger = key.starts_with("min");
+    match (old[key].as_f64(), new[key].as_f64()) {
+        (None, None) => None,
+        (None, Some(_)) => Some(true),
+        (Some(_), None) => Some(false),
+        (Some(a), Some(b)) if a != b => Some((b > a) == tighten_when_larger),
+        (Some(_), Some(_)) => {
+            let old_exclusive = is_true(&old[exclusive_key]);
+            let new_exclusive = is_true(&new[exclusive_key]);
+            (old_exclusive != new_exclusive).then_some(new_exclusive)
+        }
+    }
+}
+
+fn diff_schema(
+    location: &str,
+    direction: Direction,
+    old: &Value,
+    new: &Value,
+    changes: &mut Changes,
+) {
+    if old == new {
+        return;
+    }
+
+    for key in ["$ref", "type", "format"] {
+        if old.get(key) != new.get(key) {
+            changes.breaking_change(
+                location,
+                format!("`{key}` changed from {} to {}", old[key], new[key]),
+            );
+            return;
+        }
+    }
+
+    if let (Some(old_items), Some(new_items)) = (old["enum"].as_array(), new["enum"].as_array()) {
+        for item in old_items.iter().filter(|item| !new_items.contains(item)) {
+            changes.tightened(direction, location, format!("enum value {item} removed"));
+        }
+        for item in new_items.iter().filter(|item| !old_items.contains(item)) {
+            changes.loosened(direction, location, format!("enum value {item} added"));
+        }
+    } else if new.get("enum").is_some() {
+        changes.tightened(direction, location, "enum values added");
+    } else if old.get("enum").is_some() {
+        changes.loosened(direction, location, "enum values removed");
+    }
+
+    for (key, exclusive_key) in [
+        ("maximum", "exclusiveMaximum"),
+        ("minimum", "exclusiveMinimum"),
+        ("maxLength", ""),
+        ("minLength", ""),
+        ("maxItems", ""),
+        ("minItems", ""),
+        ("maxProperties", ""),
+        ("minProperties", ""),
+    ] {
+        match compare_bound(old, new, key, exclusive_key) {
+            Some(true) => changes.tightened(direction, location, format!("`{key}` tightened")),
+            Some(false) => changes.loosened(direction, location, format!("`{key}` loosened")),
+            None => {}
+        }
+    }
+    if old.get("pattern") != new.get("pattern") {
+        match (old.get("pattern"), new.get("pattern")) {
+            (None, Some(_)) => changes.tightened(direction, location, "`pattern` added"),
+            (Some(_), None) => changes.loosened(direction, location, "`pattern` removed"),
+            _ => changes.breaking_change(location, "`pattern` changed"),
+        }
+    }
+
+    let empty = Map::new();
+    let old_props = old["properties"].as_object().unwrap_or(&empty);
+    let new_props = new["properties"].as_object().unwrap_or(&empty);
+    let old_required = required_names(old);
+    let new_required = required_names(new);
+    for (name, old_prop) in sorted_entries(old_props) {
+        let location = format!("{location}.properties.{name}");
+        match new_props.get(name) {
+            Some(new_prop) => {
+                match (
+                    old_required.contains(&name.as_str()),
+                    new_required.contains(&name.as_str()),
+                ) {
+                    (false, true) => {
+                        changes.tightened(direction, &location, "property became required")
+                    }
+                    (true, false) => {
+                        changes.loosened(direction, &location, "property became optional")
+                    }
+                    _ => {}
+                }
+                diff_schema(&location, direction, old_prop, new_prop, changes);
+            }
+            None => changes.breaking_change(&location, "property removed"),
+        }
+    }
+    for (name, _) in sorted_entries(new_props) {
+        if old_props.contains_key(name) {
+            continue;
+        }
+        let location = format!("{location}.properties.{name}");
+        if new_required.contains(&name.as_str()) {
+            changes.tightened(direction, &location, "required property added");
+        } else {
+            changes.non_breaking_change(&location, "optional property added");
+        }
+    }
+
+    match (old.get("items"), new.get("items")) {
+        (Some(old_items), Some(new_items)) => diff_schema(
+            &format!("{location}.items"),
+            direction,
+            old_items,
+            new_items,
+            changes,
+        ),
+        (Some(_), None) | (None, Some(_)) => changes.breaking_change(location, "`items` changed"),
+        (None, None) => {}
+    }
+
+    for key in ["allOf", "oneOf", "anyOf"] {
+        let empty = Vec::new();
+        let old_schemas = old[key].as_array().unwrap_or(&empty);
+        let new_schemas = new[key].as_array().unwrap_or(&empty);
+        if old_schemas.len() != new_schemas.len() {
+            changes.breaking_change(location, format!("`{key}` changed"));
+            continue;
+        }
+        for (idx, (old_schema, new_schema)) in old_schemas.iter().zip(new_schemas).enumerate() {
+            diff_schema(
+                &format!("{location}.{key}[{idx}]"),
+                direction,
+                old_schema,
+                new_schema,
+                changes,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn descriptions(changes: &Changes, kind: ChangeKind) -> Vec<String> {
+        changes
+            .changes
+            .iter()
+            .filter(|change| change.kind == kind)
+            .map(|change| format!("{}: {}", change.location, change.description))
+            .collect()
+    }
+
+    #[test]
+    fn no_changes() {
+        let doc = json!({
+            "paths": {"/a": {"get": {"responses": {"200": {"description": ""}}}}},
+        });
+        assert!(diff(&doc, &doc).is_empty());
+    }
+
+    #[test]
+    fn paths_and_parameters() {
+        let old = json!({
+            "paths": {
+                "/a": {
+                    "get": {
+                        "parameters": [
+                            {"name": "x", "in": "query", "required": false, "schema": {"type": "string"}},
+                            {"name": "y", "in": "query", "required": false, "schema": {"type": "string"}},
+                        ],
+                        "responses": {"200": {"description": ""}},
+                    },
+                    "delete": {"responses": {}},
+                },
+                "/b": {"get": {"responses": {}}},
+            },
+        });
+        let new = json!({
+            "paths": {
+                "/a": {
+                    "get": {
+                        "parameters": [
+                            {"name": "x", "in": "query", "required": true, "schema": {"type": "string"}},
+                            {"name": "z", "in": "query", "required": false, "schema": {"type": "string"}},
+                        ],
+                        "responses": {"200": {"description": ""}},
+                    },
+                },
+                "/c": {"get": {"responses": {}}},
+            },
+        });
+        let changes = diff(&old, &new);
+        assert!(changes.is_breaking());
+        assert_eq!(
+            descriptions(&changes, ChangeKind::Breaking),
+            vec![
+                "paths./a.get.parameters.x: parameter became required",
+                "paths./a.get.parameters.y: parameter removed",
+                "paths./a.delete: operation removed",
+                "paths./b: path removed",
+            ]
+        );
+        assert_eq!(
+            descriptions(&changes, ChangeKind::NonBreaking),
+            vec![
+                "paths./a.get.parameters.z: optional parameter added",
+                "paths./c: path added",
+            ]
+        );
+    }
+
+    fn user_document(paths: Value, user: Value) -> Value {
+        json!({
+            "paths": paths,
+            "components": {"schemas": {"User": user}},
+        })
+    }
+
+    fn request_paths() -> Value {
+        json!({
+            "/users": {
+                "post": {
+                    "requestBody": {
+                        "content": {"application/json": {"schema": {"$ref": "#/components/schemas/User"}}},
+                    },
+                    "responses": {},
+                },
+            },
+        })
+    }
+
+    fn response_paths() -> Value {
+        json!({
+            "/users": {
+                "get": {
+                    "responses": {
+                        "200": {
+                            "content": {"application/json": {"schema": {
+                                "type": "array",
+                                "items": {"$ref": "#/components/schemas/User"},
+                            }}},
+                        },
+                    },
+                },
+            },
+        })
+    }
+
+    fn old_user() -> Value {
+        json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": {"type": "string", "maxLength": 32},
+                "age": {"type": "integer", "format": "int32"},
+                "email": {"type": "string"},
+                "role": {"type": "string", "enum": ["admin", "guest"]},
+            },
+        })
+    }
+
+    fn new_user() -> Value {
+        json!({
+            "type": "object",
+            "required": ["name", "age", "id"],
+            "properties": {
+                "id": {"type": "integer", "format": "int64"},
+                "name": {"type": "string", "maxLength": 16},
+                "age": {"type": "integer", "format": "int32"},
+                "role": {"type": "string", "enum": ["admin", "owner"]},
+                "nickname": {"type": "string"},
+            },
+        })
+    }
+
+    #[test]
+    fn schemas() {
+        let old = user_document(request_paths(), old_user());
+        let new = user_document(request_paths(), new_user());
+        let changes = diff(&old, &new);
+        assert_eq!(
+            descriptions(&changes, ChangeKind::Breaking),
+            vec![
+                "components.schemas.User.properties.age: property became required",
+                "components.schemas.User.properties.email: property removed",
+                "components.schemas.User.properties.name: `maxLength` tightened",
+                "components.schemas.User.properties.role: enum value \"guest\" removed",
+                "components.schemas.User.properties.id: required property added",
+            ]
+        );
+        assert_eq!(
+            descriptions(&changes, ChangeKind::NonBreaking),
+            vec![
+                "components.schemas.User.properties.role: enum value \"owner\" added",
+                "components.schemas.User.properties.nickname: optional property added",
+            ]
+        );
+    }
+
+    #[test]
+    fn response_schemas() {
+        let old = user_document(response_paths(), old_user());
+        let new = user_document(response_paths(), new_user());
+        let changes = diff(&old, &new);
+        assert_eq!(
+            descriptions(&changes, ChangeKind::Breaking),
+            vec![
+                "components.schemas.User.properties.email: property removed",
+                "components.schemas.User.properties.role: enum value \"owner\" added",
+            ]
+        );
+        assert_eq!(
+            descriptions(&changes, ChangeKind::NonBreaking),
+            vec![
+                "components.schemas.User.properties.age: property became required",
+                "components.schemas.User.properties.name: `maxLength` tightened",
+                "components.schemas.User.properties.role: enum value \"guest\" removed",
+                "components.schemas.User.properties.id: required property added",
+                "components.schemas.User.properties.nickname: optional property added",
+            ]
+        );
+    }
+
+    #[test]
+    fn unused_schemas() {
+        let old = user_document(json!({}), old_user());
+        let new = user_document(json!({}), new_user());
+        let changes = diff(&old, &new);
+        assert_eq!(
+            descriptions(&changes, ChangeKind::NonBreaking),
+            vec!["components.schemas.User.properties.nickname: optional property added"]
+        );
+    }
+
+    #[test]
+    fn exclusive_bounds() {
+        let old = json!({
+            "paths": {
+                "/a": {
+                    "get": {
+                        "parameters": [
+                            {"name": "x", "in": "query", "schema": {"type": "integer", "minimum": 1}},
+                            {"name": "y", "in": "query", "schema": {"type": "integer", "maximum": 10, "exclusiveMaximum": true}},
+                        ],
+                        "responses": {},
+                    },
+                },
+            },
+        });
+        let new = json!({
+            "paths": {
+                "/a": {
+                    "get": {
+                        "parameters": [
+                            {"name": "x", "in": "query", "schema": {"type": "integer", "minimum": 1, "exclusiveMinimum": true}},
+                            {"name": "y", "in": "query", "schema": {"type": "integer", "maximum": 10}},
+                        ],
+                        "responses": {},
+                    },
+                },
+            },
+        });
+        let changes = diff(&old, &new);
+        assert_eq!(
+            descriptions(&changes, ChangeKind::Breaking),
+            vec!["paths./a.get.parameters.x: `minimum` tightened"]
+        );
+        assert_eq!(
+            descriptions(&changes, ChangeKind::NonBreaking),
+            vec!["paths./a.get.parameters.y: `maximum` loosened"]
+        );
+    }
+
+    #[test]
+    fn type_changed() {
+        let old = json!({"components": {"schemas": {"A": {"type": "string"}}}});
+        let new = json!({"components": {"schemas": {"A": {"type": "integer"}}}});
+        let changes = diff(&old, &new);
+        assert_eq!(
+            changes.changes[0].to_string(),
+            "[breaking] components.schemas.A: `type` changed from \"string\" to \"integer\""
+        );
+    }
+}
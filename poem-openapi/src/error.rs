@@ -86,6 +86,37 @@ impl ResponseError for ContentTypeError {
     }
 }
 
+/// Multipart field content type error.
+#[derive(Debug, Error)]
+#[error("the content type of multipart field `{name}` is not supported: {content_type}")]
+pub struct MultipartFieldContentTypeError {
+    /// The name of the field.
+    pub name: &'static str,
+
+    /// The content type of the field.
+    pub content_type: String,
+}
+
+impl ResponseError for MultipartFieldContentTypeError {
+    fn status(&self) -> StatusCode {
+        StatusCode::UNSUPPORTED_MEDIA_TYPE
+    }
+}
+
+/// Request validation error.
+#[derive(Debug, Error)]
+#[error("request validation failed: {}", .errors.join("; "))]
+pub struct RequestValidationError {
+    /// The rules violated by the request.
+    pub errors: Vec<String>,
+}
+
+impl ResponseError for RequestValidationError {
+    fn status(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+}
+
 /// Authorization error.
 #[derive(Debug, Error)]
 #[error("authorization error")]
@@ -96,3 +127,14 @@ impl ResponseError for AuthorizationError {
         StatusCode::UNAUTHORIZED
     }
 }
+
+/// Insufficient scope error.
+#[derive(Debug, Error)]
+#[error("insufficient scope")]
+pub struct InsufficientScopeError;
+
+impl ResponseError for InsufficientScopeError {
+    fn status(&self) -> StatusCode {
+        StatusCode::FORBIDDEN
+    }
+}
@@ -107,9 +107,11 @@
 //! | uuid             | Integrate with the [`uuid` crate](https://crates.io/crates/uuid)                       |
 //! | url              | Integrate with the [`url` crate](https://crates.io/crates/url)                         |
 //! | geo              | Integrate with the [`geo-types` crate](https://crates.io/crates/geo-types)             |
+//! | charset          | Support for decoding the `Json` and `Form` payloads in non-UTF-8 charsets              |
 //! | bson             | Integrate with the [`bson` crate](https://crates.io/crates/bson)                       |
 //! | rust_decimal     | Integrate with the [`rust_decimal` crate](https://crates.io/crates/rust_decimal)       |
 //! | prost-wkt-types  | Integrate with the [`prost-wkt-types` crate](https://crates.io/crates/prost-wkt-types) |
+//! | protobuf         | Support for the `Protobuf` payload with [`prost`](https://crates.io/crates/prost)      |
 //! | static-files     | Support for static file response                                                       |
 //! | websocket        | Support for websocket                                                                  |
 
@@ -126,6 +128,7 @@
 pub mod macros;
 
 pub mod auth;
+pub mod diff;
 pub mod error;
 pub mod param;
 pub mod payload;
@@ -139,6 +142,7 @@ pub mod validation;
 mod base;
 mod openapi;
 mod path_util;
+mod request_validation;
 #[cfg(any(
     feature = "swagger-ui",
     feature = "rapidoc",
@@ -149,10 +153,11 @@ mod ui;
 
 pub use base::{
     ApiExtractor, ApiExtractorType, ApiResponse, ExtractParamOptions, OAuthScopes, OpenApi,
-    OperationId, ResponseContent, Tags, Webhook,
+    OperationId, OperationTags, ResponseContent, Tags, Webhook,
 };
 pub use openapi::{
-    ContactObject, ExternalDocumentObject, ExtraHeader, LicenseObject, OpenApiService, ServerObject,
+    ContactObject, ExternalDocumentObject, ExtraHeader, LicenseObject, OpenApiService,
+    ServerObject, ServerVariableObject,
 };
 #[doc = include_str!("docs/request.md")]
 pub use poem_openapi_derive::ApiRequest;
@@ -180,6 +185,16 @@ pub use poem_openapi_derive::Tags;
 pub use poem_openapi_derive::Union;
 #[doc = include_str!("docs/webhook.md")]
 pub use poem_openapi_derive::Webhook;
+pub use request_validation::RequestValidation;
+#[cfg(feature = "websocket")]
+#[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
+pub use response::WebSocketResponse;
+#[cfg(feature = "rapidoc")]
+pub use ui::rapidoc::{RapiDocConfig, RapiDocRenderStyle, RapiDocTheme};
+#[cfg(feature = "redoc")]
+pub use ui::redoc::RedocConfig;
+#[cfg(feature = "swagger-ui")]
+pub use ui::swagger_ui::{SwaggerUIConfig, SwaggerUIDocExpansion, SwaggerUIOAuthConfig};
 pub use validation::Validator;
 
 #[doc(hidden)]
@@ -190,5 +205,16 @@ pub mod __private {
     pub use serde;
     pub use serde_json;
 
-    pub use crate::{auth::CheckerReturn, base::UrlQuery, path_util::join_path};
+    pub use crate::{
+        auth::{
+            add_security_requirements, and_security_requirements, is_missing_credentials,
+            missing_credentials, CheckerReturn,
+        },
+        base::{OptionalExtractor, UrlQuery},
+        path_util::join_path,
+        types::{
+            as_string::{number_to_string, schema_as_string, string_to_number},
+            multipart::multipart_content_type_matches,
+        },
+    };
 }
@@ -23,6 +23,8 @@ macro_rules! impl_apirequest_for_payload {
                     content: vec![$crate::registry::MetaMediaType {
                         content_type: <Self as $crate::payload::Payload>::CONTENT_TYPE,
                         schema: <Self as $crate::payload::Payload>::schema_ref(),
+                        examples: vec![],
+                        encoding: vec![],
                     }],
                     required: <Self as $crate::payload::ParsePayload>::IS_REQUIRED,
                 })
@@ -44,6 +46,9 @@ macro_rules! impl_apirequest_for_payload {
 
                         }
                     }
+                    None if poem::web::ContentTypeMode::from_request(request) == poem::web::ContentTypeMode::Lenient => {
+                        <Self as $crate::payload::ParsePayload>::from_request(request, body).await
+                    }
                     None => Err($crate::error::ContentTypeError::ExpectContentType.into()),
                 }
             }
@@ -1,23 +1,30 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     marker::PhantomData,
+    sync::Arc,
 };
 
 use poem::{
     endpoint::{make_sync, BoxEndpoint},
+    http::{HeaderName, HeaderValue, Method},
     middleware::CookieJarManager,
     web::cookie::CookieKey,
     Endpoint, EndpointExt, IntoEndpoint, Request, Response, Result, Route, RouteMethod,
 };
+use serde_json::Value;
 
 use crate::{
     base::UrlQuery,
     registry::{
         Document, MetaContact, MetaExternalDocument, MetaHeader, MetaInfo, MetaLicense,
-        MetaOperationParam, MetaParamIn, MetaSchemaRef, MetaServer, Registry,
+        MetaMediaType, MetaOperationParam, MetaParamIn, MetaResponse, MetaResponses, MetaSchemaRef,
+        MetaServer, MetaServerVariable, Registry,
     },
-    types::Type,
-    OpenApi, Webhook,
+    request_validation::{
+        route_path_to_oai_path, OperationValidator, RequestValidation, RequestValidationEndpoint,
+    },
+    types::{ToHeader, Type},
+    ApiResponse, OpenApi, Webhook,
 };
 
 /// An object representing a Server.
@@ -25,6 +32,7 @@ use crate::{
 pub struct ServerObject {
     url: String,
     description: Option<String>,
+    variables: BTreeMap<String, ServerVariableObject>,
 }
 
 impl<T: Into<String>> From<T> for ServerObject {
@@ -39,6 +47,7 @@ impl ServerObject {
         Self {
             url: url.into(),
             description: None,
+            variables: BTreeMap::new(),
         }
     }
 
@@ -50,6 +59,58 @@ impl ServerObject {
             ..self
         }
     }
+
+    /// Adds a variable for server URL template substitution.
+    #[must_use]
+    pub fn variable(mut self, name: impl Into<String>, variable: ServerVariableObject) -> Self {
+        self.variables.insert(name.into(), variable);
+        self
+    }
+}
+
+/// An object representing a Server Variable for server URL template
+/// substitution.
+///
+/// Reference: <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#server-variable-object>
+#[derive(Debug, Clone)]
+pub struct ServerVariableObject {
+    default: String,
+    enum_values: Vec<String>,
+    description: Option<String>,
+}
+
+impl ServerVariableObject {
+    /// Create a server variable object by the default value.
+    pub fn new(default: impl Into<String>) -> ServerVariableObject {
+        Self {
+            default: default.into(),
+            enum_values: Vec::new(),
+            description: None,
+        }
+    }
+
+    /// Sets an enumeration of values to be used if the substitution options
+    /// are from a limited set.
+    #[must_use]
+    pub fn enum_values<I, V>(self, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        Self {
+            enum_values: values.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+
+    /// Sets an string describing the server variable.
+    #[must_use]
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
 }
 
 /// A contact information for the exposed API.
@@ -212,7 +273,55 @@ impl ExtraHeader {
     }
 }
 
+type ExtraResponses = (fn() -> MetaResponses, fn(&mut Registry));
+
+type SpecTransform = Arc<dyn Fn(&mut Document) + Send + Sync>;
+
+type UiGuard = Arc<dyn Fn(&Request) -> Result<()> + Send + Sync>;
+
 /// An OpenAPI service for Poem.
+///
+/// # Protecting the documentation
+///
+/// The UI endpoints (such as `OpenApiService::swagger_ui`) and the
+/// specification endpoints (such as [`OpenApiService::spec_endpoint`]) are
+/// ordinary Poem endpoints, they are only served at the paths they are nested
+/// at, and can be wrapped with middlewares like any other endpoint. To check
+/// the requests to all of them at once, use [`OpenApiService::ui_guard`].
+///
+/// ```
+/// use poem::{http::StatusCode, Endpoint, EndpointExt, Error, Route};
+/// use poem_openapi::{payload::PlainText, OpenApi, OpenApiService};
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/hello", method = "get")]
+///     async fn index(&self) -> PlainText<&'static str> {
+///         PlainText("hello, world!")
+///     }
+/// }
+///
+/// let api_service = OpenApiService::new(Api, "Demo", "1.0");
+/// let spec = api_service.spec_endpoint().before(|req| async move {
+///     match req.headers().get("x-docs-token") {
+///         Some(token) if token == "secret" => Ok(req),
+///         _ => Err(Error::from_status(StatusCode::UNAUTHORIZED)),
+///     }
+/// });
+/// let app = Route::new()
+///     .nest("/api", api_service)
+///     .nest("/internal/openapi.json", spec);
+/// ```
+///
+/// # Allowed methods
+///
+/// The `OPTIONS` requests of each path without an `OPTIONS` operation are
+/// answered with an `Allow` header listing the methods of the operations of
+/// the path. With [`Cors::allow_methods_from_route`](poem::middleware::Cors::allow_methods_from_route),
+/// the CORS preflight requests also only allow these methods, so the runtime
+/// behavior stays in sync with the specification.
 #[derive(Clone)]
 pub struct OpenApiService<T, W> {
     api: T,
@@ -223,7 +332,13 @@ pub struct OpenApiService<T, W> {
     cookie_key: Option<CookieKey>,
     extra_response_headers: Vec<(ExtraHeader, MetaSchemaRef, bool)>,
     extra_request_headers: Vec<(ExtraHeader, MetaSchemaRef, bool)>,
+    extra_responses: Vec<ExtraResponses>,
+    response_headers: Vec<(HeaderName, HeaderValue)>,
+    spec_transforms: Vec<SpecTransform>,
+    extensions: BTreeMap<String, Value>,
     url_prefix: Option<String>,
+    request_validation: Option<RequestValidation>,
+    ui_guard: Option<UiGuard>,
 }
 
 impl<T> OpenApiService<T, ()> {
@@ -247,7 +362,13 @@ impl<T> OpenApiService<T, ()> {
             cookie_key: None,
             extra_response_headers: vec![],
             extra_request_headers: vec![],
+            extra_responses: vec![],
+            response_headers: vec![],
+            spec_transforms: vec![],
+            extensions: BTreeMap::new(),
             url_prefix: None,
+            request_validation: None,
+            ui_guard: None,
         }
     }
 }
@@ -264,7 +385,13 @@ impl<T, W> OpenApiService<T, W> {
             cookie_key: self.cookie_key,
             extra_response_headers: self.extra_response_headers,
             extra_request_headers: self.extra_request_headers,
+            extra_responses: self.extra_responses,
+            response_headers: self.response_headers,
+            spec_transforms: self.spec_transforms,
+            extensions: self.extensions,
             url_prefix: None,
+            request_validation: self.request_validation,
+            ui_guard: self.ui_guard,
         }
     }
 
@@ -298,11 +425,27 @@ impl<T, W> OpenApiService<T, W> {
         self.servers.push(MetaServer {
             url: server.url,
             description: server.description,
+            variables: server
+                .variables
+                .into_iter()
+                .map(|(name, variable)| {
+                    (
+                        name,
+                        MetaServerVariable {
+                            default: variable.default,
+                            enum_values: variable.enum_values,
+                            description: variable.description,
+                        },
+                    )
+                })
+                .collect(),
         });
         self
     }
 
     /// Sets the contact information for the exposed API.
+    ///
+    /// Reference: <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#contact-object>
     #[must_use]
     pub fn contact(mut self, contact: ContactObject) -> Self {
         self.info.contact = Some(MetaContact {
@@ -343,6 +486,21 @@ impl<T, W> OpenApiService<T, W> {
         self
     }
 
+    /// Add a specification extension to the document, the name must begin
+    /// with `x-`.
+    ///
+    /// Reference: <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#specification-extensions>
+    #[must_use]
+    pub fn extension(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        let name = name.into();
+        assert!(
+            name.starts_with("x-"),
+            "the name of the specification extension `{name}` must begin with `x-`"
+        );
+        self.extensions.insert(name, value.into());
+        self
+    }
+
     /// Add extra response header
     #[must_use]
     pub fn extra_response_header<HT, H>(mut self, header: H) -> Self
@@ -356,6 +514,52 @@ impl<T, W> OpenApiService<T, W> {
         self
     }
 
+    /// Add a header with the specified value to all responses.
+    ///
+    /// Unlike [`OpenApiService::extra_response_header`], which only documents
+    /// the header, the header is also added to the responses at runtime,
+    /// including the error responses, unless the operation already sets it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name or the value of the header is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem_openapi::{payload::PlainText, OpenApi, OpenApiService};
+    ///
+    /// struct Api;
+    ///
+    /// #[OpenApi]
+    /// impl Api {
+    ///     #[oai(path = "/hello", method = "get")]
+    ///     async fn index(&self) -> PlainText<&'static str> {
+    ///         PlainText("hello, world!")
+    ///     }
+    /// }
+    ///
+    /// let api_service =
+    ///     OpenApiService::new(Api, "Demo", "1.0").response_header("X-API-Version", "1.0");
+    /// ```
+    #[must_use]
+    pub fn response_header<HT, H>(mut self, header: H, value: HT) -> Self
+    where
+        HT: ToHeader,
+        H: Into<ExtraHeader>,
+    {
+        let extra_header = header.into();
+        let name = HeaderName::from_bytes(extra_header.name.as_bytes())
+            .unwrap_or_else(|_| panic!("invalid response header name: {}", extra_header.name));
+        let value = value
+            .to_header()
+            .unwrap_or_else(|| panic!("invalid value of response header: {}", extra_header.name));
+        self.response_headers.push((name, value));
+        self.extra_response_headers
+            .push((extra_header, HT::schema_ref(), true));
+        self
+    }
+
     /// Add extra request header
     #[must_use]
     pub fn extra_request_header<HT, H>(mut self, header: H) -> Self
@@ -369,6 +573,59 @@ impl<T, W> OpenApiService<T, W> {
         self
     }
 
+    /// Add extra responses to all operations.
+    ///
+    /// This is useful for the error responses shared by every operation, such
+    /// as `401`, `429` or `500`. If an operation already declares a response
+    /// with the same status code, the response of the operation is kept.
+    #[must_use]
+    pub fn extra_responses<R: ApiResponse>(mut self) -> Self {
+        self.extra_responses.push((R::meta, R::register));
+        self
+    }
+
+    /// Add a function to modify the document before it is served, for
+    /// example to add vendor extensions, remove paths or rewrite the servers.
+    ///
+    /// The functions are called in the order they are added, and the schemas
+    /// which are no longer used are removed afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem_openapi::{payload::PlainText, OpenApi, OpenApiService};
+    ///
+    /// struct Api;
+    ///
+    /// #[OpenApi]
+    /// impl Api {
+    ///     #[oai(path = "/hello", method = "get")]
+    ///     async fn hello(&self) -> PlainText<&'static str> {
+    ///         PlainText("hello")
+    ///     }
+    ///
+    ///     #[oai(path = "/internal/stats", method = "get")]
+    ///     async fn stats(&self) -> PlainText<&'static str> {
+    ///         PlainText("stats")
+    ///     }
+    /// }
+    ///
+    /// let api_service = OpenApiService::new(Api, "Demo", "1.0").with_spec_transform(|doc| {
+    ///     for api in &mut doc.apis {
+    ///         api.paths.retain(|path| !path.path.starts_with("/internal"));
+    ///     }
+    /// });
+    /// assert!(!api_service.spec().contains("/internal"));
+    /// ```
+    #[must_use]
+    pub fn with_spec_transform<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut Document) + Send + Sync + 'static,
+    {
+        self.spec_transforms.push(Arc::new(f));
+        self
+    }
+
     /// Sets the cookie key.
     #[must_use]
     pub fn cookie_key(self, key: CookieKey) -> Self {
@@ -386,6 +643,62 @@ impl<T, W> OpenApiService<T, W> {
         }
     }
 
+    /// Validates incoming requests against the generated document.
+    ///
+    /// See [`RequestValidation`] for the checked rules.
+    #[must_use]
+    pub fn request_validation(self, validation: RequestValidation) -> Self {
+        Self {
+            request_validation: Some(validation),
+            ..self
+        }
+    }
+
+    /// Checks the requests to the UI and specification endpoints created by
+    /// this service, such as [`OpenApiService::spec_endpoint`], with the
+    /// specified function, the requests are rejected with the returned error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{http::StatusCode, Error};
+    /// use poem_openapi::{OpenApi, OpenApiService};
+    ///
+    /// struct Api;
+    ///
+    /// #[OpenApi]
+    /// impl Api {}
+    ///
+    /// let api_service = OpenApiService::new(Api, "Demo", "1.0").ui_guard(|req| {
+    ///     match req.headers().get("x-docs-token") {
+    ///         Some(token) if token == "secret" => Ok(()),
+    ///         _ => Err(Error::from_status(StatusCode::UNAUTHORIZED)),
+    ///     }
+    /// });
+    /// let spec = api_service.spec_endpoint();
+    /// ```
+    #[must_use]
+    pub fn ui_guard<F>(self, f: F) -> Self
+    where
+        F: Fn(&Request) -> Result<()> + Send + Sync + 'static,
+    {
+        Self {
+            ui_guard: Some(Arc::new(f)),
+            ..self
+        }
+    }
+
+    fn guard_endpoint<E: Endpoint>(&self, ep: E) -> impl Endpoint<Output = E::Output> {
+        let guard = self.ui_guard.clone();
+        ep.before(move |req| {
+            let res = match &guard {
+                Some(guard) => guard(&req),
+                None => Ok(()),
+            };
+            async move { res.map(|()| req) }
+        })
+    }
+
     /// Create the OpenAPI Explorer endpoint.
     #[must_use]
     #[cfg(feature = "openapi-explorer")]
@@ -394,7 +707,7 @@ impl<T, W> OpenApiService<T, W> {
         T: OpenApi,
         W: Webhook,
     {
-        crate::ui::openapi_explorer::create_endpoint(&self.spec())
+        self.guard_endpoint(crate::ui::openapi_explorer::create_endpoint(&self.spec()))
     }
 
     /// Create the OpenAPI Explorer HTML
@@ -415,7 +728,18 @@ impl<T, W> OpenApiService<T, W> {
         T: OpenApi,
         W: Webhook,
     {
-        crate::ui::swagger_ui::create_endpoint(&self.spec())
+        self.swagger_ui_with_config(&Default::default())
+    }
+
+    /// Create the Swagger UI endpoint with the configuration.
+    #[must_use]
+    #[cfg(feature = "swagger-ui")]
+    pub fn swagger_ui_with_config(&self, config: &crate::SwaggerUIConfig) -> impl Endpoint
+    where
+        T: OpenApi,
+        W: Webhook,
+    {
+        self.guard_endpoint(crate::ui::swagger_ui::create_endpoint(&self.spec(), config))
     }
 
     /// Create the Swagger UI HTML
@@ -425,7 +749,17 @@ impl<T, W> OpenApiService<T, W> {
         T: OpenApi,
         W: Webhook,
     {
-        crate::ui::swagger_ui::create_html(&self.spec())
+        self.swagger_ui_html_with_config(&Default::default())
+    }
+
+    /// Create the Swagger UI HTML with the configuration.
+    #[cfg(feature = "swagger-ui")]
+    pub fn swagger_ui_html_with_config(&self, config: &crate::SwaggerUIConfig) -> String
+    where
+        T: OpenApi,
+        W: Webhook,
+    {
+        crate::ui::swagger_ui::create_html(&self.spec(), config)
     }
 
     /// Create the Rapidoc endpoint.
@@ -436,7 +770,18 @@ impl<T, W> OpenApiService<T, W> {
         T: OpenApi,
         W: Webhook,
     {
-        crate::ui::rapidoc::create_endpoint(&self.spec())
+        self.rapidoc_with_config(&Default::default())
+    }
+
+    /// Create the Rapidoc endpoint with the configuration.
+    #[must_use]
+    #[cfg(feature = "rapidoc")]
+    pub fn rapidoc_with_config(&self, config: &crate::RapiDocConfig) -> impl Endpoint
+    where
+        T: OpenApi,
+        W: Webhook,
+    {
+        self.guard_endpoint(crate::ui::rapidoc::create_endpoint(&self.spec(), config))
     }
 
     /// Create the Rapidoc HTML
@@ -446,7 +791,17 @@ impl<T, W> OpenApiService<T, W> {
         T: OpenApi,
         W: Webhook,
     {
-        crate::ui::rapidoc::create_html(&self.spec())
+        self.rapidoc_html_with_config(&Default::default())
+    }
+
+    /// Create the Rapidoc HTML with the configuration.
+    #[cfg(feature = "rapidoc")]
+    pub fn rapidoc_html_with_config(&self, config: &crate::RapiDocConfig) -> String
+    where
+        T: OpenApi,
+        W: Webhook,
+    {
+        crate::ui::rapidoc::create_html(&self.spec(), config)
     }
 
     /// Create the Redoc endpoint.
@@ -457,7 +812,18 @@ impl<T, W> OpenApiService<T, W> {
         T: OpenApi,
         W: Webhook,
     {
-        crate::ui::redoc::create_endpoint(&self.spec())
+        self.redoc_with_config(&Default::default())
+    }
+
+    /// Create the Redoc endpoint with the configuration.
+    #[must_use]
+    #[cfg(feature = "redoc")]
+    pub fn redoc_with_config(&self, config: &crate::RedocConfig) -> impl Endpoint
+    where
+        T: OpenApi,
+        W: Webhook,
+    {
+        self.guard_endpoint(crate::ui::redoc::create_endpoint(&self.spec(), config))
     }
 
     /// Create the Redoc HTML
@@ -468,7 +834,18 @@ impl<T, W> OpenApiService<T, W> {
         T: OpenApi,
         W: Webhook,
     {
-        crate::ui::redoc::create_html(&self.spec())
+        self.redoc_html_with_config(&Default::default())
+    }
+
+    /// Create the Redoc HTML with the configuration.
+    #[must_use]
+    #[cfg(feature = "redoc")]
+    pub fn redoc_html_with_config(&self, config: &crate::RedocConfig) -> String
+    where
+        T: OpenApi,
+        W: Webhook,
+    {
+        crate::ui::redoc::create_html(&self.spec(), config)
     }
 
     /// Create an endpoint to serve the open api specification as JSON.
@@ -478,11 +855,11 @@ impl<T, W> OpenApiService<T, W> {
         W: Webhook,
     {
         let spec = self.spec();
-        make_sync(move |_| {
+        self.guard_endpoint(make_sync(move |_| {
             Response::builder()
                 .content_type("application/json")
                 .body(spec.clone())
-        })
+        }))
     }
 
     /// Create an endpoint to serve the open api specification as YAML.
@@ -492,15 +869,32 @@ impl<T, W> OpenApiService<T, W> {
         W: Webhook,
     {
         let spec = self.spec_yaml();
-        make_sync(move |_| {
+        self.guard_endpoint(make_sync(move |_| {
             Response::builder()
                 .content_type("application/x-yaml")
                 .header("Content-Disposition", "inline; filename=\"spec.yaml\"")
                 .body(spec.clone())
-        })
+        }))
+    }
+
+    /// Returns the OpenAPI document.
+    pub fn document(&self) -> Document
+    where
+        T: OpenApi,
+        W: Webhook,
+    {
+        let mut doc = self.untransformed_document();
+        for transform in &self.spec_transforms {
+            transform(&mut doc);
+        }
+        doc.remove_unused_schemas();
+
+        doc
     }
 
-    fn document(&self) -> Document<'_>
+    /// Returns the document generated from the APIs, before the spec
+    /// transforms are applied.
+    fn untransformed_document(&self) -> Document
     where
         T: OpenApi,
         W: Webhook,
@@ -508,6 +902,12 @@ impl<T, W> OpenApiService<T, W> {
         let mut registry = Registry::new();
         let mut apis = T::meta();
 
+        T::register(&mut registry);
+        W::register(&mut registry);
+        for (_, register) in &self.extra_responses {
+            register(&mut registry);
+        }
+
         // update extra request headers
         for operation in apis
             .iter_mut()
@@ -527,11 +927,70 @@ impl<T, W> OpenApiService<T, W> {
                         required: *is_required,
                         deprecated: header.deprecated,
                         explode: true,
+                        extensions: BTreeMap::new(),
                     },
                 );
             }
         }
 
+        // update extra responses
+        for (meta, _) in &self.extra_responses {
+            for operation in apis
+                .iter_mut()
+                .flat_map(|meta_api| meta_api.paths.iter_mut())
+                .flat_map(|path| path.operations.iter_mut())
+            {
+                for resp in meta().responses {
+                    if !operation
+                        .responses
+                        .responses
+                        .iter()
+                        .any(|item| item.status == resp.status)
+                    {
+                        operation.responses.responses.push(resp);
+                    }
+                }
+            }
+        }
+
+        // add the bad request response for operations with validators
+        for operation in apis
+            .iter_mut()
+            .flat_map(|meta_api| meta_api.paths.iter_mut())
+            .flat_map(|path| path.operations.iter_mut())
+        {
+            let has_bad_request = operation
+                .responses
+                .responses
+                .iter()
+                .any(|resp| resp.status == Some(400));
+            let has_validators = operation
+                .params
+                .iter()
+                .map(|param| &param.schema)
+                .chain(
+                    operation
+                        .request
+                        .iter()
+                        .flat_map(|request| request.content.iter())
+                        .map(|media_type| &media_type.schema),
+                )
+                .any(|schema_ref| registry.has_validators(schema_ref));
+            if !has_bad_request && has_validators {
+                operation.responses.responses.push(MetaResponse {
+                    description: "The request is invalid",
+                    status: Some(400),
+                    content: vec![MetaMediaType {
+                        content_type: "text/plain; charset=utf-8",
+                        schema: String::schema_ref(),
+                        examples: vec![],
+                        encoding: vec![],
+                    }],
+                    headers: vec![],
+                });
+            }
+        }
+
         // update extra response headers
         for resp in apis
             .iter_mut()
@@ -555,23 +1014,38 @@ impl<T, W> OpenApiService<T, W> {
             }
         }
 
-        T::register(&mut registry);
-        W::register(&mut registry);
+        // generate the cURL examples, relative to the first server
+        let base_url = self
+            .servers
+            .first()
+            .map(|server| server.url.trim_end_matches('/'))
+            .unwrap_or("http://localhost");
+        let url_prefix = self.url_prefix.as_deref().unwrap_or_default();
+        for path in apis
+            .iter_mut()
+            .flat_map(|meta_api| meta_api.paths.iter_mut())
+        {
+            let url = format!("{base_url}{url_prefix}{}", path.path);
+            for operation in &mut path.operations {
+                if operation.curl_example {
+                    let code_sample = operation.curl_code_sample(&url);
+                    operation.code_samples.push(code_sample);
+                }
+            }
+        }
 
         let webhooks = W::meta();
 
-        let mut doc = Document {
-            info: &self.info,
-            servers: &self.servers,
+        Document {
+            info: self.info.clone(),
+            servers: self.servers.clone(),
             apis,
             webhooks,
             registry,
-            external_document: self.external_document.as_ref(),
-            url_prefix: self.url_prefix.as_deref(),
-        };
-        doc.remove_unused_schemas();
-
-        doc
+            external_document: self.external_document.clone(),
+            extensions: self.extensions.clone(),
+            url_prefix: self.url_prefix.clone(),
+        }
     }
 
     /// Returns the OAS specification file as JSON.
@@ -584,6 +1058,19 @@ impl<T, W> OpenApiService<T, W> {
         serde_json::to_string_pretty(&doc).unwrap()
     }
 
+    /// Returns the OAS specification as a JSON value.
+    ///
+    /// This can be compared with a previous version of the specification
+    /// using [`diff`](crate::diff::diff).
+    pub fn spec_value(&self) -> Value
+    where
+        T: OpenApi,
+        W: Webhook,
+    {
+        let doc = self.document();
+        serde_json::to_value(&doc).unwrap()
+    }
+
     /// Returns the OAS specification file as YAML.
     pub fn spec_yaml(&self) -> String
     where
@@ -600,11 +1087,31 @@ impl<T: OpenApi, W: Webhook> IntoEndpoint for OpenApiService<T, W> {
 
     fn into_endpoint(self) -> Self::Endpoint {
         async fn extract_query(mut req: Request) -> Result<Request> {
-            let url_query: Vec<(String, String)> = req.params().unwrap_or_default();
+            let url_query = req
+                .query()
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
             req.extensions_mut().insert(UrlQuery(url_query));
             Ok(req)
         }
 
+        let mut validators = HashMap::new();
+        if let Some(config) = self.request_validation {
+            // the validators enforce the APIs as declared, the spec transforms
+            // only change the published document
+            let Document { apis, registry, .. } = self.untransformed_document();
+            let schemas = Arc::new(registry.schemas);
+            for path in apis.into_iter().flat_map(|api| api.paths) {
+                for operation in path.operations {
+                    validators.insert(
+                        (path.path.clone(), operation.method.clone()),
+                        OperationValidator::new(config, operation, schemas.clone()),
+                    );
+                }
+            }
+        }
+
         let cookie_jar_manager = match self.cookie_key {
             Some(key) => CookieJarManager::with_key(key),
             None => CookieJarManager::new(),
@@ -616,10 +1123,13 @@ impl<T: OpenApi, W: Webhook> IntoEndpoint for OpenApiService<T, W> {
             .into_iter()
             .flat_map(|api| api.paths.into_iter())
             .flat_map(|path| path.operations.into_iter())
+            .chain(W::meta().into_iter().map(|webhook| webhook.operation))
         {
             if let Some(operation_id) = operation.operation_id {
                 if !operation_ids.insert(operation_id) {
-                    panic!("duplicate operation id: {operation_id}");
+                    panic!(
+                        "duplicate operation id: {operation_id}, use `#[oai(operation_id = \"...\")]` to specify a unique one"
+                    );
                 }
             }
         }
@@ -630,29 +1140,296 @@ impl<T: OpenApi, W: Webhook> IntoEndpoint for OpenApiService<T, W> {
         let route = items
             .into_iter()
             .fold(Route::new(), |route, (path, paths)| {
+                let oai_path = route_path_to_oai_path(&path);
+                // Register the methods in a fixed order, so that the `Allow`
+                // header does not depend on the order of the hash map.
+                let mut paths = paths.into_iter().collect::<Vec<_>>();
+                paths.sort_by(|(a, _), (b, _)| {
+                    (method_order(a), a.as_str()).cmp(&(method_order(b), b.as_str()))
+                });
                 route.at(
                     path,
-                    paths
-                        .into_iter()
-                        .fold(RouteMethod::new(), |route_method, (method, ep)| {
+                    paths.into_iter().fold(
+                        RouteMethod::new().auto_options(true),
+                        |route_method, (method, ep)| {
+                            let ep = match validators.remove(&(oai_path.clone(), method.clone())) {
+                                Some(validator) => RequestValidationEndpoint {
+                                    inner: ep,
+                                    validator,
+                                }
+                                .boxed(),
+                                None => ep,
+                            };
                             route_method.method(method, ep)
-                        }),
+                        },
+                    ),
                 )
             });
 
-        route
+        let ep = route
             .with(cookie_jar_manager)
             .before(extract_query)
-            .map_to_response()
-            .boxed()
+            .map_to_response();
+        if self.response_headers.is_empty() {
+            return ep.boxed();
+        }
+
+        let response_headers = Arc::new(self.response_headers);
+        ep.around(move |ep, req| {
+            let response_headers = response_headers.clone();
+            async move {
+                // the errors are converted to responses to add the headers
+                let mut resp = ep.get_response(req).await;
+                for (name, value) in response_headers.iter() {
+                    if !resp.headers().contains_key(name) {
+                        resp.headers_mut().insert(name.clone(), value.clone());
+                    }
+                }
+                Ok(resp)
+            }
+        })
+        .boxed()
     }
 }
 
+fn method_order(method: &Method) -> usize {
+    const METHODS: [Method; 9] = [
+        Method::GET,
+        Method::POST,
+        Method::PUT,
+        Method::DELETE,
+        Method::PATCH,
+        Method::HEAD,
+        Method::OPTIONS,
+        Method::TRACE,
+        Method::CONNECT,
+    ];
+    METHODS
+        .iter()
+        .position(|m| m == method)
+        .unwrap_or(METHODS.len())
+}
+
 #[cfg(test)]
 mod tests {
+    use poem::{http::StatusCode, test::TestClient};
+
     use super::*;
     use crate::OpenApi;
 
+    #[tokio::test]
+    async fn protected_spec_endpoint() {
+        struct Api;
+
+        #[OpenApi(internal)]
+        impl Api {
+            #[oai(path = "/", method = "get")]
+            async fn test(&self) {}
+        }
+
+        let api_service = OpenApiService::new(Api, "demo", "1.0");
+        let spec = api_service.spec_endpoint().before(|req| async move {
+            match req.headers().get("x-docs-token") {
+                Some(token) if token == "secret" => Ok(req),
+                _ => Err(poem::Error::from_status(StatusCode::UNAUTHORIZED)),
+            }
+        });
+        let client = TestClient::new(Route::new().nest("/internal/openapi.json", spec));
+
+        client
+            .get("/internal/openapi.json")
+            .send()
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+        client
+            .get("/internal/openapi.json")
+            .header("x-docs-token", "secret")
+            .send()
+            .await
+            .assert_status_is_ok();
+        client
+            .get("/openapi.json")
+            .send()
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::result_large_err)]
+    async fn ui_guard() {
+        struct Api;
+
+        #[OpenApi(internal)]
+        impl Api {
+            #[oai(path = "/", method = "get")]
+            async fn test(&self) {}
+        }
+
+        let api_service = OpenApiService::new(Api, "demo", "1.0").ui_guard(|req| {
+            match req.headers().get("x-docs-token") {
+                Some(token) if token == "secret" => Ok(()),
+                _ => Err(poem::Error::from_status(StatusCode::UNAUTHORIZED)),
+            }
+        });
+        let app = Route::new()
+            .nest("/spec.json", api_service.spec_endpoint())
+            .nest("/spec.yaml", api_service.spec_endpoint_yaml());
+        #[cfg(feature = "swagger-ui")]
+        let app = app.nest("/docs", api_service.swagger_ui());
+        let client = TestClient::new(app.nest("/api", api_service));
+
+        let mut paths = vec!["/spec.json", "/spec.yaml"];
+        if cfg!(feature = "swagger-ui") {
+            paths.push("/docs");
+        }
+        for path in paths {
+            client
+                .get(path)
+                .send()
+                .await
+                .assert_status(StatusCode::UNAUTHORIZED);
+            client
+                .get(path)
+                .header("x-docs-token", "secret")
+                .send()
+                .await
+                .assert_status_is_ok();
+        }
+
+        // the operations are not guarded
+        client.get("/api").send().await.assert_status_is_ok();
+    }
+
+    #[tokio::test]
+    async fn options_and_cors() {
+        struct Api;
+
+        #[OpenApi(internal)]
+        impl Api {
+            #[oai(path = "/users/:id", method = "get")]
+            async fn get_user(&self, _id: crate::param::Path<i32>) {}
+
+            #[oai(path = "/users/:id", method = "delete")]
+            async fn delete_user(&self, _id: crate::param::Path<i32>) {}
+        }
+
+        let api_service = OpenApiService::new(Api, "demo", "1.0");
+        let client = TestClient::new(
+            Route::new()
+                .nest("/api", api_service)
+                .with(poem::middleware::Cors::new().allow_methods_from_route(true)),
+        );
+
+        let resp = client.options("/api/users/1").send().await;
+        resp.assert_status(StatusCode::NO_CONTENT);
+        resp.assert_header("allow", "GET, DELETE, HEAD, OPTIONS");
+
+        let resp = client
+            .options("/api/users/1")
+            .header("origin", "https://example.com")
+            .header("access-control-request-method", "DELETE")
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_header_csv(
+            "access-control-allow-methods",
+            ["GET", "DELETE", "HEAD", "OPTIONS"],
+        );
+
+        client
+            .options("/api/users/1")
+            .header("origin", "https://example.com")
+            .header("access-control-request-method", "PUT")
+            .send()
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn info() {
+        struct Api;
+
+        #[OpenApi(internal)]
+        impl Api {
+            #[oai(path = "/", method = "get")]
+            async fn test(&self) {}
+        }
+
+        let api_service = OpenApiService::new(Api, "demo", "1.0")
+            .summary("a demo api")
+            .terms_of_service("https://example.com/terms")
+            .contact(
+                ContactObject::new()
+                    .name("sunli")
+                    .url("https://example.com")
+                    .email("sunli@example.com"),
+            )
+            .license(LicenseObject::new("MIT").identifier("MIT"));
+        let spec: serde_json::Value = serde_json::from_str(&api_service.spec()).unwrap();
+
+        assert_eq!(
+            spec["info"],
+            serde_json::json!({
+                "title": "demo",
+                "summary": "a demo api",
+                "version": "1.0",
+                "termsOfService": "https://example.com/terms",
+                "contact": {
+                    "name": "sunli",
+                    "url": "https://example.com",
+                    "email": "sunli@example.com",
+                },
+                "license": {
+                    "name": "MIT",
+                    "identifier": "MIT",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn server_variables() {
+        struct Api;
+
+        #[OpenApi(internal)]
+        impl Api {
+            #[oai(path = "/", method = "get")]
+            async fn test(&self) {}
+        }
+
+        let api_service = OpenApiService::new(Api, "demo", "1.0")
+            .server("https://api.example.com")
+            .server(
+                ServerObject::new("https://{region}.api.example.com:{port}")
+                    .variable(
+                        "region",
+                        ServerVariableObject::new("us")
+                            .enum_values(["us", "eu"])
+                            .description("The region of the server"),
+                    )
+                    .variable("port", ServerVariableObject::new("443")),
+            );
+        let spec: serde_json::Value = serde_json::from_str(&api_service.spec()).unwrap();
+
+        assert_eq!(
+            spec["servers"],
+            serde_json::json!([
+                { "url": "https://api.example.com" },
+                {
+                    "url": "https://{region}.api.example.com:{port}",
+                    "variables": {
+                        "port": { "default": "443" },
+                        "region": {
+                            "default": "us",
+                            "enum": ["us", "eu"],
+                            "description": "The region of the server",
+                        },
+                    },
+                },
+            ])
+        );
+    }
+
     #[test]
     fn extra_response_headers() {
         struct Api;
@@ -686,6 +1463,107 @@ mod tests {
         assert_eq!(headers[2].schema, f32::schema_ref());
     }
 
+    #[tokio::test]
+    async fn response_header() {
+        struct Api;
+
+        #[OpenApi(internal)]
+        impl Api {
+            #[oai(path = "/a", method = "get")]
+            async fn a(&self) {}
+
+            #[oai(path = "/b", method = "get")]
+            async fn b(&self) -> VersionResponse {
+                VersionResponse::Ok("2.0".to_string())
+            }
+
+            #[oai(path = "/error", method = "get")]
+            async fn error(&self) -> poem::Result<()> {
+                Err(poem::Error::from_status(StatusCode::BAD_REQUEST))
+            }
+        }
+
+        #[derive(crate::ApiResponse)]
+        #[oai(internal)]
+        enum VersionResponse {
+            #[oai(status = 200)]
+            Ok(#[oai(header = "X-API-Version")] String),
+        }
+
+        let api_service = OpenApiService::new(Api, "demo", "1.0")
+            .response_header("X-API-Version", "1.0")
+            .response_header(ExtraHeader::new("X-Limit").description("abc"), 100);
+        let doc = api_service.document();
+        let headers = &doc.apis[0].paths[0].operations[0].responses.responses[0].headers;
+        assert_eq!(headers[0].name, "X-API-VERSION");
+        assert!(headers[0].required);
+        assert_eq!(headers[0].schema, String::schema_ref());
+        assert_eq!(headers[1].name, "X-LIMIT");
+        assert_eq!(headers[1].description.as_deref(), Some("abc"));
+        assert_eq!(headers[1].schema, i32::schema_ref());
+
+        let cli = TestClient::new(api_service);
+        let resp = cli.get("/a").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_header("X-API-Version", "1.0");
+        resp.assert_header("X-Limit", "100");
+
+        let resp = cli.get("/b").send().await;
+        resp.assert_header("X-API-Version", "2.0");
+        resp.assert_header("X-Limit", "100");
+
+        let resp = cli.get("/error").send().await;
+        resp.assert_status(StatusCode::BAD_REQUEST);
+        resp.assert_header("X-API-Version", "1.0");
+        resp.assert_header("X-Limit", "100");
+
+        let resp = cli.get("/c").send().await;
+        resp.assert_status(StatusCode::NOT_FOUND);
+        resp.assert_header("X-API-Version", "1.0");
+    }
+
+    #[test]
+    fn spec_transform() {
+        #[derive(crate::Object)]
+        #[oai(internal)]
+        struct Stats {
+            count: i32,
+        }
+
+        struct Api;
+
+        #[OpenApi(internal)]
+        impl Api {
+            #[oai(path = "/hello", method = "get")]
+            async fn hello(&self) {}
+
+            #[oai(path = "/internal/stats", method = "get")]
+            async fn stats(&self) -> crate::payload::Json<Stats> {
+                crate::payload::Json(Stats { count: 0 })
+            }
+        }
+
+        let api_service = OpenApiService::new(Api, "demo", "1.0")
+            .server("http://localhost:3000")
+            .with_spec_transform(|doc| {
+                for api in &mut doc.apis {
+                    api.paths.retain(|path| !path.path.starts_with("/internal"));
+                }
+            })
+            .with_spec_transform(|doc| {
+                doc.servers[0].url = "https://api.example.com".to_string();
+                doc.extensions
+                    .insert("x-logo".to_string(), Value::from("logo.png"));
+            });
+
+        let spec = api_service.spec_value();
+        assert!(spec["paths"]["/hello"].is_object());
+        assert!(spec["paths"].get("/internal/stats").is_none());
+        assert!(spec["components"]["schemas"].get("Stats").is_none());
+        assert_eq!(spec["servers"][0]["url"], "https://api.example.com");
+        assert_eq!(spec["x-logo"], "logo.png");
+    }
+
     #[test]
     fn extra_request_headers() {
         struct Api;
@@ -721,4 +1599,135 @@ mod tests {
         assert!(params[2].deprecated);
         assert_eq!(params[2].schema, f32::schema_ref());
     }
+
+    #[test]
+    fn extra_responses() {
+        use crate::{payload::Json, ApiResponse, Object};
+
+        #[derive(Object)]
+        #[oai(internal)]
+        struct ErrorBody {
+            message: String,
+        }
+
+        #[derive(ApiResponse)]
+        #[oai(internal)]
+        #[allow(dead_code)]
+        enum CommonErrors {
+            #[oai(status = 401)]
+            Unauthorized(Json<ErrorBody>),
+            #[oai(status = 404)]
+            NotFound,
+            #[oai(status = 500)]
+            InternalError(Json<ErrorBody>),
+        }
+
+        #[derive(ApiResponse)]
+        #[oai(internal)]
+        #[allow(dead_code)]
+        enum GetResponse {
+            #[oai(status = 200)]
+            Ok,
+            /// Custom not found
+            #[oai(status = 404)]
+            NotFound,
+        }
+
+        struct Api;
+
+        #[OpenApi(internal)]
+        impl Api {
+            #[oai(path = "/a", method = "get")]
+            async fn a(&self) {}
+
+            #[oai(path = "/b", method = "get")]
+            async fn b(&self) -> GetResponse {
+                GetResponse::Ok
+            }
+        }
+
+        let api_service = OpenApiService::new(Api, "demo", "1.0").extra_responses::<CommonErrors>();
+        let doc = api_service.document();
+
+        let statuses = |idx: usize| {
+            doc.apis[0].paths[idx].operations[0]
+                .responses
+                .responses
+                .iter()
+                .map(|resp| (resp.status, resp.description))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            statuses(0),
+            vec![
+                (Some(200), ""),
+                (Some(401), ""),
+                (Some(404), ""),
+                (Some(500), "")
+            ]
+        );
+        assert_eq!(
+            statuses(1),
+            vec![
+                (Some(200), ""),
+                (Some(404), "Custom not found"),
+                (Some(401), ""),
+                (Some(500), "")
+            ]
+        );
+        assert!(doc.registry.schemas.contains_key("ErrorBody"));
+    }
+
+    #[test]
+    fn bad_request_for_validators() {
+        use crate::{param::Query, payload::Json, Object};
+
+        #[derive(Object)]
+        #[oai(internal)]
+        struct Inner {
+            #[oai(validator(max_length = 10))]
+            name: String,
+        }
+
+        #[derive(Object)]
+        #[oai(internal)]
+        struct Obj {
+            inner: Vec<Inner>,
+        }
+
+        struct Api;
+
+        #[OpenApi(internal)]
+        impl Api {
+            #[oai(path = "/a", method = "get")]
+            async fn a(&self, #[oai(validator(maximum(value = "10")))] _n: Query<i32>) {}
+
+            #[oai(path = "/b", method = "post")]
+            async fn b(&self, _obj: Json<Obj>) {}
+
+            #[oai(path = "/c", method = "get")]
+            async fn c(&self, _n: Query<i32>) {}
+        }
+
+        let api_service = OpenApiService::new(Api, "demo", "1.0");
+        let doc = api_service.document();
+        let statuses = |idx: usize| {
+            doc.apis[0].paths[idx].operations[0]
+                .responses
+                .responses
+                .iter()
+                .map(|resp| resp.status)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(statuses(0), vec![Some(200), Some(400)]);
+        assert_eq!(statuses(1), vec![Some(200), Some(400)]);
+        assert_eq!(statuses(2), vec![Some(200)]);
+
+        let bad_request = &doc.apis[0].paths[0].operations[0].responses.responses[1];
+        assert_eq!(
+            bad_request.content[0].content_type,
+            "text/plain; charset=utf-8"
+        );
+        assert_eq!(bad_request.content[0].schema, String::schema_ref());
+    }
 }
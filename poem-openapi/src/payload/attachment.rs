@@ -32,6 +32,10 @@ impl AttachmentType {
 }
 
 /// A binary payload for download file.
+///
+/// The `Content-Disposition` header of the response is set from the
+/// attachment type and the file name, and is documented in the
+/// specification.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Attachment<T> {
     data: Binary<T>,
@@ -49,7 +53,8 @@ impl<T: Into<Body> + Send> Attachment<T> {
         }
     }
 
-    /// Specify the attachment. (defaults to: [`AttachmentType::Inline`])
+    /// Specify the attachment type. (defaults to:
+    /// [`AttachmentType::Attachment`])
     #[must_use]
     pub fn attachment_type(self, ty: AttachmentType) -> Self {
         Self { ty, ..self }
@@ -67,14 +72,33 @@ impl<T: Into<Body> + Send> Attachment<T> {
     fn content_disposition(&self) -> String {
         let mut content_disposition = self.ty.as_str().to_string();
 
-        if let Some(legal_filename) = self.filename.as_ref().map(|filename| {
-            filename
-                .replace('\\', "\\\\")
-                .replace('\"', "\\\"")
-                .replace('\r', "\\\r")
-                .replace('\n', "\\\n")
-        }) {
+        if let Some(filename) = &self.filename {
+            // the quoted `filename` parameter only supports ASCII characters, so
+            // the original name is also sent in the `filename*` parameter
+            // (RFC 6266)
+            let mut legal_filename = String::with_capacity(filename.len());
+            for ch in filename.chars() {
+                match ch {
+                    '\\' | '"' => {
+                        legal_filename.push('\\');
+                        legal_filename.push(ch);
+                    }
+                    ' '..='~' => legal_filename.push(ch),
+                    _ => legal_filename.push('_'),
+                }
+            }
             _ = write!(content_disposition, "; filename=\"{legal_filename}\"");
+
+            if filename.bytes().any(|b| !(b' '..=b'~').contains(&b)) {
+                content_disposition.push_str("; filename*=UTF-8''");
+                for b in filename.bytes() {
+                    if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                        content_disposition.push(b as char);
+                    } else {
+                        _ = write!(content_disposition, "%{b:02X}");
+                    }
+                }
+            }
         }
 
         content_disposition
@@ -107,6 +131,8 @@ impl<T: Into<Body> + Send> ApiResponse for Attachment<T> {
                 content: vec![MetaMediaType {
                     content_type: Self::CONTENT_TYPE,
                     schema: Self::schema_ref(),
+                    examples: vec![],
+                    encoding: vec![],
                 }],
                 headers: vec![MetaHeader {
                     name: "Content-Disposition".to_string(),
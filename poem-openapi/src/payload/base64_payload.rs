@@ -152,6 +152,8 @@ impl<T: AsRef<[u8]> + Send> ApiResponse for Base64<T> {
                 content: vec![MetaMediaType {
                     content_type: Self::CONTENT_TYPE,
                     schema: Self::schema_ref(),
+                    examples: vec![],
+                    encoding: vec![],
                 }],
                 headers: vec![],
             }],
@@ -144,6 +144,8 @@ impl<T: Into<Body> + Send> ApiResponse for Binary<T> {
                 content: vec![MetaMediaType {
                     content_type: Self::CONTENT_TYPE,
                     schema: Self::schema_ref(),
+                    examples: vec![],
+                    encoding: vec![],
                 }],
                 headers: vec![],
             }],
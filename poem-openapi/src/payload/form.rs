@@ -1,5 +1,6 @@
 use std::ops::{Deref, DerefMut};
 
+use bytes::Bytes;
 use poem::{FromRequest, Request, RequestBody, Result};
 use serde::de::DeserializeOwned;
 
@@ -53,7 +54,13 @@ impl<T: DeserializeOwned> ParsePayload for Form<T> {
     const IS_REQUIRED: bool = true;
 
     async fn from_request(req: &Request, body: &mut RequestBody) -> Result<Self> {
-        let data = Vec::<u8>::from_request(req, body).await?;
+        let data = Bytes::from_request(req, body).await?;
+        let data =
+            poem::__private::form_body_to_utf8(req.content_type(), data).map_err(|charset| {
+                ParseRequestPayloadError {
+                    reason: format!("unsupported charset `{charset}`"),
+                }
+            })?;
         Ok(Self(serde_urlencoded::from_bytes(&data).map_err(
             |err| ParseRequestPayloadError {
                 reason: err.to_string(),
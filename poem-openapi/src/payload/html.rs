@@ -66,6 +66,8 @@ impl<T: Into<String> + Send> ApiResponse for Html<T> {
                 content: vec![MetaMediaType {
                     content_type: Self::CONTENT_TYPE,
                     schema: Self::schema_ref(),
+                    examples: vec![],
+                    encoding: vec![],
                 }],
                 headers: vec![],
             }],
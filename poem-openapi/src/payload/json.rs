@@ -1,5 +1,6 @@
 use std::ops::{Deref, DerefMut};
 
+use bytes::Bytes;
 use poem::{FromRequest, IntoResponse, Request, RequestBody, Response, Result};
 use serde_json::Value;
 
@@ -54,7 +55,12 @@ impl<T: ParseFromJSON> ParsePayload for Json<T> {
     const IS_REQUIRED: bool = true;
 
     async fn from_request(request: &Request, body: &mut RequestBody) -> Result<Self> {
-        let data = Vec::<u8>::from_request(request, body).await?;
+        let data = Bytes::from_request(request, body).await?;
+        let data = poem::__private::text_body_to_utf8(request.content_type(), data).map_err(
+            |charset| ParseRequestPayloadError {
+                reason: format!("unsupported charset `{charset}`"),
+            },
+        )?;
         let value = if data.is_empty() {
             Value::Null
         } else {
@@ -85,6 +91,8 @@ impl<T: ToJSON> ApiResponse for Json<T> {
                 content: vec![MetaMediaType {
                     content_type: Self::CONTENT_TYPE,
                     schema: Self::schema_ref(),
+                    examples: vec![],
+                    encoding: vec![],
                 }],
                 headers: vec![],
             }],
@@ -7,7 +7,10 @@ mod event_stream;
 mod form;
 mod html;
 mod json;
+mod multipart;
 mod plain_text;
+#[cfg(feature = "protobuf")]
+mod protobuf;
 mod response;
 mod xml;
 mod yaml;
@@ -16,6 +19,8 @@ use std::future::Future;
 
 use poem::{Request, RequestBody, Result};
 
+#[cfg(feature = "protobuf")]
+pub use self::protobuf::Protobuf;
 pub use self::{
     attachment::{Attachment, AttachmentType},
     base64_payload::Base64,
@@ -24,6 +29,7 @@ pub use self::{
     form::Form,
     html::Html,
     json::Json,
+    multipart::{MultipartMixed, MultipartPart, MultipartRelated},
     plain_text::PlainText,
     response::Response,
     xml::Xml,
@@ -0,0 +1,300 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    io::Error as IoError,
+};
+
+use bytes::Bytes;
+use futures_util::{stream, Stream, StreamExt};
+use poem::{
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    Body, IntoResponse, Response,
+};
+
+use crate::{
+    payload::Payload,
+    registry::{MetaMediaType, MetaResponse, MetaResponses, MetaSchema, MetaSchemaRef, Registry},
+    types::ToJSON,
+    ApiResponse,
+};
+
+/// A part of the [`MultipartMixed`] and [`MultipartRelated`] payloads.
+///
+/// The body of the part can be a stream created with
+/// [`Body::from_bytes_stream`], it is sent without being buffered.
+pub struct MultipartPart {
+    headers: HeaderMap,
+    body: Body,
+}
+
+impl MultipartPart {
+    /// Create a part with the content type and the body.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the content type is not a valid header value.
+    pub fn new(content_type: &str, body: impl Into<Body>) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_str(content_type).expect("valid content type"),
+        );
+        Self {
+            headers,
+            body: body.into(),
+        }
+    }
+
+    /// Create a part with the value serialized as JSON.
+    pub fn json<T: ToJSON>(value: &T) -> Self {
+        Self::new("application/json; charset=utf-8", value.to_json_string())
+    }
+
+    /// Create a part with the binary data, whose content type is
+    /// `application/octet-stream`.
+    pub fn binary(data: impl Into<Body>) -> Self {
+        Self::new("application/octet-stream", data)
+    }
+
+    /// Appends a header to this part, it is ignored if the name or the value
+    /// is invalid.
+    #[must_use]
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName>,
+        V: TryInto<HeaderValue>,
+    {
+        if let (Ok(key), Ok(value)) = (key.try_into(), value.try_into()) {
+            self.headers.append(key, value);
+        }
+        self
+    }
+
+    /// Sets the `Content-ID` header of this part, which is used to reference
+    /// the parts of a [`MultipartRelated`] payload.
+    #[must_use]
+    pub fn content_id(self, id: impl AsRef<str>) -> Self {
+        self.header("content-id", format!("<{}>", id.as_ref()))
+    }
+
+    fn into_stream(
+        self,
+        boundary: &str,
+    ) -> impl Stream<Item = Result<Bytes, IoError>> + Send + 'static {
+        let mut head = format!("--{boundary}\r\n").into_bytes();
+        for (name, value) in &self.headers {
+            head.extend_from_slice(name.as_str().as_bytes());
+            head.extend_from_slice(b": ");
+            head.extend_from_slice(value.as_bytes());
+            head.extend_from_slice(b"\r\n");
+        }
+        head.extend_from_slice(b"\r\n");
+
+        stream::once(async move { Ok(Bytes::from(head)) })
+            .chain(self.body.into_bytes_stream())
+            .chain(stream::once(async { Ok(Bytes::from_static(b"\r\n")) }))
+    }
+}
+
+fn random_boundary() -> String {
+    let hash = RandomState::new().build_hasher().finish();
+    format!("poem-{hash:016x}")
+}
+
+fn multipart_body<S>(parts: S, boundary: String) -> Body
+where
+    S: Stream<Item = MultipartPart> + Send + 'static,
+{
+    let end = Bytes::from(format!("--{boundary}--\r\n"));
+    Body::from_bytes_stream(
+        parts
+            .flat_map(move |part| part.into_stream(&boundary))
+            .chain(stream::once(async move { Ok(end) })),
+    )
+}
+
+/// A `multipart/mixed` payload composed of the parts in a stream.
+///
+/// The parts are sent as soon as they are produced by the stream, so large
+/// responses are never buffered.
+///
+/// # Examples
+///
+/// ```rust
+/// use futures_util::{stream::BoxStream, StreamExt};
+/// use poem::test::TestClient;
+/// use poem_openapi::{
+///     payload::{MultipartMixed, MultipartPart},
+///     Object, OpenApi, OpenApiService,
+/// };
+///
+/// #[derive(Object)]
+/// struct Metadata {
+///     name: String,
+/// }
+///
+/// struct MyApi;
+///
+/// #[OpenApi]
+/// impl MyApi {
+///     #[oai(path = "/document", method = "get")]
+///     async fn document(&self) -> MultipartMixed<BoxStream<'static, MultipartPart>> {
+///         let parts = vec![
+///             MultipartPart::json(&Metadata {
+///                 name: "a.bin".to_string(),
+///             }),
+///             MultipartPart::binary(vec![1, 2, 3]),
+///         ];
+///         MultipartMixed::new(futures_util::stream::iter(parts).boxed()).boundary("boundary")
+///     }
+/// }
+///
+/// let api = OpenApiService::new(MyApi, "Demo", "0.1.0");
+/// let cli = TestClient::new(api);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli.get("/document").send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_content_type("multipart/mixed; boundary=boundary");
+/// # });
+/// ```
+pub struct MultipartMixed<S> {
+    parts: S,
+    boundary: Option<String>,
+}
+
+impl<S: Stream<Item = MultipartPart> + Send + 'static> MultipartMixed<S> {
+    /// Create a `multipart/mixed` payload with a stream of parts.
+    pub fn new(parts: S) -> Self {
+        Self {
+            parts,
+            boundary: None,
+        }
+    }
+
+    /// Sets the boundary between the parts, which defaults to a random
+    /// string.
+    #[must_use]
+    pub fn boundary(self, boundary: impl Into<String>) -> Self {
+        Self {
+            boundary: Some(boundary.into()),
+            ..self
+        }
+    }
+}
+
+impl<S: Stream<Item = MultipartPart> + Send + 'static> IntoResponse for MultipartMixed<S> {
+    fn into_response(self) -> Response {
+        let boundary = self.boundary.unwrap_or_else(random_boundary);
+        Response::builder()
+            .content_type(format!("{}; boundary={}", Self::CONTENT_TYPE, boundary))
+            .body(multipart_body(self.parts, boundary))
+    }
+}
+
+/// A `multipart/related` payload composed of the parts in a stream, such as
+/// the responses of the DICOMweb APIs.
+///
+/// The first part is the root part unless another one is specified with
+/// [`MultipartRelated::start`], the other parts are usually referenced by
+/// their [`MultipartPart::content_id`].
+pub struct MultipartRelated<S> {
+    parts: S,
+    boundary: Option<String>,
+    root_type: Option<String>,
+    start: Option<String>,
+}
+
+impl<S: Stream<Item = MultipartPart> + Send + 'static> MultipartRelated<S> {
+    /// Create a `multipart/related` payload with a stream of parts.
+    pub fn new(parts: S) -> Self {
+        Self {
+            parts,
+            boundary: None,
+            root_type: None,
+            start: None,
+        }
+    }
+
+    /// Sets the boundary between the parts, which defaults to a random
+    /// string.
+    #[must_use]
+    pub fn boundary(self, boundary: impl Into<String>) -> Self {
+        Self {
+            boundary: Some(boundary.into()),
+            ..self
+        }
+    }
+
+    /// Sets the `type` parameter, which is the content type of the root part,
+    /// such as `application/dicom`.
+    #[must_use]
+    pub fn root_type(self, root_type: impl Into<String>) -> Self {
+        Self {
+            root_type: Some(root_type.into()),
+            ..self
+        }
+    }
+
+    /// Sets the `start` parameter, which is the content id of the root part.
+    #[must_use]
+    pub fn start(self, content_id: impl Into<String>) -> Self {
+        Self {
+            start: Some(content_id.into()),
+            ..self
+        }
+    }
+}
+
+impl<S: Stream<Item = MultipartPart> + Send + 'static> IntoResponse for MultipartRelated<S> {
+    fn into_response(self) -> Response {
+        let boundary = self.boundary.unwrap_or_else(random_boundary);
+        let mut content_type = Self::CONTENT_TYPE.to_string();
+        if let Some(root_type) = &self.root_type {
+            content_type.push_str(&format!("; type=\"{root_type}\""));
+        }
+        if let Some(start) = &self.start {
+            content_type.push_str(&format!("; start=\"<{start}>\""));
+        }
+        content_type.push_str(&format!("; boundary={boundary}"));
+
+        Response::builder()
+            .content_type(content_type)
+            .body(multipart_body(self.parts, boundary))
+    }
+}
+
+macro_rules! impl_multipart_payload {
+    ($ty:ident, $content_type:literal) => {
+        impl<S: Stream<Item = MultipartPart> + Send + 'static> Payload for $ty<S> {
+            const CONTENT_TYPE: &'static str = $content_type;
+
+            fn schema_ref() -> MetaSchemaRef {
+                MetaSchemaRef::Inline(Box::new(MetaSchema::new_with_format("string", "binary")))
+            }
+        }
+
+        impl<S: Stream<Item = MultipartPart> + Send + 'static> ApiResponse for $ty<S> {
+            fn meta() -> MetaResponses {
+                MetaResponses {
+                    responses: vec![MetaResponse {
+                        description: "",
+                        status: Some(200),
+                        content: vec![MetaMediaType {
+                            content_type: Self::CONTENT_TYPE,
+                            schema: Self::schema_ref(),
+                            examples: vec![],
+                            encoding: vec![],
+                        }],
+                        headers: vec![],
+                    }],
+                }
+            }
+
+            fn register(_registry: &mut Registry) {}
+        }
+    };
+}
+
+impl_multipart_payload!(MultipartMixed, "multipart/mixed");
+impl_multipart_payload!(MultipartRelated, "multipart/related");
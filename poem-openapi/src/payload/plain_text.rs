@@ -1,8 +1,12 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
 
 use poem::{FromRequest, IntoResponse, Request, RequestBody, Response, Result};
 
 use crate::{
+    error::ParseRequestPayloadError,
     payload::{ParsePayload, Payload},
     registry::{MetaMediaType, MetaResponse, MetaResponses, MetaSchemaRef, Registry},
     types::Type,
@@ -10,6 +14,17 @@ use crate::{
 };
 
 /// A UTF8 string payload.
+///
+/// Besides strings, any type which implements [`Type`] can be used, the
+/// request body is parsed with [`FromStr`] and the response body is
+/// formatted with [`ToString`], and the schema of the payload is the schema
+/// of the type. For example, `PlainText<i64>` is documented as an integer
+/// with the `int64` format and `PlainText<Uuid>` as a string with the `uuid`
+/// format.
+///
+/// # Errors
+///
+/// - [`ParseRequestPayloadError`]
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct PlainText<T>(pub T);
 
@@ -27,7 +42,7 @@ impl<T> DerefMut for PlainText<T> {
     }
 }
 
-impl<T: Send> Payload for PlainText<T> {
+impl<T: Type> Payload for PlainText<T> {
     const CONTENT_TYPE: &'static str = "text/plain; charset=utf-8";
 
     fn check_content_type(content_type: &str) -> bool {
@@ -39,25 +54,33 @@ impl<T: Send> Payload for PlainText<T> {
     }
 
     fn schema_ref() -> MetaSchemaRef {
-        String::schema_ref()
+        T::schema_ref()
+    }
+
+    fn register(registry: &mut Registry) {
+        T::register(registry);
     }
 }
 
-impl ParsePayload for PlainText<String> {
+impl<T: FromStr + Type> ParsePayload for PlainText<T> {
     const IS_REQUIRED: bool = true;
 
     async fn from_request(request: &Request, body: &mut RequestBody) -> Result<Self> {
-        Ok(Self(String::from_request(request, body).await?))
+        let text = String::from_request(request, body).await?;
+        let value = text.parse().map_err(|_| ParseRequestPayloadError {
+            reason: format!("failed to parse `{}`", T::name()),
+        })?;
+        Ok(Self(value))
     }
 }
 
-impl<T: Into<String> + Send> IntoResponse for PlainText<T> {
+impl<T: ToString + Send> IntoResponse for PlainText<T> {
     fn into_response(self) -> Response {
-        self.0.into().into_response()
+        self.0.to_string().into_response()
     }
 }
 
-impl<T: Into<String> + Send> ApiResponse for PlainText<T> {
+impl<T: ToString + Type> ApiResponse for PlainText<T> {
     fn meta() -> MetaResponses {
         MetaResponses {
             responses: vec![MetaResponse {
@@ -66,13 +89,17 @@ impl<T: Into<String> + Send> ApiResponse for PlainText<T> {
                 content: vec![MetaMediaType {
                     content_type: Self::CONTENT_TYPE,
                     schema: Self::schema_ref(),
+                    examples: vec![],
+                    encoding: vec![],
                 }],
                 headers: vec![],
             }],
         }
     }
 
-    fn register(_registry: &mut Registry) {}
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
 }
 
-impl_apirequest_for_payload!(PlainText<String>);
+impl_apirequest_for_payload!(PlainText<T>, T: FromStr + Type);
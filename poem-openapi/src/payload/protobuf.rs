@@ -0,0 +1,131 @@
+use std::ops::{Deref, DerefMut};
+
+use poem::{FromRequest, IntoResponse, Request, RequestBody, Response, Result};
+use prost::Message;
+
+use crate::{
+    error::ParseRequestPayloadError,
+    payload::{ParsePayload, Payload},
+    registry::{MetaMediaType, MetaResponse, MetaResponses, MetaSchema, MetaSchemaRef, Registry},
+    ApiResponse,
+};
+
+/// A Protocol Buffers payload.
+///
+/// The message is documented as a binary string with the
+/// `application/protobuf` content type, so the same route can also accept
+/// other payloads, such as JSON, with the [`ApiRequest`](crate::ApiRequest)
+/// derive.
+///
+/// # Examples
+///
+/// ```rust
+/// use poem::test::TestClient;
+/// use poem_openapi::{payload::Protobuf, OpenApi, OpenApiService};
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// struct User {
+///     #[prost(string, tag = "1")]
+///     name: String,
+/// }
+///
+/// struct MyApi;
+///
+/// #[OpenApi]
+/// impl MyApi {
+///     #[oai(path = "/user", method = "post")]
+///     async fn create_user(&self, user: Protobuf<User>) -> Protobuf<User> {
+///         user
+///     }
+/// }
+///
+/// let api = OpenApiService::new(MyApi, "Demo", "0.1.0");
+/// let cli = TestClient::new(api);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli
+///     .post("/user")
+///     .body_protobuf(&User {
+///         name: "sunli".to_string(),
+///     })
+///     .send()
+///     .await;
+/// resp.assert_status_is_ok();
+/// resp.assert_content_type("application/protobuf");
+/// # });
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Protobuf<T>(pub T);
+
+impl<T> Deref for Protobuf<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Protobuf<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Send> Payload for Protobuf<T> {
+    const CONTENT_TYPE: &'static str = "application/protobuf";
+
+    fn check_content_type(content_type: &str) -> bool {
+        matches!(content_type.parse::<mime::Mime>(), Ok(content_type) if content_type.type_() == "application"
+                && (matches!(content_type.subtype().as_str(), "protobuf" | "x-protobuf")
+                || content_type
+                    .suffix()
+                    .map_or(false, |v| v == "proto" || v == "protobuf")))
+    }
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Inline(Box::new(MetaSchema {
+            format: Some("binary"),
+            ..MetaSchema::new("string")
+        }))
+    }
+}
+
+impl<T: Message + Default> ParsePayload for Protobuf<T> {
+    const IS_REQUIRED: bool = true;
+
+    async fn from_request(request: &Request, body: &mut RequestBody) -> Result<Self> {
+        let data = Vec::<u8>::from_request(request, body).await?;
+        let value = T::decode(data.as_slice()).map_err(|err| ParseRequestPayloadError {
+            reason: err.to_string(),
+        })?;
+        Ok(Self(value))
+    }
+}
+
+impl<T: Message> IntoResponse for Protobuf<T> {
+    fn into_response(self) -> Response {
+        poem::web::Protobuf(self.0).into_response()
+    }
+}
+
+impl<T: Message> ApiResponse for Protobuf<T> {
+    fn meta() -> MetaResponses {
+        MetaResponses {
+            responses: vec![MetaResponse {
+                description: "",
+                status: Some(200),
+                content: vec![MetaMediaType {
+                    content_type: Self::CONTENT_TYPE,
+                    schema: Self::schema_ref(),
+                    examples: vec![],
+                    encoding: vec![],
+                }],
+                headers: vec![],
+            }],
+        }
+    }
+
+    fn register(_registry: &mut Registry) {}
+}
+
+impl_apirequest_for_payload!(Protobuf<T>, T: Message + Default);
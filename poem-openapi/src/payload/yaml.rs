@@ -33,8 +33,8 @@ impl<T: Type> Payload for Yaml<T> {
     const CONTENT_TYPE: &'static str = "application/yaml; charset=utf-8";
 
     fn check_content_type(content_type: &str) -> bool {
-        matches!(content_type.parse::<mime::Mime>(), Ok(content_type) if content_type.type_() == "application"
-                && (content_type.subtype() == "yaml"
+        matches!(content_type.parse::<mime::Mime>(), Ok(content_type) if matches!(content_type.type_().as_str(), "application" | "text")
+                && (matches!(content_type.subtype().as_str(), "yaml" | "x-yaml")
                 || content_type
                     .suffix()
                     .map_or(false, |v| v == "yaml")))
@@ -85,6 +85,8 @@ impl<T: ToYAML> ApiResponse for Yaml<T> {
                 content: vec![MetaMediaType {
                     content_type: Self::CONTENT_TYPE,
                     schema: Self::schema_ref(),
+                    examples: vec![],
+                    encoding: vec![],
                 }],
                 headers: vec![],
             }],
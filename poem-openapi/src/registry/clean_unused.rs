@@ -1,11 +1,13 @@
 use std::collections::BTreeSet;
 
+use serde_json::Value;
+
 use crate::registry::{Document, MetaMediaType, MetaOperation, MetaSchemaRef};
 
 type UsedTypes = BTreeSet<String>;
 
-impl<'a> Document<'a> {
-    fn traverse_schema(&self, used_types: &mut UsedTypes, schema_ref: &'a MetaSchemaRef) {
+impl Document {
+    fn traverse_schema(&self, used_types: &mut UsedTypes, schema_ref: &MetaSchemaRef) {
         let schema = match schema_ref {
             MetaSchemaRef::Reference(name) => {
                 if used_types.contains(name.as_str()) {
@@ -45,13 +47,43 @@ impl<'a> Document<'a> {
         }
     }
 
-    fn traverse_media_types(&self, used_types: &mut UsedTypes, meta_types: &'a [MetaMediaType]) {
+    fn traverse_media_types(&self, used_types: &mut UsedTypes, meta_types: &[MetaMediaType]) {
         for meta_type in meta_types {
             self.traverse_schema(used_types, &meta_type.schema);
         }
     }
 
-    fn traverse_operation(&self, used_types: &mut UsedTypes, operation: &'a MetaOperation) {
+    /// Traverses the schemas referenced by the specification extensions, such
+    /// as the messages of `x-websocket`.
+    fn traverse_extension(&self, used_types: &mut UsedTypes, value: &Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    match (key.as_str(), value.as_str()) {
+                        ("$ref", Some(reference)) => {
+                            if let Some(name) = reference.strip_prefix("#/components/schemas/") {
+                                if self.registry.schemas.contains_key(name) {
+                                    self.traverse_schema(
+                                        used_types,
+                                        &MetaSchemaRef::Reference(name.to_string()),
+                                    );
+                                }
+                            }
+                        }
+                        _ => self.traverse_extension(used_types, value),
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.traverse_extension(used_types, item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn traverse_operation(&self, used_types: &mut UsedTypes, operation: &MetaOperation) {
         for param in &operation.params {
             self.traverse_schema(used_types, &param.schema);
         }
@@ -63,6 +95,16 @@ impl<'a> Document<'a> {
         for response in &operation.responses.responses {
             self.traverse_media_types(used_types, &response.content);
         }
+
+        for value in operation.extensions.values() {
+            self.traverse_extension(used_types, value);
+        }
+
+        for callback in &operation.callbacks {
+            for operation in &callback.operations {
+                self.traverse_operation(used_types, operation);
+            }
+        }
     }
 
     pub(crate) fn remove_unused_schemas(&mut self) {
@@ -0,0 +1,162 @@
+use std::{borrow::Cow, fmt::Write};
+
+use poem::http::Method;
+use serde_json::Value;
+
+use crate::registry::{MetaCodeSample, MetaOperation, MetaParamIn, MetaSchemaRef};
+
+impl MetaOperation {
+    /// Creates a cURL command from the examples of the parameters and the
+    /// request body of this operation.
+    ///
+    /// The parameters without an example or a default value are rendered as
+    /// placeholders if they are required, and omitted otherwise.
+    pub(crate) fn curl_code_sample(&self, url: &str) -> MetaCodeSample {
+        let mut url = url.to_string();
+        let mut query = Vec::new();
+        let mut headers = Vec::new();
+        let mut cookies = Vec::new();
+
+        for param in &self.params {
+            let values = match param_example(&param.schema) {
+                Some(value) => example_values(value, param.explode),
+                None if param.required => vec![format!("{{{}}}", param.name)],
+                None => continue,
+            };
+
+            match param.in_type {
+                MetaParamIn::Path => {
+                    url = url.replace(
+                        &format!("{{{}}}", param.name),
+                        &percent_encode(&values.join(",")),
+                    );
+                }
+                MetaParamIn::Query => {
+                    for value in values {
+                        query.push(format!(
+                            "{}={}",
+                            percent_encode(&param.name),
+                            percent_encode(&value)
+                        ));
+                    }
+                }
+                MetaParamIn::Header => {
+                    headers.push(format!("{}: {}", param.name, values.join(",")))
+                }
+                MetaParamIn::Cookie | MetaParamIn::CookiePrivate | MetaParamIn::CookieSigned => {
+                    cookies.push(format!("{}={}", param.name, values.join(",")))
+                }
+            }
+        }
+
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query.join("&"));
+        }
+
+        let mut source = String::from("curl");
+        if self.method != Method::GET {
+            _ = write!(source, " -X {}", self.method);
+        }
+        _ = write!(source, " {}", shell_quote(&url));
+        for header in headers {
+            _ = write!(source, " \\\n  -H {}", shell_quote(&header));
+        }
+        if !cookies.is_empty() {
+            _ = write!(source, " \\\n  -b {}", shell_quote(&cookies.join("; ")));
+        }
+
+        let body = self
+            .request
+            .iter()
+            .flat_map(|request| &request.content)
+            .find_map(|mt| {
+                let value = mt
+                    .examples
+                    .iter()
+                    .find_map(|example| example.value.as_ref())?;
+                let body = match value {
+                    Value::String(s) if !mt.content_type.contains("json") => s.clone(),
+                    value => serde_json::to_string(value).ok()?,
+                };
+                Some((mt.content_type, body))
+            });
+        if let Some((content_type, body)) = body {
+            _ = write!(
+                source,
+                " \\\n  -H {} \\\n  -d {}",
+                shell_quote(&format!("content-type: {content_type}")),
+                shell_quote(&body)
+            );
+        }
+
+        MetaCodeSample {
+            lang: "Shell",
+            label: Some("cURL"),
+            source: Cow::Owned(source),
+        }
+    }
+}
+
+fn param_example(schema: &MetaSchemaRef) -> Option<&Value> {
+    match schema {
+        MetaSchemaRef::Inline(schema) => schema.example.as_ref().or(schema.default.as_ref()),
+        MetaSchemaRef::Reference(_) => None,
+    }
+}
+
+fn example_values(value: &Value, explode: bool) -> Vec<String> {
+    fn to_string(value: &Value) -> String {
+        match value {
+            Value::String(s) => s.clone(),
+            value => value.to_string(),
+        }
+    }
+
+    match value {
+        Value::Array(values) if explode => values.iter().map(to_string).collect(),
+        Value::Array(values) => vec![values.iter().map(to_string).collect::<Vec<_>>().join(",")],
+        value => vec![to_string(value)],
+    }
+}
+
+fn percent_encode(s: &str) -> Cow<'_, str> {
+    let is_unreserved = |b: u8| b.is_ascii_alphanumeric() || b"-._~{}".contains(&b);
+    if s.bytes().all(is_unreserved) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut encoded = String::with_capacity(s.len() * 3);
+    for b in s.bytes() {
+        if is_unreserved(b) {
+            encoded.push(b as char);
+        } else {
+            _ = write!(encoded, "%{b:02X}");
+        }
+    }
+    Cow::Owned(encoded)
+}
+
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_and_encode() {
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(percent_encode("a b/c"), "a%20b%2Fc");
+        assert_eq!(percent_encode("abc"), "abc");
+        assert_eq!(
+            example_values(&serde_json::json!([1, "a"]), true),
+            vec!["1".to_string(), "a".to_string()]
+        );
+        assert_eq!(
+            example_values(&serde_json::json!([1, "a"]), false),
+            vec!["1,a".to_string()]
+        );
+    }
+}
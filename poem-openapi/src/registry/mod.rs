@@ -1,14 +1,16 @@
 mod clean_unused;
+mod curl;
 mod ser;
 
 use std::{
+    borrow::Cow,
     cmp::Ordering,
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     hash::{Hash, Hasher},
 };
 
 use poem::http::Method;
-pub(crate) use ser::Document;
+pub use ser::Document;
 use serde::{ser::SerializeMap, Serialize, Serializer};
 use serde_json::Value;
 
@@ -71,6 +73,14 @@ pub struct MetaSchema {
     pub items: Option<Box<MetaSchemaRef>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub additional_properties: Option<Box<MetaSchemaRef>>,
+    /// Serialized as `additionalProperties: false`, should not be used
+    /// together with `additional_properties`.
+    #[serde(
+        rename = "additionalProperties",
+        skip_serializing_if = "is_false",
+        serialize_with = "serialize_deny_additional_properties"
+    )]
+    pub deny_additional_properties: bool,
     #[serde(rename = "enum", skip_serializing_if = "Vec::is_empty")]
     pub enum_items: Vec<Value>,
     #[serde(skip_serializing_if = "is_false")]
@@ -116,6 +126,16 @@ pub struct MetaSchema {
     pub max_properties: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_properties: Option<usize>,
+
+    #[serde(flatten)]
+    pub extensions: BTreeMap<String, Value>,
+}
+
+fn serialize_deny_additional_properties<S: Serializer>(
+    _deny: &bool,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bool(false)
 }
 
 fn serialize_properties<S: Serializer>(
@@ -142,6 +162,7 @@ impl MetaSchema {
         properties: vec![],
         items: None,
         additional_properties: None,
+        deny_additional_properties: false,
         enum_items: vec![],
         deprecated: false,
         any_of: vec![],
@@ -164,6 +185,7 @@ impl MetaSchema {
         unique_items: None,
         max_properties: None,
         min_properties: None,
+        extensions: BTreeMap::new(),
     };
 
     pub fn new(ty: &'static str) -> Self {
@@ -194,6 +216,8 @@ impl MetaSchema {
             external_docs,
             items,
             additional_properties,
+            deny_additional_properties,
+            enum_items,
             example,
             multiple_of,
             maximum,
@@ -208,11 +232,13 @@ impl MetaSchema {
             unique_items,
             max_properties,
             min_properties,
+            extensions,
             ..
         }: MetaSchema,
     ) -> Self {
         self.read_only |= read_only;
         self.write_only |= write_only;
+        self.deny_additional_properties |= deny_additional_properties;
 
         macro_rules! merge_optional {
             ($($name:ident),*) => {
@@ -245,6 +271,12 @@ impl MetaSchema {
             min_properties
         );
 
+        self.extensions.extend(extensions);
+
+        if !enum_items.is_empty() {
+            self.enum_items = enum_items;
+        }
+
         if let Some(items) = items {
             if let Some(self_items) = self.items {
                 let items = *items;
@@ -367,6 +399,20 @@ pub struct MetaOperationParam {
     pub required: bool,
     pub deprecated: bool,
     pub explode: bool,
+    #[serde(flatten)]
+    pub extensions: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetaExample {
+    #[serde(skip)]
+    pub name: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
 }
 
 #[derive(Debug, PartialEq, Serialize)]
@@ -374,6 +420,46 @@ pub struct MetaMediaType {
     #[serde(skip)]
     pub content_type: &'static str,
     pub schema: MetaSchemaRef,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_examples"
+    )]
+    pub examples: Vec<MetaExample>,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_encoding"
+    )]
+    pub encoding: Vec<MetaEncoding>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetaEncoding {
+    #[serde(skip)]
+    pub name: &'static str,
+    pub content_type: &'static str,
+}
+
+fn serialize_encoding<S: Serializer>(
+    encoding: &[MetaEncoding],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_map(None)?;
+    for item in encoding {
+        s.serialize_entry(item.name, item)?;
+    }
+    s.end()
+}
+
+fn serialize_examples<S: Serializer>(
+    examples: &[MetaExample],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_map(None)?;
+    for example in examples {
+        s.serialize_entry(example.name, example)?;
+    }
+    s.end()
 }
 
 #[derive(Debug, PartialEq, Serialize)]
@@ -458,7 +544,7 @@ pub struct MetaCodeSample {
     pub lang: &'static str,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<&'static str>,
-    pub source: &'static str,
+    pub source: Cow<'static, str>,
 }
 
 #[derive(Debug, PartialEq, Serialize)]
@@ -487,6 +573,35 @@ pub struct MetaOperation {
     pub operation_id: Option<&'static str>,
     #[serde(rename = "x-code-samples", skip_serializing_if = "Vec::is_empty")]
     pub code_samples: Vec<MetaCodeSample>,
+    #[serde(skip)]
+    pub curl_example: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<MetaServer>,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_callbacks"
+    )]
+    pub callbacks: Vec<MetaCallback>,
+    #[serde(flatten)]
+    pub extensions: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MetaCallback {
+    pub name: &'static str,
+    pub expression: &'static str,
+    pub operations: Vec<MetaOperation>,
+}
+
+fn serialize_callbacks<S: Serializer>(
+    callbacks: &[MetaCallback],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_map(None)?;
+    for callback in callbacks {
+        s.serialize_entry(callback.name, callback)?;
+    }
+    s.end()
 }
 
 #[derive(Debug, PartialEq)]
@@ -536,6 +651,17 @@ pub struct MetaServer {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, MetaServerVariable>,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+pub struct MetaServerVariable {
+    pub default: String,
+    #[serde(rename = "enum", skip_serializing_if = "Vec::is_empty")]
+    pub enum_values: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize)]
@@ -656,7 +782,7 @@ pub struct MetaApi {
 #[derive(Default)]
 pub struct Registry {
     pub schemas: BTreeMap<String, MetaSchema>,
-    pub tags: BTreeSet<MetaTag>,
+    pub tags: Vec<MetaTag>,
     pub security_schemes: BTreeMap<&'static str, MetaSecurityScheme>,
 }
 
@@ -674,7 +800,7 @@ impl Registry {
                 if let Some(prev_typename) = schema.rust_typename {
                     if prev_typename != std::any::type_name::<T>() {
                         panic!(
-                            "`{}` and `{}` have the same OpenAPI name `{}`",
+                            "`{}` and `{}` have the same OpenAPI name `{}`, use `#[oai(rename = \"...\")]` or `#[oai(namespace = \"...\")]` to distinguish them",
                             prev_typename,
                             std::any::type_name::<T>(),
                             name,
@@ -684,8 +810,14 @@ impl Registry {
             }
             None => {
                 // Inserting a fake type before calling the function allows recursive types to
-                // exist.
-                self.schemas.insert(name.clone(), MetaSchema::new("fake"));
+                // exist, it has the type name to detect the name collisions of the nested types.
+                self.schemas.insert(
+                    name.clone(),
+                    MetaSchema {
+                        rust_typename: Some(std::any::type_name::<T>()),
+                        ..MetaSchema::new("fake")
+                    },
+                );
                 let mut meta_schema = f(self);
                 meta_schema.rust_typename = Some(std::any::type_name::<T>());
                 *self.schemas.get_mut(&name).unwrap() = meta_schema;
@@ -706,8 +838,61 @@ impl Registry {
         }
     }
 
+    /// Returns `true` if the schema, or any schema it refers to, has
+    /// validation keywords.
+    pub fn has_validators(&self, schema_ref: &MetaSchemaRef) -> bool {
+        fn check<'a>(
+            registry: &'a Registry,
+            schema_ref: &'a MetaSchemaRef,
+            visited: &mut HashSet<&'a str>,
+        ) -> bool {
+            let schema = match schema_ref {
+                MetaSchemaRef::Inline(schema) => &**schema,
+                MetaSchemaRef::Reference(name) => {
+                    if !visited.insert(name) {
+                        return false;
+                    }
+                    match registry.schemas.get(name) {
+                        Some(schema) => schema,
+                        None => return false,
+                    }
+                }
+            };
+
+            let has_validators = schema.multiple_of.is_some()
+                || schema.maximum.is_some()
+                || schema.minimum.is_some()
+                || schema.max_length.is_some()
+                || schema.min_length.is_some()
+                || schema.pattern.is_some()
+                || schema.max_items.is_some()
+                || schema.min_items.is_some()
+                || schema.unique_items.is_some()
+                || schema.max_properties.is_some()
+                || schema.min_properties.is_some();
+            if has_validators {
+                return true;
+            }
+
+            schema
+                .properties
+                .iter()
+                .map(|(_, schema_ref)| schema_ref)
+                .chain(schema.items.as_deref())
+                .chain(schema.additional_properties.as_deref())
+                .chain(&schema.any_of)
+                .chain(&schema.one_of)
+                .chain(&schema.all_of)
+                .any(|schema_ref| check(registry, schema_ref, visited))
+        }
+
+        check(self, schema_ref, &mut HashSet::new())
+    }
+
     pub fn create_tag(&mut self, tag: MetaTag) {
-        self.tags.insert(tag);
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
     }
 
     pub fn create_security_scheme(
@@ -1,10 +1,11 @@
 use std::collections::BTreeMap;
 
 use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde_json::Value;
 
 use crate::registry::{
-    MetaApi, MetaExternalDocument, MetaInfo, MetaPath, MetaResponses, MetaSchema, MetaSchemaRef,
-    MetaSecurityScheme, MetaServer, MetaWebhook, Registry,
+    MetaApi, MetaCallback, MetaExternalDocument, MetaInfo, MetaOperation, MetaPath, MetaResponses,
+    MetaSchema, MetaSchemaRef, MetaSecurityScheme, MetaServer, MetaWebhook, Registry,
 };
 
 const OPENAPI_VERSION: &str = "3.0.0";
@@ -51,6 +52,26 @@ impl Serialize for MetaPath {
     }
 }
 
+impl Serialize for MetaCallback {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct PathItem<'a>(&'a [MetaOperation]);
+
+        impl<'a> Serialize for PathItem<'a> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut s = serializer.serialize_map(None)?;
+                for operation in self.0 {
+                    s.serialize_entry(&operation.method.to_string().to_lowercase(), operation)?;
+                }
+                s.end()
+            }
+        }
+
+        let mut s = serializer.serialize_map(Some(1))?;
+        s.serialize_entry(self.expression, &PathItem(&self.operations))?;
+        s.end()
+    }
+}
+
 impl Serialize for MetaResponses {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let mut s = serializer.serialize_map(None)?;
@@ -76,17 +97,31 @@ impl<'a> Serialize for WebhookMap<'a> {
     }
 }
 
-pub(crate) struct Document<'a> {
-    pub(crate) info: &'a MetaInfo,
-    pub(crate) servers: &'a [MetaServer],
-    pub(crate) apis: Vec<MetaApi>,
-    pub(crate) webhooks: Vec<MetaWebhook>,
-    pub(crate) registry: Registry,
-    pub(crate) external_document: Option<&'a MetaExternalDocument>,
-    pub(crate) url_prefix: Option<&'a str>,
+/// The OpenAPI document served by [`OpenApiService`](crate::OpenApiService).
+///
+/// It can be modified before it is serialized with
+/// [`OpenApiService::with_spec_transform`](crate::OpenApiService::with_spec_transform),
+/// the schemas which are no longer used are removed afterwards.
+pub struct Document {
+    /// The metadata about the API.
+    pub info: MetaInfo,
+    /// The servers of the API.
+    pub servers: Vec<MetaServer>,
+    /// The paths and operations of the API.
+    pub apis: Vec<MetaApi>,
+    /// The webhooks of the API.
+    pub webhooks: Vec<MetaWebhook>,
+    /// The schemas, tags and security schemes of the API.
+    pub registry: Registry,
+    /// The external documentation of the API.
+    pub external_document: Option<MetaExternalDocument>,
+    /// The specification extensions (`x-*`) of the document.
+    pub extensions: BTreeMap<String, Value>,
+    /// The prefix which is added to all paths.
+    pub url_prefix: Option<String>,
 }
 
-impl<'a> Serialize for Document<'a> {
+impl Serialize for Document {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
@@ -100,12 +135,12 @@ impl<'a> Serialize for Document<'a> {
 
         s.serialize_entry("openapi", OPENAPI_VERSION)?;
         s.serialize_entry("info", &self.info)?;
-        s.serialize_entry("servers", self.servers)?;
+        s.serialize_entry("servers", &self.servers)?;
         s.serialize_entry("tags", &self.registry.tags)?;
         if !self.webhooks.is_empty() {
             s.serialize_entry("webhooks", &WebhookMap(&self.webhooks))?;
         }
-        s.serialize_entry("paths", &PathMap(&self.apis, self.url_prefix))?;
+        s.serialize_entry("paths", &PathMap(&self.apis, self.url_prefix.as_deref()))?;
         s.serialize_entry(
             "components",
             &Components {
@@ -114,8 +149,12 @@ impl<'a> Serialize for Document<'a> {
             },
         )?;
 
-        if let Some(external_document) = self.external_document {
-            s.serialize_entry("externalDocs", &external_document)?;
+        if let Some(external_document) = &self.external_document {
+            s.serialize_entry("externalDocs", external_document)?;
+        }
+
+        for (name, value) in &self.extensions {
+            s.serialize_entry(name, value)?;
         }
 
         s.end()
@@ -0,0 +1,408 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
+
+use poem::{Endpoint, Request, Result};
+use serde_json::Value;
+
+use crate::{
+    error::RequestValidationError,
+    registry::{
+        MetaMediaType, MetaOperation, MetaOperationParam, MetaParamIn, MetaSchema, MetaSchemaRef,
+    },
+};
+
+const DEFAULT_MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+/// Validation of incoming requests against the generated document.
+///
+/// The extractors of an operation already reject requests that can not be
+/// parsed, this is an additional layer which checks requests against the
+/// document itself, which is useful to enforce a contract when migrating
+/// clients.
+///
+/// The following rules are always checked:
+///
+/// - Required query and header parameters must be present.
+/// - Query and header parameters with enumerated values must use one of them.
+/// - Required properties of JSON request bodies must be present, and
+///   properties with enumerated values must use one of them.
+///
+/// Additional rules can be enabled with
+/// [`RequestValidation::deny_unknown_query_params`] and
+/// [`RequestValidation::deny_unknown_fields`].
+///
+/// Requests violating any rule are rejected with `400 Bad Request`. Bodies
+/// described by `oneOf` or `anyOf` schemas (such as
+/// [`Union`](crate::Union)) are not inspected.
+///
+/// The rules are checked against the operations declared by the APIs, the
+/// changes made by [`OpenApiService::with_spec_transform`](crate::OpenApiService::with_spec_transform)
+/// are not taken into account.
+///
+/// JSON request bodies are read into memory to be inspected, bodies larger
+/// than [`RequestValidation::max_body_size`] are rejected with
+/// `413 Payload Too Large`.
+///
+/// # Examples
+///
+/// ```
+/// use poem_openapi::{OpenApi, OpenApiService, RequestValidation};
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {}
+///
+/// let api_service = OpenApiService::new(Api, "Demo", "1.0").request_validation(
+///     RequestValidation::new()
+///         .deny_unknown_query_params(true)
+///         .deny_unknown_fields(true),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RequestValidation {
+    deny_unknown_query_params: bool,
+    deny_unknown_fields: bool,
+    max_body_size: usize,
+}
+
+impl Default for RequestValidation {
+    fn default() -> Self {
+        Self {
+            deny_unknown_query_params: false,
+            deny_unknown_fields: false,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+}
+
+impl RequestValidation {
+    /// Create a `RequestValidation`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject requests with query parameters that are not declared by the
+    /// operation.
+    #[must_use]
+    pub fn deny_unknown_query_params(self, deny: bool) -> Self {
+        Self {
+            deny_unknown_query_params: deny,
+            ..self
+        }
+    }
+
+    /// Reject JSON request bodies with properties that are not declared by
+    /// their schemas.
+    #[must_use]
+    pub fn deny_unknown_fields(self, deny: bool) -> Self {
+        Self {
+            deny_unknown_fields: deny,
+            ..self
+        }
+    }
+
+    /// Specify the maximum size of the JSON request bodies to be inspected.
+    /// (default to `2MB`)
+    #[must_use]
+    pub fn max_body_size(self, max_body_size: usize) -> Self {
+        Self {
+            max_body_size,
+            ..self
+        }
+    }
+}
+
+pub(crate) struct OperationValidator {
+    config: RequestValidation,
+    params: Vec<MetaOperationParam>,
+    content: Vec<MetaMediaType>,
+    schemas: Arc<BTreeMap<String, MetaSchema>>,
+}
+
+impl OperationValidator {
+    pub(crate) fn new(
+        config: RequestValidation,
+        operation: MetaOperation,
+        schemas: Arc<BTreeMap<String, MetaSchema>>,
+    ) -> Self {
+        Self {
+            config,
+            params: operation.params,
+            content: operation
+                .request
+                .map(|request| request.content)
+                .unwrap_or_default(),
+            schemas,
+        }
+    }
+
+    async fn validate(&self, req: &mut Request) -> Result<()> {
+        let mut errors = Vec::new();
+        self.validate_params(req, &mut errors);
+
+        if let Some(schema) = self.json_body_schema(req) {
+            let data = req
+                .take_body()
+                .into_bytes_limit(self.config.max_body_size)
+                .await?;
+            if let Ok(value) = serde_json::from_slice::<Value>(&data) {
+                self.validate_value(&value, schema, "", &mut errors);
+            }
+            req.set_body(data);
+        }
+
+        if !errors.is_empty() {
+            return Err(RequestValidationError { errors }.into());
+        }
+        Ok(())
+    }
+
+    fn validate_params(&self, req: &Request, errors: &mut Vec<String>) {
+        let query = req.query();
+
+        for param in &self.params {
+            let values = match param.in_type {
+                MetaParamIn::Query => query.get_all(&param.name).collect::<Vec<_>>(),
+                MetaParamIn::Header => req
+                    .headers()
+                    .get_all(param.name.as_str())
+                    .iter()
+                    .filter_map(|value| value.to_str().ok())
+                    .collect(),
+                _ => continue,
+            };
+            let location = match param.in_type {
+                MetaParamIn::Query => "query parameter",
+                _ => "header",
+            };
+
+            if values.is_empty() {
+                if param.required {
+                    errors.push(format!("missing required {location} `{}`", param.name));
+                }
+                continue;
+            }
+
+            let Some(schema) = self.resolve(&param.schema) else {
+                continue;
+            };
+            let is_array = schema.ty == "array";
+            let enum_items = match schema
+                .items
+                .as_deref()
+                .and_then(|items| self.resolve(items))
+            {
+                Some(items) if is_array => &items.enum_items,
+                _ => &schema.enum_items,
+            };
+            if enum_items.is_empty() {
+                continue;
+            }
+            let values = values.iter().flat_map(|value| {
+                if is_array {
+                    value.split(',').map(str::trim).collect::<Vec<_>>()
+                } else {
+                    vec![*value]
+                }
+            });
+            for value in values {
+                if !enum_items.iter().any(|item| match item {
+                    Value::String(item) => item == value,
+                    item => serde_json::from_str::<Value>(value).ok().as_ref() == Some(item),
+                }) {
+                    errors.push(format!(
+                        "invalid value `{value}` for {location} `{}`",
+                        param.name
+                    ));
+                }
+            }
+        }
+
+        if self.config.deny_unknown_query_params {
+            let mut reported = HashSet::new();
+            for (name, _) in query.iter() {
+                let declared = self
+                    .params
+                    .iter()
+                    .any(|param| param.in_type == MetaParamIn::Query && param.name == name);
+                if !declared && reported.insert(name) {
+                    errors.push(format!("unknown query parameter `{name}`"));
+                }
+            }
+        }
+    }
+
+    fn json_body_schema(&self, req: &Request) -> Option<&MetaSchemaRef> {
+        let content_type = req.content_type()?.parse::<mime::Mime>().ok()?;
+        let is_json =
+            content_type.subtype() == mime::JSON || content_type.suffix() == Some(mime::JSON);
+        if !is_json {
+            return None;
+        }
+
+        self.content
+            .iter()
+            .find(|media_type| {
+                media_type
+                    .content_type
+                    .parse::<mime::Mime>()
+                    .map(|mime| mime.essence_str() == content_type.essence_str())
+                    .unwrap_or(false)
+            })
+            .map(|media_type| &media_type.schema)
+    }
+
+    fn resolve<'a>(&'a self, schema_ref: &'a MetaSchemaRef) -> Option<&'a MetaSchema> {
+        match schema_ref {
+            MetaSchemaRef::Inline(schema) => Some(schema),
+            MetaSchemaRef::Reference(name) => self.schemas.get(name),
+        }
+    }
+
+    /// Collects the properties and the required properties of an object
+    /// schema, including those of the schemas in its `allOf`.
+    fn collect_properties<'a>(
+        &'a self,
+        schema: &'a MetaSchema,
+        properties: &mut Vec<(&'a str, &'a MetaSchemaRef)>,
+        required: &mut Vec<&'a str>,
+        depth: usize,
+    ) {
+        if depth > 32 {
+            return;
+        }
+        properties.extend(
+            schema
+                .properties
+                .iter()
+                .map(|(name, schema)| (*name, schema)),
+        );
+        required.extend(schema.required.iter().copied());
+        for schema_ref in &schema.all_of {
+            if let Some(schema) = self.resolve(schema_ref) {
+                self.collect_properties(schema, properties, required, depth + 1);
+            }
+        }
+    }
+
+    fn validate_value(
+        &self,
+        value: &Value,
+        schema_ref: &MetaSchemaRef,
+        path: &str,
+        errors: &mut Vec<String>,
+    ) {
+        let Some(schema) = self.resolve(schema_ref) else {
+            return;
+        };
+        if !schema.one_of.is_empty() || !schema.any_of.is_empty() {
+            return;
+        }
+
+        let display_path = if path.is_empty() { "body" } else { path };
+        if !schema.enum_items.is_empty() && !schema.enum_items.contains(value) {
+            errors.push(format!("invalid value for `{display_path}`"));
+        }
+
+        match value {
+            Value::Object(map) => {
+                let mut properties = Vec::new();
+                let mut required = Vec::new();
+                self.collect_properties(schema, &mut properties, &mut required, 0);
+
+                for name in required {
+                    if !map.contains_key(name) {
+                        errors.push(format!(
+                            "missing required field `{}`",
+                            join_field(path, name)
+                        ));
+                    }
+                }
+
+                // Walk the properties in the order of the schema so that the
+                // errors do not depend on the order of the map.
+                for (name, schema_ref) in &properties {
+                    if let Some(value) = map.get(*name) {
+                        self.validate_value(value, schema_ref, &join_field(path, name), errors);
+                    }
+                }
+
+                let mut unknown_fields = map
+                    .iter()
+                    .filter(|(name, _)| !properties.iter().any(|(property, _)| property == name))
+                    .collect::<Vec<_>>();
+                unknown_fields.sort_by_key(|(name, _)| *name);
+                for (name, value) in unknown_fields {
+                    let field_path = join_field(path, name);
+                    match &schema.additional_properties {
+                        Some(schema_ref) => {
+                            self.validate_value(value, schema_ref, &field_path, errors)
+                        }
+                        None if schema.deny_additional_properties
+                            || (self.config.deny_unknown_fields && !properties.is_empty()) =>
+                        {
+                            errors.push(format!("unknown field `{field_path}`"));
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(schema_ref) = &schema.items {
+                    for (idx, item) in items.iter().enumerate() {
+                        self.validate_value(item, schema_ref, &format!("{path}[{idx}]"), errors);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn join_field(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+/// Converts a route path such as `/users/:id` to the path used in the
+/// document, `/users/{id}`.
+pub(crate) fn route_path_to_oai_path(path: &str) -> String {
+    let mut oai_path = String::new();
+    for s in path.split('/').filter(|s| !s.is_empty()) {
+        match s.strip_prefix(':') {
+            Some(var) => {
+                oai_path.push_str("/{");
+                oai_path.push_str(var);
+                oai_path.push('}');
+            }
+            None => {
+                oai_path.push('/');
+                oai_path.push_str(s);
+            }
+        }
+    }
+    if oai_path.is_empty() {
+        oai_path.push('/');
+    }
+    oai_path
+}
+
+pub(crate) struct RequestValidationEndpoint<E> {
+    pub(crate) inner: E,
+    pub(crate) validator: OperationValidator,
+}
+
+impl<E: Endpoint> Endpoint for RequestValidationEndpoint<E> {
+    type Output = E::Output;
+
+    async fn call(&self, mut req: Request) -> Result<Self::Output> {
+        self.validator.validate(&mut req).await?;
+        self.inner.call(req).await
+    }
+}
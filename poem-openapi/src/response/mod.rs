@@ -2,3 +2,8 @@
 
 #[cfg(feature = "static-files")]
 mod static_file;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+#[cfg(feature = "websocket")]
+pub use websocket::WebSocketResponse;
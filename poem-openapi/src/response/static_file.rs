@@ -21,6 +21,8 @@ impl ApiResponse for StaticFileResponse {
                     content: vec![MetaMediaType {
                         content_type: Binary::<Body>::CONTENT_TYPE,
                         schema: Binary::<Body>::schema_ref(),
+                        examples: vec![],
+                        encoding: vec![],
                     }],
                     headers: vec![MetaHeader {
                         name: "etag".to_string(),
@@ -0,0 +1,170 @@
+use std::{collections::BTreeMap, future::Future, marker::PhantomData};
+
+use poem::{
+    web::websocket::{BoxWebSocketUpgraded, TypedWebSocketStream, WebSocket, WebSocketStream},
+    IntoResponse, Response,
+};
+
+use crate::{
+    registry::{MetaResponse, MetaResponses, Registry},
+    types::Type,
+    ApiResponse,
+};
+
+/// A WebSocket upgrade response which documents the messages exchanged on
+/// the connection.
+///
+/// The operation is documented with a `101 Switching Protocols` response and
+/// the `x-websocket` extension, which references the schemas of the messages
+/// received from the client (`ClientMsg`) and sent by the server
+/// (`ServerMsg`).
+///
+/// # Example
+///
+/// ```
+/// use poem::web::websocket::{TypedWebSocketStream, WebSocket};
+/// use poem_openapi::{Object, OpenApi, WebSocketResponse};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Object, Deserialize)]
+/// struct Question {
+///     text: String,
+/// }
+///
+/// #[derive(Object, Serialize)]
+/// struct Answer {
+///     text: String,
+/// }
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     /// Ask questions over a WebSocket connection.
+///     #[oai(path = "/ask", method = "get")]
+///     async fn ask(&self, ws: WebSocket) -> WebSocketResponse<Question, Answer> {
+///         WebSocketResponse::typed(
+///             ws,
+///             |mut socket: TypedWebSocketStream<Question, Answer>| async move {
+///                 while let Some(Ok(question)) = socket.recv().await {
+///                     let answer = Answer {
+///                         text: question.text.to_uppercase(),
+///                     };
+///                     if socket.send(&answer).await.is_err() {
+///                         break;
+///                     }
+///                 }
+///             },
+///         )
+///     }
+/// }
+/// ```
+pub struct WebSocketResponse<ClientMsg, ServerMsg> {
+    inner: BoxWebSocketUpgraded,
+    _mark: PhantomData<fn(ClientMsg) -> ServerMsg>,
+}
+
+impl<ClientMsg: Type, ServerMsg: Type> WebSocketResponse<ClientMsg, ServerMsg> {
+    /// Upgrades the connection and calls `callback` with the WebSocket
+    /// stream.
+    pub fn new<F, Fut>(ws: WebSocket, callback: F) -> Self
+    where
+        F: FnOnce(WebSocketStream) -> Fut + Send + Sync + 'static,
+        Fut: Future + Send + 'static,
+    {
+        Self {
+            inner: ws.on_upgrade(callback).boxed(),
+            _mark: PhantomData,
+        }
+    }
+
+    /// Upgrades the connection and calls `callback` with a
+    /// [`TypedWebSocketStream`] which receives `ClientMsg` and sends
+    /// `ServerMsg`.
+    pub fn typed<F, Fut>(ws: WebSocket, callback: F) -> Self
+    where
+        F: FnOnce(TypedWebSocketStream<ClientMsg, ServerMsg>) -> Fut + Send + Sync + 'static,
+        Fut: Future + Send + 'static,
+    {
+        Self::new(ws, move |stream| callback(stream.typed()))
+    }
+}
+
+impl<ClientMsg, ServerMsg> IntoResponse for WebSocketResponse<ClientMsg, ServerMsg> {
+    fn into_response(self) -> Response {
+        self.inner.into_response()
+    }
+}
+
+impl<ClientMsg: Type, ServerMsg: Type> ApiResponse for WebSocketResponse<ClientMsg, ServerMsg> {
+    fn meta() -> MetaResponses {
+        MetaResponses {
+            responses: vec![MetaResponse {
+                description: "Switching to the WebSocket protocol",
+                status: Some(101),
+                content: vec![],
+                headers: vec![],
+            }],
+        }
+    }
+
+    fn register(registry: &mut Registry) {
+        ClientMsg::register(registry);
+        ServerMsg::register(registry);
+    }
+
+    fn operation_extensions() -> BTreeMap<String, serde_json::Value> {
+        let mut extensions = BTreeMap::new();
+        extensions.insert(
+            "x-websocket".to_string(),
+            serde_json::json!({
+                "receive": ClientMsg::schema_ref(),
+                "send": ServerMsg::schema_ref(),
+            }),
+        );
+        extensions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{Object, OpenApi, OpenApiService};
+
+    #[derive(Object)]
+    #[oai(internal)]
+    struct Ping {
+        id: i32,
+    }
+
+    #[test]
+    fn spec() {
+        struct Api;
+
+        #[OpenApi(internal)]
+        impl Api {
+            #[oai(path = "/ws", method = "get")]
+            async fn ws(&self, ws: WebSocket) -> WebSocketResponse<Ping, String> {
+                WebSocketResponse::new(ws, |_| async {})
+            }
+        }
+
+        let spec: serde_json::Value =
+            serde_json::from_str(&OpenApiService::new(Api, "test", "1.0").spec()).unwrap();
+        let operation = &spec["paths"]["/ws"]["get"];
+        assert_eq!(
+            operation["responses"]["101"]["description"],
+            "Switching to the WebSocket protocol"
+        );
+        assert_eq!(
+            operation["x-websocket"],
+            json!({
+                "receive": { "$ref": "#/components/schemas/Ping" },
+                "send": { "type": "string" },
+            })
+        );
+        assert!(spec["components"]["schemas"]["Ping"].is_object());
+    }
+}
@@ -0,0 +1,60 @@
+use serde_json::Value;
+
+use crate::registry::MetaSchemaRef;
+
+/// Converts the numbers of the value to strings, it is used to serialize the
+/// fields with the `as_string` attribute.
+pub fn number_to_string(value: Value) -> Value {
+    match value {
+        Value::Number(n) => Value::String(n.to_string()),
+        Value::Array(values) => Value::Array(values.into_iter().map(number_to_string).collect()),
+        value => value,
+    }
+}
+
+/// Converts the strings of the value to numbers, it is used to deserialize the
+/// fields with the `as_string` attribute.
+///
+/// Strings that are not valid 64-bit integers are left unchanged.
+pub fn string_to_number(value: Value) -> Value {
+    match value {
+        Value::String(s) => {
+            if let Ok(n) = s.parse::<i64>() {
+                Value::Number(n.into())
+            } else if let Ok(n) = s.parse::<u64>() {
+                Value::Number(n.into())
+            } else {
+                Value::String(s)
+            }
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(string_to_number).collect()),
+        value => value,
+    }
+}
+
+/// Converts the integer schema to a string schema whose format is suffixed
+/// with `-as-string`, for example `int64-as-string`.
+pub fn schema_as_string(schema: MetaSchemaRef) -> MetaSchemaRef {
+    match schema {
+        MetaSchemaRef::Inline(mut schema) if schema.ty == "integer" => {
+            schema.ty = "string";
+            schema.format = schema.format.map(|format| match format {
+                "int8" => "int8-as-string",
+                "int16" => "int16-as-string",
+                "int32" => "int32-as-string",
+                "int64" => "int64-as-string",
+                "uint8" => "uint8-as-string",
+                "uint16" => "uint16-as-string",
+                "uint32" => "uint32-as-string",
+                "uint64" => "uint64-as-string",
+                format => format,
+            });
+            MetaSchemaRef::Inline(schema)
+        }
+        MetaSchemaRef::Inline(mut schema) if schema.ty == "array" => {
+            schema.items = schema.items.map(|items| Box::new(schema_as_string(*items)));
+            MetaSchemaRef::Inline(schema)
+        }
+        schema => schema,
+    }
+}
@@ -42,46 +42,46 @@ macro_rules! impl_type_for_integers {
 
         impl ParseFromJSON for $ty {
              fn parse_from_json(value: Option<Value>) -> ParseResult<Self> {
-                 let value = value.unwrap_or_default();
-                 if let Value::Number(n) = value {
-                     let n = n
-                         .as_i64()
-                         .ok_or_else(|| ParseError::from("invalid integer"))?;
-
-                     if n < Self::MIN as i64 || n > Self::MAX as i64 {
-                         return Err(ParseError::from(format!(
-                             "Only integers from {} to {} are accepted.",
-                             Self::MIN,
-                             Self::MAX
-                         )));
+                 match value.unwrap_or_default() {
+                     Value::Number(n) => {
+                         let n = n
+                             .as_i64()
+                             .ok_or_else(|| ParseError::from("invalid integer"))?;
+
+                         if n < Self::MIN as i64 || n > Self::MAX as i64 {
+                             return Err(ParseError::from(format!(
+                                 "Only integers from {} to {} are accepted.",
+                                 Self::MIN,
+                                 Self::MAX
+                             )));
+                         }
+
+                         Ok(n as Self)
                      }
-
-                     Ok(n as Self)
-                 } else {
-                     Err(ParseError::expected_type(value))
+                     value => Err(ParseError::expected_type(value)),
                  }
             }
         }
 
         impl ParseFromXML for $ty {
              fn parse_from_xml(value: Option<Value>) -> ParseResult<Self> {
-                 let value = value.unwrap_or_default();
-                 if let Value::Number(n) = value {
-                     let n = n
-                         .as_i64()
-                         .ok_or_else(|| ParseError::from("invalid integer"))?;
-
-                     if n < Self::MIN as i64 || n > Self::MAX as i64 {
-                         return Err(ParseError::from(format!(
-                             "Only integers from {} to {} are accepted.",
-                             Self::MIN,
-                             Self::MAX
-                         )));
+                 match value.unwrap_or_default() {
+                     Value::Number(n) => {
+                         let n = n
+                             .as_i64()
+                             .ok_or_else(|| ParseError::from("invalid integer"))?;
+
+                         if n < Self::MIN as i64 || n > Self::MAX as i64 {
+                             return Err(ParseError::from(format!(
+                                 "Only integers from {} to {} are accepted.",
+                                 Self::MIN,
+                                 Self::MAX
+                             )));
+                         }
+
+                         Ok(n as Self)
                      }
-
-                     Ok(n as Self)
-                 } else {
-                     Err(ParseError::expected_type(value))
+                     value => Err(ParseError::expected_type(value)),
                  }
             }
         }
@@ -157,23 +157,23 @@ macro_rules! impl_type_for_unsigneds {
 
         impl ParseFromJSON for $ty {
              fn parse_from_json(value: Option<Value>) -> ParseResult<Self> {
-                 let value = value.unwrap_or_default();
-                 if let Value::Number(n) = value {
-                     let n = n
-                         .as_u64()
-                         .ok_or_else(|| ParseError::from("invalid integer"))?;
-
-                     if n < Self::MIN as u64 || n > Self::MAX as u64 {
-                         return Err(ParseError::from(format!(
-                             "Only integers from {} to {} are accepted.",
-                             Self::MIN,
-                             Self::MAX
-                         )));
+                 match value.unwrap_or_default() {
+                     Value::Number(n) => {
+                         let n = n
+                             .as_u64()
+                             .ok_or_else(|| ParseError::from("invalid integer"))?;
+
+                         if n < Self::MIN as u64 || n > Self::MAX as u64 {
+                             return Err(ParseError::from(format!(
+                                 "Only integers from {} to {} are accepted.",
+                                 Self::MIN,
+                                 Self::MAX
+                             )));
+                         }
+
+                         Ok(n as Self)
                      }
-
-                     Ok(n as Self)
-                 } else {
-                     Err(ParseError::expected_type(value))
+                     value => Err(ParseError::expected_type(value)),
                  }
             }
         }
@@ -212,6 +212,102 @@ macro_rules! impl_type_for_unsigneds {
     };
 }
 
+// JSON numbers are limited to 64 bits, so 128-bit integers are described and
+// serialized as strings. Integers encoded as numbers are still accepted.
+macro_rules! impl_type_for_large_integers {
+    ($(($ty:ty, $format:literal)),*) => {
+        $(
+        impl Type for $ty {
+            const IS_REQUIRED: bool = true;
+
+            type RawValueType = Self;
+
+            type RawElementValueType = Self;
+
+            fn name() -> Cow<'static, str> {
+                format!("string({})", $format).into()
+            }
+
+            fn schema_ref() -> MetaSchemaRef {
+                MetaSchemaRef::Inline(Box::new(MetaSchema::new_with_format("string", $format)))
+            }
+
+            fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+                Some(self)
+            }
+
+            fn raw_element_iter<'a>(
+                &'a self
+            ) -> Box<dyn Iterator<Item = &'a Self::RawElementValueType> + 'a> {
+                Box::new(self.as_raw_value().into_iter())
+            }
+        }
+
+        impl ParseFromJSON for $ty {
+            #[allow(clippy::unnecessary_fallible_conversions)]
+            fn parse_from_json(value: Option<Value>) -> ParseResult<Self> {
+                match value.unwrap_or_default() {
+                    Value::Number(n) => {
+                        if let Some(n) = n.as_i64() {
+                            Self::try_from(n).map_err(ParseError::custom)
+                        } else if let Some(n) = n.as_u64() {
+                            Self::try_from(n).map_err(ParseError::custom)
+                        } else {
+                            Err(ParseError::from("invalid integer"))
+                        }
+                    }
+                    Value::String(s) => s.parse().map_err(ParseError::custom),
+                    value => Err(ParseError::expected_type(value)),
+                }
+            }
+        }
+
+        impl ParseFromXML for $ty {
+            fn parse_from_xml(value: Option<Value>) -> ParseResult<Self> {
+                Self::parse_from_json(value).map_err(ParseError::propagate)
+            }
+        }
+
+        impl ParseFromParameter for $ty {
+            fn parse_from_parameter(value: &str) -> ParseResult<Self> {
+                value.parse().map_err(ParseError::custom)
+            }
+        }
+
+        impl ParseFromMultipartField for $ty {
+            async fn parse_from_multipart(field: Option<Field>) -> ParseResult<Self> {
+                match field {
+                    Some(field) => Ok(field.text().await?.parse()?),
+                    None => Err(ParseError::expected_input()),
+                }
+            }
+        }
+
+        impl ToJSON for $ty {
+            fn to_json(&self) -> Option<Value> {
+                Some(Value::String(self.to_string()))
+            }
+        }
+
+        impl ToXML for $ty {
+            fn to_xml(&self) -> Option<Value> {
+                self.to_json()
+            }
+        }
+
+        impl ToHeader for $ty {
+            fn to_header(&self) -> Option<HeaderValue> {
+                match HeaderValue::from_str(&format!("{}", self)) {
+                    Ok(value) => Some(value),
+                    Err(_) => None,
+                }
+            }
+        }
+
+        )*
+    };
+}
+
 impl_type_for_integers!((i8, "int8"), (i16, "int16"), (i32, "int32"), (i64, "int64"));
 
 impl_type_for_unsigneds!(
@@ -221,3 +317,44 @@ impl_type_for_unsigneds!(
     (u64, "uint64"),
     (usize, "uint64")
 );
+
+impl_type_for_large_integers!((i128, "int128"), (u128, "uint128"));
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn reject_strings() {
+        assert!(u64::parse_from_json(Some(json!("1"))).is_err());
+        assert!(i32::parse_from_json(Some(json!("-10"))).is_err());
+        assert!(i32::parse_from_xml(Some(json!("-10"))).is_err());
+    }
+
+    #[test]
+    fn large_integers() {
+        assert_eq!(i128::schema_ref().unwrap_inline().ty, "string");
+        assert_eq!(u128::schema_ref().unwrap_inline().format, Some("uint128"));
+
+        assert_eq!(i128::parse_from_json(Some(json!(-10))).unwrap(), -10);
+        assert_eq!(i128::parse_from_json(Some(json!("-10"))).unwrap(), -10);
+        assert_eq!(
+            u128::parse_from_json(Some(json!(u64::MAX))).unwrap(),
+            u64::MAX as u128
+        );
+        assert_eq!(
+            u128::parse_from_json(Some(json!(u128::MAX.to_string()))).unwrap(),
+            u128::MAX
+        );
+        assert!(u128::parse_from_json(Some(json!(-1))).is_err());
+
+        assert_eq!(100i128.to_json(), Some(json!("100")));
+        assert_eq!(
+            i128::MIN.to_json(),
+            Some(json!("-170141183460469231731687303715884105728"))
+        );
+        assert_eq!(u128::MAX.to_json(), Some(json!(u128::MAX.to_string())));
+    }
+}
@@ -93,6 +93,10 @@ impl<T: ParseFromMultipartField> ParseFromMultipartField for Option<T> {
             None => Ok(None),
         }
     }
+
+    fn content_type() -> Option<&'static str> {
+        T::content_type()
+    }
 }
 
 impl<T: ToJSON> ToJSON for Option<T> {
@@ -114,3 +114,9 @@ impl<'a> ToJSON for &'a str {
         Some(Value::String(self.to_string()))
     }
 }
+
+impl ToHeader for &str {
+    fn to_header(&self) -> Option<HeaderValue> {
+        HeaderValue::from_str(self).ok()
+    }
+}
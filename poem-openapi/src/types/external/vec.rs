@@ -103,6 +103,10 @@ impl<T: ParseFromMultipartField> ParseFromMultipartField for Vec<T> {
         self.push(item);
         Ok(self)
     }
+
+    fn content_type() -> Option<&'static str> {
+        T::content_type()
+    }
 }
 
 impl<T: ToJSON> ToJSON for Vec<T> {
@@ -394,6 +394,10 @@ impl<T: ParseFromMultipartField> ParseFromMultipartField for MaybeUndefined<T> {
             None => Ok(MaybeUndefined::Undefined),
         }
     }
+
+    fn content_type() -> Option<&'static str> {
+        T::content_type()
+    }
 }
 
 impl<T: ToJSON> ToJSON for MaybeUndefined<T> {
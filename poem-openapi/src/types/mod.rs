@@ -1,11 +1,13 @@
 //! Commonly used data types.
 
 mod any;
+pub(crate) mod as_string;
 mod base64_type;
 mod binary;
 mod error;
 mod external;
 mod maybe_undefined;
+mod page;
 mod string_types;
 
 pub mod multipart;
@@ -17,6 +19,7 @@ pub use base64_type::Base64;
 pub use binary::Binary;
 pub use error::{ParseError, ParseResult};
 pub use maybe_undefined::MaybeUndefined;
+pub use page::Page;
 use poem::{http::HeaderValue, web::Field as PoemField};
 use serde_json::Value;
 #[cfg(feature = "email")]
@@ -154,6 +157,12 @@ pub trait ParseFromMultipartField: Sized + Type {
     ) -> impl Future<Output = ParseResult<Self>> + Send {
         async move { Err(ParseError::<Self>::custom("repeated field")) }
     }
+
+    /// Returns the content type of this field if it differs from the
+    /// default, used for the `encoding` of the multipart request body.
+    fn content_type() -> Option<&'static str> {
+        None
+    }
 }
 
 /// Represents a type that can converted to JSON value.
@@ -401,6 +410,10 @@ impl<T: ParseFromMultipartField> ParseFromMultipartField for Box<T> {
             .map_err(ParseError::propagate)
             .map(Box::new)
     }
+
+    fn content_type() -> Option<&'static str> {
+        T::content_type()
+    }
 }
 
 impl<T: ToJSON> ToJSON for Box<T> {
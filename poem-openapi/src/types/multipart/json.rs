@@ -57,6 +57,10 @@ impl<T: ParseFromJSON> ParseFromMultipartField for JsonField<T> {
             T::parse_from_json(Some(value)).map_err(ParseError::propagate)?,
         ))
     }
+
+    fn content_type() -> Option<&'static str> {
+        Some("application/json")
+    }
 }
 
 impl<T: ToJSON> ToJSON for JsonField<T> {
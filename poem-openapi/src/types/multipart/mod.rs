@@ -5,3 +5,48 @@ mod upload;
 
 pub use json::JsonField;
 pub use upload::Upload;
+
+#[doc(hidden)]
+pub fn multipart_content_type_matches(content_type: Option<&str>, accepted: &str) -> bool {
+    let Some(mime) = content_type.and_then(|content_type| content_type.parse::<mime::Mime>().ok())
+    else {
+        return false;
+    };
+
+    accepted
+        .split(',')
+        .map(str::trim)
+        .any(|accepted| match accepted.split_once('/') {
+            Some(("*", "*")) => true,
+            Some((ty, "*")) => mime.type_().as_str().eq_ignore_ascii_case(ty),
+            _ => mime.essence_str().eq_ignore_ascii_case(accepted),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_type_matches() {
+        assert!(multipart_content_type_matches(
+            Some("image/png"),
+            "image/png"
+        ));
+        assert!(multipart_content_type_matches(
+            Some("image/jpeg"),
+            "image/png, image/jpeg"
+        ));
+        assert!(multipart_content_type_matches(Some("image/gif"), "image/*"));
+        assert!(multipart_content_type_matches(
+            Some("text/plain; charset=utf-8"),
+            "text/plain"
+        ));
+        assert!(multipart_content_type_matches(Some("text/plain"), "*/*"));
+        assert!(!multipart_content_type_matches(
+            Some("text/plain"),
+            "image/*"
+        ));
+        assert!(!multipart_content_type_matches(None, "image/png"));
+    }
+}
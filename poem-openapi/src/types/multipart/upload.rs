@@ -3,6 +3,8 @@ use std::{
     fmt::{self, Debug, Formatter},
 };
 
+use bytes::{Bytes, BytesMut};
+use futures_util::{stream, Stream};
 use poem::web::Field as PoemField;
 use tokio::{
     fs::File,
@@ -15,6 +17,17 @@ use crate::{
 };
 
 /// A uploaded file for multipart.
+///
+/// The content of the file is written to a temporary file as it arrives, so
+/// large files are not buffered in memory unless [`Upload::into_vec`] or
+/// [`Upload::into_string`] is called. Use [`Upload::into_async_read`],
+/// [`Upload::into_stream`] or [`Upload::into_file`] to pipe it to the
+/// destination instead.
+///
+/// The whole request body is read before the operation is called, so these
+/// methods read the temporary file rather than the request itself. To process
+/// the parts while they arrive, extract
+/// [`poem::web::Multipart`](poem::web::Multipart) in the operation instead.
 pub struct Upload {
     file_name: Option<String>,
     content_type: Option<String>,
@@ -78,6 +91,18 @@ impl Upload {
         self.file
     }
 
+    /// Consumes this body object to return a stream of the file content, read
+    /// from the temporary file.
+    pub fn into_stream(self) -> impl Stream<Item = Result<Bytes, IoError>> + Send + 'static {
+        stream::try_unfold(self.file, |mut file| async move {
+            let mut buf = BytesMut::with_capacity(8 * 1024);
+            match file.read_buf(&mut buf).await? {
+                0 => Ok(None),
+                _ => Ok(Some((buf.freeze(), file))),
+            }
+        })
+    }
+
     /// Consumes this body object to return the file.
     pub fn into_file(self) -> File {
         self.file
@@ -0,0 +1,116 @@
+use poem::web::Pagination;
+
+use crate::{
+    types::{ParseFromJSON, ToJSON},
+    Object,
+};
+
+/// A page of items returned by a list endpoint.
+///
+/// It pairs with the [`Pagination`](poem::web::Pagination) extractor, which
+/// parses the `page`, `per_page` and `cursor` query parameters.
+///
+/// # Example
+///
+/// ```
+/// use poem::web::Pagination;
+/// use poem_openapi::{payload::Json, types::Page, OpenApi};
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/numbers", method = "get")]
+///     async fn numbers(&self, pagination: Pagination) -> Json<Page<u64>> {
+///         let items = (pagination.offset()..pagination.offset() + pagination.limit()).collect();
+///         Json(Page::new(items, &pagination).total(1000))
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Object)]
+#[oai(internal)]
+pub struct Page<T: ParseFromJSON + ToJSON> {
+    /// The items of this page.
+    pub items: Vec<T>,
+    /// The page number, starting from `1`, absent for a cursor based page.
+    #[oai(skip_serializing_if_is_none)]
+    pub page: Option<u32>,
+    /// The maximum number of items per page.
+    pub per_page: u32,
+    /// The total number of items.
+    #[oai(skip_serializing_if_is_none)]
+    pub total: Option<u64>,
+    /// The cursor of the next page.
+    #[oai(skip_serializing_if_is_none)]
+    pub next_cursor: Option<String>,
+}
+
+impl<T: ParseFromJSON + ToJSON> Page<T> {
+    /// Create a page with the items of the request specified by
+    /// `pagination`.
+    pub fn new(items: Vec<T>, pagination: &Pagination) -> Self {
+        Self {
+            items,
+            page: pagination.cursor().is_none().then(|| pagination.page()),
+            per_page: pagination.per_page(),
+            total: None,
+            next_cursor: None,
+        }
+    }
+
+    /// Sets the total number of items.
+    #[must_use]
+    pub fn total(self, total: u64) -> Self {
+        Self {
+            total: Some(total),
+            ..self
+        }
+    }
+
+    /// Sets the cursor of the next page.
+    #[must_use]
+    pub fn next_cursor(self, cursor: impl Into<String>) -> Self {
+        Self {
+            next_cursor: Some(cursor.into()),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use poem::{handler, test::TestClient, web::Json};
+    use serde_json::json;
+
+    use super::*;
+    use crate::types::Type;
+
+    #[test]
+    fn name() {
+        assert_eq!(Page::<i32>::name(), "Page<integer(int32)>");
+    }
+
+    #[tokio::test]
+    async fn from_pagination() {
+        #[handler]
+        fn index(pagination: Pagination) -> Json<serde_json::Value> {
+            let page = Page::new(vec![1, 2], &pagination).total(10);
+            Json(page.to_json().unwrap())
+        }
+
+        let cli = TestClient::new(index);
+        cli.get("/")
+            .query("page", &2)
+            .query("per_page", &2)
+            .send()
+            .await
+            .assert_json(json!({ "items": [1, 2], "page": 2, "per_page": 2, "total": 10 }))
+            .await;
+        cli.get("/")
+            .query("cursor", &"abc")
+            .send()
+            .await
+            .assert_json(json!({ "items": [1, 2], "per_page": 20, "total": 10 }))
+            .await;
+    }
+}
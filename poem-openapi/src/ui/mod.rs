@@ -6,3 +6,20 @@ pub(crate) mod rapidoc;
 pub(crate) mod redoc;
 #[cfg(feature = "swagger-ui")]
 pub(crate) mod swagger_ui;
+
+/// Escapes a string to be used as the text or an attribute value in HTML.
+#[allow(dead_code)]
+pub(crate) fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Serializes a value to JSON that can be embedded in a `<script>` element.
+#[allow(dead_code)]
+pub(crate) fn to_script_json(value: &impl serde::Serialize) -> String {
+    serde_json::to_string(value)
+        .unwrap_or_default()
+        .replace("</", "<\\/")
+}
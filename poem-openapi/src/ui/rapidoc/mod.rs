@@ -1,5 +1,7 @@
 use poem::{endpoint::make_sync, web::Html, Endpoint};
 
+use crate::ui::escape_html;
+
 const RAPIDOC_JS: &str = include_str!("rapidoc-min.js");
 const OAUTH_RECEIVER_HTML: &str = include_str!("oauth-receiver.html");
 
@@ -8,8 +10,7 @@ const RAPIDOC_TEMPLATE: &str = r#"
 <head>
     <meta http-equiv="Content-Type" content="text/html;charset=utf-8">
     <meta name="viewport" content="width=device-width, minimum-scale=1, initial-scale=1, user-scalable=yes">
-    <link href="https://fonts.googleapis.com/css2?family=Open+Sans:wght@300;600&family=Roboto+Mono&display=swap" rel="stylesheet">
-    <title>RapiDoc</title>
+    <title>{:title}</title>
     <script charset="UTF-8">{:script}</script>
 </head>
 </html>
@@ -17,17 +18,16 @@ const RAPIDOC_TEMPLATE: &str = r#"
 
     <rapi-doc
         id="thedoc"
-        theme="light"
-        render-style = "focused"
+        {:attributes}
         show-header	= "false"
         show-components = "true"
         allow-try="true"
         allow-authentication = "true"
-        regular-font="Open Sans"
-        mono-font = "Roboto Mono"
+        regular-font="-apple-system, BlinkMacSystemFont, Segoe UI, Helvetica, Arial, sans-serif"
+        mono-font = "Roboto Mono, Monaco, Consolas, monospace"
         font-size = "large"
         schema-description-expanded = "true"	
-    >
+    >{:logo}
     </rapi-doc>
     <script>
     document.addEventListener('DOMContentLoaded', (event) => {
@@ -39,14 +39,209 @@ const RAPIDOC_TEMPLATE: &str = r#"
 </body>
 "#;
 
-pub(crate) fn create_html(document: &str) -> String {
+/// The theme of RapiDoc.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RapiDocTheme {
+    /// The light theme.
+    Light,
+    /// The dark theme.
+    Dark,
+}
+
+/// The layout style of RapiDoc.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RapiDocRenderStyle {
+    /// Lists all the operations in a single page.
+    Read,
+    /// Lists the operations in a tree, and expands them when they are clicked.
+    View,
+    /// Shows one operation at a time.
+    Focused,
+}
+
+/// The configuration of the RapiDoc endpoint.
+///
+/// Reference: <https://rapidocweb.com/api.html>
+#[derive(Debug, Clone)]
+pub struct RapiDocConfig {
+    title: String,
+    theme: RapiDocTheme,
+    render_style: RapiDocRenderStyle,
+    primary_color: Option<String>,
+    bg_color: Option<String>,
+    text_color: Option<String>,
+    nav_bg_color: Option<String>,
+    nav_text_color: Option<String>,
+    allow_spec_file_download: bool,
+    logo: Option<String>,
+}
+
+impl Default for RapiDocConfig {
+    fn default() -> Self {
+        Self {
+            title: "RapiDoc".to_string(),
+            theme: RapiDocTheme::Light,
+            render_style: RapiDocRenderStyle::Focused,
+            primary_color: None,
+            bg_color: None,
+            text_color: None,
+            nav_bg_color: None,
+            nav_text_color: None,
+            allow_spec_file_download: false,
+            logo: None,
+        }
+    }
+}
+
+impl RapiDocConfig {
+    /// Create a new configuration with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title of the page.
+    #[must_use]
+    pub fn title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..self
+        }
+    }
+
+    /// Sets the theme, the default is [`RapiDocTheme::Light`].
+    #[must_use]
+    pub fn theme(self, theme: RapiDocTheme) -> Self {
+        Self { theme, ..self }
+    }
+
+    /// Sets the layout style, the default is [`RapiDocRenderStyle::Focused`].
+    #[must_use]
+    pub fn render_style(self, render_style: RapiDocRenderStyle) -> Self {
+        Self {
+            render_style,
+            ..self
+        }
+    }
+
+    /// Sets the color of the buttons, tabs and links.
+    #[must_use]
+    pub fn primary_color(self, color: impl Into<String>) -> Self {
+        Self {
+            primary_color: Some(color.into()),
+            ..self
+        }
+    }
+
+    /// Sets the background color.
+    #[must_use]
+    pub fn bg_color(self, color: impl Into<String>) -> Self {
+        Self {
+            bg_color: Some(color.into()),
+            ..self
+        }
+    }
+
+    /// Sets the text color.
+    #[must_use]
+    pub fn text_color(self, color: impl Into<String>) -> Self {
+        Self {
+            text_color: Some(color.into()),
+            ..self
+        }
+    }
+
+    /// Sets the background color of the navigation bar.
+    #[must_use]
+    pub fn nav_bg_color(self, color: impl Into<String>) -> Self {
+        Self {
+            nav_bg_color: Some(color.into()),
+            ..self
+        }
+    }
+
+    /// Sets the text color of the navigation bar.
+    #[must_use]
+    pub fn nav_text_color(self, color: impl Into<String>) -> Self {
+        Self {
+            nav_text_color: Some(color.into()),
+            ..self
+        }
+    }
+
+    /// Shows the buttons to open and download the specification, they are
+    /// hidden by default.
+    #[must_use]
+    pub fn allow_spec_file_download(self, enabled: bool) -> Self {
+        Self {
+            allow_spec_file_download: enabled,
+            ..self
+        }
+    }
+
+    /// Sets the URL of the logo shown in the navigation bar.
+    #[must_use]
+    pub fn logo(self, url: impl Into<String>) -> Self {
+        Self {
+            logo: Some(url.into()),
+            ..self
+        }
+    }
+
+    fn attributes(&self) -> String {
+        let theme = match self.theme {
+            RapiDocTheme::Light => "light",
+            RapiDocTheme::Dark => "dark",
+        };
+        let render_style = match self.render_style {
+            RapiDocRenderStyle::Read => "read",
+            RapiDocRenderStyle::View => "view",
+            RapiDocRenderStyle::Focused => "focused",
+        };
+        let mut attributes = vec![
+            format!("theme=\"{theme}\""),
+            format!("render-style = \"{render_style}\""),
+        ];
+        let colors = [
+            ("primary-color", &self.primary_color),
+            ("bg-color", &self.bg_color),
+            ("text-color", &self.text_color),
+            ("nav-bg-color", &self.nav_bg_color),
+            ("nav-text-color", &self.nav_text_color),
+        ];
+        for (name, color) in colors {
+            if let Some(color) = color {
+                attributes.push(format!("{name}=\"{}\"", escape_html(color)));
+            }
+        }
+        if self.allow_spec_file_download {
+            attributes.push("allow-spec-file-download=\"true\"".to_string());
+        }
+        attributes.join("\n        ")
+    }
+}
+
+pub(crate) fn create_html(document: &str, config: &RapiDocConfig) -> String {
+    let logo = config
+        .logo
+        .as_deref()
+        .map(|url| {
+            format!(
+                "\n        <img slot=\"nav-logo\" src=\"{}\" style=\"max-width: 100%\" />",
+                escape_html(url)
+            )
+        })
+        .unwrap_or_default();
+
     RAPIDOC_TEMPLATE
+        .replace("{:title}", &escape_html(&config.title))
+        .replace("{:attributes}", &config.attributes())
+        .replace("{:logo}", &logo)
         .replace("{:script}", RAPIDOC_JS)
         .replace("{:spec}", document)
 }
 
-pub(crate) fn create_endpoint(document: &str) -> impl Endpoint {
-    let ui_html = create_html(document);
+pub(crate) fn create_endpoint(document: &str, config: &RapiDocConfig) -> impl Endpoint {
+    let ui_html = create_html(document, config);
     let oauth_receiver_html = OAUTH_RECEIVER_HTML.replace("{:script}", RAPIDOC_JS);
 
     poem::Route::new()
@@ -56,3 +251,34 @@ pub(crate) fn create_endpoint(document: &str) -> impl Endpoint {
             make_sync(move |_| Html(oauth_receiver_html.clone())),
         )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config() {
+        let html = create_html("{}", &RapiDocConfig::default());
+        assert!(html.contains("<title>RapiDoc</title>"));
+        assert!(html.contains("theme=\"light\""));
+        assert!(html.contains("render-style = \"focused\""));
+        assert!(!html.contains("allow-spec-file-download=\"true\""));
+
+        let html = create_html(
+            "{}",
+            &RapiDocConfig::new()
+                .title("My API")
+                .theme(RapiDocTheme::Dark)
+                .render_style(RapiDocRenderStyle::Read)
+                .primary_color("#ff0000")
+                .allow_spec_file_download(true)
+                .logo("/logo.png"),
+        );
+        assert!(html.contains("<title>My API</title>"));
+        assert!(html.contains("theme=\"dark\""));
+        assert!(html.contains("render-style = \"read\""));
+        assert!(html.contains("primary-color=\"#ff0000\""));
+        assert!(html.contains("allow-spec-file-download=\"true\""));
+        assert!(html.contains("<img slot=\"nav-logo\" src=\"/logo.png\""));
+    }
+}
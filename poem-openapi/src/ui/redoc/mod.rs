@@ -1,4 +1,7 @@
 use poem::{endpoint::make_sync, web::Html, Endpoint};
+use serde_json::{json, Map, Value};
+
+use crate::ui::{escape_html, to_script_json};
 
 const REDOC_JS: &str = include_str!("redoc.standalone.js");
 
@@ -6,11 +9,10 @@ const REDOC_TEMPLATE: &str = r#"
 <!DOCTYPE html>
 <html>
   <head>
-    <title>Redoc</title>
+    <title>{:title}</title>
     <!-- needed for adaptive design -->
     <meta charset="utf-8"/>
     <meta name="viewport" content="width=device-width, initial-scale=1">
-    <link href="https://fonts.googleapis.com/css?family=Montserrat:300,400,700|Roboto:300,400,700" rel="stylesheet">
 
     <!--
     Redoc doesn't change outer page styles
@@ -28,21 +30,190 @@ const REDOC_TEMPLATE: &str = r#"
     
     <script>
         let spec = {:spec};
-        Redoc.init(spec, {
+        let logo = {:logo};
+        if (logo) {
+          spec.info = Object.assign({}, spec.info, { "x-logo": logo });
+        }
+        Redoc.init(spec, Object.assign({
           scrollYOffset: 50
-        }, document.getElementById('redoc-container'));
+        }, {:options}), document.getElementById('redoc-container'));
     </script>
   </body>
 </html>
 "#;
 
-pub(crate) fn create_html(document: &str) -> String {
+/// The configuration of the Redoc endpoint.
+///
+/// Reference: <https://redocly.com/docs/redoc/config>
+#[derive(Debug, Clone)]
+pub struct RedocConfig {
+    title: String,
+    primary_color: Option<String>,
+    text_color: Option<String>,
+    sidebar_background_color: Option<String>,
+    sidebar_text_color: Option<String>,
+    hide_download_button: bool,
+    logo: Option<String>,
+}
+
+impl Default for RedocConfig {
+    fn default() -> Self {
+        Self {
+            title: "Redoc".to_string(),
+            primary_color: None,
+            text_color: None,
+            sidebar_background_color: None,
+            sidebar_text_color: None,
+            hide_download_button: false,
+            logo: None,
+        }
+    }
+}
+
+impl RedocConfig {
+    /// Create a new configuration with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title of the page.
+    #[must_use]
+    pub fn title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..self
+        }
+    }
+
+    /// Sets the primary color of the theme.
+    #[must_use]
+    pub fn primary_color(self, color: impl Into<String>) -> Self {
+        Self {
+            primary_color: Some(color.into()),
+            ..self
+        }
+    }
+
+    /// Sets the text color of the theme.
+    #[must_use]
+    pub fn text_color(self, color: impl Into<String>) -> Self {
+        Self {
+            text_color: Some(color.into()),
+            ..self
+        }
+    }
+
+    /// Sets the background color of the sidebar.
+    #[must_use]
+    pub fn sidebar_background_color(self, color: impl Into<String>) -> Self {
+        Self {
+            sidebar_background_color: Some(color.into()),
+            ..self
+        }
+    }
+
+    /// Sets the text color of the sidebar.
+    #[must_use]
+    pub fn sidebar_text_color(self, color: impl Into<String>) -> Self {
+        Self {
+            sidebar_text_color: Some(color.into()),
+            ..self
+        }
+    }
+
+    /// Hides the button to download the specification.
+    #[must_use]
+    pub fn hide_download_button(self, hidden: bool) -> Self {
+        Self {
+            hide_download_button: hidden,
+            ..self
+        }
+    }
+
+    /// Sets the URL of the logo shown above the sidebar.
+    #[must_use]
+    pub fn logo(self, url: impl Into<String>) -> Self {
+        Self {
+            logo: Some(url.into()),
+            ..self
+        }
+    }
+
+    fn options(&self) -> Value {
+        let mut theme = Map::new();
+        let mut colors = Map::new();
+        if let Some(color) = &self.primary_color {
+            colors.insert("primary".to_string(), json!({ "main": color }));
+        }
+        if let Some(color) = &self.text_color {
+            colors.insert("text".to_string(), json!({ "primary": color }));
+        }
+        if !colors.is_empty() {
+            theme.insert("colors".to_string(), Value::Object(colors));
+        }
+
+        let mut sidebar = Map::new();
+        if let Some(color) = &self.sidebar_background_color {
+            sidebar.insert("backgroundColor".to_string(), json!(color));
+        }
+        if let Some(color) = &self.sidebar_text_color {
+            sidebar.insert("textColor".to_string(), json!(color));
+        }
+        if !sidebar.is_empty() {
+            theme.insert("sidebar".to_string(), Value::Object(sidebar));
+        }
+
+        let mut options = Map::new();
+        if !theme.is_empty() {
+            options.insert("theme".to_string(), Value::Object(theme));
+        }
+        if self.hide_download_button {
+            options.insert("hideDownloadButton".to_string(), Value::Bool(true));
+        }
+        Value::Object(options)
+    }
+}
+
+pub(crate) fn create_html(document: &str, config: &RedocConfig) -> String {
+    let logo = config.logo.as_ref().map(|url| json!({ "url": url }));
+
     REDOC_TEMPLATE
+        .replace("{:title}", &escape_html(&config.title))
+        .replace("{:logo}", &to_script_json(&logo))
+        .replace("{:options}", &to_script_json(&config.options()))
         .replace("{:script}", REDOC_JS)
         .replace("{:spec}", document)
 }
 
-pub(crate) fn create_endpoint(document: &str) -> impl Endpoint {
-    let ui_html = create_html(document);
+pub(crate) fn create_endpoint(document: &str, config: &RedocConfig) -> impl Endpoint {
+    let ui_html = create_html(document, config);
     poem::Route::new().at("/", make_sync(move |_| Html(ui_html.clone())))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config() {
+        let html = create_html("{}", &RedocConfig::default());
+        assert!(html.contains("<title>Redoc</title>"));
+        assert!(html.contains("let logo = null;"));
+        assert!(html.contains("}, {}), document"));
+
+        let html = create_html(
+            "{}",
+            &RedocConfig::new()
+                .title("My API")
+                .primary_color("#ff0000")
+                .sidebar_background_color("#000000")
+                .hide_download_button(true)
+                .logo("/logo.png"),
+        );
+        assert!(html.contains("<title>My API</title>"));
+        assert!(html.contains(r#"let logo = {"url":"/logo.png"};"#));
+        assert!(html.contains(
+            r##"{"theme":{"colors":{"primary":{"main":"#ff0000"}},"sidebar":{"backgroundColor":"#000000"}},"hideDownloadButton":true}"##
+        ));
+    }
+}
@@ -1,4 +1,8 @@
 use poem::{endpoint::make_sync, web::Html, Endpoint};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::ui::{escape_html, to_script_json};
 
 const SWAGGER_UI_JS: &str = include_str!("swagger-ui-bundle.js");
 const SWAGGER_UI_CSS: &str = include_str!("swagger-ui.css");
@@ -8,7 +12,7 @@ const SWAGGER_UI_TEMPLATE: &str = r#"
 <html charset="UTF-8">
 <head>
     <meta http-equiv="Content-Type" content="text/html;charset=utf-8">
-    <title>Swagger UI</title>
+    <title>{:title}</title>{:favicon}
     <style charset="UTF-8">{:style}</style>
     <script charset="UTF-8">{:script}</script>
 </head>
@@ -31,27 +35,244 @@ const SWAGGER_UI_TEMPLATE: &str = r#"
     }
     oauth2RedirectUrl += "oauth-receiver.html";
 
-    SwaggerUIBundle({
+    let ui = SwaggerUIBundle(Object.assign({
         dom_id: '#ui',
         spec: spec,
         filter: false,
+        validatorUrl: null,
         oauth2RedirectUrl: oauth2RedirectUrl,
-    })
+    }, {:options}));
+
+    let oauth = {:oauth};
+    if (oauth) {
+        ui.initOAuth(oauth);
+    }
 </script>
 
 </body>
 </html>
 "#;
 
-pub(crate) fn create_html(document: &str) -> String {
+/// The initial expansion state of the operations and tags in Swagger UI.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SwaggerUIDocExpansion {
+    /// Expands only the tags.
+    List,
+    /// Expands the tags and operations.
+    Full,
+    /// Expands nothing.
+    None,
+}
+
+/// The OAuth2 client configuration used by the authorization dialog of
+/// Swagger UI.
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwaggerUIOAuthConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_secret: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    realm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    app_name: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    scopes: Vec<String>,
+    use_pkce_with_authorization_code_grant: bool,
+}
+
+impl SwaggerUIOAuthConfig {
+    /// Create a new OAuth2 client configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the default client id.
+    #[must_use]
+    pub fn client_id(self, client_id: impl Into<String>) -> Self {
+        Self {
+            client_id: Some(client_id.into()),
+            ..self
+        }
+    }
+
+    /// Sets the default client secret.
+    ///
+    /// Never use this parameter in your production environment, it exposes
+    /// crucial security information.
+    #[must_use]
+    pub fn client_secret(self, client_secret: impl Into<String>) -> Self {
+        Self {
+            client_secret: Some(client_secret.into()),
+            ..self
+        }
+    }
+
+    /// Sets the realm query parameter added to the authorization URL and the
+    /// token URL.
+    #[must_use]
+    pub fn realm(self, realm: impl Into<String>) -> Self {
+        Self {
+            realm: Some(realm.into()),
+            ..self
+        }
+    }
+
+    /// Sets the application name displayed in the authorization popup.
+    #[must_use]
+    pub fn app_name(self, app_name: impl Into<String>) -> Self {
+        Self {
+            app_name: Some(app_name.into()),
+            ..self
+        }
+    }
+
+    /// Sets the scopes selected by default.
+    #[must_use]
+    pub fn scopes<I, S>(self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            scopes: scopes.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+
+    /// Uses the Proof Key for Code Exchange with the authorization code flow.
+    #[must_use]
+    pub fn use_pkce_with_authorization_code_grant(self, enabled: bool) -> Self {
+        Self {
+            use_pkce_with_authorization_code_grant: enabled,
+            ..self
+        }
+    }
+}
+
+/// The configuration of the Swagger UI endpoint.
+///
+/// Reference: <https://swagger.io/docs/open-source-tools/swagger-ui/usage/configuration/>
+#[derive(Debug, Clone)]
+pub struct SwaggerUIConfig {
+    title: String,
+    favicon: Option<String>,
+    doc_expansion: Option<SwaggerUIDocExpansion>,
+    try_it_out_enabled: Option<bool>,
+    persist_authorization: Option<bool>,
+    oauth: Option<SwaggerUIOAuthConfig>,
+}
+
+impl Default for SwaggerUIConfig {
+    fn default() -> Self {
+        Self {
+            title: "Swagger UI".to_string(),
+            favicon: None,
+            doc_expansion: None,
+            try_it_out_enabled: None,
+            persist_authorization: None,
+            oauth: None,
+        }
+    }
+}
+
+impl SwaggerUIConfig {
+    /// Create a new configuration with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title of the page.
+    #[must_use]
+    pub fn title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..self
+        }
+    }
+
+    /// Sets the URL of the favicon of the page.
+    #[must_use]
+    pub fn favicon(self, url: impl Into<String>) -> Self {
+        Self {
+            favicon: Some(url.into()),
+            ..self
+        }
+    }
+
+    /// Sets the default expansion setting for the operations and tags.
+    #[must_use]
+    pub fn doc_expansion(self, doc_expansion: SwaggerUIDocExpansion) -> Self {
+        Self {
+            doc_expansion: Some(doc_expansion),
+            ..self
+        }
+    }
+
+    /// Enables the "Try it out" section by default.
+    #[must_use]
+    pub fn try_it_out_enabled(self, enabled: bool) -> Self {
+        Self {
+            try_it_out_enabled: Some(enabled),
+            ..self
+        }
+    }
+
+    /// Persists the authorization data, so it would not be lost on browser
+    /// close or refresh.
+    #[must_use]
+    pub fn persist_authorization(self, enabled: bool) -> Self {
+        Self {
+            persist_authorization: Some(enabled),
+            ..self
+        }
+    }
+
+    /// Sets the OAuth2 client configuration used by the authorization dialog.
+    #[must_use]
+    pub fn oauth(self, oauth: SwaggerUIOAuthConfig) -> Self {
+        Self {
+            oauth: Some(oauth),
+            ..self
+        }
+    }
+
+    fn options(&self) -> Value {
+        let mut options = Map::new();
+        if let Some(doc_expansion) = self.doc_expansion {
+            options.insert("docExpansion".to_string(), serde_json::json!(doc_expansion));
+        }
+        if let Some(enabled) = self.try_it_out_enabled {
+            options.insert("tryItOutEnabled".to_string(), Value::Bool(enabled));
+        }
+        if let Some(enabled) = self.persist_authorization {
+            options.insert("persistAuthorization".to_string(), Value::Bool(enabled));
+        }
+        Value::Object(options)
+    }
+}
+
+pub(crate) fn create_html(document: &str, config: &SwaggerUIConfig) -> String {
+    let favicon = config
+        .favicon
+        .as_deref()
+        .map(|url| format!("\n    <link rel=\"icon\" href=\"{}\">", escape_html(url)))
+        .unwrap_or_default();
+
     SWAGGER_UI_TEMPLATE
+        .replace("{:title}", &escape_html(&config.title))
+        .replace("{:favicon}", &favicon)
+        .replace("{:options}", &to_script_json(&config.options()))
+        .replace("{:oauth}", &to_script_json(&config.oauth))
         .replace("{:style}", SWAGGER_UI_CSS)
         .replace("{:script}", SWAGGER_UI_JS)
         .replace("{:spec}", document)
 }
 
-pub(crate) fn create_endpoint(document: &str) -> impl Endpoint {
-    let ui_html = create_html(document);
+pub(crate) fn create_endpoint(document: &str, config: &SwaggerUIConfig) -> impl Endpoint {
+    let ui_html = create_html(document, config);
     poem::Route::new()
         .at("/", make_sync(move |_| Html(ui_html.clone())))
         .at(
@@ -59,3 +280,32 @@ pub(crate) fn create_endpoint(document: &str) -> impl Endpoint {
             make_sync(move |_| Html(OAUTH_RECEIVER_HTML.to_string())),
         )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config() {
+        let html = create_html(
+            "{}",
+            &SwaggerUIConfig::new()
+                .title("My <API>")
+                .favicon("/favicon.png")
+                .doc_expansion(SwaggerUIDocExpansion::None)
+                .persist_authorization(true)
+                .oauth(
+                    SwaggerUIOAuthConfig::new()
+                        .client_id("my-client")
+                        .scopes(["read", "write"]),
+                ),
+        );
+
+        assert!(html.contains("<title>My &lt;API&gt;</title>"));
+        assert!(html.contains(r#"<link rel="icon" href="/favicon.png">"#));
+        assert!(html.contains(r#"{"docExpansion":"none","persistAuthorization":true}"#));
+        assert!(html.contains(
+            r#"{"clientId":"my-client","scopes":["read","write"],"usePkceWithAuthorizationCodeGrant":false}"#
+        ));
+    }
+}
@@ -0,0 +1,33 @@
+use derive_more::Display;
+use serde_json::Value;
+
+use crate::{
+    registry::MetaSchema,
+    validation::{Validator, ValidatorMeta},
+};
+
+#[derive(Display)]
+#[display(fmt = "const(\"{value}\")")]
+pub struct ConstValue {
+    value: &'static str,
+}
+
+impl ConstValue {
+    #[inline]
+    pub fn new(value: &'static str) -> Self {
+        Self { value }
+    }
+}
+
+impl<T: AsRef<str>> Validator<T> for ConstValue {
+    #[inline]
+    fn check(&self, value: &T) -> bool {
+        value.as_ref() == self.value
+    }
+}
+
+impl ValidatorMeta for ConstValue {
+    fn update_meta(&self, meta: &mut MetaSchema) {
+        meta.enum_items = vec![Value::String(self.value.to_string())];
+    }
+}
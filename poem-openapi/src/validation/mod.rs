@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+mod const_value;
 mod max_items;
 mod max_length;
 mod max_properties;
@@ -12,6 +13,7 @@ mod multiple_of;
 mod pattern;
 mod unique_items;
 
+pub use const_value::ConstValue;
 pub use max_items::MaxItems;
 pub use max_length::MaxLength;
 pub use max_properties::MaxProperties;
@@ -32,6 +34,36 @@ pub trait Validator<T>: Display {
     fn check(&self, value: &T) -> bool;
 }
 
+/// Represents a validator that updates the schema with its constraints.
+///
+/// Implement this trait for a custom validator to reflect it in the schema.
 pub trait ValidatorMeta {
+    /// Updates the schema with the constraints of this validator.
     fn update_meta(&self, meta: &mut MetaSchema);
 }
+
+/// Updates the schema with a custom validator that implements
+/// [`ValidatorMeta`].
+#[doc(hidden)]
+pub trait CustomValidatorMeta {
+    fn update_custom_meta(&self, meta: &mut MetaSchema);
+}
+
+impl<T: ValidatorMeta> CustomValidatorMeta for T {
+    #[inline]
+    fn update_custom_meta(&self, meta: &mut MetaSchema) {
+        self.update_meta(meta);
+    }
+}
+
+/// The fallback of [`CustomValidatorMeta`] for the custom validators that
+/// don't implement [`ValidatorMeta`].
+#[doc(hidden)]
+pub trait NoCustomValidatorMeta {
+    fn update_custom_meta(&self, meta: &mut MetaSchema);
+}
+
+impl<T> NoCustomValidatorMeta for &T {
+    #[inline]
+    fn update_custom_meta(&self, _meta: &mut MetaSchema) {}
+}
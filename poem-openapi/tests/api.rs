@@ -1,15 +1,17 @@
 use poem::{
     http::{Method, StatusCode},
+    middleware::SetHeader,
     test::TestClient,
     web::Data,
-    Endpoint, EndpointExt, Error,
+    Endpoint, EndpointExt, Error, IntoEndpoint,
 };
 use poem_openapi::{
     param::{Path, Query},
     payload::{Binary, Json, Payload, PlainText},
     registry::{MetaApi, MetaExternalDocument, MetaOperation, MetaParamIn, MetaSchema, Registry},
     types::Type,
-    ApiRequest, ApiResponse, Object, OpenApi, OpenApiService, Tags,
+    ApiRequest, ApiResponse, Enum, Object, OpenApi, OpenApiService, OperationId, OperationTags,
+    RequestValidation, Tags, Webhook,
 };
 
 #[tokio::test]
@@ -351,6 +353,60 @@ async fn payload_request() {
         .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
 }
 
+#[tokio::test]
+async fn optional_payload_request() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "post")]
+        async fn test(&self, req: Option<Json<i32>>, n: Option<Query<i32>>) -> PlainText<String> {
+            PlainText(format!("{:?} {:?}", req.map(|req| req.0), n.map(|n| n.0)))
+        }
+    }
+
+    let meta: MetaApi = Api::meta().remove(0);
+    let meta_request = meta.paths[0].operations[0].request.as_ref().unwrap();
+    assert!(!meta_request.required);
+
+    let ep = OpenApiService::new(Api, "test", "1.0");
+    let cli = TestClient::new(ep);
+
+    let resp = cli.post("/").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_text("None None").await;
+
+    let resp = cli
+        .post("/")
+        .query("n", &1)
+        .content_type("application/json")
+        .body("100")
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_text("Some(100) Some(1)").await;
+
+    cli.post("/")
+        .content_type("application/json")
+        .body("{")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    cli.post("/")
+        .content_type("text/plain")
+        .body("100")
+        .send()
+        .await
+        .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    cli.post("/")
+        .query("n", &"a")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn response() {
     #[derive(ApiResponse)]
@@ -819,6 +875,202 @@ async fn multiple_methods() {
     cli.put("/abc").send().await.assert_status_is_ok();
 }
 
+#[tokio::test]
+async fn operation_id() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/a", method = "get")]
+        async fn get_a(&self) {}
+
+        #[oai(path = "/b", method = "get", operation_id = "custom")]
+        async fn get_b(&self) {}
+
+        #[oai(path = "/c", method = "post", method = "put")]
+        async fn r#update(&self) {}
+    }
+
+    let meta: MetaApi = Api::meta().remove(0);
+    assert_eq!(meta.paths[0].operations[0].operation_id, Some("Api_get_a"));
+    assert_eq!(meta.paths[1].operations[0].operation_id, Some("custom"));
+    assert_eq!(
+        meta.paths[2].operations[0].operation_id,
+        Some("Api_update_post")
+    );
+    assert_eq!(
+        meta.paths[2].operations[1].operation_id,
+        Some("Api_update_put")
+    );
+
+    let ep = OpenApiService::new(Api, "test", "1.0");
+    let cli = TestClient::new(ep);
+    let resp = cli.get("/a").send().await;
+    resp.assert_status_is_ok();
+    assert_eq!(
+        resp.0.data::<OperationId>().map(|id| id.0),
+        Some("Api_get_a")
+    );
+}
+
+#[tokio::test]
+async fn operation_id_in_request() {
+    #[derive(Tags)]
+    enum MyTags {
+        UserOperations,
+        CommonOperations,
+    }
+
+    struct Api;
+
+    #[OpenApi(tag = "MyTags::CommonOperations")]
+    impl Api {
+        #[oai(path = "/users", method = "get", tag = "MyTags::UserOperations")]
+        async fn list_users(
+            &self,
+            operation_id: Data<&OperationId>,
+            operation_tags: Data<&OperationTags>,
+        ) -> PlainText<String> {
+            PlainText(format!(
+                "{} {}",
+                operation_id.0,
+                operation_tags.0 .0.join(",")
+            ))
+        }
+    }
+
+    let ep = OpenApiService::new(Api, "test", "1.0").around(|ep, req| async move {
+        let resp = ep.get_response(req).await;
+        let operation_id = resp.data::<OperationId>().copied();
+        let operation_tags = resp.data::<OperationTags>().cloned();
+        assert_eq!(operation_id, Some(OperationId("Api_list_users")));
+        assert_eq!(
+            operation_tags,
+            Some(OperationTags(vec!["CommonOperations", "UserOperations"]))
+        );
+        Ok(resp)
+    });
+    let cli = TestClient::new(ep);
+    let resp = cli.get("/users").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_text("Api_list_users CommonOperations,UserOperations")
+        .await;
+}
+
+#[test]
+#[should_panic(expected = "duplicate operation id: test")]
+fn duplicate_operation_id() {
+    struct Api1;
+
+    #[OpenApi]
+    impl Api1 {
+        #[oai(path = "/a", method = "get", operation_id = "test")]
+        async fn a(&self) {}
+    }
+
+    struct Api2;
+
+    #[OpenApi]
+    impl Api2 {
+        #[oai(path = "/b", method = "get", operation_id = "test")]
+        async fn b(&self) {}
+    }
+
+    let _ = OpenApiService::new((Api1, Api2), "test", "1.0").into_endpoint();
+}
+
+#[test]
+fn default_operation_id_across_apis() {
+    struct Api1;
+
+    #[OpenApi]
+    impl Api1 {
+        #[oai(path = "/a", method = "get")]
+        async fn test(&self) {}
+    }
+
+    struct Api2;
+
+    #[OpenApi]
+    impl Api2 {
+        #[oai(path = "/b", method = "get")]
+        async fn test(&self) {}
+    }
+
+    let meta = <(Api1, Api2)>::meta();
+    assert_eq!(
+        meta[0].paths[0].operations[0].operation_id,
+        Some("Api1_test")
+    );
+    assert_eq!(
+        meta[1].paths[0].operations[0].operation_id,
+        Some("Api2_test")
+    );
+    let _ = OpenApiService::new((Api1, Api2), "test", "1.0").into_endpoint();
+}
+
+#[tokio::test]
+async fn combined_apis() {
+    #[derive(Tags)]
+    enum MyTags {
+        Users,
+        Pets,
+    }
+
+    struct UserApi;
+
+    #[OpenApi(prefix_path = "/users", tag = "MyTags::Users")]
+    impl UserApi {
+        #[oai(path = "/", method = "get")]
+        async fn list_users(&self) -> PlainText<&'static str> {
+            PlainText("users")
+        }
+    }
+
+    struct PetApi;
+
+    #[OpenApi(prefix_path = "/pets", tag = "MyTags::Pets")]
+    impl PetApi {
+        #[oai(path = "/", method = "get")]
+        async fn list_pets(&self) -> PlainText<&'static str> {
+            PlainText("pets")
+        }
+    }
+
+    let meta = <(UserApi, PetApi)>::meta();
+    assert_eq!(meta[0].paths[0].path, "/users");
+    assert_eq!(meta[0].paths[0].operations[0].tags, vec!["Users"]);
+    assert_eq!(meta[1].paths[0].path, "/pets");
+    assert_eq!(meta[1].paths[0].operations[0].tags, vec!["Pets"]);
+
+    let ep = OpenApiService::new((UserApi, PetApi), "test", "1.0");
+    let cli = TestClient::new(ep);
+    cli.get("/users").send().await.assert_text("users").await;
+    cli.get("/pets").send().await.assert_text("pets").await;
+}
+
+#[test]
+#[should_panic(expected = "duplicate operation: GET /a")]
+fn duplicate_operation() {
+    struct Api1;
+
+    #[OpenApi]
+    impl Api1 {
+        #[oai(path = "/a", method = "get")]
+        async fn a1(&self) {}
+    }
+
+    struct Api2;
+
+    #[OpenApi]
+    impl Api2 {
+        #[oai(path = "/a", method = "get")]
+        async fn a2(&self) {}
+    }
+
+    let _ = OpenApiService::new((Api1, Api2), "test", "1.0").into_endpoint();
+}
+
 #[tokio::test]
 async fn actual_type() {
     #[derive(Debug, Object)]
@@ -912,6 +1164,251 @@ async fn code_samples() {
     assert_eq!(code_sample.source, "Google Go");
 }
 
+#[test]
+fn curl_example() {
+    #[derive(Debug, Object)]
+    struct Pet {
+        name: String,
+    }
+
+    fn cat() -> Pet {
+        Pet {
+            name: "Tom's".to_string(),
+        }
+    }
+
+    fn pet_id() -> i64 {
+        42
+    }
+
+    fn kind() -> String {
+        "a cat".to_string()
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/pets/:id", method = "put", curl_example)]
+        async fn update(
+            &self,
+            #[oai(example = "pet_id")] id: Path<i64>,
+            #[oai(example = "kind")] kind: Query<String>,
+            limit: Query<Option<i32>>,
+            #[oai(example(value = "cat"))] pet: Json<Pet>,
+        ) {
+            let _ = (id, kind, limit, pet);
+        }
+
+        #[oai(path = "/pets/:id", method = "delete")]
+        async fn delete(&self, id: Path<i64>) {
+            let _ = id;
+        }
+    }
+
+    let spec = OpenApiService::new(Api, "test", "1.0")
+        .server("https://api.example.com/")
+        .url_prefix("/v1")
+        .spec_value();
+    assert_eq!(
+        spec["paths"]["/v1/pets/{id}"]["put"]["x-code-samples"],
+        serde_json::json!([{
+            "lang": "Shell",
+            "label": "cURL",
+            "source": "curl -X PUT 'https://api.example.com/v1/pets/42?kind=a%20cat' \\\n  -H 'content-type: application/json; charset=utf-8' \\\n  -d '{\"name\":\"Tom'\\''s\"}'",
+        }])
+    );
+    assert!(spec["paths"]["/v1/pets/{id}"]["delete"]
+        .get("x-code-samples")
+        .is_none());
+}
+
+#[tokio::test]
+async fn request_body_examples() {
+    #[derive(Debug, Object)]
+    struct Pet {
+        name: String,
+    }
+
+    fn cat() -> Pet {
+        Pet {
+            name: "cat".to_string(),
+        }
+    }
+
+    fn dog() -> Pet {
+        Pet {
+            name: "dog".to_string(),
+        }
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "post")]
+        async fn create(
+            &self,
+            #[oai(
+                example(name = "cat", value = "cat", summary = "A cat"),
+                example(name = "dog", value = "dog")
+            )]
+            pet: Json<Pet>,
+        ) -> PlainText<String> {
+            PlainText(pet.0.name)
+        }
+    }
+
+    let meta: MetaApi = Api::meta().remove(0);
+    let request = meta.paths[0].operations[0].request.as_ref().unwrap();
+    let examples = &request.content[0].examples;
+    assert_eq!(examples.len(), 2);
+    assert_eq!(examples[0].name, "cat");
+    assert_eq!(examples[0].summary, Some("A cat"));
+    assert_eq!(
+        examples[0].value,
+        Some(serde_json::json!({ "name": "cat" }))
+    );
+    assert_eq!(examples[1].name, "dog");
+    assert_eq!(
+        examples[1].value,
+        Some(serde_json::json!({ "name": "dog" }))
+    );
+
+    let spec: serde_json::Value =
+        serde_json::from_str(&OpenApiService::new(Api, "test", "1.0").spec()).unwrap();
+    assert_eq!(
+        spec["paths"]["/"]["post"]["requestBody"]["content"]["application/json; charset=utf-8"]
+            ["examples"],
+        serde_json::json!({
+            "cat": { "summary": "A cat", "value": { "name": "cat" } },
+            "dog": { "value": { "name": "dog" } },
+        })
+    );
+}
+
+#[tokio::test]
+async fn extensions() {
+    #[derive(Debug, Object)]
+    #[oai(extension(name = "x-internal", value = "true"))]
+    struct MyObj {
+        value: i32,
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(
+            path = "/",
+            method = "post",
+            extension(name = "x-rate-limit", value = "100"),
+            extension(name = "x-gateway", value = r#"{"type": "http", "uri": "/backend"}"#)
+        )]
+        async fn test(
+            &self,
+            #[oai(extension(name = "x-example-id", value = r#""abc""#))] id: Query<String>,
+            req: Json<MyObj>,
+        ) -> Json<i32> {
+            let _ = id;
+            Json(req.0.value)
+        }
+    }
+
+    let meta: MetaApi = Api::meta().remove(0);
+    let operation = &meta.paths[0].operations[0];
+    assert_eq!(
+        operation.extensions,
+        [
+            (
+                "x-gateway".to_string(),
+                serde_json::json!({"type": "http", "uri": "/backend"})
+            ),
+            ("x-rate-limit".to_string(), serde_json::json!(100)),
+        ]
+        .into_iter()
+        .collect()
+    );
+    assert_eq!(
+        operation.params[0].extensions,
+        [("x-example-id".to_string(), serde_json::json!("abc"))]
+            .into_iter()
+            .collect()
+    );
+
+    let spec: serde_json::Value = serde_json::from_str(
+        &OpenApiService::new(Api, "test", "1.0")
+            .extension(
+                "x-logo",
+                serde_json::json!({"url": "https://example.com/logo.png"}),
+            )
+            .spec(),
+    )
+    .unwrap();
+    assert_eq!(
+        spec["x-logo"],
+        serde_json::json!({"url": "https://example.com/logo.png"})
+    );
+    assert_eq!(
+        spec["paths"]["/"]["post"]["x-rate-limit"],
+        serde_json::json!(100)
+    );
+    assert_eq!(
+        spec["paths"]["/"]["post"]["parameters"][0]["x-example-id"],
+        serde_json::json!("abc")
+    );
+    assert_eq!(
+        spec["components"]["schemas"]["MyObj"]["x-internal"],
+        serde_json::json!(true)
+    );
+}
+
+#[tokio::test]
+async fn servers_and_callbacks() {
+    #[derive(Debug, Object)]
+    struct Event {
+        id: i32,
+    }
+
+    #[Webhook]
+    #[allow(dead_code)]
+    trait EventCallbacks {
+        #[oai(method = "post")]
+        fn on_event(&self, event: Json<Event>);
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(
+            path = "/subscribe",
+            method = "post",
+            server(url = "https://events.example.com", description = "Events server"),
+            callback(
+                name = "onEvent",
+                expression = "{$request.query.callbackUrl}",
+                webhook = "&dyn EventCallbacks"
+            )
+        )]
+        async fn subscribe(&self, #[oai(name = "callbackUrl")] _callback_url: Query<String>) {}
+    }
+
+    let spec: serde_json::Value =
+        serde_json::from_str(&OpenApiService::new(Api, "test", "1.0").spec()).unwrap();
+    let operation = &spec["paths"]["/subscribe"]["post"];
+    assert_eq!(
+        operation["servers"],
+        serde_json::json!([{ "url": "https://events.example.com", "description": "Events server" }])
+    );
+    assert_eq!(
+        operation["callbacks"]["onEvent"]["{$request.query.callbackUrl}"]["post"]["requestBody"]
+            ["content"]["application/json; charset=utf-8"]["schema"],
+        serde_json::json!({ "$ref": "#/components/schemas/Event" })
+    );
+    assert!(spec["components"]["schemas"]["Event"].is_object());
+}
+
 #[tokio::test]
 async fn hidden() {
     #[derive(Debug, Object)]
@@ -973,6 +1470,40 @@ fn issue_405() {
     }
 }
 
+#[tokio::test]
+async fn operation_middleware() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(
+            path = "/hello",
+            method = "get",
+            middleware = "SetHeader::new().appending(\"X-A\", \"1\")",
+            middleware = "SetHeader::new().appending(\"X-B\", \"2\")"
+        )]
+        async fn hello(&self) -> PlainText<&'static str> {
+            PlainText("hello")
+        }
+
+        #[oai(path = "/goodbye", method = "get")]
+        async fn goodbye(&self) -> PlainText<&'static str> {
+            PlainText("goodbye")
+        }
+    }
+
+    let cli = TestClient::new(OpenApiService::new(Api, "test", "1.0"));
+
+    let resp = cli.get("/hello").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_header("X-A", "1");
+    resp.assert_header("X-B", "2");
+
+    let resp = cli.get("/goodbye").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_header_is_not_exist("X-A");
+}
+
 #[tokio::test]
 async fn issue_489() {
     struct Api;
@@ -996,3 +1527,117 @@ async fn issue_489() {
         .await
         .assert_status(StatusCode::METHOD_NOT_ALLOWED);
 }
+
+#[tokio::test]
+async fn request_validation() {
+    #[derive(Enum)]
+    enum Role {
+        Admin,
+        Guest,
+    }
+
+    #[derive(Object)]
+    struct Profile {
+        age: i32,
+    }
+
+    #[derive(Object)]
+    struct CreateUser {
+        name: String,
+        role: Role,
+        profile: Option<Profile>,
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/users/:group", method = "post")]
+        async fn create(
+            &self,
+            group: Path<String>,
+            verbose: Query<Option<bool>>,
+            user: Json<CreateUser>,
+        ) {
+            let _ = (group, verbose, user);
+        }
+    }
+
+    let cli = TestClient::new(OpenApiService::new(Api, "test", "1.0"));
+    cli.post("/users/a")
+        .query("foo", &1)
+        .body_json(&serde_json::json!({"name": "a", "role": "Admin", "extra": 1}))
+        .send()
+        .await
+        .assert_status_is_ok();
+
+    let cli = TestClient::new(
+        OpenApiService::new(Api, "test", "1.0").request_validation(
+            RequestValidation::new()
+                .deny_unknown_query_params(true)
+                .deny_unknown_fields(true),
+        ),
+    );
+    cli.post("/users/a")
+        .query("verbose", &true)
+        .body_json(&serde_json::json!({"name": "a", "role": "Admin", "profile": {"age": 10}}))
+        .send()
+        .await
+        .assert_status_is_ok();
+
+    let resp = cli
+        .post("/users/a")
+        .query("foo", &1)
+        .body_json(&serde_json::json!({
+            "role": "Owner",
+            "extra": 1,
+            "profile": {"age": 10, "height": 180},
+        }))
+        .send()
+        .await;
+    resp.assert_status(StatusCode::BAD_REQUEST);
+    resp.assert_text(
+        "request validation failed: unknown query parameter `foo`; missing required field `name`; invalid value for `role`; unknown field `profile.height`; unknown field `extra`",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn request_validation_ignores_spec_transforms() {
+    #[derive(Object)]
+    struct CreateUser {
+        name: String,
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/users", method = "post")]
+        async fn create(&self, user: Json<CreateUser>) {
+            let _ = user;
+        }
+    }
+
+    let cli = TestClient::new(
+        OpenApiService::new(Api, "test", "1.0")
+            .request_validation(RequestValidation::new().max_body_size(32))
+            .with_spec_transform(|doc| {
+                for api in &mut doc.apis {
+                    api.paths.clear();
+                }
+            }),
+    );
+
+    cli.post("/users")
+        .body_json(&serde_json::json!({}))
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    cli.post("/users")
+        .body_json(&serde_json::json!({"name": "a".repeat(32)}))
+        .send()
+        .await
+        .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+}
@@ -175,6 +175,37 @@ fn description() {
     assert_eq!(meta.description, Some("A\n\nAB\nCDE"));
 }
 
+#[test]
+fn item_descriptions() {
+    #[derive(Enum)]
+    enum MyEnumA {
+        A,
+        B,
+    }
+
+    let mut registry = Registry::new();
+    MyEnumA::register(&mut registry);
+    let meta = registry.schemas.remove("MyEnumA").unwrap();
+    assert!(!meta.extensions.contains_key("x-enum-descriptions"));
+
+    #[derive(Enum)]
+    enum MyEnumB {
+        /// Item A
+        A,
+        B,
+        /// Item C
+        C,
+    }
+
+    let mut registry = Registry::new();
+    MyEnumB::register(&mut registry);
+    let meta = registry.schemas.remove("MyEnumB").unwrap();
+    assert_eq!(
+        meta.extensions.get("x-enum-descriptions"),
+        Some(&json!(["Item A", "", "Item C"]))
+    );
+}
+
 #[test]
 fn deprecated() {
     #[derive(Enum)]
@@ -1,9 +1,10 @@
 use std::io::Write;
 
-use poem::{Request, RequestBody};
+use futures_util::TryStreamExt;
+use poem::{http::StatusCode, Request, RequestBody};
 use poem_openapi::{
     payload::{ParsePayload, Payload},
-    registry::{MetaSchema, MetaSchemaRef},
+    registry::{MetaEncoding, MetaSchema, MetaSchemaRef},
     types::{
         multipart::{JsonField, Upload},
         Binary,
@@ -239,6 +240,69 @@ async fn upload() {
     assert_eq!(a.file.into_vec().await.unwrap(), vec![1, 2, 3]);
 }
 
+#[tokio::test]
+async fn upload_into_stream() {
+    #[derive(Multipart, Debug)]
+    struct A {
+        file: Upload,
+    }
+
+    let content = (0..100_000).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let data = create_multipart_payload(&[("file", Some("1.bin"), &content)]);
+    let a = A::from_request(
+        &Request::builder()
+            .header("content-type", "multipart/form-data; boundary=X-BOUNDARY")
+            .finish(),
+        &mut RequestBody::new(data.into()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(a.file.size(), content.len());
+
+    let chunks = a.file.into_stream().try_collect::<Vec<_>>().await.unwrap();
+    assert!(chunks.len() > 1);
+    assert_eq!(chunks.concat(), content);
+}
+
+#[tokio::test]
+async fn upload_multiple() {
+    #[derive(Multipart, Debug)]
+    struct A {
+        files: Vec<Upload>,
+    }
+
+    let schema_ref = A::schema_ref();
+    let schema: &MetaSchema = schema_ref.unwrap_inline();
+    assert_eq!(schema.properties[0].0, "files");
+    let files_schema = schema.properties[0].1.unwrap_inline();
+    assert_eq!(files_schema.ty, "array");
+    let items_schema = files_schema.items.as_ref().unwrap().unwrap_inline();
+    assert_eq!(items_schema.ty, "string");
+    assert_eq!(items_schema.format, Some("binary"));
+
+    let data = create_multipart_payload(&[
+        ("files", Some("1.txt"), &[1, 2, 3]),
+        ("files", Some("2.txt"), &[4, 5]),
+    ]);
+    let a = A::from_request(
+        &Request::builder()
+            .header("content-type", "multipart/form-data; boundary=X-BOUNDARY")
+            .finish(),
+        &mut RequestBody::new(data.into()),
+    )
+    .await
+    .unwrap();
+
+    let mut files = a.files.into_iter();
+    let file = files.next().unwrap();
+    assert_eq!(file.file_name(), Some("1.txt"));
+    assert_eq!(file.into_vec().await.unwrap(), vec![1, 2, 3]);
+    let file = files.next().unwrap();
+    assert_eq!(file.file_name(), Some("2.txt"));
+    assert_eq!(file.into_vec().await.unwrap(), vec![4, 5]);
+    assert!(files.next().is_none());
+}
+
 #[tokio::test]
 async fn validator() {
     #[derive(Multipart, Debug, Eq, PartialEq)]
@@ -520,3 +584,127 @@ async fn deny_unknown_fields() {
     .unwrap_err();
     assert_eq!(err.to_string(), "parse multipart error: unknown field `c`");
 }
+
+#[tokio::test]
+async fn json_field_encoding() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Metadata {
+        title: String,
+        tags: Vec<String>,
+    }
+
+    #[derive(Multipart, Debug)]
+    struct A {
+        metadata: JsonField<Metadata>,
+        extra: Option<JsonField<i32>>,
+        file: Upload,
+    }
+
+    let meta = <A as poem_openapi::ApiExtractor>::request_meta().unwrap();
+    assert_eq!(
+        meta.content[0].encoding,
+        vec![
+            MetaEncoding {
+                name: "metadata",
+                content_type: "application/json",
+            },
+            MetaEncoding {
+                name: "extra",
+                content_type: "application/json",
+            }
+        ]
+    );
+
+    let data = create_multipart_payload(&[
+        ("metadata", None, br#"{"title": "abc", "tags": ["a", "b"]}"#),
+        ("file", Some("1.txt"), &[1, 2, 3]),
+    ]);
+    let a = A::from_request(
+        &Request::builder()
+            .header("content-type", "multipart/form-data; boundary=X-BOUNDARY")
+            .finish(),
+        &mut RequestBody::new(data.into()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        a.metadata,
+        JsonField(Metadata {
+            title: "abc".to_string(),
+            tags: vec!["a".to_string(), "b".to_string()],
+        })
+    );
+    assert_eq!(a.extra, None);
+    assert_eq!(a.file.into_vec().await.unwrap(), vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn field_content_type() {
+    #[derive(Multipart, Debug)]
+    struct A {
+        #[oai(content_type = "image/png, image/jpeg")]
+        file: Upload,
+        #[oai(content_type = "image/*")]
+        images: Vec<Upload>,
+    }
+
+    let meta = <A as poem_openapi::ApiExtractor>::request_meta().unwrap();
+    assert_eq!(
+        meta.content[0].encoding,
+        vec![
+            MetaEncoding {
+                name: "file",
+                content_type: "image/png, image/jpeg",
+            },
+            MetaEncoding {
+                name: "images",
+                content_type: "image/*",
+            }
+        ]
+    );
+
+    fn create_payload(parts: &[(&str, &str)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        for (name, content_type) in parts {
+            data.write_all(b"--X-BOUNDARY\r\n").unwrap();
+            data.write_all(
+                format!(
+                    "Content-Disposition: form-data; name=\"{name}\"; filename=\"1.bin\"\r\nContent-Type: {content_type}\r\n\r\n"
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+            data.write_all(&[1, 2, 3]).unwrap();
+            data.write_all(b"\r\n").unwrap();
+        }
+        data.write_all(b"--X-BOUNDARY--\r\n").unwrap();
+        data
+    }
+
+    let data = create_payload(&[("file", "image/jpeg"), ("images", "image/gif")]);
+    let a = A::from_request(
+        &Request::builder()
+            .header("content-type", "multipart/form-data; boundary=X-BOUNDARY")
+            .finish(),
+        &mut RequestBody::new(data.into()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(a.file.content_type(), Some("image/jpeg"));
+    assert_eq!(a.images.len(), 1);
+
+    let data = create_payload(&[("file", "text/plain"), ("images", "image/gif")]);
+    let err = A::from_request(
+        &Request::builder()
+            .header("content-type", "multipart/form-data; boundary=X-BOUNDARY")
+            .finish(),
+        &mut RequestBody::new(data.into()),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(
+        err.to_string(),
+        "the content type of multipart field `file` is not supported: text/plain"
+    );
+}
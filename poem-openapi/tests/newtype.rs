@@ -19,6 +19,7 @@ async fn new_type_summary_and_description() {
     /// B
     /// C
     #[derive(NewType)]
+    #[oai(title_from_docs)]
     struct MyString(String);
 
     let schema = MyString::schema_ref();
@@ -27,6 +28,32 @@ async fn new_type_summary_and_description() {
     assert_eq!(schema.description, Some("A\nB\nC"));
 }
 
+#[tokio::test]
+async fn new_type_title() {
+    /// MyString
+    ///
+    /// A
+    #[derive(NewType)]
+    #[oai(title = "My String")]
+    struct MyStringA(String);
+
+    let schema = MyStringA::schema_ref();
+    let schema = schema.unwrap_inline();
+    assert_eq!(schema.title.as_deref(), Some("My String"));
+    assert_eq!(schema.description, Some("MyString\n\nA"));
+
+    /// MyString
+    ///
+    /// A
+    #[derive(NewType)]
+    struct MyStringB(String);
+
+    let schema = MyStringB::schema_ref();
+    let schema = schema.unwrap_inline();
+    assert_eq!(schema.title, None);
+    assert_eq!(schema.description, Some("MyString\n\nA"));
+}
+
 #[tokio::test]
 async fn new_type_example() {
     #[derive(NewType)]
@@ -44,6 +71,32 @@ async fn new_type_example() {
     assert_eq!(schema.example, Some("abc".into()));
 }
 
+#[tokio::test]
+async fn new_type_const_value() {
+    #[derive(NewType, Debug)]
+    #[oai(const_value = "payment.created")]
+    struct EventType(String);
+
+    let schema = EventType::schema_ref();
+    assert_eq!(
+        schema.unwrap_inline().enum_items,
+        vec![serde_json::json!("payment.created")]
+    );
+
+    assert_eq!(
+        EventType::parse_from_json(Some(serde_json::json!("payment.created")))
+            .unwrap()
+            .0,
+        "payment.created"
+    );
+    assert_eq!(
+        EventType::parse_from_parameter("payment.failed")
+            .unwrap_err()
+            .into_message(),
+        r#"failed to parse "string": verification failed. const("payment.created")"#
+    );
+}
+
 #[tokio::test]
 async fn generic() {
     #[derive(NewType)]
@@ -239,6 +239,34 @@ fn description() {
     assert_eq!(meta.description, Some("A\n\nAB\nCDE"));
 }
 
+#[test]
+fn title() {
+    /// A
+    ///
+    /// AB
+    /// CDE
+    #[derive(Object)]
+    #[oai(title_from_docs)]
+    struct ObjA {
+        a: i32,
+    }
+
+    let meta = get_meta::<ObjA>();
+    assert_eq!(meta.title.as_deref(), Some("A"));
+    assert_eq!(meta.description, Some("AB\nCDE"));
+
+    /// A
+    #[derive(Object)]
+    #[oai(title = "My Object")]
+    struct ObjB {
+        a: i32,
+    }
+
+    let meta = get_meta::<ObjB>();
+    assert_eq!(meta.title.as_deref(), Some("My Object"));
+    assert_eq!(meta.description, Some("A"));
+}
+
 #[test]
 fn field_description() {
     #[derive(Object)]
@@ -537,8 +565,59 @@ fn duplicate_name() {
     t::ObjA::register(&mut registry);
 }
 
+#[test]
+#[should_panic(expected = "have the same OpenAPI name `ObjA`")]
+fn duplicate_nested_name() {
+    mod t {
+        use super::*;
+
+        #[derive(Object)]
+        pub struct ObjA {
+            value2: i32,
+        }
+    }
+
+    #[derive(Object)]
+    struct ObjA {
+        value1: t::ObjA,
+    }
+
+    let mut registry = Registry::new();
+    ObjA::register(&mut registry);
+}
+
+#[test]
+fn namespace() {
+    mod t {
+        use super::*;
+
+        #[derive(Object)]
+        #[oai(namespace = "t")]
+        pub struct ObjA {
+            value2: i32,
+        }
+    }
+
+    #[derive(Object)]
+    struct ObjA {
+        value1: t::ObjA,
+    }
+
+    assert_eq!(t::ObjA::name(), "t.ObjA");
+
+    let mut registry = Registry::new();
+    ObjA::register(&mut registry);
+    assert!(registry.schemas.contains_key("ObjA"));
+    assert!(registry.schemas.contains_key("t.ObjA"));
+}
+
 #[test]
 fn deny_unknown_fields() {
+    #[derive(Object)]
+    struct ObjWithUnknownFields {
+        a: i32,
+    }
+
     #[derive(Object, Debug, Eq, PartialEq)]
     #[oai(deny_unknown_fields)]
     struct Obj {
@@ -565,6 +644,14 @@ fn deny_unknown_fields() {
         .into_message(),
         "failed to parse \"Obj\": unknown field `c`."
     );
+
+    let meta = get_meta::<Obj>();
+    assert!(meta.deny_additional_properties);
+    assert_eq!(
+        serde_json::to_value(&meta).unwrap()["additionalProperties"],
+        json!(false)
+    );
+    assert!(!get_meta::<ObjWithUnknownFields>().deny_additional_properties);
 }
 
 #[test]
@@ -1077,3 +1164,91 @@ fn deserialize_with() {
         Obj { a: 7 }
     );
 }
+
+#[test]
+fn as_string() {
+    #[derive(Debug, PartialEq, Object)]
+    struct Obj {
+        #[oai(as_string)]
+        id: u64,
+        #[oai(as_string)]
+        ids: Vec<i64>,
+        #[oai(as_string)]
+        parent_id: Option<u128>,
+        count: i32,
+    }
+
+    let meta = get_meta::<Obj>();
+    let id_schema = meta.properties[0].1.unwrap_inline();
+    assert_eq!(id_schema.ty, "string");
+    assert_eq!(id_schema.format, Some("uint64-as-string"));
+    let items = meta.properties[1].1.unwrap_inline().items.as_ref().unwrap();
+    assert_eq!(items.unwrap_inline().format, Some("int64-as-string"));
+    assert_eq!(meta.properties[2].1.unwrap_inline().format, Some("uint128"));
+    assert_eq!(meta.properties[3].1.unwrap_inline().ty, "integer");
+
+    let obj = Obj {
+        id: u64::MAX,
+        ids: vec![1, -2],
+        parent_id: None,
+        count: 3,
+    };
+    let value = json!({
+        "id": "18446744073709551615",
+        "ids": ["1", "-2"],
+        "parent_id": null,
+        "count": 3,
+    });
+    assert_eq!(obj.to_json(), Some(value.clone()));
+    assert_eq!(Obj::parse_from_json(Some(value)).unwrap(), obj);
+
+    let obj = Obj {
+        parent_id: Some(u128::MAX),
+        ..obj
+    };
+    let value = json!({
+        "id": "18446744073709551615",
+        "ids": ["1", "-2"],
+        "parent_id": u128::MAX.to_string(),
+        "count": 3,
+    });
+    assert_eq!(obj.to_json(), Some(value.clone()));
+    assert_eq!(Obj::parse_from_json(Some(value)).unwrap(), obj);
+
+    // only the fields with the `as_string` attribute accept strings
+    assert!(Obj::parse_from_json(Some(json!({
+        "id": "1",
+        "ids": [],
+        "count": "3",
+    })))
+    .is_err());
+}
+
+#[test]
+fn hidden() {
+    #[derive(Object, Debug, PartialEq)]
+    struct Inner {
+        a: i32,
+    }
+
+    #[derive(Object, Debug, PartialEq)]
+    #[oai(hidden)]
+    struct Obj {
+        inner: Inner,
+    }
+
+    let mut registry = Registry::new();
+    Obj::register(&mut registry);
+    assert!(registry.schemas.is_empty());
+    assert_eq!(
+        Obj::schema_ref(),
+        MetaSchemaRef::Inline(Box::new(MetaSchema::new("object")))
+    );
+
+    let obj = Obj {
+        inner: Inner { a: 1 },
+    };
+    let value = json!({ "inner": { "a": 1 } });
+    assert_eq!(obj.to_json(), Some(value.clone()));
+    assert_eq!(Obj::parse_from_json(Some(value)).unwrap(), obj);
+}
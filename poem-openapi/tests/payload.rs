@@ -1,7 +1,10 @@
-use poem::{http::StatusCode, test::TestClient, Error};
+use poem::{http::StatusCode, test::TestClient, Error, IntoResponse};
 use poem_openapi::{
     param::Query,
-    payload::{Json, Response},
+    payload::{
+        Attachment, AttachmentType, Json, MultipartMixed, MultipartPart, MultipartRelated, Payload,
+        PlainText, Response, Yaml,
+    },
     ApiResponse, OpenApi, OpenApiService,
 };
 
@@ -51,3 +54,158 @@ async fn response_wrapper() {
     resp.assert_status(StatusCode::BAD_REQUEST);
     resp.assert_header("MY-HEADER1", "def");
 }
+
+#[test]
+fn yaml_content_types() {
+    for content_type in [
+        "application/yaml",
+        "application/x-yaml",
+        "text/yaml",
+        "text/x-yaml; charset=utf-8",
+        "application/vnd.config+yaml",
+    ] {
+        assert!(Yaml::<i32>::check_content_type(content_type));
+    }
+    assert!(!Yaml::<i32>::check_content_type("text/plain"));
+}
+
+#[tokio::test]
+async fn plain_text_from_str() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/incr", method = "post")]
+        async fn incr(&self, value: PlainText<i64>) -> PlainText<i64> {
+            PlainText(value.0 + 1)
+        }
+    }
+
+    let ep = OpenApiService::new(Api, "test", "1.0");
+    let spec: serde_json::Value = serde_json::from_str(&ep.spec()).unwrap();
+    let operation = &spec["paths"]["/incr"]["post"];
+    assert_eq!(
+        operation["requestBody"]["content"]["text/plain; charset=utf-8"]["schema"],
+        serde_json::json!({"type": "integer", "format": "int64"})
+    );
+    assert_eq!(
+        operation["responses"]["200"]["content"]["text/plain; charset=utf-8"]["schema"],
+        serde_json::json!({"type": "integer", "format": "int64"})
+    );
+
+    let cli = TestClient::new(ep);
+    let resp = cli
+        .post("/incr")
+        .content_type("text/plain")
+        .body("41")
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_text("42").await;
+
+    cli.post("/incr")
+        .content_type("text/plain")
+        .body("abc")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn attachment_content_disposition() {
+    let resp = Attachment::new(vec![1u8, 2, 3]).into_response();
+    assert_eq!(resp.header("content-disposition"), Some("attachment"));
+
+    let resp = Attachment::new(vec![1u8, 2, 3])
+        .attachment_type(AttachmentType::Inline)
+        .filename("a \"b\".txt")
+        .into_response();
+    assert_eq!(
+        resp.header("content-disposition"),
+        Some(r#"inline; filename="a \"b\".txt""#)
+    );
+
+    let resp = Attachment::new(vec![1u8, 2, 3])
+        .filename("résumé 1.pdf")
+        .into_response();
+    assert_eq!(
+        resp.header("content-disposition"),
+        Some("attachment; filename=\"r_sum_ 1.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9%201.pdf")
+    );
+}
+
+#[tokio::test]
+async fn multipart_responses() {
+    use futures_util::{stream::BoxStream, StreamExt};
+
+    #[derive(poem_openapi::Object)]
+    struct Metadata {
+        name: String,
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/mixed", method = "get")]
+        async fn mixed(&self) -> MultipartMixed<BoxStream<'static, MultipartPart>> {
+            let blob = futures_util::stream::iter(vec![
+                Ok::<_, std::io::Error>(b"ab".to_vec()),
+                Ok(b"cd".to_vec()),
+            ]);
+            let parts = vec![
+                MultipartPart::json(&Metadata {
+                    name: "a.bin".to_string(),
+                }),
+                MultipartPart::binary(poem::Body::from_bytes_stream(blob)),
+            ];
+            MultipartMixed::new(futures_util::stream::iter(parts).boxed()).boundary("XYZ")
+        }
+
+        #[oai(path = "/related", method = "get")]
+        async fn related(&self) -> MultipartRelated<BoxStream<'static, MultipartPart>> {
+            let parts = vec![
+                MultipartPart::new("application/dicom", vec![1u8]).content_id("root"),
+                MultipartPart::new("application/dicom", vec![2u8]).content_id("image"),
+            ];
+            MultipartRelated::new(futures_util::stream::iter(parts).boxed())
+                .boundary("XYZ")
+                .root_type("application/dicom")
+                .start("root")
+        }
+    }
+
+    let meta = Api::meta().remove(0);
+    assert_eq!(
+        meta.paths[0].operations[0].responses.responses[0].content[0].content_type,
+        "multipart/mixed"
+    );
+    assert_eq!(
+        meta.paths[1].operations[0].responses.responses[0].content[0].content_type,
+        "multipart/related"
+    );
+
+    let cli = TestClient::new(OpenApiService::new(Api, "test", "1.0"));
+
+    let resp = cli.get("/mixed").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_content_type("multipart/mixed; boundary=XYZ");
+    resp.assert_text(
+        "--XYZ\r\ncontent-type: application/json; charset=utf-8\r\n\r\n{\"name\":\"a.bin\"}\r\n\
+         --XYZ\r\ncontent-type: application/octet-stream\r\n\r\nabcd\r\n\
+         --XYZ--\r\n",
+    )
+    .await;
+
+    let resp = cli.get("/related").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_content_type(
+        "multipart/related; type=\"application/dicom\"; start=\"<root>\"; boundary=XYZ",
+    );
+    resp.assert_bytes(
+        b"--XYZ\r\ncontent-type: application/dicom\r\ncontent-id: <root>\r\n\r\n\x01\r\n\
+          --XYZ\r\ncontent-type: application/dicom\r\ncontent-id: <image>\r\n\r\n\x02\r\n\
+          --XYZ--\r\n",
+    )
+    .await;
+}
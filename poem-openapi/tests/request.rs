@@ -1,6 +1,7 @@
+use poem::web::ContentTypeMode;
 use poem_openapi::{
     payload::{Json, PlainText},
-    registry::{MetaMediaType, MetaRequest, MetaSchema, MetaSchemaRef},
+    registry::{MetaExample, MetaMediaType, MetaRequest, MetaSchema, MetaSchemaRef},
     types::ParseFromJSON,
     ApiExtractor, ApiRequest, Object,
 };
@@ -30,10 +31,14 @@ fn meta() {
                 MetaMediaType {
                     content_type: "application/json; charset=utf-8",
                     schema: MetaSchemaRef::Reference("CreateUser".to_string()),
+                    examples: vec![],
+                    encoding: vec![],
                 },
                 MetaMediaType {
                     content_type: "text/plain; charset=utf-8",
                     schema: MetaSchemaRef::Inline(Box::new(MetaSchema::new("string"))),
+                    examples: vec![],
+                    encoding: vec![],
                 }
             ],
             required: true
@@ -95,6 +100,120 @@ async fn from_request() {
     );
 }
 
+#[tokio::test]
+async fn content_type_mode() {
+    let body = serde_json::to_vec(&serde_json::json!({
+        "user": "sunli",
+        "password": "123456",
+    }))
+    .unwrap();
+
+    let request = poem::Request::builder().body(body.clone());
+    let (request, mut body_) = request.split();
+    assert!(
+        MyRequest::from_request(&request, &mut body_, Default::default())
+            .await
+            .is_err()
+    );
+    assert!(
+        Json::<CreateUser>::from_request(&request, &mut body_, Default::default())
+            .await
+            .is_err()
+    );
+
+    let request = poem::Request::builder()
+        .extension(ContentTypeMode::Lenient)
+        .body(body.clone());
+    let (request, mut body_) = request.split();
+    assert_eq!(
+        MyRequest::from_request(&request, &mut body_, Default::default())
+            .await
+            .unwrap(),
+        MyRequest::CreateByJson(Json(CreateUser {
+            user: "sunli".to_string(),
+            password: "123456".to_string()
+        }))
+    );
+
+    let request = poem::Request::builder()
+        .extension(ContentTypeMode::Lenient)
+        .body(body);
+    let (request, mut body) = request.split();
+    assert_eq!(
+        Json::<CreateUser>::from_request(&request, &mut body, Default::default())
+            .await
+            .unwrap(),
+        Json(CreateUser {
+            user: "sunli".to_string(),
+            password: "123456".to_string()
+        })
+    );
+}
+
+#[cfg(feature = "charset")]
+#[tokio::test]
+async fn charset() {
+    let request = poem::Request::builder()
+        .content_type("application/json; charset=iso-8859-1")
+        .body(&b"{\"user\": \"caf\xe9\", \"password\": \"123456\"}"[..]);
+    let (request, mut body) = request.split();
+    assert_eq!(
+        MyRequest::from_request(&request, &mut body, Default::default())
+            .await
+            .unwrap(),
+        MyRequest::CreateByJson(Json(CreateUser {
+            user: "café".to_string(),
+            password: "123456".to_string()
+        }))
+    );
+}
+
+#[cfg(feature = "protobuf")]
+#[tokio::test]
+async fn protobuf() {
+    use poem_openapi::payload::Protobuf;
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct User {
+        #[prost(string, tag = "1")]
+        user: String,
+        #[prost(string, tag = "2")]
+        password: String,
+    }
+
+    #[derive(Debug, ApiRequest, PartialEq)]
+    enum CreateUserRequest {
+        Json(Json<CreateUser>),
+        Protobuf(Protobuf<User>),
+    }
+
+    assert_eq!(
+        CreateUserRequest::request_meta()
+            .unwrap()
+            .content
+            .iter()
+            .map(|media_type| media_type.content_type)
+            .collect::<Vec<_>>(),
+        vec!["application/json; charset=utf-8", "application/protobuf"]
+    );
+
+    let user = User {
+        user: "sunli".to_string(),
+        password: "123456".to_string(),
+    };
+    let request = poem::Request::builder()
+        .content_type("application/x-protobuf")
+        .body(user.encode_to_vec());
+    let (request, mut body) = request.split();
+    assert_eq!(
+        CreateUserRequest::from_request(&request, &mut body, Default::default())
+            .await
+            .unwrap(),
+        CreateUserRequest::Protobuf(Protobuf(user))
+    );
+}
+
 #[tokio::test]
 async fn generic() {
     #[derive(Debug, ApiRequest, Eq, PartialEq)]
@@ -113,6 +232,8 @@ async fn generic() {
             content: vec![MetaMediaType {
                 content_type: "application/json; charset=utf-8",
                 schema: MetaSchemaRef::Inline(Box::new(MetaSchema::new("string"))),
+                examples: vec![],
+                encoding: vec![],
             },],
             required: true
         }
@@ -144,6 +265,8 @@ async fn item_content_type() {
                 schema: MetaSchemaRef::Inline(Box::new(MetaSchema::new_with_format(
                     "integer", "int32"
                 ))),
+                examples: vec![],
+                encoding: vec![],
             },],
             required: true
         }
@@ -160,3 +283,54 @@ async fn item_content_type() {
         Req::Create(Json(100))
     );
 }
+
+#[tokio::test]
+async fn examples() {
+    fn json_example() -> i32 {
+        100
+    }
+
+    fn json_example2() -> i32 {
+        200
+    }
+
+    fn text_example() -> String {
+        "100".to_string()
+    }
+
+    #[derive(Debug, ApiRequest, Eq, PartialEq)]
+    enum Req {
+        #[oai(example = "json_example", example = "json_example2")]
+        CreateByJson(Json<i32>),
+        #[oai(example(name = "text", value = "text_example", summary = "A number"))]
+        CreateByPlainText(PlainText<String>),
+    }
+
+    let meta = Req::request_meta().unwrap();
+    assert_eq!(
+        meta.content[0].examples,
+        vec![
+            MetaExample {
+                name: "example1",
+                summary: None,
+                description: None,
+                value: Some(serde_json::json!(100)),
+            },
+            MetaExample {
+                name: "example2",
+                summary: None,
+                description: None,
+                value: Some(serde_json::json!(200)),
+            }
+        ]
+    );
+    assert_eq!(
+        meta.content[1].examples,
+        vec![MetaExample {
+            name: "text",
+            summary: Some("A number"),
+            description: None,
+            value: Some(serde_json::json!("100")),
+        }]
+    );
+}
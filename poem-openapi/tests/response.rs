@@ -10,7 +10,8 @@ use poem::{
 use poem_openapi::{
     payload::{Binary, Json, Payload, PlainText, Yaml},
     registry::{
-        MetaApi, MetaMediaType, MetaResponse, MetaResponses, MetaSchema, MetaSchemaRef, Registry,
+        MetaApi, MetaExample, MetaMediaType, MetaResponse, MetaResponses, MetaSchema,
+        MetaSchemaRef, Registry,
     },
     types::{ToJSON, Type},
     ApiResponse, Object, OpenApi, OpenApiService,
@@ -58,7 +59,9 @@ fn meta() {
                     status: Some(400),
                     content: vec![MetaMediaType {
                         content_type: "application/json; charset=utf-8",
-                        schema: MetaSchemaRef::Reference("BadRequestResult".to_string())
+                        schema: MetaSchemaRef::Reference("BadRequestResult".to_string()),
+                        examples: vec![],
+                        encoding: vec![],
                     }],
                     headers: vec![]
                 },
@@ -67,7 +70,9 @@ fn meta() {
                     status: Some(400),
                     content: vec![MetaMediaType {
                         content_type: "application/yaml; charset=utf-8",
-                        schema: MetaSchemaRef::Reference("BadRequestResult".to_string())
+                        schema: MetaSchemaRef::Reference("BadRequestResult".to_string()),
+                        examples: vec![],
+                        encoding: vec![],
                     }],
                     headers: vec![]
                 },
@@ -77,6 +82,8 @@ fn meta() {
                     content: vec![MetaMediaType {
                         content_type: "text/plain; charset=utf-8",
                         schema: MetaSchemaRef::Inline(Box::new(MetaSchema::new("string"))),
+                        examples: vec![],
+                        encoding: vec![],
                     }],
                     headers: vec![]
                 }
@@ -273,7 +280,9 @@ async fn generic() {
                 status: Some(200),
                 content: vec![MetaMediaType {
                     content_type: "application/json; charset=utf-8",
-                    schema: MetaSchemaRef::Inline(Box::new(MetaSchema::new("string")))
+                    schema: MetaSchemaRef::Inline(Box::new(MetaSchema::new("string"))),
+                    examples: vec![],
+                    encoding: vec![],
                 }],
                 headers: vec![]
             },],
@@ -309,7 +318,9 @@ async fn item_content_type() {
                         content_type: "application/json2",
                         schema: MetaSchemaRef::Inline(Box::new(MetaSchema::new_with_format(
                             "integer", "int32"
-                        )))
+                        ))),
+                        examples: vec![],
+                        encoding: vec![],
                     }],
                     headers: vec![]
                 },
@@ -320,7 +331,9 @@ async fn item_content_type() {
                         content_type: "application/json3",
                         schema: MetaSchemaRef::Inline(Box::new(MetaSchema::new_with_format(
                             "integer", "int32"
-                        )))
+                        ))),
+                        examples: vec![],
+                        encoding: vec![],
                     }],
                     headers: vec![]
                 }
@@ -546,3 +559,59 @@ async fn actual_type() {
     let type_name: Vec<&String> = registry.schemas.keys().collect();
     assert_eq!(&type_name, &["MyObj"]);
 }
+
+#[tokio::test]
+async fn examples() {
+    fn ok_example() -> i32 {
+        100
+    }
+
+    fn not_found_example() -> String {
+        "not found".to_string()
+    }
+
+    fn gone_example() -> String {
+        "gone".to_string()
+    }
+
+    #[derive(ApiResponse)]
+    #[allow(dead_code)]
+    enum MyResponse {
+        #[oai(status = 200, example = "ok_example")]
+        Ok(Json<i32>),
+        #[oai(
+            status = 404,
+            example(name = "not_found", value = "not_found_example", summary = "Not found"),
+            example(name = "gone", value = "gone_example", description = "Gone")
+        )]
+        NotFound(PlainText<String>),
+    }
+
+    let meta = MyResponse::meta();
+    assert_eq!(
+        meta.responses[0].content[0].examples,
+        vec![MetaExample {
+            name: "example1",
+            summary: None,
+            description: None,
+            value: Some(Value::from(100)),
+        }]
+    );
+    assert_eq!(
+        meta.responses[1].content[0].examples,
+        vec![
+            MetaExample {
+                name: "not_found",
+                summary: Some("Not found"),
+                description: None,
+                value: Some(Value::from("not found")),
+            },
+            MetaExample {
+                name: "gone",
+                summary: None,
+                description: Some("Gone"),
+                value: Some(Value::from("gone")),
+            }
+        ]
+    );
+}
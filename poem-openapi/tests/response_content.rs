@@ -20,15 +20,21 @@ async fn meta() {
         vec![
             MetaMediaType {
                 content_type: <Json<i32>>::CONTENT_TYPE,
-                schema: <Json<i32>>::schema_ref()
+                schema: <Json<i32>>::schema_ref(),
+                examples: vec![],
+                encoding: vec![],
             },
             MetaMediaType {
                 content_type: <PlainText<String>>::CONTENT_TYPE,
-                schema: <PlainText<String>>::schema_ref()
+                schema: <PlainText<String>>::schema_ref(),
+                examples: vec![],
+                encoding: vec![],
             },
             MetaMediaType {
                 content_type: <Binary<Vec<u8>>>::CONTENT_TYPE,
-                schema: <Binary<Vec<u8>>>::schema_ref()
+                schema: <Binary<Vec<u8>>>::schema_ref(),
+                examples: vec![],
+                encoding: vec![],
             }
         ]
     );
@@ -86,7 +92,9 @@ async fn content_type() {
         MyResp::media_types(),
         vec![MetaMediaType {
             content_type: "application/json2",
-            schema: <Json<i32>>::schema_ref()
+            schema: <Json<i32>>::schema_ref(),
+            examples: vec![],
+            encoding: vec![],
         }]
     );
 
@@ -3,10 +3,10 @@ use poem::{
     http::{header, StatusCode},
     test::TestClient,
     web::{cookie::Cookie, headers},
-    Request,
+    EndpointExt, Request,
 };
 use poem_openapi::{
-    auth::{ApiKey, Basic, Bearer},
+    auth::{ApiKey, Basic, Bearer, GrantedScopes},
     payload::PlainText,
     registry::{MetaOAuthFlow, MetaOAuthFlows, MetaOAuthScope, MetaSecurityScheme, Registry},
     ApiExtractor, OAuthScopes, OpenApi, OpenApiService, SecurityScheme,
@@ -534,6 +534,145 @@ async fn checker_option() {
     resp.assert_status(StatusCode::UNAUTHORIZED);
 }
 
+#[tokio::test]
+async fn checker_with_data() {
+    #[derive(Clone)]
+    struct TokenStore(Vec<(&'static str, &'static str)>);
+
+    impl TokenStore {
+        async fn get_user(&self, token: &str) -> Option<String> {
+            self.0
+                .iter()
+                .find(|(t, _)| *t == token)
+                .map(|(_, username)| username.to_string())
+        }
+    }
+
+    #[derive(SecurityScheme)]
+    #[oai(ty = "bearer", checker = "check_token")]
+    struct MySecurityScheme(String);
+
+    async fn check_token(req: &Request, bearer: Bearer) -> Option<String> {
+        let store = req.data::<TokenStore>()?;
+        store.get_user(&bearer.token).await
+    }
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/test", method = "get")]
+        async fn test(&self, auth: MySecurityScheme) -> PlainText<String> {
+            PlainText(format!("Authed: {}", auth.0))
+        }
+    }
+
+    let service = OpenApiService::new(MyApi, "test", "1.0");
+    let client = TestClient::new(service.data(TokenStore(vec![("abc", "sunli")])));
+
+    let resp = client
+        .get("/test")
+        .typed_header(headers::Authorization::bearer("abc").unwrap())
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_text("Authed: sunli").await;
+
+    let resp = client
+        .get("/test")
+        .typed_header(headers::Authorization::bearer("def").unwrap())
+        .send()
+        .await;
+    resp.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn check_scopes() {
+    #[derive(OAuthScopes)]
+    #[oai(rename_all = "lowercase")]
+    enum GithubScopes {
+        Read,
+        Write,
+    }
+
+    #[derive(SecurityScheme)]
+    #[oai(
+        ty = "oauth2",
+        flows(implicit(
+            authorization_url = "https://test.com/authorize",
+            scopes = "GithubScopes"
+        )),
+        checker = "check_token",
+        check_scopes
+    )]
+    struct MySecurityScheme(User);
+
+    struct User {
+        scopes: Vec<String>,
+    }
+
+    impl GrantedScopes for User {
+        fn has_scope(&self, scope: &str) -> bool {
+            self.scopes.iter().any(|s| s == scope)
+        }
+    }
+
+    async fn check_token(_req: &Request, bearer: Bearer, scopes: &[&str]) -> Option<User> {
+        assert!(scopes.iter().all(|scope| ["read", "write"].contains(scope)));
+        Some(User {
+            scopes: bearer.token.split(',').map(ToString::to_string).collect(),
+        })
+    }
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/read", method = "get")]
+        async fn read(
+            &self,
+            #[oai(scope = "GithubScopes::Read")] auth: MySecurityScheme,
+        ) -> PlainText<String> {
+            PlainText(auth.0.scopes.join(","))
+        }
+
+        #[oai(path = "/write", method = "get")]
+        async fn write(
+            &self,
+            #[oai(scope = "GithubScopes::Read", scope = "GithubScopes::Write")]
+            auth: MySecurityScheme,
+        ) -> PlainText<String> {
+            PlainText(auth.0.scopes.join(","))
+        }
+    }
+
+    let service = OpenApiService::new(MyApi, "test", "1.0");
+    let client = TestClient::new(service);
+
+    let resp = client
+        .get("/read")
+        .typed_header(headers::Authorization::bearer("read").unwrap())
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_text("read").await;
+
+    let resp = client
+        .get("/write")
+        .typed_header(headers::Authorization::bearer("read").unwrap())
+        .send()
+        .await;
+    resp.assert_status(StatusCode::FORBIDDEN);
+
+    let resp = client
+        .get("/write")
+        .typed_header(headers::Authorization::bearer("read,write").unwrap())
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_text("read,write").await;
+}
+
 #[tokio::test]
 async fn multiple_auth_methods() {
     #[derive(SecurityScheme)]
@@ -589,6 +728,123 @@ async fn multiple_auth_methods() {
     resp.assert_status(StatusCode::UNAUTHORIZED);
 }
 
+#[tokio::test]
+async fn combined_auth_methods() {
+    #[derive(SecurityScheme)]
+    #[oai(ty = "basic")]
+    struct MySecurityScheme1(Basic);
+
+    #[derive(SecurityScheme)]
+    #[oai(ty = "api_key", key_name = "X-API-Key", key_in = "header")]
+    struct MySecurityScheme2(ApiKey);
+
+    #[derive(SecurityScheme)]
+    #[oai(ty = "bearer")]
+    struct MySecurityScheme3(Bearer);
+
+    #[derive(SecurityScheme)]
+    enum MySecurityScheme {
+        BasicAndApiKey(MySecurityScheme1, MySecurityScheme2),
+        Bearer(MySecurityScheme3),
+    }
+
+    assert_eq!(
+        MySecurityScheme::security_requirements(),
+        vec![
+            vec!["MySecurityScheme1", "MySecurityScheme2"],
+            vec!["MySecurityScheme3"]
+        ]
+    );
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/test", method = "get")]
+        async fn test(&self, auth: MySecurityScheme) -> PlainText<String> {
+            match auth {
+                MySecurityScheme::BasicAndApiKey(basic, api_key) => PlainText(format!(
+                    "basic: {}, api-key: {}",
+                    basic.0.username, api_key.0.key
+                )),
+                MySecurityScheme::Bearer(bearer) => {
+                    PlainText(format!("bearer: {}", bearer.0.token))
+                }
+            }
+        }
+    }
+
+    let service = OpenApiService::new(MyApi, "test", "1.0");
+    let spec = serde_json::from_str::<serde_json::Value>(&service.spec()).unwrap();
+    assert_eq!(
+        spec["paths"]["/test"]["get"]["security"],
+        serde_json::json!([
+            { "MySecurityScheme1": [], "MySecurityScheme2": [] },
+            { "MySecurityScheme3": [] }
+        ])
+    );
+
+    let client = TestClient::new(service);
+    let resp = client
+        .get("/test")
+        .typed_header(headers::Authorization::basic("sunli", "password"))
+        .header("X-API-Key", "abcdef")
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_text("basic: sunli, api-key: abcdef").await;
+
+    let resp = client
+        .get("/test")
+        .typed_header(headers::Authorization::bearer("abcdef").unwrap())
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_text("bearer: abcdef").await;
+
+    let resp = client
+        .get("/test")
+        .typed_header(headers::Authorization::basic("sunli", "password"))
+        .send()
+        .await;
+    resp.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn multiple_security_params() {
+    #[derive(SecurityScheme)]
+    #[oai(ty = "basic")]
+    struct MySecurityScheme1(Basic);
+
+    #[derive(SecurityScheme)]
+    #[oai(ty = "api_key", key_name = "X-API-Key", key_in = "header")]
+    struct MySecurityScheme2(ApiKey);
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/test", method = "get")]
+        async fn test(
+            &self,
+            basic: MySecurityScheme1,
+            api_key: MySecurityScheme2,
+        ) -> PlainText<String> {
+            PlainText(format!(
+                "basic: {}, api-key: {}",
+                basic.0.username, api_key.0.key
+            ))
+        }
+    }
+
+    let service = OpenApiService::new(MyApi, "test", "1.0");
+    let spec = serde_json::from_str::<serde_json::Value>(&service.spec()).unwrap();
+    assert_eq!(
+        spec["paths"]["/test"]["get"]["security"],
+        serde_json::json!([{ "MySecurityScheme1": [], "MySecurityScheme2": [] }])
+    );
+}
+
 #[tokio::test]
 async fn fallback() {
     #[derive(SecurityScheme)]
@@ -618,6 +874,12 @@ async fn fallback() {
     }
 
     let service = OpenApiService::new(MyApi, "test", "1.0");
+    let spec = serde_json::from_str::<serde_json::Value>(&service.spec()).unwrap();
+    assert_eq!(
+        spec["paths"]["/test"]["get"]["security"],
+        serde_json::json!([{ "MySecuritySchemeBasic": [] }, {}])
+    );
+
     let client = TestClient::new(service);
 
     let resp = client
@@ -632,3 +894,92 @@ async fn fallback() {
     resp.assert_status_is_ok();
     resp.assert_text("NoAuth").await;
 }
+
+#[tokio::test]
+async fn optional_auth() {
+    #[derive(SecurityScheme)]
+    #[oai(ty = "basic")]
+    struct MySecurityScheme(Basic);
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/test", method = "get")]
+        async fn test(&self, auth: Option<MySecurityScheme>) -> PlainText<String> {
+            match auth {
+                Some(auth) => PlainText(format!("Authed: {}", auth.0.username)),
+                None => PlainText("NoAuth".to_string()),
+            }
+        }
+    }
+
+    let service = OpenApiService::new(MyApi, "test", "1.0");
+    let spec = serde_json::from_str::<serde_json::Value>(&service.spec()).unwrap();
+    assert_eq!(
+        spec["paths"]["/test"]["get"]["security"],
+        serde_json::json!([{ "MySecurityScheme": [] }, {}])
+    );
+    assert!(spec["components"]["securitySchemes"]
+        .get("MySecurityScheme")
+        .is_some());
+
+    let client = TestClient::new(service);
+
+    let resp = client
+        .get("/test")
+        .typed_header(headers::Authorization::basic("sunli", "password"))
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_text("Authed: sunli").await;
+
+    let resp = client.get("/test").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_text("NoAuth").await;
+}
+
+#[tokio::test]
+async fn optional_auth_with_checker() {
+    #[derive(SecurityScheme)]
+    #[oai(ty = "basic", checker = "check")]
+    struct MySecurityScheme(String);
+
+    async fn check(_req: &Request, basic: Basic) -> Option<String> {
+        (basic.password == "password").then_some(basic.username)
+    }
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/test", method = "get")]
+        async fn test(&self, auth: Option<MySecurityScheme>) -> PlainText<String> {
+            match auth {
+                Some(auth) => PlainText(format!("Authed: {}", auth.0)),
+                None => PlainText("NoAuth".to_string()),
+            }
+        }
+    }
+
+    let client = TestClient::new(OpenApiService::new(MyApi, "test", "1.0"));
+
+    let resp = client
+        .get("/test")
+        .typed_header(headers::Authorization::basic("sunli", "password"))
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_text("Authed: sunli").await;
+
+    let resp = client.get("/test").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_text("NoAuth").await;
+
+    client
+        .get("/test")
+        .typed_header(headers::Authorization::basic("sunli", "wrong"))
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+}
@@ -1,5 +1,3 @@
-use std::collections::BTreeSet;
-
 use poem_openapi::{
     registry::{MetaExternalDocument, MetaTag, Registry},
     Tags,
@@ -70,8 +68,6 @@ async fn meta() {
                 external_docs: None
             }
         ]
-        .into_iter()
-        .collect::<BTreeSet<_>>()
     );
 }
 
@@ -101,3 +97,29 @@ async fn external_docs() {
         }
     );
 }
+
+#[tokio::test]
+async fn declaration_order() {
+    #[derive(Tags)]
+    #[allow(dead_code)]
+    enum MyTags {
+        Users,
+        Pets,
+        Admin,
+    }
+
+    #[derive(Tags)]
+    #[allow(dead_code)]
+    enum OtherTags {
+        Stores,
+        Users,
+    }
+
+    let mut registry = Registry::new();
+    MyTags::Pets.register(&mut registry);
+    OtherTags::Stores.register(&mut registry);
+    assert_eq!(
+        registry.tags.iter().map(|tag| tag.name).collect::<Vec<_>>(),
+        vec!["Users", "Pets", "Admin", "Stores"]
+    );
+}
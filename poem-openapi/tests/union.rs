@@ -65,6 +65,7 @@ fn with_discriminator() {
     assert_eq!(
         schema_myobj_a,
         MetaSchema {
+            rust_typename: Some("(union::with_discriminator::MyObj, union::with_discriminator::A)"),
             all_of: vec![
                 MetaSchemaRef::Inline(Box::new(MetaSchema {
                     required: vec!["type"],
@@ -89,6 +90,7 @@ fn with_discriminator() {
     assert_eq!(
         schema_myobj_b,
         MetaSchema {
+            rust_typename: Some("(union::with_discriminator::MyObj, union::with_discriminator::B)"),
             all_of: vec![
                 MetaSchemaRef::Inline(Box::new(MetaSchema {
                     required: vec!["type"],
@@ -201,6 +203,9 @@ fn with_discriminator_mapping() {
     assert_eq!(
         schema_myobj_a,
         MetaSchema {
+            rust_typename: Some(
+                "(union::with_discriminator_mapping::MyObj, union::with_discriminator_mapping::A)"
+            ),
             all_of: vec![
                 MetaSchemaRef::Inline(Box::new(MetaSchema {
                     required: vec!["type"],
@@ -225,6 +230,9 @@ fn with_discriminator_mapping() {
     assert_eq!(
         schema_myobj_b,
         MetaSchema {
+            rust_typename: Some(
+                "(union::with_discriminator_mapping::MyObj, union::with_discriminator_mapping::B)"
+            ),
             all_of: vec![
                 MetaSchemaRef::Inline(Box::new(MetaSchema {
                     required: vec!["type"],
@@ -479,6 +487,44 @@ fn title_and_description() {
     assert_eq!(schema.description, Some("A\n\nB\nC"));
 }
 
+#[test]
+fn item_descriptions() {
+    #[derive(Object, Debug, PartialEq)]
+    struct A {
+        v1: i32,
+    }
+
+    #[derive(Union, Debug, PartialEq)]
+    enum MyObj {
+        /// An object
+        A(A),
+        B(f32),
+    }
+
+    let schema = get_meta::<MyObj>();
+    assert_eq!(
+        schema.any_of,
+        vec![
+            MetaSchemaRef::Inline(Box::new(MetaSchema {
+                description: Some("An object"),
+                all_of: vec![MetaSchemaRef::Reference("A".to_string())],
+                ..MetaSchema::ANY
+            })),
+            f32::schema_ref(),
+        ]
+    );
+
+    #[derive(Union, Debug, PartialEq)]
+    #[oai(discriminator_name = "type")]
+    enum MyObj2 {
+        /// An object
+        A(A),
+    }
+
+    let schema = get_meta_by_name::<MyObj2>("MyObj2_A");
+    assert_eq!(schema.description, Some("An object"));
+}
+
 #[tokio::test]
 async fn external_docs() {
     #[derive(Union, Debug, PartialEq)]
@@ -215,6 +215,36 @@ fn test_pattern() {
     assert_eq!(schema.pattern.as_deref(), Some(r"\[.*\]"));
 }
 
+#[test]
+fn test_const_value() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct A {
+        #[oai(rename = "type", validator(const_value = "payment.created"))]
+        ty: String,
+    }
+
+    assert_eq!(
+        A::parse_from_json(Some(json!({ "type": "payment.created" }))).unwrap(),
+        A {
+            ty: "payment.created".to_string()
+        }
+    );
+    assert_eq!(
+        A::parse_from_json(Some(json!({ "type": "payment.failed" })))
+            .unwrap_err()
+            .into_message(),
+        r#"failed to parse "A": field `type` verification failed. const("payment.created")"#
+    );
+
+    let mut registry = Registry::new();
+    A::register(&mut registry);
+    let schema = registry.schemas.remove("A").unwrap();
+    assert_eq!(
+        schema.properties[0].1.unwrap_inline().enum_items,
+        vec![json!("payment.created")]
+    );
+}
+
 #[test]
 fn test_max_items() {
     #[derive(Object, Debug, Eq, PartialEq)]
@@ -742,3 +772,55 @@ fn test_custom_validator() {
         "failed to parse \"A\": field `value` verification failed. MyIntValidator"
     );
 }
+
+#[test]
+fn test_custom_validator_meta() {
+    struct MyIntValidator(Range<i32>);
+
+    impl Display for MyIntValidator {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.write_str("MyIntValidator")
+        }
+    }
+
+    impl Validator<i32> for MyIntValidator {
+        fn check(&self, value: &i32) -> bool {
+            self.0.contains(value)
+        }
+    }
+
+    impl ValidatorMeta for MyIntValidator {
+        fn update_meta(&self, meta: &mut MetaSchema) {
+            meta.minimum = Some(self.0.start as f64);
+            meta.maximum = Some(self.0.end as f64);
+            meta.exclusive_maximum = Some(true);
+        }
+    }
+
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct A {
+        #[oai(validator(custom = "MyIntValidator(100..200)"))]
+        value: i32,
+        #[oai(validator(custom = "MyIntValidator(1..10)"))]
+        values: Vec<i32>,
+    }
+
+    let mut registry = Registry::default();
+    A::register(&mut registry);
+    let schema = registry.schemas.get_mut("A").unwrap();
+
+    let (name, field_value) = schema.properties.remove(0);
+    assert_eq!(name, "value");
+    let schema_value = field_value.unwrap_inline();
+    assert_eq!(schema_value.minimum, Some(100.0));
+    assert_eq!(schema_value.maximum, Some(200.0));
+    assert_eq!(schema_value.exclusive_maximum, Some(true));
+
+    let (name, field_values) = schema.properties.remove(0);
+    assert_eq!(name, "values");
+    let schema_items = field_values.unwrap_inline().items.as_ref().unwrap();
+    let schema_items = schema_items.unwrap_inline();
+    assert_eq!(schema_items.minimum, Some(1.0));
+    assert_eq!(schema_items.maximum, Some(10.0));
+    assert_eq!(schema_items.exclusive_maximum, Some(true));
+}
@@ -4,10 +4,10 @@ use poem_openapi::{
     payload::Json,
     registry::{
         MetaExternalDocument, MetaMediaType, MetaOperationParam, MetaParamIn, MetaRequest,
-        MetaResponse, MetaResponses,
+        MetaResponse, MetaResponses, Registry,
     },
     types::Type,
-    OpenApiService, Tags, Webhook,
+    Object, OpenApiService, Tags, Webhook,
 };
 
 #[tokio::test]
@@ -120,6 +120,7 @@ async fn parameters() {
                 required: true,
                 deprecated: false,
                 explode: true,
+                extensions: Default::default(),
             },
             MetaOperationParam {
                 name: "b".to_string(),
@@ -129,6 +130,7 @@ async fn parameters() {
                 required: true,
                 deprecated: false,
                 explode: true,
+                extensions: Default::default(),
             }
         ]
     );
@@ -149,6 +151,8 @@ async fn request_body() {
             content: vec![MetaMediaType {
                 content_type: "application/json; charset=utf-8",
                 schema: i32::schema_ref(),
+                examples: vec![],
+                encoding: vec![],
             }],
             required: true
         })
@@ -172,6 +176,8 @@ async fn response() {
                 content: vec![MetaMediaType {
                     content_type: "application/json; charset=utf-8",
                     schema: i32::schema_ref(),
+                    examples: vec![],
+                    encoding: vec![],
                 }],
                 headers: vec![]
             }]
@@ -210,3 +216,36 @@ async fn external_docs() {
         })
     );
 }
+
+#[tokio::test]
+async fn hidden() {
+    #[derive(Object)]
+    #[allow(dead_code)]
+    struct MyObj1 {
+        value: i32,
+    }
+
+    #[derive(Object)]
+    struct MyObj2 {
+        value: i32,
+    }
+
+    #[Webhook]
+    #[allow(dead_code)]
+    trait MyWebhooks {
+        #[oai(method = "post", hidden)]
+        fn test1(&self, req: Json<MyObj1>);
+
+        #[oai(method = "get")]
+        fn test2(&self, req: Json<MyObj2>);
+    }
+
+    let meta = <&dyn MyWebhooks>::meta();
+    assert_eq!(meta.len(), 1);
+    assert_eq!(meta[0].name, "test2");
+
+    let mut registry = Registry::new();
+    <&dyn MyWebhooks>::register(&mut registry);
+    assert!(!registry.schemas.contains_key("MyObj1"));
+    assert!(registry.schemas.contains_key("MyObj2"));
+}
@@ -0,0 +1,68 @@
+//! Benchmarks reading chunked bodies with and without the buffer pool.
+//!
+//! Run with `cargo bench -p poem --bench buffer_pool`.
+
+use std::{hint::black_box, time::Instant};
+
+use bytes::{Bytes, BytesMut};
+use poem::BufferPool;
+
+const ITERATIONS: u32 = 100_000;
+
+fn chunks(size: usize) -> Vec<Bytes> {
+    (0..size / 1024)
+        .map(|_| Bytes::from(vec![b'x'; 1024]))
+        .collect()
+}
+
+fn bench(name: &str, chunks: &[Bytes], f: impl Fn(&[Bytes]) -> Bytes) {
+    // warm up
+    for _ in 0..ITERATIONS / 10 {
+        black_box(f(chunks));
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f(chunks));
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{name:<40} {:>8.1} ns/iter",
+        elapsed.as_nanos() as f64 / ITERATIONS as f64
+    );
+}
+
+fn main() {
+    // the configuration of the global pool
+    let pool = BufferPool::default();
+
+    for size in [2 * 1024, 4 * 1024, 8 * 1024, 64 * 1024] {
+        let chunks = chunks(size);
+
+        bench(
+            &format!("growing buffer ({}KB)", size / 1024),
+            &chunks,
+            |chunks| {
+                let mut buf = BytesMut::new();
+                for chunk in chunks {
+                    buf.extend_from_slice(chunk);
+                }
+                buf.freeze()
+            },
+        );
+
+        bench(
+            &format!("pooled buffer, default ({}KB)", size / 1024),
+            &chunks,
+            |chunks| {
+                let mut buf = pool.get();
+                for chunk in chunks {
+                    buf.extend_from_slice(chunk);
+                }
+                buf.freeze()
+            },
+        );
+    }
+
+    println!("pool hit rate: {:.4}", pool.metrics().hit_rate());
+}
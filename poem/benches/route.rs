@@ -0,0 +1,66 @@
+//! Benchmarks the router and path parameter extraction.
+//!
+//! Run with `cargo bench -p poem --bench route`.
+
+use std::{hint::black_box, time::Instant};
+
+use poem::{get, handler, http::Uri, web::Path, Endpoint, Request, Route};
+
+#[handler]
+fn static_path() -> &'static str {
+    "ok"
+}
+
+#[handler]
+fn owned_params(Path((user_id, repo)): Path<(String, String)>) -> String {
+    format!("{user_id}/{repo}")
+}
+
+#[handler]
+fn borrowed_params(Path((user_id, repo)): Path<(&str, &str)>) -> String {
+    format!("{user_id}/{repo}")
+}
+
+fn bench<E: Endpoint>(rt: &tokio::runtime::Runtime, name: &str, ep: &E, uri: &'static str) {
+    const ITERATIONS: u32 = 200_000;
+
+    let uri = Uri::from_static(uri);
+    let run = || {
+        rt.block_on(async {
+            for _ in 0..ITERATIONS {
+                let req = Request::builder().uri(uri.clone()).finish();
+                let _ = black_box(ep.call(req).await);
+            }
+        })
+    };
+
+    // warm up
+    run();
+
+    let start = Instant::now();
+    run();
+    let elapsed = start.elapsed();
+    println!(
+        "{name:<24} {:>8.1} ns/iter",
+        elapsed.as_nanos() as f64 / ITERATIONS as f64
+    );
+}
+
+fn main() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let app = Route::new()
+        .at("/static/path", get(static_path))
+        .at("/users/:user_id/owned/:repo", get(owned_params))
+        .at("/users/:user_id/borrowed/:repo", get(borrowed_params))
+        .nest(
+            "/api",
+            Route::new().at("/users/:user_id/:repo", get(borrowed_params)),
+        );
+
+    bench(&rt, "static", &app, "/static/path");
+    bench(&rt, "params (String)", &app, "/users/sunli/owned/poem");
+    bench(&rt, "params (&str)", &app, "/users/sunli/borrowed/poem");
+    bench(&rt, "nested params (&str)", &app, "/api/users/sunli/poem");
+}
@@ -0,0 +1,82 @@
+//! Benchmarks serving files with `StaticFilesEndpoint` and tower-http's
+//! `ServeDir`.
+//!
+//! Run with `cargo bench -p poem --bench static_files --features static-files`.
+
+use std::{hint::black_box, path::Path, time::Instant};
+
+use http_body_util::BodyExt;
+use poem::{endpoint::StaticFilesEndpoint, http::Uri, Endpoint, Request};
+use tower::ServiceExt;
+use tower_http::services::ServeDir;
+
+fn bench<F, Fut>(rt: &tokio::runtime::Runtime, name: &str, size: usize, iterations: u32, f: F)
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = usize>,
+{
+    let run = || {
+        rt.block_on(async {
+            for _ in 0..iterations {
+                assert_eq!(black_box(f().await), size);
+            }
+        })
+    };
+
+    // warm up
+    run();
+
+    let start = Instant::now();
+    run();
+    let elapsed = start.elapsed();
+    println!(
+        "{name:<32} {:>10.1} us/iter {:>10.1} MB/s",
+        elapsed.as_micros() as f64 / iterations as f64,
+        (size as f64 * iterations as f64) / elapsed.as_secs_f64() / 1024.0 / 1024.0
+    );
+}
+
+fn main() {
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let dir = std::env::temp_dir().join(format!("poem-bench-static-files-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let poem_ep = StaticFilesEndpoint::new(&dir);
+    let serve_dir = ServeDir::new(&dir);
+
+    for (size, iterations) in [
+        (4 * 1024, 5_000),
+        (1024 * 1024, 500),
+        (16 * 1024 * 1024, 20),
+    ] {
+        let name = format!("{}KB.bin", size / 1024);
+        std::fs::write(Path::new(&dir).join(&name), vec![b'x'; size]).unwrap();
+        let uri: Uri = format!("/{name}").parse().unwrap();
+
+        bench(&rt, &format!("poem ({name})"), size, iterations, || async {
+            let req = Request::builder().uri(uri.clone()).finish();
+            let resp = poem_ep.get_response(req).await;
+            resp.into_body().into_bytes().await.unwrap().len()
+        });
+
+        bench(
+            &rt,
+            &format!("tower-http ({name})"),
+            size,
+            iterations,
+            || async {
+                let req = poem::http::Request::builder()
+                    .uri(uri.clone())
+                    .body(http_body_util::Empty::<bytes::Bytes>::new())
+                    .unwrap();
+                let resp = serve_dir.clone().oneshot(req).await.unwrap();
+                resp.into_body().collect().await.unwrap().to_bytes().len()
+            },
+        );
+    }
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -1,21 +1,21 @@
 use std::{
     fmt::{Debug, Formatter},
-    io::{Error as IoError, ErrorKind},
+    io::Error as IoError,
     pin::Pin,
-    task::Poll,
+    task::{Context, Poll},
 };
 
-use bytes::{Bytes, BytesMut};
+use bytes::Bytes;
 use futures_util::{Stream, TryStreamExt};
 use http_body_util::BodyExt;
 use hyper::body::{Body as _, Frame};
 use serde::{de::DeserializeOwned, Serialize};
 use sync_wrapper::SyncStream;
-use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::io::AsyncRead;
 
 use crate::{
     error::{ParseJsonError, ReadBodyError},
-    Result,
+    BufferPool, Result,
 };
 
 pub(crate) type BoxBody = http_body_util::combinators::BoxBody<Bytes, IoError>;
@@ -117,14 +117,54 @@ impl Body {
         data.into()
     }
 
+    /// The default size of the chunks read by [`Body::from_async_read`].
+    pub const DEFAULT_CHUNK_SIZE: usize = 4096;
+
     /// Create a body object from reader.
+    ///
+    /// The data is read in chunks of at most [`Body::DEFAULT_CHUNK_SIZE`]
+    /// bytes, use [`Body::from_async_read_with_chunk_size`] for large
+    /// payloads.
     #[inline]
     pub fn from_async_read(reader: impl AsyncRead + Send + 'static) -> Self {
+        Self::from_async_read_with_chunk_size(reader, Self::DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Create a body object from reader, which reads the data in chunks of at
+    /// most `chunk_size` bytes.
+    pub fn from_async_read_with_chunk_size(
+        reader: impl AsyncRead + Send + 'static,
+        chunk_size: usize,
+    ) -> Self {
         Self(BoxBody::new(http_body_util::StreamBody::new(
-            SyncStream::new(tokio_util::io::ReaderStream::new(reader).map_ok(Frame::data)),
+            SyncStream::new(
+                tokio_util::io::ReaderStream::with_capacity(reader, chunk_size).map_ok(Frame::data),
+            ),
         )))
     }
 
+    /// Hints the exact length of this body, so that the response is sent with
+    /// a `Content-Length` header instead of the chunked transfer encoding.
+    ///
+    /// The length must be correct, the connection fails if the body is
+    /// shorter or longer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::Body;
+    ///
+    /// let body = Body::from_async_read(&b"hello"[..]).with_content_length(5);
+    /// assert_eq!(body.exact_size(), Some(5));
+    /// ```
+    #[must_use]
+    pub fn with_content_length(self, len: u64) -> Self {
+        Self(BoxBody::new(SizedBody {
+            inner: self.0,
+            remaining: len,
+        }))
+    }
+
     /// Create a body object from bytes stream.
     pub fn from_bytes_stream<S, O, E>(stream: S) -> Self
     where
@@ -162,14 +202,15 @@ impl Body {
         size_hint.lower() == 0 && size_hint.upper() == Some(0)
     }
 
+    /// Returns the size of this body if it is known exactly, which is the case
+    /// for bodies that are already in memory.
+    pub fn exact_size(&self) -> Option<u64> {
+        hyper::body::Body::size_hint(&self.0).exact()
+    }
+
     /// Consumes this body object to return a [`Bytes`] that contains all data.
     pub async fn into_bytes(self) -> Result<Bytes, ReadBodyError> {
-        Ok(self
-            .0
-            .collect()
-            .await
-            .map_err(|err| ReadBodyError::Io(IoError::new(ErrorKind::Other, err)))?
-            .to_bytes())
+        self.read_bytes(usize::MAX).await
     }
 
     /// Consumes this body object to return a [`Vec<u8>`] that contains all
@@ -204,19 +245,34 @@ impl Body {
     /// # });
     /// ```
     pub async fn into_bytes_limit(self, limit: usize) -> Result<Bytes, ReadBodyError> {
-        let mut reader = self.into_async_read();
-        let mut buf = [0; 4096];
-        let mut data = BytesMut::new();
+        self.read_bytes(limit).await
+    }
+
+    async fn read_bytes(self, limit: usize) -> Result<Bytes, ReadBodyError> {
+        let mut stream = std::pin::pin!(self.into_bytes_stream());
+
+        // a body with a single chunk is returned without copying it
+        let Some(first) = stream.try_next().await? else {
+            return Ok(Bytes::new());
+        };
+        if first.len() > limit {
+            return Err(ReadBodyError::PayloadTooLarge);
+        }
+        let Some(mut chunk) = stream.try_next().await? else {
+            return Ok(first);
+        };
 
+        let mut data = BufferPool::global().get();
+        data.extend_from_slice(&first);
         loop {
-            let sz = reader.read(&mut buf).await?;
-            if sz == 0 {
-                break;
-            }
-            if data.len() + sz > limit {
+            if chunk.len() > limit - data.len() {
                 return Err(ReadBodyError::PayloadTooLarge);
             }
-            data.extend_from_slice(&buf[..sz]);
+            data.extend_from_slice(&chunk);
+            match stream.try_next().await? {
+                Some(next) => chunk = next,
+                None => break,
+            }
         }
 
         Ok(data.freeze())
@@ -271,6 +327,38 @@ impl Body {
     }
 }
 
+/// A body with a known length, see [`Body::with_content_length`].
+struct SizedBody {
+    inner: BoxBody,
+    remaining: u64,
+}
+
+impl hyper::body::Body for SizedBody {
+    type Data = Bytes;
+    type Error = IoError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let res = Pin::new(&mut self.inner).poll_frame(cx);
+        if let Poll::Ready(Some(Ok(frame))) = &res {
+            if let Some(data) = frame.data_ref() {
+                self.remaining = self.remaining.saturating_sub(data.len() as u64);
+            }
+        }
+        res
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.remaining == 0 || self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> hyper::body::SizeHint {
+        hyper::body::SizeHint::with_exact(self.remaining)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -314,7 +402,36 @@ mod tests {
         ));
         assert_eq!(body.into_string().await.unwrap(), "abcdefghi");
 
+        let body = Body::from_async_read_with_chunk_size(&b"abcdefghi"[..], 2);
+        assert_eq!(body.exact_size(), None);
+        assert_eq!(
+            body.into_bytes_stream()
+                .map_ok(|data| data.len())
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap(),
+            vec![2, 2, 2, 2, 1]
+        );
+
+        let body = Body::from_async_read(&b"abcdefghi"[..]).with_content_length(9);
+        assert_eq!(body.exact_size(), Some(9));
+        assert!(!body.is_empty());
+        assert_eq!(body.into_string().await.unwrap(), "abcdefghi");
+
         let body = Body::from_json("abc").unwrap();
         assert_eq!(body.into_json::<String>().await.unwrap(), "abc");
     }
+
+    #[tokio::test]
+    async fn into_bytes_limit() {
+        let body = Body::from("12345");
+        assert_eq!(body.into_bytes_limit(5).await.unwrap(), "12345");
+
+        // an endless body is rejected once the limit is exceeded
+        let body = Body::from_async_read(tokio::io::repeat(b'a'));
+        assert!(matches!(
+            body.into_bytes_limit(1024).await,
+            Err(ReadBodyError::PayloadTooLarge)
+        ));
+    }
 }
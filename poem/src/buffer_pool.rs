@@ -0,0 +1,256 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+};
+
+use bytes::{Bytes, BytesMut};
+use parking_lot::Mutex;
+
+static GLOBAL_POOL: OnceLock<BufferPool> = OnceLock::new();
+
+/// A pool of buffers used to read bodies.
+///
+/// Reading a body of unknown length into a growing buffer reallocates it
+/// several times. The buffers of this pool are allocated with a fixed
+/// capacity and are returned to the pool when dropped, so the next request
+/// reuses the allocation instead of growing a new buffer from scratch.
+///
+/// The content of a buffer which has not grown beyond `buffer_size` is copied
+/// out of it by [`PooledBuffer::freeze`] and [`PooledBuffer::into_vec`], so
+/// the returned data never keeps a pooled allocation alive. A buffer which
+/// has grown beyond `buffer_size` is handed over to the returned data instead
+/// and is not returned to the pool, so a few large bodies do not make the
+/// pool hold large allocations.
+///
+/// The global pool is used by [`Body::into_bytes`](crate::Body::into_bytes),
+/// [`Field::bytes`](crate::web::Field::bytes), the
+/// [`JsonLines`](crate::web::JsonLines) extractor and the encoder of
+/// [`TypedWebSocketStream`](crate::web::websocket::TypedWebSocketStream).
+///
+/// # Example
+///
+/// ```
+/// use poem::BufferPool;
+///
+/// // must be called before the first request is processed
+/// BufferPool::set_global(BufferPool::new(16 * 1024, 256)).ok();
+///
+/// let metrics = BufferPool::global().metrics();
+/// println!("hit rate: {:.2}", metrics.hit_rate());
+/// ```
+pub struct BufferPool {
+    buffer_size: usize,
+    max_buffers: usize,
+    buffers: Mutex<Vec<BytesMut>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        Self::new(8 * 1024, 1024)
+    }
+}
+
+impl BufferPool {
+    /// Create a `BufferPool` which keeps at most `max_buffers` idle buffers
+    /// of `buffer_size` bytes.
+    pub fn new(buffer_size: usize, max_buffers: usize) -> Self {
+        Self {
+            buffer_size,
+            max_buffers,
+            buffers: Mutex::new(Vec::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the global pool, which is created with the default
+    /// configuration (`8KB` buffers, at most `1024` idle buffers) unless
+    /// [`BufferPool::set_global`] has been called before.
+    pub fn global() -> &'static BufferPool {
+        GLOBAL_POOL.get_or_init(BufferPool::default)
+    }
+
+    /// Sets the global pool, returns `Err(pool)` if it has already been
+    /// initialized.
+    pub fn set_global(pool: BufferPool) -> Result<(), BufferPool> {
+        GLOBAL_POOL.set(pool)
+    }
+
+    /// Takes a buffer from the pool, or allocates a new one if the pool is
+    /// empty. The buffer is returned to the pool when dropped.
+    pub fn get(&self) -> PooledBuffer<'_> {
+        let buf = match self.buffers.lock().pop() {
+            Some(buf) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                buf
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                BytesMut::with_capacity(self.buffer_size)
+            }
+        };
+        PooledBuffer { pool: self, buf }
+    }
+
+    /// Returns the metrics of this pool.
+    pub fn metrics(&self) -> BufferPoolMetrics {
+        BufferPoolMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            idle: self.buffers.lock().len(),
+        }
+    }
+
+    fn put(&self, mut buf: BytesMut) {
+        if buf.capacity() == 0 || buf.capacity() > self.buffer_size {
+            return;
+        }
+        buf.clear();
+        let mut buffers = self.buffers.lock();
+        if buffers.len() < self.max_buffers {
+            buffers.push(buf);
+        }
+    }
+}
+
+/// Metrics of a [`BufferPool`].
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct BufferPoolMetrics {
+    /// The number of buffers taken from the pool.
+    pub hits: u64,
+    /// The number of buffers allocated because the pool was empty.
+    pub misses: u64,
+    /// The number of idle buffers in the pool.
+    pub idle: usize,
+}
+
+impl BufferPoolMetrics {
+    /// Returns the ratio of buffers taken from the pool, between `0.0` and
+    /// `1.0`.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+}
+
+/// A buffer taken from a [`BufferPool`].
+pub struct PooledBuffer<'a> {
+    pool: &'a BufferPool,
+    buf: BytesMut,
+}
+
+impl PooledBuffer<'_> {
+    /// Consumes this buffer to return its content as [`Bytes`].
+    pub fn freeze(mut self) -> Bytes {
+        if self.is_grown() {
+            return std::mem::take(&mut self.buf).freeze();
+        }
+        Bytes::copy_from_slice(&self.buf)
+    }
+
+    /// Consumes this buffer to return its content as [`Vec<u8>`].
+    pub fn into_vec(mut self) -> Vec<u8> {
+        if self.is_grown() {
+            return std::mem::take(&mut self.buf).into();
+        }
+        self.buf.to_vec()
+    }
+
+    #[inline]
+    fn is_grown(&self) -> bool {
+        self.buf.capacity() > self.pool.buffer_size
+    }
+}
+
+impl Deref for PooledBuffer<'_> {
+    type Target = BytesMut;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buf
+    }
+}
+
+impl DerefMut for PooledBuffer<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buf
+    }
+}
+
+impl Drop for PooledBuffer<'_> {
+    fn drop(&mut self) {
+        self.pool.put(std::mem::take(&mut self.buf));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuse_buffers() {
+        let pool = BufferPool::new(1024, 1);
+
+        let mut buf = pool.get();
+        buf.extend_from_slice(b"hello");
+        let ptr = buf.as_ptr();
+        let data = buf.freeze();
+        assert_eq!(data, "hello");
+        // the content is copied out, so the pooled allocation is not pinned
+        assert_ne!(data.as_ptr(), ptr);
+
+        let mut buf = pool.get();
+        assert!(buf.is_empty());
+        assert!(buf.capacity() >= 1024);
+        buf.extend_from_slice(b"world");
+        assert_eq!(buf.as_ptr(), ptr);
+        assert_eq!(data, "hello");
+
+        let buf2 = pool.get();
+        assert_eq!(buf.into_vec(), b"world");
+        drop(buf2);
+
+        assert_eq!(
+            pool.metrics(),
+            BufferPoolMetrics {
+                hits: 1,
+                misses: 2,
+                idle: 1,
+            }
+        );
+        assert!((pool.metrics().hit_rate() - 1.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn drop_grown_buffers() {
+        let pool = BufferPool::new(16, 4);
+
+        let mut buf = pool.get();
+        buf.extend_from_slice(&[0; 100]);
+        assert_eq!(buf.freeze().len(), 100);
+        assert_eq!(pool.metrics().idle, 0);
+
+        let mut buf = pool.get();
+        buf.extend_from_slice(&[0; 100]);
+        drop(buf);
+        assert_eq!(pool.metrics().idle, 0);
+
+        let mut buf = pool.get();
+        buf.extend_from_slice(&[0; 100]);
+        assert_eq!(buf.into_vec().len(), 100);
+        assert_eq!(pool.metrics().idle, 0);
+
+        let mut buf = pool.get();
+        buf.extend_from_slice(&[0; 8]);
+        let data = buf.freeze();
+        assert_eq!(pool.metrics().idle, 1);
+        drop(data);
+    }
+}
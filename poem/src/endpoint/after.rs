@@ -1,6 +1,6 @@
 use std::future::Future;
 
-use crate::{Endpoint, IntoResponse, Request, Result};
+use crate::{endpoint::MiddlewareChain, Endpoint, IntoResponse, Request, Result};
 
 /// Endpoint for the [`after`](super::EndpointExt::after) method.
 pub struct After<E, F> {
@@ -27,4 +27,8 @@ where
     async fn call(&self, req: Request) -> Result<Self::Output> {
         (self.f)(self.inner.call(req).await).await
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.inner.middleware_chains()
+    }
 }
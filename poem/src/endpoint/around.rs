@@ -1,6 +1,6 @@
 use std::{future::Future, sync::Arc};
 
-use crate::{Endpoint, IntoResponse, Request, Result};
+use crate::{endpoint::MiddlewareChain, Endpoint, IntoResponse, Request, Result};
 
 /// Endpoint for the [`around`](super::EndpointExt::around) method.
 pub struct Around<E, F> {
@@ -30,4 +30,8 @@ where
     async fn call(&self, req: Request) -> Result<Self::Output> {
         (self.f)(self.inner.clone(), req).await
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.inner.middleware_chains()
+    }
 }
@@ -1,6 +1,6 @@
 use std::{future::Future, marker::PhantomData};
 
-use crate::{Endpoint, Error, IntoResponse, Request, Response, Result};
+use crate::{endpoint::MiddlewareChain, Endpoint, Error, IntoResponse, Request, Response, Result};
 
 /// Endpoint for the [`catch_all_error`](super::EndpointExt::catch_all_error)
 /// method.
@@ -36,4 +36,8 @@ where
             Err(err) => Ok((self.f)(err).await.into_response()),
         }
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.inner.middleware_chains()
+    }
 }
@@ -1,6 +1,6 @@
 use std::{future::Future, marker::PhantomData};
 
-use crate::{Endpoint, IntoResponse, Request, Response, Result};
+use crate::{endpoint::MiddlewareChain, Endpoint, IntoResponse, Request, Response, Result};
 
 /// Endpoint for the [`catch_error`](super::EndpointExt::catch_error) method.
 pub struct CatchError<E, F, R, ErrType> {
@@ -41,4 +41,8 @@ where
             Err(err) => Err(err),
         }
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.inner.middleware_chains()
+    }
 }
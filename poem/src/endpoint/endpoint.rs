@@ -1,10 +1,10 @@
-use std::{future::Future, marker::PhantomData, sync::Arc};
+use std::{borrow::Cow, future::Future, marker::PhantomData, sync::Arc};
 
 use futures_util::{future::BoxFuture, FutureExt};
 
 use super::{
     After, AndThen, Around, Before, CatchAllError, CatchError, InspectAllError, InspectError, Map,
-    MapToResponse, ToResponse,
+    MapToResponse, MiddlewareChain, Named, ToResponse, WithWhen,
 };
 use crate::{
     error::IntoResult,
@@ -54,6 +54,41 @@ pub trait Endpoint: Send + Sync {
                 .unwrap_or_else(|err| err.into_response())
         }
     }
+
+    /// Returns the chains of the middlewares wrapping this endpoint, one for
+    /// each route, which is useful for debugging the order of middlewares.
+    ///
+    /// Only the middlewares provided by `Poem` and the endpoints named with
+    /// [`EndpointExt::named`] are reported, and the default implementation
+    /// treats the endpoint as a route without middlewares.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{
+    ///     endpoint::make_sync,
+    ///     middleware::{Cors, Tracing},
+    ///     Endpoint, EndpointExt, Route,
+    /// };
+    ///
+    /// let app = Route::new()
+    ///     .at("/a", make_sync(|_| "a").with(Cors::new()))
+    ///     .at("/b", make_sync(|_| "b").named("b_endpoint"))
+    ///     .with(Tracing);
+    ///
+    /// let chains = app
+    ///     .middleware_chains()
+    ///     .iter()
+    ///     .map(ToString::to_string)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     chains,
+    ///     vec!["/a: Tracing -> Cors", "/b: Tracing -> b_endpoint"]
+    /// );
+    /// ```
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        vec![MiddlewareChain::default()]
+    }
 }
 
 struct SyncFnEndpoint<T, F> {
@@ -112,6 +147,13 @@ where
             EitherEndpoint::B(b) => b.call(req).await.map(IntoResponse::into_response),
         }
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        match self {
+            EitherEndpoint::A(a) => a.middleware_chains(),
+            EitherEndpoint::B(b) => b.middleware_chains(),
+        }
+    }
 }
 
 /// Create an endpoint with a function.
@@ -181,6 +223,10 @@ impl<T: Endpoint + ?Sized> Endpoint for &T {
     async fn call(&self, req: Request) -> Result<Self::Output> {
         T::call(self, req).await
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        T::middleware_chains(self)
+    }
 }
 
 impl<T: Endpoint + ?Sized> Endpoint for Box<T> {
@@ -189,6 +235,10 @@ impl<T: Endpoint + ?Sized> Endpoint for Box<T> {
     async fn call(&self, req: Request) -> Result<Self::Output> {
         self.as_ref().call(req).await
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.as_ref().middleware_chains()
+    }
 }
 
 impl<T: Endpoint + ?Sized> Endpoint for Arc<T> {
@@ -197,6 +247,10 @@ impl<T: Endpoint + ?Sized> Endpoint for Arc<T> {
     async fn call(&self, req: Request) -> Result<Self::Output> {
         self.as_ref().call(req).await
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.as_ref().middleware_chains()
+    }
 }
 
 /// A `endpoint` that can be dynamically dispatched.
@@ -206,6 +260,11 @@ pub trait DynEndpoint: Send + Sync {
 
     /// Get the response to the request.
     fn call(&self, req: Request) -> BoxFuture<Result<Self::Output>>;
+
+    /// Returns the chains of the middlewares wrapping this endpoint.
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        vec![MiddlewareChain::default()]
+    }
 }
 
 /// A [`Endpoint`] wrapper used to implement [`DynEndpoint`].
@@ -221,6 +280,11 @@ where
     fn call(&self, req: Request) -> BoxFuture<Result<Self::Output>> {
         self.0.call(req).boxed()
     }
+
+    #[inline]
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.0.middleware_chains()
+    }
 }
 
 impl<T> Endpoint for dyn DynEndpoint<Output = T> + '_
@@ -233,6 +297,11 @@ where
     async fn call(&self, req: Request) -> Result<Self::Output> {
         DynEndpoint::call(self, req).await
     }
+
+    #[inline]
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        DynEndpoint::middleware_chains(self)
+    }
 }
 
 /// An owned dynamically typed `Endpoint` for use in cases where you can’t
@@ -330,6 +399,59 @@ pub trait EndpointExt: IntoEndpoint {
         }
     }
 
+    /// Use middleware to transform this endpoint, but only apply it to the
+    /// requests for which `predicate` returns `true`, the other requests are
+    /// passed to this endpoint directly.
+    ///
+    /// Unlike [`EndpointExt::with_if`], the predicate is checked for every
+    /// request, so that heavy middleware can be skipped for health checks or
+    /// internal paths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{
+    ///     get, handler, http::Method, middleware::AddData, test::TestClient, web::Data, EndpointExt,
+    ///     Route,
+    /// };
+    ///
+    /// #[handler]
+    /// async fn index(data: Option<Data<&i32>>) -> String {
+    ///     match data {
+    ///         Some(data) => data.0.to_string(),
+    ///         None => "none".to_string(),
+    ///     }
+    /// }
+    ///
+    /// let app = Route::new()
+    ///     .at("/health", get(index))
+    ///     .at("/api/users", get(index).post(index))
+    ///     .with_when(
+    ///         |req| req.uri().path().starts_with("/api/") && req.method() == Method::GET,
+    ///         AddData::new(100i32),
+    ///     );
+    /// let cli = TestClient::new(app);
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// cli.get("/api/users").send().await.assert_text("100").await;
+    /// cli.post("/api/users")
+    ///     .send()
+    ///     .await
+    ///     .assert_text("none")
+    ///     .await;
+    /// cli.get("/health").send().await.assert_text("none").await;
+    /// # });
+    /// ```
+    fn with_when<P, T>(self, predicate: P, middleware: T) -> WithWhen<Self::Endpoint, P, T::Output>
+    where
+        P: Fn(&Request) -> bool + Send + Sync,
+        T: Middleware<Arc<Self::Endpoint>>,
+        Self: Sized,
+    {
+        let inner = Arc::new(self.into_endpoint());
+        WithWhen::new(inner.clone(), middleware.transform(inner), predicate)
+    }
+
     /// Attach a state data to the endpoint, similar to `with(AddData(T))`.
     ///
     /// # Example
@@ -756,6 +878,33 @@ pub trait EndpointExt: IntoEndpoint {
     {
         InspectError::new(self, f)
     }
+
+    /// Names this endpoint, the name is reported by
+    /// [`Endpoint::middleware_chains`] as a middleware wrapping the endpoint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{endpoint::make_sync, middleware::Cors, Endpoint, EndpointExt, Route};
+    ///
+    /// let app = Route::new().at(
+    ///     "/",
+    ///     make_sync(|_| "hello")
+    ///         .named("handler")
+    ///         .with(Cors::new())
+    ///         .named("api_cors"),
+    /// );
+    /// assert_eq!(
+    ///     app.middleware_chains()[0].to_string(),
+    ///     "/: api_cors -> Cors -> handler"
+    /// );
+    /// ```
+    fn named(self, name: impl Into<Cow<'static, str>>) -> Named<Self::Endpoint>
+    where
+        Self: Sized,
+    {
+        Named::new(self.into_endpoint(), name.into())
+    }
 }
 
 impl<T: IntoEndpoint> EndpointExt for T {}
@@ -919,6 +1068,26 @@ mod test {
         assert_eq!(resp.headers().get("a"), None);
     }
 
+    #[tokio::test]
+    async fn test_with_when() {
+        let ep = make_sync(|_| ()).with_when(
+            |req| req.headers().contains_key("x-enable"),
+            SetHeader::new().appending("a", 1),
+        );
+
+        let resp = ep
+            .call(Request::builder().header("x-enable", "1").finish())
+            .await
+            .unwrap();
+        assert_eq!(
+            resp.headers().get("a"),
+            Some(&HeaderValue::from_static("1"))
+        );
+
+        let resp = ep.call(Request::default()).await.unwrap();
+        assert_eq!(resp.headers().get("a"), None);
+    }
+
     #[tokio::test]
     async fn test_into_endpoint() {
         struct MyEndpointFactory;
@@ -978,4 +1147,74 @@ mod test {
         resp.assert_status_is_ok();
         resp.assert_text("none").await;
     }
+
+    #[tokio::test]
+    async fn test_generic_handler() {
+        #[handler(internal)]
+        async fn describe<T: std::fmt::Display + Send + Sync + 'static>(data: Data<&T>) -> String {
+            format!("value: {}", data.0)
+        }
+
+        #[handler(internal)]
+        fn with_default<T: Default + std::fmt::Debug, const N: usize>() -> String {
+            format!("{:?} {N}", T::default())
+        }
+
+        let cli = TestClient::new(describe::<i32>::default().data(100));
+        let resp = cli.get("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("value: 100").await;
+
+        let cli = TestClient::new(describe::<String>::default().data("hello".to_string()));
+        let resp = cli.get("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("value: hello").await;
+
+        let cli = TestClient::new(with_default::<Vec<u8>, 3>::default());
+        let resp = cli.get("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("[] 3").await;
+
+        // the argument names are not mistaken for `impl Trait` arguments
+        #[handler(internal)]
+        fn with_arg<T: Default + std::fmt::Debug>(simpl: Data<&i32>) -> String {
+            format!("{:?} {}", T::default(), simpl.0)
+        }
+
+        let cli = TestClient::new(with_arg::<String>::default().data(1));
+        let resp = cli.get("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("\"\" 1").await;
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_blocking_handler() {
+        static RUNTIME_THREAD: std::sync::OnceLock<std::thread::ThreadId> =
+            std::sync::OnceLock::new();
+
+        #[handler(internal, blocking)]
+        fn index(body: String) -> Result<String, Error> {
+            assert_ne!(
+                Some(&std::thread::current().id()),
+                RUNTIME_THREAD.get(),
+                "should not run on the runtime thread"
+            );
+            if body.is_empty() {
+                return Err(Error::from_status(StatusCode::BAD_REQUEST));
+            }
+            Ok(body.to_uppercase())
+        }
+
+        RUNTIME_THREAD.set(std::thread::current().id()).unwrap();
+        let cli = TestClient::new(index);
+
+        let resp = cli.post("/").body("hello").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("HELLO").await;
+
+        cli.post("/")
+            .send()
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
 }
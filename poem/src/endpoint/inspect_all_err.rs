@@ -1,4 +1,4 @@
-use crate::{Endpoint, Error, Request, Result};
+use crate::{endpoint::MiddlewareChain, Endpoint, Error, Request, Result};
 
 /// Endpoint for the [`inspect_all_err`](super::EndpointExt::inspect_all_err)
 /// method.
@@ -30,4 +30,8 @@ where
             }
         }
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.inner.middleware_chains()
+    }
 }
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use crate::{Endpoint, Request, Result};
+use crate::{endpoint::MiddlewareChain, Endpoint, Request, Result};
 
 /// Endpoint for the
 /// [`inspect_err`](super::EndpointExt::inspect_err) method.
@@ -39,4 +39,8 @@ where
             Err(err) => Err(err),
         }
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.inner.middleware_chains()
+    }
 }
@@ -14,13 +14,18 @@ mod inspect_all_err;
 mod inspect_err;
 mod map;
 mod map_to_response;
+mod named;
 #[cfg(feature = "prometheus")]
 mod prometheus_exporter;
 #[cfg(feature = "static-files")]
 mod static_files;
+mod static_response;
 mod to_response;
 #[cfg(feature = "tower-compat")]
 mod tower_compat;
+#[cfg(feature = "static-files")]
+mod upload_files;
+mod with_when;
 
 pub use after::After;
 pub use and_then::AndThen;
@@ -37,10 +42,15 @@ pub use inspect_all_err::InspectAllError;
 pub use inspect_err::InspectError;
 pub use map::Map;
 pub use map_to_response::MapToResponse;
+pub use named::{MiddlewareChain, Named};
 #[cfg(feature = "prometheus")]
 pub use prometheus_exporter::PrometheusExporter;
 #[cfg(feature = "static-files")]
 pub use static_files::{StaticFileEndpoint, StaticFilesEndpoint};
+pub use static_response::{NotImplemented, ServiceUnavailable, StaticResponse};
 pub use to_response::ToResponse;
 #[cfg(feature = "tower-compat")]
 pub use tower_compat::TowerCompatExt;
+#[cfg(feature = "static-files")]
+pub use upload_files::FilesUploadEndpoint;
+pub use with_when::WithWhen;
@@ -0,0 +1,76 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{http::Method, Endpoint, Request, Result};
+
+/// The middlewares wrapping the endpoint of a route, from the outermost to the
+/// innermost, returned by [`Endpoint::middleware_chains`].
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct MiddlewareChain {
+    /// The method of the route, `None` means any method.
+    pub method: Option<Method>,
+    /// The path of the route.
+    pub path: String,
+    /// The names of the middlewares.
+    pub names: Vec<Cow<'static, str>>,
+}
+
+impl MiddlewareChain {
+    /// Prepends the middleware name to each chain.
+    pub fn wrap(name: impl Into<Cow<'static, str>>, mut chains: Vec<Self>) -> Vec<Self> {
+        let name = name.into();
+        for chain in &mut chains {
+            chain.names.insert(0, name.clone());
+        }
+        chains
+    }
+}
+
+impl Display for MiddlewareChain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(method) = &self.method {
+            write!(f, "{method} ")?;
+        }
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{path}:")?;
+        for (idx, name) in self.names.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" ->")?;
+            }
+            write!(f, " {name}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Endpoint for the [`named`](super::EndpointExt::named) method.
+pub struct Named<E> {
+    inner: E,
+    name: Cow<'static, str>,
+}
+
+impl<E> Named<E> {
+    #[inline]
+    pub(crate) fn new(inner: E, name: Cow<'static, str>) -> Named<E> {
+        Self { inner, name }
+    }
+}
+
+impl<E: Endpoint> Endpoint for Named<E> {
+    type Output = E::Output;
+
+    #[inline]
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        self.inner.call(req).await
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap(self.name.clone(), self.inner.middleware_chains())
+    }
+}
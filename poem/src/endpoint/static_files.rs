@@ -1,15 +1,18 @@
 use std::{
+    borrow::Cow,
     ffi::OsStr,
     fmt::Write,
     path::{Path, PathBuf},
+    time::UNIX_EPOCH,
 };
 
 use http::header::LOCATION;
+use serde::Serialize;
 
 use crate::{
     error::StaticFileError,
     http::{header, Method, StatusCode},
-    web::StaticFileRequest,
+    web::{Accept, Json, StaticFileRequest},
     Body, Endpoint, FromRequest, IntoResponse, Request, Response, Result,
 };
 
@@ -58,12 +61,64 @@ impl<'a> DirectoryTemplate<'a> {
     }
 }
 
+#[derive(Serialize)]
 struct FileRef {
-    url: String,
+    #[serde(rename = "name")]
     filename: String,
+    url: String,
+    size: u64,
+    /// Seconds since the Unix epoch.
+    mtime: Option<u64>,
     is_dir: bool,
 }
 
+/// Returns `true` if `application/json` is preferred over `text/html`.
+fn prefers_json(accept: &Accept) -> bool {
+    accept
+        .0
+        .iter()
+        .find_map(|mime| match (mime.type_(), mime.subtype()) {
+            (mime::APPLICATION, mime::JSON) => Some(true),
+            (mime::TEXT, mime::HTML) => Some(false),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Resolves the path of the request relative to the base directory, and
+/// ensures that it does not escape from it.
+pub(super) fn resolve_path<'a>(
+    base: &Path,
+    req: &'a Request,
+) -> Result<(Cow<'a, str>, PathBuf), StaticFileError> {
+    let path = req
+        .uri()
+        .path()
+        .trim_start_matches('/')
+        .trim_end_matches('/');
+
+    let path = percent_encoding::percent_decode_str(path)
+        .decode_utf8()
+        .map_err(|_| StaticFileError::InvalidPath)?;
+
+    let mut file_path = base.to_path_buf();
+    for p in Path::new(&*path) {
+        if p == OsStr::new(".") {
+            continue;
+        } else if p == OsStr::new("..") {
+            file_path.pop();
+        } else {
+            file_path.push(p);
+        }
+    }
+
+    if !file_path.starts_with(base) {
+        return Err(StaticFileError::Forbidden(file_path.display().to_string()));
+    }
+
+    Ok((path, file_path))
+}
+
 /// Static files handling service.
 ///
 /// # Errors
@@ -107,6 +162,11 @@ impl StaticFilesEndpoint {
 
     /// Show files listing for directories.
     ///
+    /// The listing is rendered as HTML, unless the `Accept` header of the
+    /// request prefers `application/json`, in which case it is a JSON array
+    /// of entries with the `name`, `url`, `size`, `mtime` (seconds since the
+    /// Unix epoch) and `is_dir` fields.
+    ///
     /// By default show files listing is disabled.
     #[must_use]
     pub fn show_files_listing(self) -> Self {
@@ -169,30 +229,7 @@ impl Endpoint for StaticFilesEndpoint {
             return Err(StaticFileError::MethodNotAllowed(req.method().clone()).into());
         }
 
-        let path = req
-            .uri()
-            .path()
-            .trim_start_matches('/')
-            .trim_end_matches('/');
-
-        let path = percent_encoding::percent_decode_str(path)
-            .decode_utf8()
-            .map_err(|_| StaticFileError::InvalidPath)?;
-
-        let mut file_path = self.path.clone();
-        for p in Path::new(&*path) {
-            if p == OsStr::new(".") {
-                continue;
-            } else if p == OsStr::new("..") {
-                file_path.pop();
-            } else {
-                file_path.push(p);
-            }
-        }
-
-        if !file_path.starts_with(&self.path) {
-            return Err(StaticFileError::Forbidden(file_path.display().to_string()).into());
-        }
+        let (path, file_path) = resolve_path(&self.path, &req)?;
 
         if !file_path.exists() {
             if self.fallback_to_index {
@@ -238,6 +275,7 @@ impl Endpoint for StaticFilesEndpoint {
 
             if self.show_files_listing {
                 let read_dir = file_path.read_dir().map_err(StaticFileError::Io)?;
+                let accept = Accept::from_request_without_body(&req).await?;
                 let mut template = DirectoryTemplate {
                     path: &path,
                     files: Vec::new(),
@@ -255,14 +293,29 @@ impl Endpoint for StaticFilesEndpoint {
                             filename.as_bytes(),
                             percent_encoding::NON_ALPHANUMERIC,
                         );
+                        let metadata = entry
+                            .path()
+                            .metadata()
+                            .or_else(|_| entry.metadata())
+                            .map_err(StaticFileError::Io)?;
                         template.files.push(FileRef {
-                            url: format!("{base_url}{filename_url}"),
                             filename: filename.to_string(),
-                            is_dir: entry.path().is_dir(),
+                            url: format!("{base_url}{filename_url}"),
+                            size: if metadata.is_dir() { 0 } else { metadata.len() },
+                            mtime: metadata
+                                .modified()
+                                .ok()
+                                .and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok())
+                                .map(|mtime| mtime.as_secs()),
+                            is_dir: metadata.is_dir(),
                         });
                     }
                 }
 
+                if prefers_json(&accept) {
+                    return Ok(Json(template.files).into_response());
+                }
+
                 let html = template.render();
                 Ok(Response::builder()
                     .header(header::CONTENT_TYPE, mime::TEXT_HTML_UTF_8.as_ref())
@@ -324,3 +377,48 @@ impl Endpoint for StaticFileEndpoint {
             .into_response())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::test::TestClient;
+
+    #[tokio::test]
+    async fn json_files_listing() {
+        let dir = std::env::temp_dir().join(format!("poem-files-listing-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("a.txt"), "hello").unwrap();
+
+        let cli = TestClient::new(StaticFilesEndpoint::new(&dir).show_files_listing());
+
+        let resp = cli
+            .get("/")
+            .header(header::ACCEPT, "text/html;q=0.9, application/json")
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_content_type("application/json; charset=utf-8");
+        let mut entries = resp.0.into_body().into_json::<Vec<Value>>().await.unwrap();
+        entries.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["name"], "a.txt");
+        assert_eq!(entries[0]["url"], "/a%2Etxt");
+        assert_eq!(entries[0]["size"], 5);
+        assert_eq!(entries[0]["is_dir"], false);
+        assert!(entries[0]["mtime"].as_u64().unwrap() > 0);
+        assert_eq!(entries[1]["name"], "sub");
+        assert_eq!(entries[1]["is_dir"], true);
+
+        let resp = cli
+            .get("/")
+            .header(header::ACCEPT, "text/html, application/json")
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_content_type("text/html; charset=utf-8");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,200 @@
+use bytes::Bytes;
+use http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode};
+
+use crate::{Endpoint, Request, Response, Result};
+
+/// An endpoint that always returns the same response.
+///
+/// It is useful for scaffolding routes, mocking upstream services in tests
+/// and as the fallback of proxies.
+///
+/// # Example
+///
+/// ```
+/// use poem::{endpoint::StaticResponse, http::StatusCode, test::TestClient, Route};
+///
+/// let app = Route::new()
+///     .at(
+///         "/hello",
+///         StaticResponse::ok()
+///             .content_type("text/plain; charset=utf-8")
+///             .body("hello"),
+///     )
+///     .at("/teapot", StaticResponse::new(StatusCode::IM_A_TEAPOT));
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli.get("/hello").send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_content_type("text/plain; charset=utf-8");
+/// resp.assert_text("hello").await;
+///
+/// cli.get("/teapot")
+///     .send()
+///     .await
+///     .assert_status(StatusCode::IM_A_TEAPOT);
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct StaticResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl StaticResponse {
+    /// Create a `StaticResponse` with the specified status code and an empty
+    /// body.
+    #[must_use]
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    /// Create a `StaticResponse` with the status code `200 OK`.
+    #[must_use]
+    pub fn ok() -> Self {
+        Self::new(StatusCode::OK)
+    }
+
+    /// Appends a header to the response.
+    #[must_use]
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName>,
+        V: TryInto<HeaderValue>,
+    {
+        if let (Ok(key), Ok(value)) = (key.try_into(), value.try_into()) {
+            self.headers.append(key, value);
+        }
+        self
+    }
+
+    /// Sets the `Content-Type` header of the response.
+    #[must_use]
+    pub fn content_type(mut self, content_type: impl AsRef<str>) -> Self {
+        if let Ok(value) = HeaderValue::from_str(content_type.as_ref()) {
+            self.headers.insert(header::CONTENT_TYPE, value);
+        }
+        self
+    }
+
+    /// Sets the body of the response.
+    #[must_use]
+    pub fn body(self, body: impl Into<Bytes>) -> Self {
+        Self {
+            body: body.into(),
+            ..self
+        }
+    }
+}
+
+impl Endpoint for StaticResponse {
+    type Output = Response;
+
+    async fn call(&self, _req: Request) -> Result<Self::Output> {
+        let mut resp = Response::builder()
+            .status(self.status)
+            .body(self.body.clone());
+        resp.headers_mut().extend(self.headers.clone());
+        Ok(resp)
+    }
+}
+
+/// An endpoint that always returns `503 Service Unavailable`.
+///
+/// # Example
+///
+/// ```
+/// use poem::{endpoint::ServiceUnavailable, http::StatusCode, test::TestClient, Route};
+///
+/// let app = Route::new().at("/upstream/*", ServiceUnavailable);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// TestClient::new(app)
+///     .get("/upstream/a")
+///     .send()
+///     .await
+///     .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ServiceUnavailable;
+
+impl Endpoint for ServiceUnavailable {
+    type Output = Response;
+
+    async fn call(&self, _req: Request) -> Result<Self::Output> {
+        Ok(StatusCode::SERVICE_UNAVAILABLE.into())
+    }
+}
+
+/// An endpoint that always returns `501 Not Implemented`.
+///
+/// # Example
+///
+/// ```
+/// use poem::{endpoint::NotImplemented, get, http::StatusCode, test::TestClient, Route};
+///
+/// let app = Route::new().at("/users", get(NotImplemented));
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// TestClient::new(app)
+///     .get("/users")
+///     .send()
+///     .await
+///     .assert_status(StatusCode::NOT_IMPLEMENTED);
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NotImplemented;
+
+impl Endpoint for NotImplemented {
+    type Output = Response;
+
+    async fn call(&self, _req: Request) -> Result<Self::Output> {
+        Ok(StatusCode::NOT_IMPLEMENTED.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestClient;
+
+    #[tokio::test]
+    async fn static_response() {
+        let cli = TestClient::new(
+            StaticResponse::new(StatusCode::ACCEPTED)
+                .header("x-a", "1")
+                .header("x-a", "2")
+                .content_type("application/json")
+                .body(r#"{"a":1}"#),
+        );
+
+        for _ in 0..2 {
+            let resp = cli.post("/").send().await;
+            resp.assert_status(StatusCode::ACCEPTED);
+            resp.assert_header_all("x-a", ["1", "2"]);
+            resp.assert_content_type("application/json");
+            resp.assert_text(r#"{"a":1}"#).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn status_endpoints() {
+        TestClient::new(ServiceUnavailable)
+            .get("/")
+            .send()
+            .await
+            .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+        TestClient::new(NotImplemented)
+            .get("/")
+            .send()
+            .await
+            .assert_status(StatusCode::NOT_IMPLEMENTED);
+    }
+}
@@ -0,0 +1,398 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use futures_util::TryStreamExt;
+use tokio::io::AsyncWriteExt;
+
+use super::static_files::resolve_path;
+use crate::{
+    error::StaticFileError,
+    http::{header, Method, StatusCode},
+    Endpoint, Request, Response, Result,
+};
+
+/// Writable files service, which stores the body of `PUT` requests as files
+/// and removes the files on `DELETE` requests.
+///
+/// - `PUT` creates or replaces the file at the request path, creating the
+///   parent directories as needed, and responds with `201 Created` or
+///   `204 No Content`. The file is written to a temporary file first, so
+///   readers never see a partially written file.
+/// - `DELETE` removes the file at the request path and responds with
+///   `204 No Content`.
+///
+/// Requests can not access files outside of the base directory.
+///
+/// # Security
+///
+/// This service modifies the filesystem, so all requests are rejected with
+/// `401 Unauthorized` unless a token is required with
+/// [`FilesUploadEndpoint::bearer_token`]. If the service is protected by an
+/// authentication middleware instead, the requests must be allowed explicitly
+/// with [`FilesUploadEndpoint::allow_unauthenticated`].
+///
+/// # Errors
+///
+/// - [`StaticFileError`]
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     endpoint::{FilesUploadEndpoint, StaticFilesEndpoint},
+///     Route,
+/// };
+///
+/// let app = Route::new()
+///     .nest("/artifacts", StaticFilesEndpoint::new("/var/artifacts"))
+///     .nest(
+///         "/upload",
+///         FilesUploadEndpoint::new("/var/artifacts")
+///             .bearer_token("secret")
+///             .max_size(100 * 1024 * 1024),
+///     );
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "static-files")))]
+pub struct FilesUploadEndpoint {
+    path: PathBuf,
+    bearer_token: Option<String>,
+    allow_unauthenticated: bool,
+    max_size: u64,
+    allow_delete: bool,
+}
+
+impl FilesUploadEndpoint {
+    /// Create a writable files service for a specified base directory.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            bearer_token: None,
+            allow_unauthenticated: false,
+            max_size: 10 * 1024 * 1024,
+            allow_delete: true,
+        }
+    }
+
+    /// Requires requests to have an `Authorization: Bearer <token>` header
+    /// with the specified token.
+    #[must_use]
+    pub fn bearer_token(self, token: impl Into<String>) -> Self {
+        Self {
+            bearer_token: Some(token.into()),
+            ..self
+        }
+    }
+
+    /// Specifies whether the requests are allowed without a token when
+    /// [`FilesUploadEndpoint::bearer_token`] is not set, which is only safe if
+    /// the service is protected by an authentication middleware. (default to
+    /// `false`)
+    #[must_use]
+    pub fn allow_unauthenticated(self, allow_unauthenticated: bool) -> Self {
+        Self {
+            allow_unauthenticated,
+            ..self
+        }
+    }
+
+    /// Specify the maximum size of the uploaded files. (default to `10MB`)
+    #[must_use]
+    pub fn max_size(self, max_size: u64) -> Self {
+        Self { max_size, ..self }
+    }
+
+    /// Specifies whether `DELETE` requests are allowed. (default to `true`)
+    #[must_use]
+    pub fn allow_delete(self, allow_delete: bool) -> Self {
+        Self {
+            allow_delete,
+            ..self
+        }
+    }
+
+    fn authorize(&self, req: &Request) -> Result<(), StaticFileError> {
+        let Some(expected) = &self.bearer_token else {
+            if self.allow_unauthenticated {
+                return Ok(());
+            }
+            return Err(StaticFileError::Unauthorized);
+        };
+        let token = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(StaticFileError::Unauthorized)?;
+        if !constant_time_eq(token.trim().as_bytes(), expected.as_bytes()) {
+            return Err(StaticFileError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Ensures that the directory, after resolving symbolic links, is inside
+    /// the base directory.
+    ///
+    /// The directory may not exist yet, in this case its deepest existing
+    /// ancestor is checked.
+    async fn check_containment(&self, dir: &Path) -> Result<(), StaticFileError> {
+        let base = tokio::fs::canonicalize(&self.path).await?;
+        let mut existing = dir;
+        let resolved = loop {
+            match tokio::fs::canonicalize(existing).await {
+                Ok(path) => break path,
+                Err(err) if err.kind() == ErrorKind::NotFound => match existing.parent() {
+                    Some(parent) => existing = parent,
+                    None => return Err(err.into()),
+                },
+                Err(err) => return Err(err.into()),
+            }
+        };
+        if !resolved.starts_with(&base) {
+            return Err(StaticFileError::Forbidden(resolved.display().to_string()));
+        }
+        Ok(())
+    }
+
+    async fn put(&self, mut req: Request, file_path: PathBuf) -> Result<Response> {
+        if req
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+            .is_some_and(|len| len > self.max_size)
+        {
+            return Err(StaticFileError::PayloadTooLarge.into());
+        }
+
+        if file_path.is_dir() {
+            return Err(StaticFileError::Forbidden(file_path.display().to_string()).into());
+        }
+        let (Some(dir), Some(file_name)) = (file_path.parent(), file_path.file_name()) else {
+            return Err(StaticFileError::InvalidPath.into());
+        };
+        self.check_containment(dir).await?;
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(StaticFileError::Io)?;
+
+        static TEMP_ID: AtomicU64 = AtomicU64::new(0);
+        let temp_path = dir.join(format!(
+            ".{}.{}-{}.upload",
+            file_name.to_string_lossy(),
+            std::process::id(),
+            TEMP_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let res = async {
+            let mut file = tokio::fs::File::create(&temp_path).await?;
+            let mut stream = req.take_body().into_bytes_stream();
+            let mut size = 0;
+            while let Some(data) = stream.try_next().await? {
+                size += data.len() as u64;
+                if size > self.max_size {
+                    return Err(StaticFileError::PayloadTooLarge);
+                }
+                file.write_all(&data).await?;
+            }
+            file.sync_all().await?;
+            Ok(())
+        }
+        .await;
+        if let Err(err) = res {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(err.into());
+        }
+
+        let exists = file_path.exists();
+        tokio::fs::rename(&temp_path, &file_path)
+            .await
+            .map_err(StaticFileError::Io)?;
+
+        Ok(Response::builder()
+            .status(if exists {
+                StatusCode::NO_CONTENT
+            } else {
+                StatusCode::CREATED
+            })
+            .finish())
+    }
+
+    async fn delete(&self, file_path: PathBuf) -> Result<Response> {
+        if !file_path.is_file() {
+            if file_path.exists() {
+                return Err(StaticFileError::Forbidden(file_path.display().to_string()).into());
+            }
+            return Err(StaticFileError::NotFound.into());
+        }
+        if let Some(dir) = file_path.parent() {
+            self.check_containment(dir).await?;
+        }
+        tokio::fs::remove_file(&file_path)
+            .await
+            .map_err(StaticFileError::Io)?;
+        Ok(Response::builder().status(StatusCode::NO_CONTENT).finish())
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+impl Endpoint for FilesUploadEndpoint {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let method = req.method().clone();
+        if method != Method::PUT && !(method == Method::DELETE && self.allow_delete) {
+            return Err(StaticFileError::MethodNotAllowed(method).into());
+        }
+        self.authorize(&req)?;
+
+        let (path, file_path) = resolve_path(&self.path, &req)?;
+        if path.is_empty() || file_path == self.path {
+            return Err(StaticFileError::InvalidPath.into());
+        }
+
+        if method == Method::PUT {
+            self.put(req, file_path).await
+        } else {
+            self.delete(file_path).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::TestClient, Route};
+
+    #[tokio::test]
+    async fn upload_files() {
+        let dir = std::env::temp_dir().join(format!("poem-upload-files-{}", std::process::id()));
+        let root = dir.join("root");
+        std::fs::create_dir_all(&root).unwrap();
+        let app = Route::new().nest(
+            "/files",
+            FilesUploadEndpoint::new(&root)
+                .bearer_token("secret")
+                .max_size(8),
+        );
+        let cli = TestClient::new(app);
+        let put = |path: &str, token: &str, body: &'static str| {
+            cli.put(path)
+                .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                .body(body)
+                .send()
+        };
+
+        put("/files/a/b.txt", "wrong", "hello")
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+        cli.put("/files/a/b.txt")
+            .body("hello")
+            .send()
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+        assert!(!root.join("a").exists());
+
+        put("/files/a/b.txt", "secret", "hello")
+            .await
+            .assert_status(StatusCode::CREATED);
+        assert_eq!(
+            std::fs::read_to_string(root.join("a/b.txt")).unwrap(),
+            "hello"
+        );
+
+        put("/files/a/b.txt", "secret", "world")
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        assert_eq!(
+            std::fs::read_to_string(root.join("a/b.txt")).unwrap(),
+            "world"
+        );
+
+        put("/files/a/c.txt", "secret", "too large!")
+            .await
+            .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(std::fs::read_dir(root.join("a")).unwrap().count(), 1);
+
+        put("/files/a/../../escape.txt", "secret", "x")
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        put("/files/a", "secret", "x")
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        put("/files/", "secret", "x")
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        cli.get("/files/a/b.txt")
+            .send()
+            .await
+            .assert_status(StatusCode::METHOD_NOT_ALLOWED);
+
+        cli.delete("/files/a/b.txt")
+            .header(header::AUTHORIZATION, "Bearer secret")
+            .send()
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        assert!(!root.join("a/b.txt").exists());
+        cli.delete("/files/a/b.txt")
+            .header(header::AUTHORIZATION, "Bearer secret")
+            .send()
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn unauthenticated() {
+        let dir = std::env::temp_dir().join(format!("poem-upload-unauth-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let cli = TestClient::new(FilesUploadEndpoint::new(&dir));
+        cli.put("/a.txt")
+            .body("hello")
+            .send()
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+        assert!(!dir.join("a.txt").exists());
+
+        let cli = TestClient::new(FilesUploadEndpoint::new(&dir).allow_unauthenticated(true));
+        cli.put("/a.txt")
+            .body("hello")
+            .send()
+            .await
+            .assert_status(StatusCode::CREATED);
+        assert!(dir.join("a.txt").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn symlink_escape() {
+        let dir = std::env::temp_dir().join(format!("poem-upload-symlink-{}", std::process::id()));
+        let root = dir.join("root");
+        let outside = dir.join("outside");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+
+        let cli = TestClient::new(FilesUploadEndpoint::new(&root).bearer_token("secret"));
+        cli.put("/link/new/a.txt")
+            .header(header::AUTHORIZATION, "Bearer secret")
+            .body("hello")
+            .send()
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        // nothing is created outside of the base directory
+        assert!(!outside.join("new").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use crate::{endpoint::MiddlewareChain, Endpoint, IntoResponse, Request, Response, Result};
+
+/// Endpoint for the [`with_when`](super::EndpointExt::with_when) method.
+pub struct WithWhen<E, P, M> {
+    inner: Arc<E>,
+    wrapped: M,
+    predicate: P,
+}
+
+impl<E, P, M> WithWhen<E, P, M> {
+    #[inline]
+    pub(crate) fn new(inner: Arc<E>, wrapped: M, predicate: P) -> WithWhen<E, P, M> {
+        Self {
+            inner,
+            wrapped,
+            predicate,
+        }
+    }
+}
+
+impl<E, P, M> Endpoint for WithWhen<E, P, M>
+where
+    E: Endpoint,
+    P: Fn(&Request) -> bool + Send + Sync,
+    M: Endpoint,
+{
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        if (self.predicate)(&req) {
+            self.wrapped
+                .call(req)
+                .await
+                .map(IntoResponse::into_response)
+        } else {
+            self.inner.call(req).await.map(IntoResponse::into_response)
+        }
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.wrapped.middleware_chains()
+    }
+}
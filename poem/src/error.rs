@@ -8,7 +8,7 @@ use std::{
 };
 
 use headers::{ContentRange, HeaderMapExt};
-use http::{Extensions, Method};
+use http::{header, Extensions, HeaderValue, Method};
 
 use crate::{http::StatusCode, IntoResponse, Response};
 
@@ -467,6 +467,11 @@ impl Error {
         self.extensions.insert(data);
     }
 
+    #[inline]
+    pub(crate) fn extensions_mut(&mut self) -> &mut Extensions {
+        &mut self.extensions
+    }
+
     /// Get a reference from extensions
     pub fn data<T: Send + Sync + 'static>(&self) -> Option<&T> {
         self.extensions.get()
@@ -641,6 +646,9 @@ define_simple_errors!(
 
     /// Error occurred in the router.
     (MethodNotAllowedError, METHOD_NOT_ALLOWED, "method not allowed");
+
+    /// Error occurred in the `CircuitBreaker` middleware when the circuit is open.
+    (CircuitOpenError, SERVICE_UNAVAILABLE, "circuit breaker is open");
 );
 
 /// A possible error value when reading the body.
@@ -710,6 +718,30 @@ impl ResponseError for GetDataError {
     }
 }
 
+/// A possible error value when loading a [`Config`](crate::web::Config).
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// Failed to read the configuration file.
+    #[error("failed to read the configuration file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Failed to parse the configuration.
+    #[error("failed to parse the configuration: {0}")]
+    Parse(String),
+}
+
+/// A possible error value when extracts a value from the
+/// [`RequestLocal`](crate::web::RequestLocal) store fails.
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+#[error("request local value of type `{0}` was not found.")]
+pub struct GetLocalError(pub &'static str);
+
+impl ResponseError for GetLocalError {
+    fn status(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
 /// A possible error value when parsing form.
 #[derive(Debug, thiserror::Error)]
 pub enum ParseFormError {
@@ -721,6 +753,10 @@ pub enum ParseFormError {
     #[error("expect content type `application/x-www-form-urlencoded`")]
     ContentTypeRequired,
 
+    /// Unsupported charset.
+    #[error("unsupported charset `{0}`")]
+    UnsupportedCharset(String),
+
     /// Url decode error.
     #[error("url decode: {0}")]
     UrlDecode(#[from] serde_urlencoded::de::Error),
@@ -731,6 +767,7 @@ impl ResponseError for ParseFormError {
         match self {
             ParseFormError::InvalidContentType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             ParseFormError::ContentTypeRequired => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseFormError::UnsupportedCharset(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             ParseFormError::UrlDecode(_) => StatusCode::BAD_REQUEST,
         }
     }
@@ -747,6 +784,10 @@ pub enum ParseJsonError {
     #[error("expect content type `application/json`")]
     ContentTypeRequired,
 
+    /// Unsupported charset.
+    #[error("unsupported charset `{0}`")]
+    UnsupportedCharset(String),
+
     /// Url decode error.
     #[error("parse error: {0}")]
     Parse(#[from] serde_json::Error),
@@ -757,6 +798,7 @@ impl ResponseError for ParseJsonError {
         match self {
             ParseJsonError::InvalidContentType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             ParseJsonError::ContentTypeRequired => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseJsonError::UnsupportedCharset(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             ParseJsonError::Parse(_) => StatusCode::BAD_REQUEST,
         }
     }
@@ -818,6 +860,115 @@ impl ResponseError for ParseYamlError {
     }
 }
 
+/// A possible error value when parsing MessagePack.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, thiserror::Error)]
+pub enum ParseMsgPackError {
+    /// Invalid content type.
+    #[error("invalid content type `{0}`, expect: `application/msgpack`")]
+    InvalidContentType(String),
+
+    /// `Content-Type` header is required.
+    #[error("expect content type `application/msgpack`")]
+    ContentTypeRequired,
+
+    /// MessagePack decode error.
+    #[error("parse error: {0}")]
+    Parse(#[from] rmp_serde::decode::Error),
+}
+
+#[cfg(feature = "msgpack")]
+impl ResponseError for ParseMsgPackError {
+    fn status(&self) -> StatusCode {
+        match self {
+            ParseMsgPackError::InvalidContentType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseMsgPackError::ContentTypeRequired => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseMsgPackError::Parse(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// A possible error value when parsing CBOR.
+#[cfg(feature = "cbor")]
+#[derive(Debug, thiserror::Error)]
+pub enum ParseCborError {
+    /// Invalid content type.
+    #[error("invalid content type `{0}`, expect: `application/cbor`")]
+    InvalidContentType(String),
+
+    /// `Content-Type` header is required.
+    #[error("expect content type `application/cbor`")]
+    ContentTypeRequired,
+
+    /// CBOR decode error.
+    #[error("parse error: {0}")]
+    Parse(#[from] ciborium::de::Error<std::io::Error>),
+}
+
+#[cfg(feature = "cbor")]
+impl ResponseError for ParseCborError {
+    fn status(&self) -> StatusCode {
+        match self {
+            ParseCborError::InvalidContentType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseCborError::ContentTypeRequired => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseCborError::Parse(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// A possible error value when parsing Protocol Buffers.
+#[cfg(feature = "protobuf")]
+#[derive(Debug, thiserror::Error)]
+pub enum ParseProtobufError {
+    /// Invalid content type.
+    #[error("invalid content type `{0}`, expect: `application/protobuf`")]
+    InvalidContentType(String),
+
+    /// `Content-Type` header is required.
+    #[error("expect content type `application/protobuf`")]
+    ContentTypeRequired,
+
+    /// Protocol Buffers decode error.
+    #[error("parse error: {0}")]
+    Parse(#[from] prost::DecodeError),
+}
+
+#[cfg(feature = "protobuf")]
+impl ResponseError for ParseProtobufError {
+    fn status(&self) -> StatusCode {
+        match self {
+            ParseProtobufError::InvalidContentType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseProtobufError::ContentTypeRequired => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseProtobufError::Parse(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// A possible error value occurred in the `VerifyHmac` middleware.
+#[cfg(feature = "hmac")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hmac")))]
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum VerifyHmacError {
+    /// The signature header is missing.
+    #[error("missing signature")]
+    MissingSignature,
+
+    /// There is no key for the request.
+    #[error("unknown signing key")]
+    UnknownKey,
+
+    /// The signature is malformed or does not match the body.
+    #[error("invalid signature")]
+    InvalidSignature,
+}
+
+#[cfg(feature = "hmac")]
+impl ResponseError for VerifyHmacError {
+    fn status(&self) -> StatusCode {
+        StatusCode::UNAUTHORIZED
+    }
+}
+
 /// A possible error value when parsing query.
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
@@ -829,6 +980,25 @@ impl ResponseError for ParseQueryError {
     }
 }
 
+/// A possible error value when parsing the pagination parameters.
+#[derive(Debug, thiserror::Error)]
+pub enum ParsePaginationError {
+    /// The parameter is not a positive integer.
+    #[error("invalid `{name}` parameter `{value}`, expect a positive integer")]
+    InvalidNumber {
+        /// The name of the parameter.
+        name: &'static str,
+        /// The value of the parameter.
+        value: String,
+    },
+}
+
+impl ResponseError for ParsePaginationError {
+    fn status(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+}
+
 /// A possible error value when parsing multipart.
 #[cfg(feature = "multipart")]
 #[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
@@ -853,6 +1023,27 @@ pub enum ParseMultipartError {
     /// Io error
     #[error("io: {0}")]
     Io(#[from] std::io::Error),
+
+    /// Payload too large
+    #[error("payload too large")]
+    PayloadTooLarge,
+
+    /// A required field is missing.
+    #[error("field `{0}` is required")]
+    MissingField(String),
+
+    /// A field which can only appear once is repeated.
+    #[error("field `{0}` is repeated")]
+    RepeatedField(String),
+
+    /// The value of a field is invalid.
+    #[error("failed to parse field `{name}`: {reason}")]
+    InvalidField {
+        /// The name of the field.
+        name: String,
+        /// The reason of the error.
+        reason: String,
+    },
 }
 
 #[cfg(feature = "multipart")]
@@ -861,9 +1052,16 @@ impl ResponseError for ParseMultipartError {
         match self {
             ParseMultipartError::InvalidContentType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             ParseMultipartError::ContentTypeRequired => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseMultipartError::Multipart(
+                multer::Error::FieldSizeExceeded { .. } | multer::Error::StreamSizeExceeded { .. },
+            ) => StatusCode::PAYLOAD_TOO_LARGE,
             ParseMultipartError::Multipart(_) => StatusCode::BAD_REQUEST,
             ParseMultipartError::Utf8(_) => StatusCode::BAD_REQUEST,
             ParseMultipartError::Io(_) => StatusCode::BAD_REQUEST,
+            ParseMultipartError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            ParseMultipartError::MissingField(_) => StatusCode::BAD_REQUEST,
+            ParseMultipartError::RepeatedField(_) => StatusCode::BAD_REQUEST,
+            ParseMultipartError::InvalidField { .. } => StatusCode::BAD_REQUEST,
         }
     }
 }
@@ -886,6 +1084,32 @@ impl ResponseError for ParseTypedHeaderError {
     }
 }
 
+/// A possible error value occurred in the `Subdomain` extractor.
+#[derive(Debug, thiserror::Error, Copy, Clone, Eq, PartialEq)]
+pub enum SubdomainError {
+    /// The `BaseDomain` has not been added to the endpoint data.
+    #[error("base domain is not configured")]
+    BaseDomainNotConfigured,
+
+    /// Missing `Host` header
+    #[error("missing `Host` header")]
+    MissingHost,
+
+    /// The host is not a subdomain of the base domain.
+    #[error("host is not a subdomain of the base domain")]
+    NoSubdomain,
+}
+
+impl ResponseError for SubdomainError {
+    fn status(&self) -> StatusCode {
+        match self {
+            SubdomainError::BaseDomainNotConfigured => StatusCode::INTERNAL_SERVER_ERROR,
+            SubdomainError::MissingHost => StatusCode::BAD_REQUEST,
+            SubdomainError::NoSubdomain => StatusCode::NOT_FOUND,
+        }
+    }
+}
+
 /// A possible error value when handling websocket.
 #[cfg(feature = "websocket")]
 #[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
@@ -961,6 +1185,14 @@ pub enum StaticFileError {
         size: u64,
     },
 
+    /// Missing or invalid credentials
+    #[error("unauthorized")]
+    Unauthorized,
+
+    /// Payload too large
+    #[error("payload too large")]
+    PayloadTooLarge,
+
     /// Io error
     #[error("io: {0}")]
     Io(#[from] std::io::Error),
@@ -975,6 +1207,8 @@ impl ResponseError for StaticFileError {
             StaticFileError::NotFound => StatusCode::NOT_FOUND,
             StaticFileError::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
             StaticFileError::RangeNotSatisfiable { .. } => StatusCode::RANGE_NOT_SATISFIABLE,
+            StaticFileError::Unauthorized => StatusCode::UNAUTHORIZED,
+            StaticFileError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
             StaticFileError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -982,9 +1216,16 @@ impl ResponseError for StaticFileError {
     fn as_response(&self) -> Response {
         let mut resp = self.to_string().into_response();
         resp.set_status(self.status());
-        if let StaticFileError::RangeNotSatisfiable { size } = self {
-            resp.headers_mut()
-                .typed_insert(ContentRange::unsatisfied_bytes(*size));
+        match self {
+            StaticFileError::RangeNotSatisfiable { size } => {
+                resp.headers_mut()
+                    .typed_insert(ContentRange::unsatisfied_bytes(*size));
+            }
+            StaticFileError::Unauthorized => {
+                resp.headers_mut()
+                    .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            }
+            _ => {}
         }
         resp
     }
@@ -1011,6 +1252,33 @@ impl ResponseError for SizedLimitError {
     }
 }
 
+/// A possible error value occurred in the `RequestLimits` middleware.
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum RequestLimitError {
+    /// The request URI is too long.
+    #[error("uri too long")]
+    UriTooLong,
+
+    /// The request has too many headers.
+    #[error("too many headers")]
+    TooManyHeaders,
+
+    /// The request headers are too large.
+    #[error("headers too large")]
+    HeadersTooLarge,
+}
+
+impl ResponseError for RequestLimitError {
+    fn status(&self) -> StatusCode {
+        match self {
+            RequestLimitError::UriTooLong => StatusCode::URI_TOO_LONG,
+            RequestLimitError::TooManyHeaders | RequestLimitError::HeadersTooLarge => {
+                StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE
+            }
+        }
+    }
+}
+
 /// A possible error value occurred when adding a route.
 #[derive(Debug, thiserror::Error, Eq, PartialEq)]
 pub enum RouteError {
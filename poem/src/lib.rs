@@ -256,6 +256,15 @@
 //! | embed  | Integrate with [`rust-embed`](https://crates.io/crates/rust-embed) crate. |
 //! | xml | Integrate with [`quick-xml`](https://crates.io/crates/quick-xml) crate. |
 //! | yaml | Integrate with [`serde-yaml`](https://crates.io/crates/serde-yaml) crate.                   |
+//! | charset | Support for decoding the `JSON` and `Form` bodies in non-UTF-8 charsets with [`encoding_rs`](https://crates.io/crates/encoding_rs) |
+//! | csv | Support for streaming `CSV` responses with [`csv`](https://crates.io/crates/csv) |
+//! | msgpack | Integrate with [`rmp-serde`](https://crates.io/crates/rmp-serde) crate. |
+//! | cbor | Integrate with [`ciborium`](https://crates.io/crates/ciborium) crate. |
+//! | protobuf | Integrate with [`prost`](https://crates.io/crates/prost) crate. |
+//! | hmac | Support for verifying the HMAC signatures of request bodies with the `VerifyHmac` middleware |
+//! | toml | Support for loading the `Config` from `TOML` files with [`toml`](https://crates.io/crates/toml) |
+//! | quic | Experimental support for HTTP/3 over QUIC with [`quinn`](https://crates.io/crates/quinn) and [`h3`](https://crates.io/crates/h3) |
+//! | systemd | Support for the systemd socket activation with `SystemdListener` |
 
 #![doc(html_favicon_url = "https://raw.githubusercontent.com/poem-web/poem/master/favicon.ico")]
 #![doc(html_logo_url = "https://raw.githubusercontent.com/poem-web/poem/master/logo.png")]
@@ -287,6 +296,7 @@ pub use http;
 
 mod addr;
 mod body;
+mod buffer_pool;
 mod request;
 mod response;
 mod route;
@@ -295,16 +305,35 @@ mod server;
 
 pub use addr::Addr;
 pub use body::Body;
+pub use buffer_pool::{BufferPool, BufferPoolMetrics, PooledBuffer};
 pub use endpoint::{Endpoint, EndpointExt, IntoEndpoint};
 pub use error::{Error, Result};
 pub use middleware::Middleware;
-pub use poem_derive::handler;
+#[cfg(feature = "multipart")]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub use poem_derive::FromMultipart;
+pub use poem_derive::{handler, FromRequest, IntoResponse};
 pub use request::{OnUpgrade, Request, RequestBuilder, RequestParts, Upgraded};
 pub use response::{Response, ResponseBuilder, ResponseParts};
 pub use route::{
-    connect, delete, get, head, options, patch, post, put, trace, PathPattern, Route, RouteDomain,
-    RouteMethod, RouteScheme,
+    connect, delete, get, head, options, patch, post, put, trace, DynamicRoute, DynamicRouteHandle,
+    PathPattern, Route, RouteDomain, RouteMethod, RouteScheme,
 };
 #[cfg(feature = "server")]
-pub use server::Server;
+pub use server::{ConnectionGauge, Server};
 pub use web::{FromRequest, IntoResponse, RequestBody};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use tokio;
+
+    #[allow(clippy::result_large_err)]
+    pub fn apply_response_data(
+        req: &crate::Request,
+        res: crate::Result<crate::Response>,
+    ) -> crate::Result<crate::Response> {
+        req.state().response_data.apply(res)
+    }
+
+    pub use crate::web::content_type::{form_body_to_utf8, text_body_to_utf8};
+}
@@ -1,12 +1,16 @@
 use std::{
     fmt::{self, Debug, Formatter},
     path::PathBuf,
+    sync::Arc,
 };
 
 use crate::listener::acme::{
-    builder::AutoCertBuilder, endpoint::Http01Endpoint, ChallengeType, Http01TokensMap,
+    builder::AutoCertBuilder, endpoint::Http01Endpoint, AutoCertEvent, ChallengeType,
+    Http01TokensMap, ResolveServerCert,
 };
 
+pub(crate) type EventHandler = Arc<dyn Fn(&AutoCertEvent) + Send + Sync>;
+
 /// ACME configuration
 pub struct AutoCert {
     pub(crate) directory_url: String,
@@ -17,6 +21,8 @@ pub struct AutoCert {
     pub(crate) cache_path: Option<PathBuf>,
     pub(crate) cache_cert: Option<Vec<u8>>,
     pub(crate) cache_key: Option<Vec<u8>>,
+    pub(crate) cert_resolver: Arc<ResolveServerCert>,
+    pub(crate) event_handler: Option<EventHandler>,
 }
 
 impl AutoCert {
@@ -41,6 +47,30 @@ impl AutoCert {
             panic!("current challenge type is not `HTTP-01`");
         }
     }
+
+    /// Returns the cert resolver that holds the certificate obtained by this
+    /// configuration.
+    ///
+    /// It can be kept after the configuration is passed to
+    /// [`Listener::acme`](crate::listener::Listener::acme) to query the
+    /// expiration time of the current certificate.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use poem::listener::{acme::AutoCert, Listener, TcpListener};
+    ///
+    /// let auto_cert = AutoCert::builder().domain("poem.rs").build().unwrap();
+    /// let cert_resolver = auto_cert.cert_resolver();
+    /// let listener = TcpListener::bind("0.0.0.0:443").acme(auto_cert);
+    ///
+    /// if let Some(secs) = cert_resolver.seconds_until_expiry() {
+    ///     println!("the certificate expires in {secs} seconds");
+    /// }
+    /// ```
+    pub fn cert_resolver(&self) -> Arc<ResolveServerCert> {
+        self.cert_resolver.clone()
+    }
 }
 
 impl Debug for AutoCert {
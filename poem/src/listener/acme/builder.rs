@@ -2,9 +2,12 @@ use std::{
     collections::HashSet,
     io::{Error as IoError, ErrorKind, Result as IoResult},
     path::PathBuf,
+    sync::Arc,
 };
 
-use crate::listener::acme::{AutoCert, ChallengeType, LETS_ENCRYPT_PRODUCTION};
+use crate::listener::acme::{
+    auto_cert::EventHandler, AutoCert, AutoCertEvent, ChallengeType, LETS_ENCRYPT_PRODUCTION,
+};
 
 /// ACME configuration builder
 pub struct AutoCertBuilder {
@@ -13,6 +16,7 @@ pub struct AutoCertBuilder {
     contacts: HashSet<String>,
     challenge_type: ChallengeType,
     cache_path: Option<PathBuf>,
+    event_handler: Option<EventHandler>,
 }
 
 impl AutoCertBuilder {
@@ -23,6 +27,7 @@ impl AutoCertBuilder {
             contacts: Default::default(),
             challenge_type: ChallengeType::TlsAlpn01,
             cache_path: None,
+            event_handler: None,
         }
     }
 
@@ -75,6 +80,35 @@ impl AutoCertBuilder {
         }
     }
 
+    /// Sets a handler that is called on the lifecycle events of the
+    /// certificate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::listener::acme::{AutoCert, AutoCertEvent};
+    ///
+    /// let auto_cert = AutoCert::builder()
+    ///     .domain("poem.rs")
+    ///     .on_event(|event| {
+    ///         if let AutoCertEvent::RenewalFailed { error, .. } = event {
+    ///             eprintln!("failed to renew the certificate: {error}");
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    pub fn on_event<F>(self, f: F) -> Self
+    where
+        F: Fn(&AutoCertEvent) + Send + Sync + 'static,
+    {
+        Self {
+            event_handler: Some(Arc::new(f)),
+            ..self
+        }
+    }
+
     /// Consumes this builder and returns a [`AutoCert`] object.
     pub fn build(self) -> IoResult<AutoCert> {
         let directory_url = self.directory_url.parse().map_err(|err| {
@@ -116,6 +150,8 @@ impl AutoCertBuilder {
             cache_path: self.cache_path,
             cache_key,
             cache_cert,
+            cert_resolver: Default::default(),
+            event_handler: self.event_handler,
         })
     }
 }
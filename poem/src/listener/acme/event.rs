@@ -0,0 +1,34 @@
+use std::{io::Error as IoError, time::SystemTime};
+
+/// A lifecycle event emitted by [`AutoCert`](super::AutoCert) while obtaining
+/// or renewing the certificate.
+///
+/// Register a handler with
+/// [`AutoCertBuilder::on_event`](super::AutoCertBuilder::on_event).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AutoCertEvent {
+    /// A new order has been created for the domains.
+    OrderCreated {
+        /// The domains of the order.
+        domains: Vec<String>,
+    },
+    /// The challenge for a domain has been validated.
+    ChallengeValidated {
+        /// The validated domain.
+        domain: String,
+    },
+    /// A new certificate has been issued and is being used.
+    CertIssued {
+        /// The expiration time of the new certificate.
+        expires_at: Option<SystemTime>,
+    },
+    /// Failed to issue a new certificate, it will be retried later.
+    RenewalFailed {
+        /// The error that caused the failure.
+        error: IoError,
+        /// The expiration time of the certificate currently in use, `None` if
+        /// there is no certificate.
+        expires_at: Option<SystemTime>,
+    },
+}
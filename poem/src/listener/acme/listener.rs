@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     io::{Error as IoError, ErrorKind, Result as IoResult},
     sync::{Arc, Weak},
     time::{Duration, UNIX_EPOCH},
@@ -25,10 +26,10 @@ use crate::{
         acme::{
             client::AcmeClient,
             jose,
-            resolver::{ResolveServerCert, ACME_TLS_ALPN_NAME},
-            AutoCert, ChallengeType, Http01TokensMap,
+            resolver::{expires_at, ResolveServerCert, ACME_TLS_ALPN_NAME},
+            AutoCert, AutoCertEvent, ChallengeType, Http01TokensMap,
         },
-        Acceptor, HandshakeStream, Listener,
+        Acceptor, AlpnProtocolSlot, HandshakeStream, Listener, PeerCredentials,
     },
     web::{LocalAddr, RemoteAddr},
 };
@@ -136,7 +137,7 @@ impl<T: Listener> Listener for AutoCertListener<T> {
             (certs, key)
         };
 
-        let cert_resolver = Arc::new(ResolveServerCert::default());
+        let cert_resolver = self.auto_cert.cert_resolver;
 
         if let (Some(certs), Some(key)) = (cache_certs, cert_key) {
             let certs = certs
@@ -169,20 +170,29 @@ impl<T: Listener> Listener for AutoCertListener<T> {
         let domains = self.auto_cert.domains;
         let keys_for_http01 = self.auto_cert.keys_for_http01;
         let cache_path = self.auto_cert.cache_path;
+        let event_handler = self.auto_cert.event_handler;
+        let emit = move |event: AutoCertEvent| {
+            if let Some(event_handler) = &event_handler {
+                event_handler(&event);
+            }
+        };
         tokio::spawn(async move {
             while let Some(cert_resolver) = Weak::upgrade(&weak_cert_resolver) {
                 if cert_resolver.is_expired() {
-                    match issue_cert(
+                    match issue_cert_with_events(
                         &mut client,
                         &cert_resolver,
                         &domains,
                         challenge_type,
                         keys_for_http01.as_ref(),
+                        &emit,
                     )
                     .await
                     {
                         Ok(res) => {
+                            let expires_at = expires_at(&res.rustls_key);
                             *cert_resolver.cert.write() = Some(res.rustls_key);
+                            emit(AutoCertEvent::CertIssued { expires_at });
                             if let Some(cache_path) = &cache_path {
                                 let pkey_path = cache_path.join("key.pem");
                                 tracing::debug!(path =% pkey_path.display(), "write private key to cache path");
@@ -198,6 +208,10 @@ impl<T: Listener> Listener for AutoCertListener<T> {
                         }
                         Err(err) => {
                             tracing::error!(error =% err, "failed to issue certificate");
+                            emit(AutoCertEvent::RenewalFailed {
+                                error: err,
+                                expires_at: cert_resolver.expires_at(),
+                            });
                         }
                     }
                 }
@@ -223,9 +237,21 @@ impl<T: Acceptor> Acceptor for AutoCertAcceptor<T> {
 
     async fn accept(&mut self) -> IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
         let (stream, local_addr, remote_addr, _) = self.inner.accept().await?;
-        let stream = HandshakeStream::new(self.acceptor.accept(stream));
+        let peer_credentials = T::peer_credentials(&stream);
+        let stream = HandshakeStream::new(self.acceptor.accept(stream), |stream| {
+            stream.get_ref().1.alpn_protocol().map(<[u8]>::to_vec)
+        })
+        .with_peer_credentials(peer_credentials);
         Ok((stream, local_addr, remote_addr, Scheme::HTTPS))
     }
+
+    fn alpn_protocol(io: &Self::Io) -> Option<AlpnProtocolSlot> {
+        Some(io.alpn_protocol_slot())
+    }
+
+    fn peer_credentials(io: &Self::Io) -> Option<PeerCredentials> {
+        io.peer_credentials()
+    }
 }
 
 fn gen_acme_cert(domain: &str, acme_hash: &[u8]) -> IoResult<CertifiedKey> {
@@ -264,9 +290,35 @@ pub async fn issue_cert<T: AsRef<str>>(
     domains: &[T],
     challenge_type: ChallengeType,
     keys_for_http01: Option<&Http01TokensMap>,
+) -> IoResult<IssueCertResult> {
+    issue_cert_with_events(
+        client,
+        resolver,
+        domains,
+        challenge_type,
+        keys_for_http01,
+        &|_| {},
+    )
+    .await
+}
+
+async fn issue_cert_with_events<T: AsRef<str>>(
+    client: &mut AcmeClient,
+    resolver: &ResolveServerCert,
+    domains: &[T],
+    challenge_type: ChallengeType,
+    keys_for_http01: Option<&Http01TokensMap>,
+    emit: &(dyn Fn(AutoCertEvent) + Send + Sync),
 ) -> IoResult<IssueCertResult> {
     tracing::debug!("issue certificate");
     let order_resp = client.new_order(domains).await?;
+    emit(AutoCertEvent::OrderCreated {
+        domains: domains
+            .iter()
+            .map(|domain| domain.as_ref().to_string())
+            .collect(),
+    });
+    let mut validated_domains = HashSet::new();
 
     // trigger challenge
     let mut valid = false;
@@ -278,6 +330,11 @@ pub async fn issue_cert<T: AsRef<str>>(
             let resp = client.fetch_authorization(auth_url).await?;
 
             if resp.status == "valid" {
+                if validated_domains.insert(resp.identifier.value.clone()) {
+                    emit(AutoCertEvent::ChallengeValidated {
+                        domain: resp.identifier.value,
+                    });
+                }
                 continue;
             }
 
@@ -7,6 +7,7 @@ mod auto_cert;
 mod builder;
 mod client;
 mod endpoint;
+mod event;
 mod jose;
 mod keypair;
 mod listener;
@@ -17,9 +18,10 @@ pub use auto_cert::AutoCert;
 pub use builder::AutoCertBuilder;
 pub use client::AcmeClient;
 pub use endpoint::{Http01Endpoint, Http01TokensMap};
+pub use event::AutoCertEvent;
 pub use listener::{issue_cert, AutoCertAcceptor, AutoCertListener, ResolvedCertListener};
 pub use protocol::ChallengeType;
-pub use resolver::{seconds_until_expiry, ResolveServerCert};
+pub use resolver::{expires_at, seconds_until_expiry, ResolveServerCert};
 
 /// Let's Encrypt production directory url
 pub const LETS_ENCRYPT_PRODUCTION: &str = "https://acme-v02.api.letsencrypt.org/directory";
@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use parking_lot::RwLock;
@@ -20,13 +20,21 @@ pub fn seconds_until_expiry(cert: &CertifiedKey) -> i64 {
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs() as i64;
-    let expires_at = cert
-        .cert
+    let expires_at = expiry_timestamp(cert).unwrap_or(0);
+    expires_at - now
+}
+
+/// Returns the expiration time of the certificate or `None` if there's no
+/// certificate in the key.
+pub fn expires_at(cert: &CertifiedKey) -> Option<SystemTime> {
+    expiry_timestamp(cert).map(|timestamp| UNIX_EPOCH + Duration::from_secs(timestamp as u64))
+}
+
+fn expiry_timestamp(cert: &CertifiedKey) -> Option<i64> {
+    cert.cert
         .first()
         .and_then(|cert| X509Certificate::from_der(cert.as_ref()).ok())
         .map(|(_, cert)| cert.validity().not_after.timestamp())
-        .unwrap_or(0);
-    expires_at - now
 }
 
 /// Shared ACME key state.
@@ -38,6 +46,18 @@ pub struct ResolveServerCert {
 }
 
 impl ResolveServerCert {
+    /// Returns the expiration time of the current certificate, `None` if
+    /// there is no certificate.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.cert.read().as_deref().and_then(expires_at)
+    }
+
+    /// Returns the number of seconds until the current certificate expires,
+    /// `None` if there is no certificate.
+    pub fn seconds_until_expiry(&self) -> Option<i64> {
+        self.cert.read().as_deref().map(seconds_until_expiry)
+    }
+
     pub(crate) fn is_expired(&self) -> bool {
         self.cert
             .read()
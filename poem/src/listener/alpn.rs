@@ -0,0 +1,49 @@
+use std::sync::{Arc, OnceLock};
+
+/// The ALPN protocol negotiated with the client for a TLS connection.
+///
+/// It is added to the extensions of the requests received from the
+/// connection, and can be extracted with [`Data`](crate::web::Data).
+///
+/// # Example
+///
+/// ```
+/// use poem::{handler, listener::AlpnProtocol, web::Data};
+///
+/// #[handler]
+/// fn index(protocol: Option<Data<&AlpnProtocol>>) -> String {
+///     match protocol {
+///         Some(Data(protocol)) => String::from_utf8_lossy(&protocol.0).into_owned(),
+///         None => "none".to_string(),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AlpnProtocol(pub Vec<u8>);
+
+/// A handle to the ALPN protocol of a connection, which is set after the TLS
+/// handshake is completed.
+///
+/// It is returned by [`Acceptor::alpn_protocol`](super::Acceptor::alpn_protocol)
+/// for the connections whose protocol is negotiated while they are being
+/// served, the clones of a slot share the same protocol.
+#[derive(Clone, Default)]
+pub struct AlpnProtocolSlot(Arc<OnceLock<AlpnProtocol>>);
+
+impl AlpnProtocolSlot {
+    /// Create an empty slot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the negotiated protocol, it is ignored if the protocol has
+    /// already been set.
+    pub fn set(&self, protocol: AlpnProtocol) {
+        let _ = self.0.set(protocol);
+    }
+
+    /// Returns the negotiated protocol, or `None` if it has not been set.
+    pub fn get(&self) -> Option<&AlpnProtocol> {
+        self.0.get()
+    }
+}
@@ -7,7 +7,7 @@ use http::uri::Scheme;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf, Result as IoResult};
 
 use crate::{
-    listener::{Acceptor, Listener},
+    listener::{Acceptor, AlpnProtocolSlot, Listener, PeerCredentials},
     web::{LocalAddr, RemoteAddr},
 };
 
@@ -58,6 +58,20 @@ impl<A: Acceptor, B: Acceptor> Acceptor for Combined<A, B> {
             }
         }
     }
+
+    fn alpn_protocol(io: &Self::Io) -> Option<AlpnProtocolSlot> {
+        match io {
+            CombinedStream::A(a) => A::alpn_protocol(a),
+            CombinedStream::B(b) => B::alpn_protocol(b),
+        }
+    }
+
+    fn peer_credentials(io: &Self::Io) -> Option<PeerCredentials> {
+        match io {
+            CombinedStream::A(a) => A::peer_credentials(a),
+            CombinedStream::B(b) => B::peer_credentials(b),
+        }
+    }
 }
 
 /// A IO stream for CombinedAcceptor.
@@ -8,6 +8,8 @@ use std::{
 use futures_util::{future::BoxFuture, FutureExt};
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf, Result};
 
+use crate::listener::{AlpnProtocol, AlpnProtocolSlot, PeerCredentials};
+
 enum State<S> {
     Handshaking(BoxFuture<'static, Result<S>>),
     Ready(S),
@@ -17,17 +19,56 @@ enum State<S> {
 /// A handshake stream for tls.
 pub struct HandshakeStream<S> {
     state: State<S>,
+    alpn_protocol: AlpnProtocolSlot,
+    peer_credentials: Option<PeerCredentials>,
 }
 
 impl<S> HandshakeStream<S> {
-    pub(crate) fn new<F>(handshake: F) -> Self
+    pub(crate) fn new<F>(handshake: F, alpn_protocol: fn(&S) -> Option<Vec<u8>>) -> Self
     where
         F: Future<Output = Result<S>> + Send + 'static,
+        S: 'static,
     {
+        let slot = AlpnProtocolSlot::default();
+        let handshake = {
+            let slot = slot.clone();
+            async move {
+                let stream = handshake.await?;
+                if let Some(protocol) = alpn_protocol(&stream) {
+                    slot.set(AlpnProtocol(protocol));
+                }
+                Ok(stream)
+            }
+        };
         Self {
             state: State::Handshaking(handshake.boxed()),
+            alpn_protocol: slot,
+            peer_credentials: None,
+        }
+    }
+
+    /// Sets the credentials of the peer process of the underlying
+    /// connection, which must be taken before the handshake.
+    pub(crate) fn with_peer_credentials(self, peer_credentials: Option<PeerCredentials>) -> Self {
+        Self {
+            peer_credentials,
+            ..self
         }
     }
+
+    /// Returns the ALPN protocol negotiated with the client, it is `None`
+    /// before the handshake is completed or if no protocol is negotiated.
+    pub fn alpn_protocol(&self) -> Option<&AlpnProtocol> {
+        self.alpn_protocol.get()
+    }
+
+    pub(crate) fn alpn_protocol_slot(&self) -> AlpnProtocolSlot {
+        self.alpn_protocol.clone()
+    }
+
+    pub(crate) fn peer_credentials(&self) -> Option<PeerCredentials> {
+        self.peer_credentials
+    }
 }
 
 impl<S> AsyncRead for HandshakeStream<S>
@@ -3,6 +3,7 @@
 #[cfg(feature = "acme-base")]
 #[cfg_attr(docsrs, doc(cfg(feature = "acme-base")))]
 pub mod acme;
+mod alpn;
 mod combined;
 #[cfg(any(feature = "native-tls", feature = "rustls", feature = "openssl-tls"))]
 mod handshake_stream;
@@ -10,8 +11,13 @@ mod handshake_stream;
 mod native_tls;
 #[cfg(feature = "openssl-tls")]
 mod openssl_tls;
+mod peer_credentials;
+#[cfg(feature = "quic")]
+mod quic;
 #[cfg(feature = "rustls")]
 mod rustls;
+#[cfg(all(unix, feature = "systemd"))]
+mod systemd;
 mod tcp;
 #[cfg(any(feature = "rustls", feature = "native-tls", feature = "openssl-tls"))]
 mod tls;
@@ -37,14 +43,20 @@ pub use self::handshake_stream::HandshakeStream;
 pub use self::native_tls::{NativeTlsAcceptor, NativeTlsConfig, NativeTlsListener};
 #[cfg(feature = "openssl-tls")]
 pub use self::openssl_tls::{OpensslTlsAcceptor, OpensslTlsConfig, OpensslTlsListener};
+#[cfg(feature = "quic")]
+pub use self::quic::{QuicConfig, QuicListener};
 #[cfg(feature = "rustls")]
 pub use self::rustls::{RustlsAcceptor, RustlsCertificate, RustlsConfig, RustlsListener};
+#[cfg(all(unix, feature = "systemd"))]
+pub use self::systemd::{SystemdAcceptor, SystemdListener};
 #[cfg(any(feature = "rustls", feature = "native-tls", feature = "openssl-tls"))]
 pub use self::tls::IntoTlsConfigStream;
 #[cfg(unix)]
 pub use self::unix::{UnixAcceptor, UnixListener};
 pub use self::{
+    alpn::{AlpnProtocol, AlpnProtocolSlot},
     combined::{Combined, CombinedStream},
+    peer_credentials::PeerCredentials,
     tcp::{TcpAcceptor, TcpListener},
 };
 use crate::web::{LocalAddr, RemoteAddr};
@@ -53,14 +65,22 @@ use crate::web::{LocalAddr, RemoteAddr};
 pub struct BoxIo {
     reader: Box<dyn AsyncRead + Send + Unpin + 'static>,
     writer: Box<dyn AsyncWrite + Send + Unpin + 'static>,
+    alpn_protocol: Option<AlpnProtocolSlot>,
+    peer_credentials: Option<PeerCredentials>,
 }
 
 impl BoxIo {
-    fn new(io: impl AsyncRead + AsyncWrite + Send + Unpin + 'static) -> Self {
+    fn new(
+        io: impl AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        alpn_protocol: Option<AlpnProtocolSlot>,
+        peer_credentials: Option<PeerCredentials>,
+    ) -> Self {
         let (reader, writer) = tokio::io::split(io);
         Self {
             reader: Box::new(reader),
             writer: Box::new(writer),
+            alpn_protocol,
+            peer_credentials,
         }
     }
 }
@@ -123,7 +143,9 @@ impl<A: Acceptor> DynAcceptor for ToDynAcceptor<A> {
     fn accept(&mut self) -> BoxFuture<IoResult<(BoxIo, LocalAddr, RemoteAddr, Scheme)>> {
         async move {
             let (io, local_addr, remote_addr, scheme) = self.0.accept().await?;
-            let io = BoxIo::new(io);
+            let alpn_protocol = A::alpn_protocol(&io);
+            let peer_credentials = A::peer_credentials(&io);
+            let io = BoxIo::new(io, alpn_protocol, peer_credentials);
             Ok((io, local_addr, remote_addr, scheme))
         }
         .boxed()
@@ -142,9 +164,109 @@ impl Acceptor for dyn DynAcceptor + '_ {
     async fn accept(&mut self) -> IoResult<(BoxIo, LocalAddr, RemoteAddr, Scheme)> {
         DynAcceptor::accept(self).await
     }
+
+    #[inline]
+    fn alpn_protocol(io: &Self::Io) -> Option<AlpnProtocolSlot> {
+        io.alpn_protocol.clone()
+    }
+
+    #[inline]
+    fn peer_credentials(io: &Self::Io) -> Option<PeerCredentials> {
+        io.peer_credentials
+    }
 }
 
 /// Represents a acceptor type.
+///
+/// An acceptor produces the connections served by [`Server`](crate::Server),
+/// it can be implemented for custom transports, such as tunneled streams or
+/// in-process pipes, and passed to
+/// [`Server::new_with_acceptor`](crate::Server::new_with_acceptor), or
+/// returned by a [`Listener`] passed to [`Server::new`](crate::Server::new).
+///
+/// # Contract
+///
+/// - [`Acceptor::accept`] must be cancel safe. The server polls it together
+///   with the shutdown signal, and drops the future when the signal completes
+///   first, so a connection must not be lost if the future is dropped before
+///   it completes.
+/// - The errors returned by [`Acceptor::accept`] are ignored by the server,
+///   which calls it again immediately. They should only be returned for the
+///   failures of a single connection, such as a failed handshake. The method
+///   should wait rather than returning errors when there is no connection,
+///   including when the transport is closed permanently.
+/// - The [`LocalAddr`] and the [`RemoteAddr`] returned with each connection
+///   are added to the requests of the connection, and [`Addr::Custom`]
+///   can be used for the transports that are not sockets. The [`Scheme`] is
+///   the scheme of the request URIs, usually [`Scheme::HTTP`], or
+///   [`Scheme::HTTPS`] for encrypted transports.
+/// - [`Acceptor::local_addr`] is logged when the server starts and passed to
+///   the [`Server::on_start`](crate::Server::on_start) hooks, so it should
+///   return the addresses the clients can connect to.
+///
+/// [`Addr::Custom`]: crate::Addr::Custom
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     handler,
+///     http::uri::Scheme,
+///     listener::Acceptor,
+///     web::{LocalAddr, RemoteAddr},
+///     Addr, Server,
+/// };
+/// use tokio::{
+///     io::{AsyncReadExt, AsyncWriteExt, DuplexStream},
+///     sync::mpsc,
+/// };
+///
+/// /// Accepts the in-process pipes sent through a channel.
+/// struct PipeAcceptor(mpsc::Receiver<DuplexStream>);
+///
+/// impl Acceptor for PipeAcceptor {
+///     type Io = DuplexStream;
+///
+///     fn local_addr(&self) -> Vec<LocalAddr> {
+///         vec![LocalAddr(Addr::Custom("pipe", "server".into()))]
+///     }
+///
+///     async fn accept(&mut self) -> std::io::Result<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
+///         // `mpsc::Receiver::recv` is cancel safe
+///         match self.0.recv().await {
+///             Some(io) => Ok((
+///                 io,
+///                 LocalAddr(Addr::Custom("pipe", "server".into())),
+///                 RemoteAddr(Addr::Custom("pipe", "client".into())),
+///                 Scheme::HTTP,
+///             )),
+///             // no more connections, wait for the shutdown
+///             None => std::future::pending().await,
+///         }
+///     }
+/// }
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let (tx, rx) = mpsc::channel(16);
+/// tokio::spawn(Server::new_with_acceptor(PipeAcceptor(rx)).run(index));
+///
+/// let (mut client, server) = tokio::io::duplex(1024);
+/// tx.send(server).await.unwrap();
+/// client
+///     .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n")
+///     .await
+///     .unwrap();
+/// let mut resp = String::new();
+/// client.read_to_string(&mut resp).await.unwrap();
+/// assert!(resp.starts_with("HTTP/1.1 200 OK"));
+/// assert!(resp.ends_with("hello"));
+/// # });
+/// ```
 pub trait Acceptor: Send {
     /// IO stream type.
     type Io: AsyncRead + AsyncWrite + Send + Unpin + 'static;
@@ -154,12 +276,37 @@ pub trait Acceptor: Send {
 
     /// Accepts a new incoming connection from this listener.
     ///
-    /// This function will yield once a new TCP connection is established. When
+    /// This function will yield once a new connection is established. When
     /// established, the corresponding IO stream and the remote peer’s
     /// address will be returned.
+    ///
+    /// It must be cancel safe, see the [contract](Acceptor#contract).
     fn accept(
         &mut self,
     ) -> impl Future<Output = IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)>> + Send;
+
+    /// Returns a handle to the ALPN protocol negotiated for the connection,
+    /// which is added to the request extensions as [`AlpnProtocol`].
+    ///
+    /// The acceptors that negotiate the protocol, such as the TLS acceptors,
+    /// return a slot which is set with [`AlpnProtocolSlot::set`] when the
+    /// handshake completes, and the acceptors wrapping another acceptor
+    /// should forward it. The connection is served with HTTP/2 if the
+    /// protocol is `h2`. (defaults to `None`)
+    fn alpn_protocol(io: &Self::Io) -> Option<AlpnProtocolSlot> {
+        let _ = io;
+        None
+    }
+
+    /// Returns the credentials of the peer process of the connection, which
+    /// are added to the request extensions as [`PeerCredentials`].
+    ///
+    /// The acceptors wrapping another acceptor should forward it. (defaults
+    /// to `None`)
+    fn peer_credentials(io: &Self::Io) -> Option<PeerCredentials> {
+        let _ = io;
+        None
+    }
 }
 
 /// An owned dynamically typed Acceptor for use in cases where you can’t
@@ -222,11 +369,17 @@ pub trait AcceptorExt: Acceptor {
 impl<T: Acceptor> AcceptorExt for T {}
 
 /// Represents a listener that can be listens for incoming connections.
+///
+/// A listener describes how to bind a transport, such as an address of
+/// [`TcpListener`], it is converted to an [`Acceptor`] when the server starts,
+/// and the errors of binding are returned by
+/// [`Server::run`](crate::Server::run). It can be implemented for the custom
+/// acceptors to be passed to [`Server::new`](crate::Server::new).
 pub trait Listener: Send {
     /// The acceptor type.
     type Acceptor: Acceptor;
 
-    /// Create a acceptor instance.
+    /// Create a acceptor instance, which binds the transport.
     fn into_acceptor(self) -> impl Future<Output = IoResult<Self::Acceptor>> + Send;
 
     /// Combine two listeners.
@@ -353,6 +506,14 @@ impl<T: Acceptor + ?Sized> Acceptor for Box<T> {
     async fn accept(&mut self) -> IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
         self.as_mut().accept().await
     }
+
+    fn alpn_protocol(io: &Self::Io) -> Option<AlpnProtocolSlot> {
+        T::alpn_protocol(io)
+    }
+
+    fn peer_credentials(io: &Self::Io) -> Option<PeerCredentials> {
+        T::peer_credentials(io)
+    }
 }
 
 impl Acceptor for Infallible {
@@ -410,4 +571,67 @@ mod tests {
 
         let _ = a.combine(b);
     }
+
+    #[tokio::test]
+    async fn custom_listener() {
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt, DuplexStream},
+            sync::mpsc,
+        };
+
+        use crate::{handler, Addr, Server};
+
+        struct PipeListener(mpsc::Receiver<DuplexStream>);
+
+        struct PipeAcceptor(mpsc::Receiver<DuplexStream>);
+
+        impl Listener for PipeListener {
+            type Acceptor = PipeAcceptor;
+
+            async fn into_acceptor(self) -> IoResult<Self::Acceptor> {
+                Ok(PipeAcceptor(self.0))
+            }
+        }
+
+        impl Acceptor for PipeAcceptor {
+            type Io = DuplexStream;
+
+            fn local_addr(&self) -> Vec<LocalAddr> {
+                vec![LocalAddr(Addr::Custom("pipe", "server".into()))]
+            }
+
+            async fn accept(&mut self) -> IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
+                match self.0.recv().await {
+                    Some(io) => Ok((
+                        io,
+                        LocalAddr(Addr::Custom("pipe", "server".into())),
+                        RemoteAddr(Addr::Custom("pipe", "client".into())),
+                        Scheme::HTTPS,
+                    )),
+                    None => std::future::pending().await,
+                }
+            }
+        }
+
+        #[handler(internal)]
+        fn index(req: &crate::Request) -> String {
+            format!("{} {}", req.remote_addr(), req.scheme())
+        }
+
+        let (tx, rx) = mpsc::channel(1);
+        tokio::spawn(Server::new(PipeListener(rx)).run(index));
+
+        for _ in 0..2 {
+            let (mut client, server) = tokio::io::duplex(1024);
+            tx.send(server).await.unwrap();
+            client
+                .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n")
+                .await
+                .unwrap();
+            let mut resp = String::new();
+            client.read_to_string(&mut resp).await.unwrap();
+            assert!(resp.starts_with("HTTP/1.1 200 OK"));
+            assert!(resp.ends_with("pipe://client https"));
+        }
+    }
 }
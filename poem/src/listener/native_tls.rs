@@ -7,7 +7,7 @@ use tokio::io::{Error as IoError, ErrorKind, Result as IoResult};
 use tokio_native_tls::{native_tls::Identity, TlsStream};
 
 use crate::{
-    listener::{Acceptor, HandshakeStream, IntoTlsConfigStream, Listener},
+    listener::{Acceptor, HandshakeStream, IntoTlsConfigStream, Listener, PeerCredentials},
     web::{LocalAddr, RemoteAddr},
 };
 
@@ -168,17 +168,24 @@ where
                 }
                 res = self.inner.accept() => {
                     let (stream, local_addr, remote_addr, _) = res?;
+                    let peer_credentials = T::peer_credentials(&stream);
                     let tls_acceptor = match &self.current_tls_acceptor {
                         Some(tls_acceptor) => tls_acceptor.clone(),
                         None => return Err(IoError::new(ErrorKind::Other, "no valid tls config.")),
                     };
                     let fut = async move { tls_acceptor.accept(stream).map_err(|err| IoError::new(ErrorKind::Other, err.to_string())).await };
-                    let stream = HandshakeStream::new(fut);
+                    // `native-tls` does not support ALPN on the server side
+                    let stream = HandshakeStream::new(fut, |_| None)
+                        .with_peer_credentials(peer_credentials);
                     return Ok((stream, local_addr, remote_addr, Scheme::HTTPS));
                 }
             }
         }
     }
+
+    fn peer_credentials(io: &Self::Io) -> Option<PeerCredentials> {
+        io.peer_credentials()
+    }
 }
 
 #[cfg(test)]
@@ -18,7 +18,9 @@ use tokio_openssl::SslStream;
 use tokio_util::either::Either;
 
 use crate::{
-    listener::{Acceptor, HandshakeStream, IntoTlsConfigStream, Listener},
+    listener::{
+        Acceptor, AlpnProtocolSlot, HandshakeStream, IntoTlsConfigStream, Listener, PeerCredentials,
+    },
     web::{LocalAddr, RemoteAddr},
 };
 
@@ -213,6 +215,7 @@ where
                 }
                 res = self.inner.accept() => {
                     let (stream, local_addr, remote_addr, _) = res?;
+                    let peer_credentials = T::peer_credentials(&stream);
                     let tls_acceptor = match &self.current_tls_acceptor {
                         Some(tls_acceptor) => tls_acceptor.clone(),
                         None => return Err(IoError::new(ErrorKind::Other, "no valid tls config.")),
@@ -226,12 +229,23 @@ where
                         Pin::new(&mut tls_stream).accept().await.map_err(|err|
                             IoError::new(ErrorKind::Other, err.to_string()))?;
                         Ok(tls_stream) };
-                    let stream = HandshakeStream::new(fut);
+                    let stream = HandshakeStream::new(fut, |stream| {
+                        stream.ssl().selected_alpn_protocol().map(<[u8]>::to_vec)
+                    })
+                    .with_peer_credentials(peer_credentials);
                     return Ok((stream, local_addr, remote_addr, Scheme::HTTPS));
                 }
             }
         }
     }
+
+    fn alpn_protocol(io: &Self::Io) -> Option<AlpnProtocolSlot> {
+        Some(io.alpn_protocol_slot())
+    }
+
+    fn peer_credentials(io: &Self::Io) -> Option<PeerCredentials> {
+        io.peer_credentials()
+    }
 }
 
 #[cfg(test)]
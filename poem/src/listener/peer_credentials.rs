@@ -0,0 +1,30 @@
+/// The credentials of the process on the other side of a Unix domain socket
+/// connection.
+///
+/// It is added to the extensions of the requests received from a
+/// [`UnixListener`](crate::listener::UnixListener), and can be extracted with
+/// [`Data`](crate::web::Data).
+///
+/// # Example
+///
+/// ```
+/// use poem::{handler, listener::PeerCredentials, web::Data};
+///
+/// #[handler]
+/// fn index(cred: Option<Data<&PeerCredentials>>) -> String {
+///     match cred {
+///         Some(Data(cred)) => format!("uid={} gid={}", cred.uid, cred.gid),
+///         None => "unknown".to_string(),
+///     }
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PeerCredentials {
+    /// The user id of the peer process.
+    pub uid: u32,
+    /// The group id of the peer process.
+    pub gid: u32,
+    /// The process id of the peer process, if it is supported by the
+    /// platform.
+    pub pid: Option<i32>,
+}
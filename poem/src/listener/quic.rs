@@ -0,0 +1,419 @@
+use std::{future::Future, sync::Arc, time::Duration};
+
+use bytes::{Buf, Bytes};
+use h3::server::RequestStream;
+use http::uri::Scheme;
+use http_body_util::BodyExt;
+use tokio::{
+    io::{Error as IoError, ErrorKind, Result as IoResult},
+    net::{lookup_host, ToSocketAddrs},
+    task::JoinSet,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    endpoint::{DynEndpoint, ToDynEndpoint},
+    web::{LocalAddr, RemoteAddr},
+    Body, Endpoint, EndpointExt, IntoEndpoint, Request, Response,
+};
+
+/// The TLS configuration of the [`QuicListener`].
+#[cfg_attr(docsrs, doc(cfg(feature = "quic")))]
+pub struct QuicConfig {
+    cert: Vec<u8>,
+    key: Vec<u8>,
+    server_config: Option<quinn::ServerConfig>,
+}
+
+impl QuicConfig {
+    /// Create a new QUIC config with the certificate chain and the private
+    /// key in PEM format.
+    pub fn new(cert: impl Into<Vec<u8>>, key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            cert: cert.into(),
+            key: key.into(),
+            server_config: None,
+        }
+    }
+
+    fn create_server_config(self) -> IoResult<quinn::ServerConfig> {
+        if let Some(server_config) = self.server_config {
+            return Ok(server_config);
+        }
+
+        let certs = rustls_pemfile::certs(&mut self.cert.as_slice()).collect::<Result<_, _>>()?;
+        let key = rustls_pemfile::private_key(&mut self.key.as_slice())?
+            .ok_or_else(|| IoError::other("failed to parse tls private key"))?;
+
+        let provider = Arc::new(quinn::rustls::crypto::ring::default_provider());
+        let mut tls_config = quinn::rustls::ServerConfig::builder_with_provider(provider)
+            .with_protocol_versions(&[&quinn::rustls::version::TLS13])
+            .map_err(IoError::other)?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(IoError::other)?;
+        tls_config.alpn_protocols = vec![b"h3".to_vec()];
+
+        let quic_config = quinn::crypto::rustls::QuicServerConfig::try_from(tls_config)
+            .map_err(IoError::other)?;
+        Ok(quinn::ServerConfig::with_crypto(Arc::new(quic_config)))
+    }
+}
+
+impl From<quinn::ServerConfig> for QuicConfig {
+    fn from(server_config: quinn::ServerConfig) -> Self {
+        Self {
+            cert: Vec::new(),
+            key: Vec::new(),
+            server_config: Some(server_config),
+        }
+    }
+}
+
+/// An experimental listener that serves the endpoints over HTTP/3 with
+/// [`quinn`](https://crates.io/crates/quinn) and [`h3`](https://crates.io/crates/h3).
+///
+/// HTTP/3 runs over QUIC, which is based on UDP rather than TCP, so this
+/// listener cannot be used with [`Server`](crate::Server) and serves the
+/// endpoint by itself. It is usually used together with a TCP listener
+/// serving the same endpoint, and the [`AltSvc`](crate::middleware::AltSvc)
+/// middleware advertising the HTTP/3 endpoint to the clients.
+///
+/// # Example
+///
+/// ```no_run
+/// use poem::{
+///     handler,
+///     listener::{
+///         Listener, QuicConfig, QuicListener, RustlsCertificate, RustlsConfig, TcpListener,
+///     },
+///     middleware::AltSvc,
+///     EndpointExt, Server,
+/// };
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let cert = std::fs::read("cert.pem").unwrap();
+/// let key = std::fs::read("key.pem").unwrap();
+/// let app = || index.with(AltSvc::new().h3(443));
+///
+/// let tls_config =
+///     RustlsConfig::new().fallback(RustlsCertificate::new().cert(cert.clone()).key(key.clone()));
+/// let tcp = Server::new(TcpListener::bind("0.0.0.0:443").rustls(tls_config)).run(app());
+/// let quic = QuicListener::bind("0.0.0.0:443", QuicConfig::new(cert, key)).run(app());
+/// let _ = tokio::join!(tcp, quic);
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "quic")))]
+pub struct QuicListener<T> {
+    addr: T,
+    config: QuicConfig,
+}
+
+impl<T: ToSocketAddrs> QuicListener<T> {
+    /// Binds to the provided UDP address with the TLS configuration.
+    pub fn bind(addr: T, config: QuicConfig) -> Self {
+        Self { addr, config }
+    }
+
+    /// Run this listener.
+    pub async fn run<E>(self, ep: E) -> IoResult<()>
+    where
+        E: IntoEndpoint,
+        E::Endpoint: 'static,
+    {
+        self.run_with_graceful_shutdown(ep, futures_util::future::pending(), None)
+            .await
+    }
+
+    /// Run this listener and a signal to initiate graceful shutdown.
+    ///
+    /// When `signal` completes, the listener stops accepting new connections
+    /// and requests, and waits for the in-flight requests to complete. If
+    /// `timeout` is specified, the connections that are still open after it
+    /// elapses are closed.
+    pub async fn run_with_graceful_shutdown<E>(
+        self,
+        ep: E,
+        signal: impl Future<Output = ()>,
+        timeout: Option<Duration>,
+    ) -> IoResult<()>
+    where
+        E: IntoEndpoint,
+        E::Endpoint: 'static,
+    {
+        let ep: Arc<dyn DynEndpoint<Output = Response>> =
+            Arc::new(ToDynEndpoint(ep.into_endpoint().map_to_response()));
+        let server_config = self.config.create_server_config()?;
+        let addr = lookup_host(self.addr)
+            .await?
+            .next()
+            .ok_or_else(|| IoError::new(ErrorKind::AddrNotAvailable, "no address to bind"))?;
+        let endpoint = quinn::Endpoint::server(server_config, addr)?;
+        let local_addr = LocalAddr(endpoint.local_addr()?.into());
+        let shutdown = CancellationToken::new();
+        let mut connections = JoinSet::new();
+
+        tracing::info!(addr = %local_addr, "listening");
+        tracing::info!("http/3 server started");
+
+        tokio::pin!(signal);
+
+        loop {
+            tokio::select! {
+                _ = &mut signal => break,
+                incoming = endpoint.accept() => {
+                    let Some(incoming) = incoming else {
+                        break;
+                    };
+                    connections.spawn(serve_connection(
+                        incoming,
+                        ep.clone(),
+                        local_addr.clone(),
+                        shutdown.clone(),
+                    ));
+                }
+            }
+        }
+
+        tracing::info!("initiate graceful shutdown");
+        endpoint.set_server_config(None);
+        shutdown.cancel();
+
+        let drain = async { while connections.join_next().await.is_some() {} };
+        match timeout {
+            Some(timeout) => {
+                if tokio::time::timeout(timeout, drain).await.is_err() {
+                    connections.abort_all();
+                }
+            }
+            None => drain.await,
+        }
+
+        endpoint.close(0u32.into(), b"");
+        endpoint.wait_idle().await;
+        tracing::info!("http/3 server stopped");
+        Ok(())
+    }
+}
+
+async fn serve_connection(
+    incoming: quinn::Incoming,
+    ep: Arc<dyn DynEndpoint<Output = Response>>,
+    local_addr: LocalAddr,
+    shutdown: CancellationToken,
+) {
+    let conn = match incoming.await {
+        Ok(conn) => conn,
+        Err(err) => {
+            tracing::debug!(error = %err, "failed to accept quic connection");
+            return;
+        }
+    };
+    let remote_addr = RemoteAddr(conn.remote_address().into());
+    let mut conn =
+        match h3::server::Connection::<_, Bytes>::new(h3_quinn::Connection::new(conn)).await {
+            Ok(conn) => conn,
+            Err(err) => {
+                tracing::debug!(error = %err, "failed to establish http/3 connection");
+                return;
+            }
+        };
+    let mut requests = JoinSet::new();
+    let mut shutting_down = false;
+
+    loop {
+        let res = tokio::select! {
+            res = conn.accept() => Some(res),
+            _ = shutdown.cancelled(), if !shutting_down => None,
+        };
+
+        match res {
+            Some(Ok(Some((req, stream)))) => {
+                requests.spawn(serve_request(
+                    req,
+                    stream,
+                    ep.clone(),
+                    local_addr.clone(),
+                    remote_addr.clone(),
+                ));
+            }
+            Some(Ok(None)) => break,
+            Some(Err(err)) => {
+                tracing::debug!(error = %err, "http/3 connection closed");
+                break;
+            }
+            None => {
+                // sends GOAWAY, the accepted requests are still served
+                shutting_down = true;
+                if conn.shutdown(0).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    while requests.join_next().await.is_some() {}
+}
+
+async fn serve_request(
+    req: http::Request<()>,
+    stream: RequestStream<h3_quinn::BidiStream<Bytes>, Bytes>,
+    ep: Arc<dyn DynEndpoint<Output = Response>>,
+    local_addr: LocalAddr,
+    remote_addr: RemoteAddr,
+) {
+    let (mut send, recv) = stream.split();
+
+    let body = Body::from_bytes_stream(futures_util::stream::unfold(recv, |mut recv| async move {
+        match recv.recv_data().await {
+            Ok(Some(mut data)) => Some((Ok(data.copy_to_bytes(data.remaining())), recv)),
+            Ok(None) => None,
+            Err(err) => Some((Err(IoError::other(err)), recv)),
+        }
+    }));
+
+    let (parts, ()) = req.into_parts();
+    let mut request = Request::builder()
+        .method(parts.method)
+        .uri(parts.uri.clone())
+        .version(parts.version)
+        .body(body);
+    *request.headers_mut() = parts.headers;
+    *request.extensions_mut() = parts.extensions;
+    let state = request.state_mut();
+    state.local_addr = local_addr;
+    state.remote_addr = remote_addr;
+    state.scheme = Scheme::HTTPS;
+    state.original_uri = parts.uri;
+
+    let (parts, body) = ep.get_response(request).await.into_parts();
+    let mut resp = http::Response::new(());
+    *resp.status_mut() = parts.status;
+    *resp.headers_mut() = parts.headers;
+
+    if let Err(err) = send.send_response(resp).await {
+        tracing::debug!(error = %err, "failed to send http/3 response");
+        return;
+    }
+
+    let mut body = body.0;
+    while let Some(frame) = body.frame().await {
+        let frame = match frame {
+            Ok(frame) => frame,
+            Err(err) => {
+                tracing::debug!(error = %err, "failed to read response body");
+                return;
+            }
+        };
+        let res = match frame.into_data() {
+            Ok(data) => send.send_data(data).await,
+            Err(frame) => match frame.into_trailers() {
+                Ok(trailers) => send.send_trailers(trailers).await,
+                Err(_) => Ok(()),
+            },
+        };
+        if let Err(err) = res {
+            tracing::debug!(error = %err, "failed to send http/3 response body");
+            return;
+        }
+    }
+
+    let _ = send.finish().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::future::poll_fn;
+
+    use super::*;
+    use crate::handler;
+
+    #[handler(internal)]
+    fn index(body: String) -> String {
+        format!("hello {body}")
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let port = std::net::UdpSocket::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let listener = QuicListener::bind(
+            ("127.0.0.1", port),
+            QuicConfig::new(
+                include_bytes!("certs/cert1.pem").as_ref(),
+                include_bytes!("certs/key1.pem").as_ref(),
+            ),
+        );
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(listener.run_with_graceful_shutdown(
+            index,
+            async move {
+                let _ = rx.await;
+            },
+            None,
+        ));
+
+        let mut roots = quinn::rustls::RootCertStore::empty();
+        for cert in rustls_pemfile::certs(&mut include_bytes!("certs/chain1.pem").as_ref()) {
+            roots.add(cert.unwrap()).unwrap();
+        }
+        let provider = Arc::new(quinn::rustls::crypto::ring::default_provider());
+        let mut tls_config = quinn::rustls::ClientConfig::builder_with_provider(provider)
+            .with_protocol_versions(&[&quinn::rustls::version::TLS13])
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        tls_config.alpn_protocols = vec![b"h3".to_vec()];
+        let quic_config = quinn::crypto::rustls::QuicClientConfig::try_from(tls_config).unwrap();
+
+        let mut client = quinn::Endpoint::client("127.0.0.1:0".parse().unwrap()).unwrap();
+        client.set_default_client_config(quinn::ClientConfig::new(Arc::new(quic_config)));
+        let conn = loop {
+            // the server may not be listening yet
+            let connecting = client
+                .connect(([127, 0, 0, 1], port).into(), "testserver.com")
+                .unwrap();
+            match connecting.await {
+                Ok(conn) => break conn,
+                Err(_) => tokio::time::sleep(Duration::from_millis(50)).await,
+            }
+        };
+
+        let (mut driver, mut send_request) = h3::client::new(h3_quinn::Connection::new(conn))
+            .await
+            .unwrap();
+        let driver = tokio::spawn(async move { poll_fn(|cx| driver.poll_close(cx)).await });
+
+        let req = http::Request::post("https://testserver.com/")
+            .body(())
+            .unwrap();
+        let mut stream = send_request.send_request(req).await.unwrap();
+        stream
+            .send_data(Bytes::from_static(b"http/3"))
+            .await
+            .unwrap();
+        stream.finish().await.unwrap();
+
+        let resp = stream.recv_response().await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let mut body = Vec::new();
+        while let Some(mut data) = stream.recv_data().await.unwrap() {
+            body.extend_from_slice(&data.copy_to_bytes(data.remaining()));
+        }
+        assert_eq!(body, b"hello http/3");
+
+        drop(send_request);
+        tx.send(()).unwrap();
+        server.await.unwrap().unwrap();
+        let _ = driver.await;
+        client.wait_idle().await;
+    }
+}
@@ -18,7 +18,9 @@ use tokio_rustls::{
 };
 
 use crate::{
-    listener::{Acceptor, HandshakeStream, IntoTlsConfigStream, Listener},
+    listener::{
+        Acceptor, AlpnProtocolSlot, HandshakeStream, IntoTlsConfigStream, Listener, PeerCredentials,
+    },
     web::{LocalAddr, RemoteAddr},
 };
 
@@ -109,6 +111,7 @@ pub struct RustlsConfig {
     certificates: HashMap<String, RustlsCertificate>,
     fallback: Option<RustlsCertificate>,
     client_auth: TlsClientAuth,
+    alpn_protocols: Vec<Vec<u8>>,
 }
 
 impl Default for RustlsConfig {
@@ -124,6 +127,7 @@ impl RustlsConfig {
             certificates: HashMap::new(),
             fallback: Default::default(),
             client_auth: TlsClientAuth::Off,
+            alpn_protocols: vec![b"h2".to_vec(), b"http/1.1".to_vec()],
         }
     }
 
@@ -215,6 +219,36 @@ impl RustlsConfig {
         self
     }
 
+    /// Sets the protocols advertised with ALPN, in order of preference.
+    /// (default to `["h2", "http/1.1"]`)
+    ///
+    /// The protocol negotiated for a connection is added to the request
+    /// extensions as [`AlpnProtocol`](crate::listener::AlpnProtocol).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use poem::listener::{Listener, RustlsCertificate, RustlsConfig, TcpListener};
+    ///
+    /// # let cert_bytes: Vec<u8> = todo!();
+    /// # let key_bytes: Vec<u8> = todo!();
+    ///
+    /// // only accept HTTP/1.1
+    /// let config = RustlsConfig::new()
+    ///     .fallback(RustlsCertificate::new().cert(cert_bytes).key(key_bytes))
+    ///     .alpn_protocols(["http/1.1"]);
+    /// let listener = TcpListener::bind("0.0.0.0:3000").rustls(config);
+    /// ```
+    #[must_use]
+    pub fn alpn_protocols<I, T>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Vec<u8>>,
+    {
+        self.alpn_protocols = protocols.into_iter().map(Into::into).collect();
+        self
+    }
+
     fn create_server_config(&self) -> IoResult<ServerConfig> {
         let fallback = self
             .fallback
@@ -255,7 +289,7 @@ impl RustlsConfig {
             certificate_keys,
             fallback,
         }));
-        server_config.alpn_protocols = vec!["h2".into(), "http/1.1".into()];
+        server_config.alpn_protocols = self.alpn_protocols.clone();
 
         Ok(server_config)
     }
@@ -386,17 +420,29 @@ where
                 }
                 res = self.inner.accept() => {
                     let (stream, local_addr, remote_addr, _) = res?;
+                    let peer_credentials = T::peer_credentials(&stream);
                     let tls_acceptor = match &self.current_tls_acceptor {
                         Some(tls_acceptor) => tls_acceptor,
                         None => return Err(IoError::new(ErrorKind::Other, "no valid tls config.")),
                     };
 
-                    let stream = HandshakeStream::new(tls_acceptor.accept(stream));
+                    let stream = HandshakeStream::new(tls_acceptor.accept(stream), |stream| {
+                        stream.get_ref().1.alpn_protocol().map(<[u8]>::to_vec)
+                    })
+                    .with_peer_credentials(peer_credentials);
                     return Ok((stream, local_addr, remote_addr, Scheme::HTTPS));
                 }
             }
         }
     }
+
+    fn alpn_protocol(io: &Self::Io) -> Option<AlpnProtocolSlot> {
+        Some(io.alpn_protocol_slot())
+    }
+
+    fn peer_credentials(io: &Self::Io) -> Option<PeerCredentials> {
+        io.peer_credentials()
+    }
 }
 
 #[derive(Debug)]
@@ -456,4 +502,88 @@ mod tests {
         let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
         assert_eq!(stream.read_i32().await.unwrap(), 10);
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn peer_credentials() {
+        use crate::listener::{AcceptorExt, BoxAcceptor, UnixListener};
+
+        let path = std::env::temp_dir().join(format!("poem-rustls-{}", std::process::id()));
+        let listener = UnixListener::bind(&path).rustls(
+            RustlsConfig::new().fallback(
+                RustlsCertificate::new()
+                    .cert(include_bytes!("certs/cert1.pem").as_ref())
+                    .key(include_bytes!("certs/key1.pem").as_ref()),
+            ),
+        );
+        let mut acceptor = listener.into_acceptor().await.unwrap().boxed();
+
+        tokio::spawn({
+            let path = path.clone();
+            async move {
+                let config = ClientConfig::builder()
+                    .with_root_certificates(
+                        read_trust_anchor(include_bytes!("certs/chain1.pem")).unwrap(),
+                    )
+                    .with_no_client_auth();
+
+                let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+                let domain = ServerName::try_from("testserver.com").unwrap();
+                let stream = tokio::net::UnixStream::connect(path).await.unwrap();
+                let mut stream = connector.connect(domain, stream).await.unwrap();
+                stream.write_i32(10).await.unwrap();
+                // keep the connection open until the server closes it
+                let _ = stream.read_u8().await;
+            }
+        });
+
+        let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
+        assert_eq!(
+            BoxAcceptor::peer_credentials(&stream).map(|cred| cred.uid),
+            Some(nix::unistd::getuid().as_raw())
+        );
+        assert_eq!(stream.read_i32().await.unwrap(), 10);
+        drop(acceptor);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn alpn_protocols() {
+        let listener = TcpListener::bind("127.0.0.1:0").rustls(
+            RustlsConfig::new()
+                .fallback(
+                    RustlsCertificate::new()
+                        .cert(include_bytes!("certs/cert1.pem").as_ref())
+                        .key(include_bytes!("certs/key1.pem").as_ref()),
+                )
+                .alpn_protocols(["custom", "http/1.1"]),
+        );
+        let mut acceptor = listener.into_acceptor().await.unwrap();
+        let local_addr = acceptor.local_addr().pop().unwrap();
+
+        tokio::spawn(async move {
+            let mut config = ClientConfig::builder()
+                .with_root_certificates(
+                    read_trust_anchor(include_bytes!("certs/chain1.pem")).unwrap(),
+                )
+                .with_no_client_auth();
+            config.alpn_protocols = vec![b"custom".to_vec()];
+
+            let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+            let domain = ServerName::try_from("testserver.com").unwrap();
+            let stream = TcpStream::connect(*local_addr.as_socket_addr().unwrap())
+                .await
+                .unwrap();
+            let mut stream = connector.connect(domain, stream).await.unwrap();
+            stream.write_i32(10).await.unwrap();
+        });
+
+        let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
+        assert!(stream.alpn_protocol().is_none());
+        assert_eq!(stream.read_i32().await.unwrap(), 10);
+        assert_eq!(
+            stream.alpn_protocol(),
+            Some(&crate::listener::AlpnProtocol(b"custom".to_vec()))
+        );
+    }
 }
@@ -0,0 +1,282 @@
+use std::{
+    ffi::OsStr,
+    io::{Error, ErrorKind, Result},
+    os::{
+        fd::{AsRawFd, OwnedFd},
+        unix::{ffi::OsStrExt, net::UnixDatagram},
+    },
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use http::uri::Scheme;
+use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+use tokio::io::Result as IoResult;
+
+use crate::{
+    listener::{
+        Acceptor, AcceptorExt, AlpnProtocolSlot, BoxAcceptor, BoxIo, Listener, PeerCredentials,
+        TcpAcceptor, UnixAcceptor,
+    },
+    web::{LocalAddr, RemoteAddr},
+};
+
+/// Whether the sockets passed by systemd have been taken.
+static SOCKETS_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// A listener for the sockets passed by the systemd socket activation.
+///
+/// The sockets are passed with the `LISTEN_PID` and `LISTEN_FDS` environment
+/// variables, which are left untouched. The sockets can only be taken once in
+/// a process, converting another `SystemdListener` into an acceptor returns an
+/// error. Both the TCP and the Unix domain sockets are supported, the
+/// connections of all the sockets are accepted.
+///
+/// # Example
+///
+/// ```no_run
+/// use poem::{handler, listener::SystemdListener, Server};
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// Server::new(SystemdListener::new().notify_ready(true))
+///     .run(index)
+///     .await
+///     .unwrap();
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "systemd"))))]
+#[derive(Debug, Default)]
+pub struct SystemdListener {
+    notify_ready: bool,
+}
+
+impl SystemdListener {
+    /// Create a `SystemdListener`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specifies whether to notify systemd with `READY=1` when the server
+    /// starts accepting connections, which is required by the units with
+    /// `Type=notify`. (defaults to `false`)
+    ///
+    /// The notification is sent to the socket in the `NOTIFY_SOCKET`
+    /// environment variable, nothing is sent if it is not set.
+    #[must_use]
+    pub fn notify_ready(self, enable: bool) -> Self {
+        Self {
+            notify_ready: enable,
+        }
+    }
+}
+
+impl Listener for SystemdListener {
+    type Acceptor = SystemdAcceptor;
+
+    async fn into_acceptor(self) -> IoResult<Self::Acceptor> {
+        let inner = acceptor_from_sockets(listen_fds()?)?;
+        let notify_socket = if self.notify_ready {
+            std::env::var_os("NOTIFY_SOCKET").map(|path| path.as_bytes().to_vec())
+        } else {
+            None
+        };
+        Ok(SystemdAcceptor {
+            inner,
+            notify_socket,
+        })
+    }
+}
+
+/// A acceptor that accepts connections from the sockets passed by systemd.
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "systemd"))))]
+pub struct SystemdAcceptor {
+    inner: BoxAcceptor,
+    notify_socket: Option<Vec<u8>>,
+}
+
+impl Acceptor for SystemdAcceptor {
+    type Io = BoxIo;
+
+    #[inline]
+    fn local_addr(&self) -> Vec<LocalAddr> {
+        self.inner.local_addr()
+    }
+
+    async fn accept(&mut self) -> Result<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
+        if let Some(path) = self.notify_socket.take() {
+            if let Err(err) = notify(&path, "READY=1") {
+                tracing::warn!(error = %err, "failed to notify systemd");
+            }
+        }
+        self.inner.accept().await
+    }
+
+    #[inline]
+    fn alpn_protocol(io: &Self::Io) -> Option<AlpnProtocolSlot> {
+        <BoxAcceptor as Acceptor>::alpn_protocol(io)
+    }
+
+    #[inline]
+    fn peer_credentials(io: &Self::Io) -> Option<PeerCredentials> {
+        <BoxAcceptor as Acceptor>::peer_credentials(io)
+    }
+}
+
+/// A socket passed by systemd.
+enum Socket {
+    Tcp(std::net::TcpListener),
+    Unix(std::os::unix::net::UnixListener),
+}
+
+/// Takes the sockets passed by systemd, which can only be done once because
+/// the file descriptors are owned by the returned sockets.
+fn listen_fds() -> Result<Vec<Socket>> {
+    if SOCKETS_TAKEN.swap(true, Ordering::SeqCst) {
+        return Err(Error::new(
+            ErrorKind::AlreadyExists,
+            "the sockets passed by systemd have already been taken",
+        ));
+    }
+
+    let fds = sd_listen_fds::get().map_err(|err| Error::new(ErrorKind::InvalidInput, err))?;
+    fds.into_iter()
+        .map(|(_, fd)| socket_from_fd(fd.into_std()))
+        .collect()
+}
+
+fn socket_from_fd(fd: OwnedFd) -> Result<Socket> {
+    fcntl(fd.as_raw_fd(), FcntlArg::F_SETFD(FdFlag::FD_CLOEXEC))?;
+    // the local address of a Unix domain socket cannot be parsed as a socket
+    // address
+    let listener = std::net::TcpListener::from(fd);
+    if listener.local_addr().is_ok() {
+        Ok(Socket::Tcp(listener))
+    } else {
+        Ok(Socket::Unix(OwnedFd::from(listener).into()))
+    }
+}
+
+fn acceptor_from_sockets(sockets: Vec<Socket>) -> Result<BoxAcceptor> {
+    let mut acceptor: Option<BoxAcceptor> = None;
+
+    for socket in sockets {
+        let next = match socket {
+            Socket::Tcp(listener) => TcpAcceptor::from_std(listener)?.boxed(),
+            Socket::Unix(listener) => {
+                listener.set_nonblocking(true)?;
+                UnixAcceptor::from_std(listener)?.boxed()
+            }
+        };
+        acceptor = Some(match acceptor {
+            Some(acceptor) => acceptor.combine(next).boxed(),
+            None => next,
+        });
+    }
+
+    acceptor.ok_or_else(|| Error::new(ErrorKind::NotFound, "no sockets are passed by systemd"))
+}
+
+/// Sends a notification to the socket of the service manager, a socket
+/// starting with `@` is in the abstract namespace.
+fn notify(path: &[u8], state: &str) -> Result<()> {
+    let socket = UnixDatagram::unbound()?;
+    match path.strip_prefix(b"@") {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        Some(name) => {
+            #[cfg(target_os = "android")]
+            use std::os::android::net::SocketAddrExt;
+            #[cfg(target_os = "linux")]
+            use std::os::linux::net::SocketAddrExt;
+
+            let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+            socket.send_to_addr(state.as_bytes(), &addr)?;
+        }
+        _ => {
+            socket.send_to(state.as_bytes(), OsStr::from_bytes(path))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpStream, UnixStream},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn accept_from_fds() {
+        let tcp = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let tcp_addr = tcp.local_addr().unwrap();
+        let path = std::env::temp_dir().join(format!("poem-systemd-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let unix = std::os::unix::net::UnixListener::bind(&path).unwrap();
+
+        let mut acceptor =
+            acceptor_from_sockets(vec![Socket::Tcp(tcp), Socket::Unix(unix)]).unwrap();
+        assert_eq!(acceptor.local_addr().len(), 2);
+
+        tokio::spawn(async move {
+            let mut stream = TcpStream::connect(tcp_addr).await.unwrap();
+            stream.write_i32(10).await.unwrap();
+        });
+        let (mut stream, local_addr, _, _) = acceptor.accept().await.unwrap();
+        assert_eq!(local_addr.as_socket_addr(), Some(&tcp_addr));
+        assert_eq!(stream.read_i32().await.unwrap(), 10);
+
+        let unix_path = path.clone();
+        tokio::spawn(async move {
+            let mut stream = UnixStream::connect(unix_path).await.unwrap();
+            stream.write_i32(20).await.unwrap();
+        });
+        let (mut stream, local_addr, _, _) = acceptor.accept().await.unwrap();
+        assert!(local_addr.as_unix_socket_addr().is_some());
+        assert_eq!(stream.read_i32().await.unwrap(), 20);
+
+        drop(acceptor);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn socket_family() {
+        let tcp = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let tcp_addr = tcp.local_addr().unwrap();
+        match socket_from_fd(tcp.into()).unwrap() {
+            Socket::Tcp(listener) => assert_eq!(listener.local_addr().unwrap(), tcp_addr),
+            Socket::Unix(_) => panic!("expected a tcp socket"),
+        }
+
+        let path = std::env::temp_dir().join(format!("poem-family-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let unix = std::os::unix::net::UnixListener::bind(&path).unwrap();
+        match socket_from_fd(unix.into()).unwrap() {
+            Socket::Unix(listener) => assert_eq!(
+                listener.local_addr().unwrap().as_pathname(),
+                Some(path.as_path())
+            ),
+            Socket::Tcp(_) => panic!("expected a unix socket"),
+        }
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn notify_ready() {
+        let path = std::env::temp_dir().join(format!("poem-notify-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let socket = UnixDatagram::bind(&path).unwrap();
+
+        notify(path.as_os_str().as_bytes(), "READY=1").unwrap();
+        let mut buf = [0; 16];
+        let n = socket.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"READY=1");
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
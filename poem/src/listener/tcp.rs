@@ -12,14 +12,185 @@ use crate::{
 };
 
 /// A TCP listener.
+///
+/// # Example
+///
+/// ```no_run
+/// use poem::{handler, listener::TcpListener, Server};
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let listener = TcpListener::bind("0.0.0.0:3000")
+///     .reuse_addr(true)
+///     .backlog(4096)
+///     .nodelay(true);
+/// Server::new(listener).run(index).await.unwrap();
+/// # });
+/// ```
 pub struct TcpListener<T> {
     addr: T,
+    options: SocketOptions,
+}
+
+/// The options of the listening socket.
+#[derive(Debug, Default, Clone, Copy)]
+struct SocketOptions {
+    reuse_addr: Option<bool>,
+    reuse_port: bool,
+    backlog: Option<u32>,
+    nodelay: bool,
+}
+
+impl SocketOptions {
+    /// Returns `true` if the socket needs to be created with
+    /// [`TcpSocket`](tokio::net::TcpSocket) to apply the options.
+    fn is_custom(&self) -> bool {
+        self.reuse_addr.is_some() || self.reuse_port || self.backlog.is_some()
+    }
 }
 
 impl<T> TcpListener<T> {
     /// Binds to the provided address, and returns a [`TcpListener<T>`].
     pub fn bind(addr: T) -> Self {
-        Self { addr }
+        Self {
+            addr,
+            options: SocketOptions::default(),
+        }
+    }
+
+    /// Sets the `SO_REUSEADDR` option on the socket, so that the address can
+    /// be bound again while the connections of the previous socket are in the
+    /// `TIME_WAIT` state. (defaults to `true` on Unix, and `false` on other
+    /// platforms)
+    #[must_use]
+    pub fn reuse_addr(self, reuse_addr: bool) -> Self {
+        Self {
+            options: SocketOptions {
+                reuse_addr: Some(reuse_addr),
+                ..self.options
+            },
+            ..self
+        }
+    }
+
+    /// Sets the `SO_REUSEPORT` option on the socket, so that multiple
+    /// processes can bind to the same address, and the kernel distributes the
+    /// incoming connections among them. (default to `false`)
+    ///
+    /// This allows a new process to start accepting connections before the old
+    /// one stops, which is useful for restarting without dropping connections.
+    /// `SO_REUSEADDR` is also set unless it is disabled with
+    /// [`TcpListener::reuse_addr`].
+    #[cfg(all(unix, not(target_os = "solaris"), not(target_os = "illumos")))]
+    #[must_use]
+    pub fn reuse_port(self, reuse_port: bool) -> Self {
+        Self {
+            options: SocketOptions {
+                reuse_port,
+                ..self.options
+            },
+            ..self
+        }
+    }
+
+    /// Sets the maximum length of the queue of the pending connections.
+    /// (defaults to `1024`)
+    #[must_use]
+    pub fn backlog(self, backlog: u32) -> Self {
+        Self {
+            options: SocketOptions {
+                backlog: Some(backlog),
+                ..self.options
+            },
+            ..self
+        }
+    }
+
+    /// Sets the `TCP_NODELAY` option on the accepted connections, which
+    /// disables the Nagle's algorithm. (defaults to `false`)
+    #[must_use]
+    pub fn nodelay(self, nodelay: bool) -> Self {
+        Self {
+            options: SocketOptions {
+                nodelay,
+                ..self.options
+            },
+            ..self
+        }
+    }
+}
+
+async fn bind_with_options(
+    addr: impl ToSocketAddrs,
+    options: SocketOptions,
+) -> IoResult<TokioTcpListener> {
+    let mut last_err = None;
+    for addr in tokio::net::lookup_host(addr).await? {
+        let socket = match addr {
+            std::net::SocketAddr::V4(_) => tokio::net::TcpSocket::new_v4()?,
+            std::net::SocketAddr::V6(_) => tokio::net::TcpSocket::new_v6()?,
+        };
+        let reuse_addr = options
+            .reuse_addr
+            .unwrap_or(cfg!(unix) || options.reuse_port);
+        #[allow(unused_mut)]
+        let mut res = socket.set_reuseaddr(reuse_addr);
+        #[cfg(all(unix, not(target_os = "solaris"), not(target_os = "illumos")))]
+        if options.reuse_port {
+            res = res.and_then(|_| socket.set_reuseport(true));
+        }
+        let res = res
+            .and_then(|_| socket.bind(addr))
+            .and_then(|_| socket.listen(options.backlog.unwrap_or(1024)));
+        match res {
+            Ok(listener) => return Ok(listener),
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "could not resolve to any address",
+        )
+    }))
+}
+
+/// A listening socket which is created elsewhere, for example inherited from a
+/// supervisor with [`listenfd`](https://crates.io/crates/listenfd) or created
+/// with custom socket options, can also be used as a listener.
+///
+/// # Example
+///
+/// ```no_run
+/// use poem::{handler, Server};
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let listener = std::net::TcpListener::bind("127.0.0.1:3000").unwrap();
+/// Server::new(listener).run(index).await.unwrap();
+/// # });
+/// ```
+impl Listener for std::net::TcpListener {
+    type Acceptor = TcpAcceptor;
+
+    async fn into_acceptor(self) -> IoResult<Self::Acceptor> {
+        TcpAcceptor::from_std(self)
+    }
+}
+
+impl Listener for TokioTcpListener {
+    type Acceptor = TcpAcceptor;
+
+    async fn into_acceptor(self) -> IoResult<Self::Acceptor> {
+        TcpAcceptor::from_tokio(self)
     }
 }
 
@@ -27,12 +198,12 @@ impl<T: ToSocketAddrs + Send> Listener for TcpListener<T> {
     type Acceptor = TcpAcceptor;
 
     async fn into_acceptor(self) -> IoResult<Self::Acceptor> {
-        let listener = TokioTcpListener::bind(self.addr).await?;
-        let local_addr = listener.local_addr().map(|addr| LocalAddr(addr.into()))?;
-        Ok(TcpAcceptor {
-            local_addr,
-            listener,
-        })
+        let listener = if self.options.is_custom() {
+            bind_with_options(self.addr, self.options).await?
+        } else {
+            TokioTcpListener::bind(self.addr).await?
+        };
+        Ok(TcpAcceptor::from_tokio(listener)?.nodelay(self.options.nodelay))
     }
 }
 
@@ -40,15 +211,21 @@ impl<T: ToSocketAddrs + Send> Listener for TcpListener<T> {
 pub struct TcpAcceptor {
     local_addr: LocalAddr,
     listener: TokioTcpListener,
+    nodelay: bool,
 }
 
 impl TcpAcceptor {
     /// Creates new `TcpAcceptor` from a `std::net::TcpListener`.
+    ///
+    /// The listener is switched to the non-blocking mode, which is required by
+    /// the runtime.
     pub fn from_std(listener: std::net::TcpListener) -> Result<Self> {
+        listener.set_nonblocking(true)?;
         let local_addr = listener.local_addr().map(|addr| LocalAddr(addr.into()))?;
         Ok(Self {
             local_addr,
             listener: TokioTcpListener::from_std(listener)?,
+            nodelay: false,
         })
     }
 
@@ -58,8 +235,79 @@ impl TcpAcceptor {
         Ok(Self {
             local_addr,
             listener,
+            nodelay: false,
         })
     }
+
+    /// Sets the `TCP_NODELAY` option on the accepted connections, which
+    /// disables the Nagle's algorithm. (defaults to `false`)
+    ///
+    /// This is useful for the listening sockets created elsewhere, whose
+    /// options are not inherited by the accepted connections.
+    #[must_use]
+    pub fn nodelay(self, nodelay: bool) -> Self {
+        Self { nodelay, ..self }
+    }
+
+    /// Creates new `TcpAcceptor` from the file descriptor of a listening
+    /// socket, such as the one exported with [`TcpAcceptor::export_fd`] by
+    /// another process.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::os::fd::{FromRawFd, OwnedFd, RawFd};
+    ///
+    /// use poem::listener::TcpAcceptor;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let fd: RawFd = std::env::var("LISTEN_FD").unwrap().parse().unwrap();
+    /// // SAFETY: the file descriptor is inherited from the parent process.
+    /// let acceptor = TcpAcceptor::from_fd(unsafe { OwnedFd::from_raw_fd(fd) })?;
+    /// # Ok(())
+    /// # })
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn from_fd(fd: std::os::fd::OwnedFd) -> Result<Self> {
+        Self::from_std(std::net::TcpListener::from(fd))
+    }
+
+    /// Duplicates the listening socket into a file descriptor that is
+    /// inherited by the child processes, so that a new process can take over
+    /// accepting connections with [`TcpAcceptor::from_fd`], while this one
+    /// stops accepting and drains the existing connections with a graceful
+    /// shutdown.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::os::fd::AsRawFd;
+    ///
+    /// use poem::listener::{Acceptor, Listener, TcpListener};
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let acceptor = TcpListener::bind("0.0.0.0:3000").into_acceptor().await?;
+    /// let fd = acceptor.export_fd()?;
+    /// std::process::Command::new(std::env::current_exe()?)
+    ///     .env("LISTEN_FD", fd.as_raw_fd().to_string())
+    ///     .spawn()?;
+    /// # Ok(())
+    /// # })
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn export_fd(&self) -> Result<std::os::fd::OwnedFd> {
+        use std::os::fd::{AsFd, AsRawFd};
+
+        use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+
+        let fd = self.listener.as_fd().try_clone_to_owned()?;
+        fcntl(fd.as_raw_fd(), FcntlArg::F_SETFD(FdFlag::empty()))?;
+        Ok(fd)
+    }
 }
 
 impl Acceptor for TcpAcceptor {
@@ -72,14 +320,16 @@ impl Acceptor for TcpAcceptor {
 
     #[inline]
     async fn accept(&mut self) -> Result<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
-        self.listener.accept().await.map(|(io, addr)| {
-            (
-                io,
-                self.local_addr.clone(),
-                RemoteAddr(addr.into()),
-                Scheme::HTTP,
-            )
-        })
+        let (io, addr) = self.listener.accept().await?;
+        if self.nodelay {
+            io.set_nodelay(true)?;
+        }
+        Ok((
+            io,
+            self.local_addr.clone(),
+            RemoteAddr(addr.into()),
+            Scheme::HTTP,
+        ))
     }
 }
 
@@ -105,4 +355,97 @@ mod tests {
         let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
         assert_eq!(stream.read_i32().await.unwrap(), 10);
     }
+
+    #[tokio::test]
+    async fn std_listener() {
+        // the listener is in the blocking mode, like the inherited sockets
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let mut acceptor = listener.into_acceptor().await.unwrap();
+        assert_eq!(
+            acceptor.local_addr().remove(0).as_socket_addr(),
+            Some(&local_addr)
+        );
+
+        tokio::spawn(async move {
+            let mut stream = TcpStream::connect(local_addr).await.unwrap();
+            stream.write_i32(10).await.unwrap();
+        });
+
+        let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
+        assert_eq!(stream.read_i32().await.unwrap(), 10);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn reuse_port() {
+        let mut acceptor = TcpListener::bind("127.0.0.1:0")
+            .reuse_port(true)
+            .into_acceptor()
+            .await
+            .unwrap();
+        let local_addr = *acceptor.local_addr().remove(0).as_socket_addr().unwrap();
+
+        assert!(TcpListener::bind(local_addr).into_acceptor().await.is_err());
+        let acceptor2 = TcpListener::bind(local_addr)
+            .reuse_port(true)
+            .into_acceptor()
+            .await
+            .unwrap();
+        drop(acceptor2);
+
+        tokio::spawn(async move {
+            let mut stream = TcpStream::connect(local_addr).await.unwrap();
+            stream.write_i32(10).await.unwrap();
+        });
+
+        let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
+        assert_eq!(stream.read_i32().await.unwrap(), 10);
+    }
+
+    #[tokio::test]
+    async fn socket_options() {
+        let mut acceptor = TcpListener::bind("127.0.0.1:0")
+            .reuse_addr(true)
+            .backlog(16)
+            .nodelay(true)
+            .into_acceptor()
+            .await
+            .unwrap();
+        let local_addr = *acceptor.local_addr().remove(0).as_socket_addr().unwrap();
+
+        tokio::spawn(async move {
+            let mut stream = TcpStream::connect(local_addr).await.unwrap();
+            stream.write_i32(10).await.unwrap();
+        });
+
+        let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
+        assert!(stream.nodelay().unwrap());
+        assert_eq!(stream.read_i32().await.unwrap(), 10);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn export_fd() {
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let local_addr = *acceptor.local_addr().remove(0).as_socket_addr().unwrap();
+
+        let mut acceptor2 = TcpAcceptor::from_fd(acceptor.export_fd().unwrap()).unwrap();
+        drop(acceptor);
+        assert_eq!(
+            acceptor2.local_addr().remove(0).as_socket_addr(),
+            Some(&local_addr)
+        );
+
+        tokio::spawn(async move {
+            let mut stream = TcpStream::connect(local_addr).await.unwrap();
+            stream.write_i32(10).await.unwrap();
+        });
+
+        let (mut stream, _, _, _) = acceptor2.accept().await.unwrap();
+        assert_eq!(stream.read_i32().await.unwrap(), 10);
+    }
 }
@@ -12,11 +12,14 @@ use tokio::{
 };
 
 use crate::{
-    listener::{Acceptor, Listener},
+    listener::{Acceptor, Listener, PeerCredentials},
     web::{LocalAddr, RemoteAddr},
 };
 
 /// A Unix domain socket listener.
+///
+/// The credentials of the peer process are added to the extensions of the
+/// requests as [`PeerCredentials`].
 #[cfg_attr(docsrs, doc(cfg(unix)))]
 pub struct UnixListener<T> {
     path: T,
@@ -121,6 +124,15 @@ impl Acceptor for UnixAcceptor {
             Scheme::HTTP,
         ))
     }
+
+    fn peer_credentials(io: &Self::Io) -> Option<PeerCredentials> {
+        let cred = io.peer_cred().ok()?;
+        Some(PeerCredentials {
+            uid: cred.uid(),
+            gid: cred.gid(),
+            pid: cred.pid(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -143,6 +155,10 @@ mod tests {
         });
 
         let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
+        assert_eq!(
+            UnixAcceptor::peer_credentials(&stream).map(|cred| cred.uid),
+            Some(nix::unistd::getuid().as_raw())
+        );
         assert_eq!(stream.read_i32().await.unwrap(), 10);
 
         tokio::time::sleep(Duration::from_secs(1)).await;
@@ -1,4 +1,4 @@
-use crate::{Endpoint, Middleware, Request, Result};
+use crate::{endpoint::MiddlewareChain, Endpoint, Middleware, Request, Result};
 
 /// Middleware for add any data to request.
 pub struct AddData<T> {
@@ -44,6 +44,10 @@ where
         req.extensions_mut().insert(self.value.clone());
         self.inner.call(req).await
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("AddData", self.inner.middleware_chains())
+    }
 }
 
 #[cfg(test)]
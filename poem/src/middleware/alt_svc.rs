@@ -0,0 +1,191 @@
+use std::time::Duration;
+
+use http::{header, HeaderValue, Version};
+
+use crate::{endpoint::MiddlewareChain, Endpoint, Middleware, Request, Response, Result};
+
+/// Middleware for advertising the alternative services of the server with the
+/// `Alt-Svc` header, such as the HTTP/3 endpoint served by `QuicListener`.
+///
+/// The header is not added to the responses of the HTTP/3 requests, or the
+/// responses that already have it.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use poem::{handler, middleware::AltSvc, test::TestClient, EndpointExt};
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// let app = index.with(AltSvc::new().h3(443).max_age(Duration::from_secs(3600)));
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli.get("/").send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_header("alt-svc", r#"h3=":443"; ma=3600"#);
+/// # });
+/// ```
+#[derive(Default)]
+pub struct AltSvc {
+    services: Vec<(String, String)>,
+    max_age: Option<Duration>,
+}
+
+impl AltSvc {
+    /// Create new `AltSvc` middleware.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Advertises an alternative service with the ALPN protocol id and the
+    /// authority, such as `h3` and `:443`.
+    ///
+    /// The service is ignored if the protocol id is not a valid token or the
+    /// authority contains characters that are not allowed in the header.
+    #[must_use]
+    pub fn service(mut self, protocol: impl Into<String>, authority: impl Into<String>) -> Self {
+        let protocol = protocol.into();
+        let authority = authority.into();
+        let is_valid_protocol = !protocol.is_empty()
+            && protocol
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b));
+        let is_valid_authority = authority
+            .bytes()
+            .all(|b| (b == b' ' || b.is_ascii_graphic()) && b != b'"' && b != b'\\');
+        if is_valid_protocol && is_valid_authority {
+            self.services.push((protocol, authority));
+        }
+        self
+    }
+
+    /// Advertises the HTTP/3 service on the specified port of the same host.
+    #[must_use]
+    pub fn h3(self, port: u16) -> Self {
+        self.service("h3", format!(":{port}"))
+    }
+
+    /// Specify how long the alternative services are considered fresh by the
+    /// clients, which defaults to 24 hours.
+    #[must_use]
+    pub fn max_age(self, max_age: Duration) -> Self {
+        Self {
+            max_age: Some(max_age),
+            ..self
+        }
+    }
+
+    fn header_value(&self) -> Option<HeaderValue> {
+        if self.services.is_empty() {
+            return None;
+        }
+        let max_age = self
+            .max_age
+            .map(|max_age| format!("; ma={}", max_age.as_secs()))
+            .unwrap_or_default();
+        let value = self
+            .services
+            .iter()
+            .map(|(protocol, authority)| format!("{protocol}=\"{authority}\"{max_age}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        HeaderValue::from_str(&value).ok()
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for AltSvc {
+    type Output = AltSvcEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        AltSvcEndpoint {
+            inner: ep,
+            value: self.header_value(),
+        }
+    }
+}
+
+/// Endpoint for the AltSvc middleware.
+pub struct AltSvcEndpoint<E> {
+    inner: E,
+    value: Option<HeaderValue>,
+}
+
+impl<E: Endpoint> Endpoint for AltSvcEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let is_http3 = req.version() == Version::HTTP_3;
+        let mut resp = self.inner.get_response(req).await;
+        if let Some(value) = &self.value {
+            if !is_http3 && !resp.headers().contains_key(header::ALT_SVC) {
+                resp.headers_mut().insert(header::ALT_SVC, value.clone());
+            }
+        }
+        Ok(resp)
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("AltSvc", self.inner.middleware_chains())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{handler, test::TestClient, EndpointExt};
+
+    #[handler(internal)]
+    fn index() -> &'static str {
+        "hello"
+    }
+
+    #[tokio::test]
+    async fn alt_svc() {
+        let ep = index.with(AltSvc::new().h3(443).service("h3", "alt.example.com:8443"));
+        let resp = TestClient::new(&ep).get("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_header("alt-svc", r#"h3=":443", h3="alt.example.com:8443""#);
+
+        let resp = ep
+            .call(Request::builder().version(Version::HTTP_3).finish())
+            .await
+            .unwrap();
+        assert!(!resp.headers().contains_key(header::ALT_SVC));
+    }
+
+    #[tokio::test]
+    async fn error_response() {
+        #[handler(internal)]
+        fn index() -> Result<()> {
+            Err(crate::error::NotFoundError.into())
+        }
+
+        let ep = index.with(AltSvc::new().h3(443));
+        let resp = TestClient::new(ep).get("/").send().await;
+        resp.assert_status(http::StatusCode::NOT_FOUND);
+        resp.assert_header("alt-svc", r#"h3=":443""#);
+    }
+
+    #[tokio::test]
+    async fn invalid_service() {
+        let ep = index.with(
+            AltSvc::new()
+                .service("h3", "bad\"authority")
+                .service("h 3", ":443")
+                .service("h3", "\n:443"),
+        );
+        let resp = TestClient::new(ep).get("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_header_is_not_exist("alt-svc");
+
+        let ep = index.with(AltSvc::new().service("h3", "bad\"authority").h3(443));
+        let resp = TestClient::new(ep).get("/").send().await;
+        resp.assert_header("alt-svc", r#"h3=":443""#);
+    }
+}
@@ -0,0 +1,607 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use bytes::Bytes;
+use headers::CacheControl;
+use http::{header, HeaderMap, HeaderName, Method, StatusCode};
+use parking_lot::Mutex;
+
+use crate::{
+    endpoint::MiddlewareChain, web::headers::HeaderMapExt, Endpoint, IntoResponse, Middleware,
+    Request, Response, Result,
+};
+
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+const DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// A response stored by the [`Cache`] middleware.
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    /// The status code.
+    pub status: StatusCode,
+    /// The response headers.
+    pub headers: HeaderMap,
+    /// The response body.
+    pub body: Bytes,
+    /// The values of the request headers listed in the `Vary` header of the
+    /// response.
+    pub vary: HeaderMap,
+    /// The time when the response was stored.
+    pub stored_at: SystemTime,
+}
+
+/// Represents a back-end storage of the [`Cache`] middleware.
+pub trait CacheStorage: Send + Sync {
+    /// Load a response, returns `None` if it does not exist or has expired.
+    fn get<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> impl Future<Output = Result<Option<CachedResponse>>> + Send + 'a;
+
+    /// Store a response which expires after `ttl`.
+    fn set<'a>(
+        &'a self,
+        key: &'a str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> impl Future<Output = Result<()>> + Send + 'a;
+}
+
+/// A cache storage using memory.
+///
+/// When the number of stored responses reaches the capacity, expired
+/// responses are evicted, and new responses are not stored until there is
+/// room again.
+pub struct MemoryCacheStorage {
+    capacity: usize,
+    entries: Mutex<HashMap<String, (Instant, CachedResponse)>>,
+}
+
+impl Default for MemoryCacheStorage {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl MemoryCacheStorage {
+    /// Create a `MemoryCacheStorage` which stores at most `capacity`
+    /// responses.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Default::default(),
+        }
+    }
+}
+
+impl CacheStorage for MemoryCacheStorage {
+    async fn get<'a>(&'a self, key: &'a str) -> Result<Option<CachedResponse>> {
+        let mut entries = self.entries.lock();
+        match entries.get(key) {
+            Some((expires_at, response)) if *expires_at > Instant::now() => {
+                Ok(Some(response.clone()))
+            }
+            Some(_) => {
+                entries.remove(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn set<'a>(
+        &'a self,
+        key: &'a str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> Result<()> {
+        let mut entries = self.entries.lock();
+        let now = Instant::now();
+        if entries.len() >= self.capacity && !entries.contains_key(key) {
+            entries.retain(|_, (expires_at, _)| *expires_at > now);
+            if entries.len() >= self.capacity {
+                return Ok(());
+            }
+        }
+        entries.insert(key.to_string(), (now + ttl, response));
+        Ok(())
+    }
+}
+
+/// Middleware for caching responses.
+///
+/// Successful responses to `GET` and `HEAD` requests are stored in a
+/// [`CacheStorage`], keyed by the method, the URI and the values of the
+/// headers specified with [`Cache::vary_header`]. A stored response is only
+/// used for requests with the same values of the headers listed in its `Vary`
+/// header, and responses with `Vary: *` are never stored.
+///
+/// The `Cache-Control` header of the response is honored, responses marked as
+/// `no-store`, `no-cache` or `private` are not stored, and `s-maxage` or
+/// `max-age` take precedence over the configured TTL. Responses which set
+/// cookies or whose body is not in memory are never stored, and responses to
+/// requests with an `Authorization` header are only stored if they are marked
+/// as `public` or specify `s-maxage`.
+///
+/// If the storage fails to load or store a response, the request is passed
+/// to the inner endpoint as if there were no stored response.
+///
+/// The following `Cache-Control` request directives are supported:
+///
+/// - `no-store` bypasses the cache.
+/// - `no-cache` skips the stored response and refreshes it.
+/// - `max-age` skips stored responses older than the specified age.
+/// - `only-if-cached` responds with `504 Gateway Timeout` when there is no
+///   stored response.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use poem::{
+///     get, handler,
+///     middleware::{Cache, MemoryCacheStorage},
+///     EndpointExt, Route,
+/// };
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// let app = Route::new().at("/", get(index)).with(
+///     Cache::new(MemoryCacheStorage::default())
+///         .ttl(Duration::from_secs(30))
+///         .vary_header("accept-language"),
+/// );
+/// ```
+pub struct Cache<S> {
+    storage: Arc<S>,
+    ttl: Duration,
+    vary_headers: Vec<HeaderName>,
+    max_body_size: usize,
+}
+
+impl<S: CacheStorage> Cache<S> {
+    /// Create a `Cache` middleware with the specified storage.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage: Arc::new(storage),
+            ttl: DEFAULT_TTL,
+            vary_headers: Vec::new(),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+
+    /// Specify how long responses are stored when they do not specify it with
+    /// the `Cache-Control` header. (default to `60s`)
+    #[must_use]
+    pub fn ttl(self, ttl: Duration) -> Self {
+        Self { ttl, ..self }
+    }
+
+    /// Adds a request header whose value is part of the cache key.
+    #[must_use]
+    pub fn vary_header<K>(mut self, key: K) -> Self
+    where
+        K: TryInto<HeaderName>,
+    {
+        if let Ok(key) = key.try_into() {
+            self.vary_headers.push(key);
+        }
+        self
+    }
+
+    /// Specify the maximum size of the response bodies to be stored. (default
+    /// to `1MB`)
+    #[must_use]
+    pub fn max_body_size(self, max_body_size: usize) -> Self {
+        Self {
+            max_body_size,
+            ..self
+        }
+    }
+}
+
+impl<E: Endpoint, S: CacheStorage> Middleware<E> for Cache<S> {
+    type Output = CacheEndpoint<E, S>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        CacheEndpoint {
+            inner: ep,
+            storage: self.storage.clone(),
+            ttl: self.ttl,
+            vary_headers: self.vary_headers.clone(),
+            max_body_size: self.max_body_size,
+        }
+    }
+}
+
+/// Endpoint for Cache middleware.
+pub struct CacheEndpoint<E, S> {
+    inner: E,
+    storage: Arc<S>,
+    ttl: Duration,
+    vary_headers: Vec<HeaderName>,
+    max_body_size: usize,
+}
+
+impl<E, S> CacheEndpoint<E, S> {
+    fn cache_key(&self, req: &Request) -> String {
+        let mut key = format!("{} {}", req.method(), req.uri());
+        for name in &self.vary_headers {
+            key.push('\n');
+            key.push_str(name.as_str());
+            key.push(':');
+            for (idx, value) in req.headers().get_all(name).iter().enumerate() {
+                if idx > 0 {
+                    key.push(',');
+                }
+                key.push_str(&String::from_utf8_lossy(value.as_bytes()));
+            }
+        }
+        key
+    }
+
+    fn response_ttl(&self, resp: &Response, authorized: bool) -> Option<Duration> {
+        if resp.status() != StatusCode::OK || resp.headers().contains_key(header::SET_COOKIE) {
+            return None;
+        }
+        let ttl = match resp.headers().typed_get::<CacheControl>() {
+            Some(cc) if cc.no_store() || cc.no_cache() || cc.private() => return None,
+            Some(cc) if authorized && !cc.public() && cc.s_max_age().is_none() => return None,
+            Some(cc) => cc.s_max_age().or_else(|| cc.max_age()).unwrap_or(self.ttl),
+            None if authorized => return None,
+            None => self.ttl,
+        };
+        (!ttl.is_zero()).then_some(ttl)
+    }
+}
+
+/// Returns the header names listed in the `Vary` header, or `None` if it
+/// contains `*`.
+fn vary_names(headers: &HeaderMap) -> Option<Vec<HeaderName>> {
+    let mut names = Vec::new();
+    for value in headers.get_all(header::VARY) {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        for name in value.split(',').map(str::trim) {
+            if name == "*" {
+                return None;
+            }
+            if let Ok(name) = HeaderName::from_bytes(name.as_bytes()) {
+                names.push(name);
+            }
+        }
+    }
+    Some(names)
+}
+
+fn vary_matches(cached: &CachedResponse, headers: &HeaderMap) -> bool {
+    let Some(names) = vary_names(&cached.headers) else {
+        return false;
+    };
+    names.iter().all(|name| {
+        headers
+            .get_all(name)
+            .iter()
+            .eq(cached.vary.get_all(name).iter())
+    })
+}
+
+impl<E: Endpoint, S: CacheStorage> Endpoint for CacheEndpoint<E, S> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let cache_control = req.headers().typed_get::<CacheControl>();
+        let cache_control = cache_control.as_ref();
+        if !matches!(*req.method(), Method::GET | Method::HEAD)
+            || cache_control.is_some_and(CacheControl::no_store)
+        {
+            return self.inner.call(req).await.map(IntoResponse::into_response);
+        }
+
+        let key = self.cache_key(&req);
+        if !cache_control.is_some_and(CacheControl::no_cache) {
+            let cached = self.storage.get(&key).await.ok().flatten();
+            if let Some(cached) = cached.filter(|cached| vary_matches(cached, req.headers())) {
+                let age = SystemTime::now()
+                    .duration_since(cached.stored_at)
+                    .unwrap_or_default();
+                if cache_control
+                    .and_then(CacheControl::max_age)
+                    .filter(|max_age| age > *max_age)
+                    .is_none()
+                {
+                    let mut resp = Response::builder().status(cached.status).body(cached.body);
+                    *resp.headers_mut() = cached.headers;
+                    resp.headers_mut().insert(header::AGE, age.as_secs().into());
+                    return Ok(resp);
+                }
+            }
+        }
+
+        if cache_control.is_some_and(CacheControl::only_if_cached) {
+            return Ok(StatusCode::GATEWAY_TIMEOUT.into_response());
+        }
+
+        let authorized = req.headers().contains_key(header::AUTHORIZATION);
+        let request_headers = req.headers().clone();
+        let mut resp = self.inner.call(req).await?.into_response();
+        let Some(ttl) = self.response_ttl(&resp, authorized) else {
+            return Ok(resp);
+        };
+        let Some(vary_names) = vary_names(resp.headers()) else {
+            return Ok(resp);
+        };
+        let mut vary = HeaderMap::new();
+        for name in vary_names {
+            for value in request_headers.get_all(&name) {
+                vary.append(name.clone(), value.clone());
+            }
+        }
+
+        let body = resp.take_body();
+        if !matches!(body.exact_size(), Some(size) if size <= self.max_body_size as u64) {
+            resp.set_body(body);
+            return Ok(resp);
+        }
+        let data = body.into_bytes().await?;
+        let cached = CachedResponse {
+            status: resp.status(),
+            headers: resp.headers().clone(),
+            body: data.clone(),
+            vary,
+            stored_at: SystemTime::now(),
+        };
+        // a failure to store the response must not fail the request
+        let _ = self.storage.set(&key, cached, ttl).await;
+        resp.set_body(data);
+        Ok(resp)
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("Cache", self.inner.middleware_chains())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{
+        endpoint::{make_sync, EndpointExt},
+        test::TestClient,
+    };
+
+    fn counter_endpoint(counter: Arc<AtomicUsize>) -> impl Endpoint<Output = impl IntoResponse> {
+        make_sync(move |req| {
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            let lang = req
+                .headers()
+                .get("accept-language")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            format!("{lang}{n}")
+        })
+    }
+
+    #[tokio::test]
+    async fn cache() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let cli = TestClient::new(
+            counter_endpoint(counter.clone())
+                .with(Cache::new(MemoryCacheStorage::default()).vary_header("accept-language")),
+        );
+
+        cli.get("/").send().await.assert_text("0").await;
+        let resp = cli.get("/").send().await;
+        resp.assert_header(header::AGE, "0");
+        resp.assert_text("0").await;
+
+        cli.get("/a").send().await.assert_text("1").await;
+        cli.get("/")
+            .header("accept-language", "en")
+            .send()
+            .await
+            .assert_text("en2")
+            .await;
+        cli.get("/")
+            .header("accept-language", "en")
+            .send()
+            .await
+            .assert_text("en2")
+            .await;
+
+        cli.post("/").send().await.assert_text("3").await;
+        cli.post("/").send().await.assert_text("4").await;
+    }
+
+    #[tokio::test]
+    async fn request_cache_control() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let cli = TestClient::new(
+            counter_endpoint(counter.clone()).with(Cache::new(MemoryCacheStorage::default())),
+        );
+
+        cli.get("/")
+            .header(header::CACHE_CONTROL, "only-if-cached")
+            .send()
+            .await
+            .assert_status(StatusCode::GATEWAY_TIMEOUT);
+
+        cli.get("/")
+            .header(header::CACHE_CONTROL, "no-store")
+            .send()
+            .await
+            .assert_text("0")
+            .await;
+        cli.get("/").send().await.assert_text("1").await;
+        cli.get("/")
+            .header(header::CACHE_CONTROL, "no-cache")
+            .send()
+            .await
+            .assert_text("2")
+            .await;
+        cli.get("/")
+            .header(header::CACHE_CONTROL, "max-age=60")
+            .send()
+            .await
+            .assert_text("2")
+            .await;
+        cli.get("/")
+            .header(header::CACHE_CONTROL, "only-if-cached")
+            .send()
+            .await
+            .assert_text("2")
+            .await;
+    }
+
+    #[tokio::test]
+    async fn response_cache_control() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let cli = TestClient::new(
+            make_sync(move |_| {
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                n.to_string()
+                    .with_header(header::CACHE_CONTROL, "private, max-age=60")
+            })
+            .with(Cache::new(MemoryCacheStorage::default())),
+        );
+        cli.get("/").send().await.assert_text("0").await;
+        cli.get("/").send().await.assert_text("1").await;
+    }
+
+    #[tokio::test]
+    async fn authorization() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let cli = TestClient::new(
+            make_sync(move |req| {
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                let resp = n.to_string().into_response();
+                match req.uri().path() {
+                    "/public" => resp
+                        .with_header(header::CACHE_CONTROL, "public, max-age=60")
+                        .into_response(),
+                    _ => resp,
+                }
+            })
+            .with(Cache::new(MemoryCacheStorage::default())),
+        );
+
+        for expected in ["0", "1"] {
+            cli.get("/")
+                .header(header::AUTHORIZATION, "Bearer abc")
+                .send()
+                .await
+                .assert_text(expected)
+                .await;
+        }
+
+        cli.get("/public")
+            .header(header::AUTHORIZATION, "Bearer abc")
+            .send()
+            .await
+            .assert_text("2")
+            .await;
+        cli.get("/public").send().await.assert_text("2").await;
+    }
+
+    #[tokio::test]
+    async fn response_vary() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let cli = TestClient::new(
+            make_sync(move |req| {
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                let vary = match req.uri().path() {
+                    "/star" => "*",
+                    _ => "Accept-Encoding",
+                };
+                n.to_string().with_header(header::VARY, vary)
+            })
+            .with(Cache::new(MemoryCacheStorage::default())),
+        );
+
+        cli.get("/")
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .send()
+            .await
+            .assert_text("0")
+            .await;
+        cli.get("/")
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .send()
+            .await
+            .assert_text("0")
+            .await;
+        cli.get("/").send().await.assert_text("1").await;
+        cli.get("/").send().await.assert_text("1").await;
+
+        cli.get("/star").send().await.assert_text("2").await;
+        cli.get("/star").send().await.assert_text("3").await;
+    }
+
+    #[tokio::test]
+    async fn storage_error() {
+        struct FailingStorage;
+
+        impl CacheStorage for FailingStorage {
+            async fn get<'a>(&'a self, _key: &'a str) -> Result<Option<CachedResponse>> {
+                Err(crate::Error::from_status(StatusCode::SERVICE_UNAVAILABLE))
+            }
+
+            async fn set<'a>(
+                &'a self,
+                _key: &'a str,
+                _response: CachedResponse,
+                _ttl: Duration,
+            ) -> Result<()> {
+                Err(crate::Error::from_status(StatusCode::SERVICE_UNAVAILABLE))
+            }
+        }
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let cli = TestClient::new(counter_endpoint(counter).with(Cache::new(FailingStorage)));
+        cli.get("/").send().await.assert_text("0").await;
+        cli.get("/").send().await.assert_text("1").await;
+    }
+
+    #[tokio::test]
+    async fn memory_storage_expires() {
+        let storage = MemoryCacheStorage::new(1);
+        let response = CachedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(b"hello"),
+            vary: HeaderMap::new(),
+            stored_at: SystemTime::now(),
+        };
+
+        storage
+            .set("a", response.clone(), Duration::from_millis(10))
+            .await
+            .unwrap();
+        storage
+            .set("b", response.clone(), Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(storage.get("a").await.unwrap().is_some());
+        assert!(storage.get("b").await.unwrap().is_none());
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(storage.get("a").await.unwrap().is_none());
+        storage
+            .set("b", response, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(storage.get("b").await.unwrap().is_some());
+    }
+}
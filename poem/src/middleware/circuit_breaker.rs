@@ -0,0 +1,427 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use crate::{
+    endpoint::MiddlewareChain, error::CircuitOpenError, Endpoint, IntoResponse, Middleware,
+    Request, Response, Result,
+};
+
+enum State {
+    Closed { outcomes: VecDeque<bool> },
+    Open { until: Instant },
+    HalfOpen { permits: usize, successes: usize },
+}
+
+struct Config {
+    failure_rate: f64,
+    window_size: usize,
+    minimum_requests: usize,
+    open_duration: Duration,
+    half_open_requests: usize,
+}
+
+struct Breaker {
+    config: Config,
+    state: Mutex<State>,
+}
+
+impl Breaker {
+    /// Returns `None` if the request must fail fast, otherwise whether the
+    /// request is a probe of the half-open circuit.
+    fn acquire(&self) -> Option<bool> {
+        let mut state = self.state.lock();
+        if let State::Open { until } = &*state {
+            if Instant::now() < *until {
+                return None;
+            }
+            *state = State::HalfOpen {
+                permits: 0,
+                successes: 0,
+            };
+        }
+        match &mut *state {
+            State::HalfOpen { permits, .. } => {
+                if *permits >= self.config.half_open_requests {
+                    return None;
+                }
+                *permits += 1;
+                Some(true)
+            }
+            _ => Some(false),
+        }
+    }
+
+    fn record(&self, success: bool) {
+        let mut state = self.state.lock();
+        match &mut *state {
+            State::Closed { outcomes } => {
+                outcomes.push_back(success);
+                if outcomes.len() > self.config.window_size {
+                    outcomes.pop_front();
+                }
+                let failures = outcomes.iter().filter(|success| !**success).count();
+                if outcomes.len() >= self.config.minimum_requests
+                    && failures as f64 >= outcomes.len() as f64 * self.config.failure_rate
+                {
+                    self.open(&mut state);
+                }
+            }
+            State::HalfOpen { successes, .. } => {
+                if !success {
+                    self.open(&mut state);
+                } else {
+                    *successes += 1;
+                    if *successes >= self.config.half_open_requests {
+                        *state = State::Closed {
+                            outcomes: VecDeque::new(),
+                        };
+                    }
+                }
+            }
+            State::Open { .. } => {}
+        }
+    }
+
+    fn open(&self, state: &mut State) {
+        *state = State::Open {
+            until: Instant::now() + self.config.open_duration,
+        };
+    }
+}
+
+/// Records a probe as failed if it is dropped before completion, so that a
+/// cancelled probe does not keep the circuit half-open forever. Other
+/// cancelled calls are not recorded.
+struct CallGuard<'a> {
+    breaker: &'a Breaker,
+    probe: bool,
+    completed: bool,
+}
+
+impl CallGuard<'_> {
+    fn complete(mut self, success: bool) {
+        self.completed = true;
+        self.breaker.record(success);
+    }
+}
+
+impl Drop for CallGuard<'_> {
+    fn drop(&mut self) {
+        if self.probe && !self.completed {
+            self.breaker.record(false);
+        }
+    }
+}
+
+/// Middleware implementing the circuit breaker pattern, intended to wrap
+/// endpoints calling an upstream service, such as proxies or gateways.
+///
+/// The circuit starts closed, and the outcomes of the most recent requests are
+/// recorded. Responses and errors with a `5xx` status code are counted as
+/// failures, other errors such as `404 Not Found` are not. When the failure rate reaches the threshold, the circuit opens
+/// and all requests fail fast with `503 Service Unavailable`.
+///
+/// After [`CircuitBreaker::open_duration`], the circuit becomes half-open and a
+/// limited number of probe requests are passed to the endpoint. If all of them
+/// succeed the circuit closes again, otherwise it reopens.
+///
+/// Each endpoint wrapped by this middleware has its own circuit.
+///
+/// # Errors
+///
+/// - [`CircuitOpenError`]
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use poem::{get, handler, middleware::CircuitBreaker, EndpointExt, Route};
+///
+/// #[handler]
+/// async fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// let app = Route::new().at(
+///     "/",
+///     get(index).with(
+///         CircuitBreaker::new()
+///             .failure_rate(0.5)
+///             .minimum_requests(20)
+///             .open_duration(Duration::from_secs(10)),
+///     ),
+/// );
+/// ```
+pub struct CircuitBreaker {
+    failure_rate: f64,
+    window_size: usize,
+    minimum_requests: usize,
+    open_duration: Duration,
+    half_open_requests: usize,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            failure_rate: 0.5,
+            window_size: 100,
+            minimum_requests: 10,
+            open_duration: Duration::from_secs(30),
+            half_open_requests: 1,
+        }
+    }
+}
+
+impl CircuitBreaker {
+    /// Creates a new `CircuitBreaker` middleware.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify the failure rate, greater than `0.0` and at most `1.0`, which
+    /// opens the circuit. (default to `0.5`)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `failure_rate` is not in the range `(0.0, 1.0]`.
+    #[must_use]
+    pub fn failure_rate(self, failure_rate: f64) -> Self {
+        assert!(
+            failure_rate > 0.0 && failure_rate <= 1.0,
+            "the failure rate must be greater than 0 and at most 1"
+        );
+        Self {
+            failure_rate,
+            ..self
+        }
+    }
+
+    /// Specify the number of most recent requests used to compute the failure
+    /// rate. (default to `100`)
+    #[must_use]
+    pub fn window_size(self, window_size: usize) -> Self {
+        Self {
+            window_size: window_size.max(1),
+            ..self
+        }
+    }
+
+    /// Specify the minimum number of recorded requests before the circuit can
+    /// open. (default to `10`)
+    #[must_use]
+    pub fn minimum_requests(self, minimum_requests: usize) -> Self {
+        Self {
+            minimum_requests,
+            ..self
+        }
+    }
+
+    /// Specify how long the circuit stays open before probing the endpoint.
+    /// (default to `30s`)
+    #[must_use]
+    pub fn open_duration(self, open_duration: Duration) -> Self {
+        Self {
+            open_duration,
+            ..self
+        }
+    }
+
+    /// Specify the number of probe requests allowed while the circuit is
+    /// half-open. (default to `1`)
+    #[must_use]
+    pub fn half_open_requests(self, half_open_requests: usize) -> Self {
+        Self {
+            half_open_requests: half_open_requests.max(1),
+            ..self
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for CircuitBreaker {
+    type Output = CircuitBreakerEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        CircuitBreakerEndpoint {
+            inner: ep,
+            breaker: Breaker {
+                config: Config {
+                    failure_rate: self.failure_rate,
+                    window_size: self.window_size,
+                    minimum_requests: self.minimum_requests,
+                    open_duration: self.open_duration,
+                    half_open_requests: self.half_open_requests,
+                },
+                state: Mutex::new(State::Closed {
+                    outcomes: VecDeque::new(),
+                }),
+            },
+        }
+    }
+}
+
+/// Endpoint for CircuitBreaker middleware.
+pub struct CircuitBreakerEndpoint<E> {
+    inner: E,
+    breaker: Breaker,
+}
+
+impl<E: Endpoint> Endpoint for CircuitBreakerEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let Some(probe) = self.breaker.acquire() else {
+            return Err(CircuitOpenError.into());
+        };
+
+        let guard = CallGuard {
+            breaker: &self.breaker,
+            probe,
+            completed: false,
+        };
+        match self.inner.call(req).await {
+            Ok(resp) => {
+                let resp = resp.into_response();
+                guard.complete(!resp.status().is_server_error());
+                Ok(resp)
+            }
+            Err(err) => {
+                guard.complete(!err.status().is_server_error());
+                Err(err)
+            }
+        }
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("CircuitBreaker", self.inner.middleware_chains())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    use http::StatusCode;
+
+    use super::*;
+    use crate::{
+        endpoint::{make_sync, EndpointExt},
+        test::TestClient,
+    };
+
+    #[tokio::test]
+    async fn circuit_breaker() {
+        let healthy = Arc::new(AtomicBool::new(false));
+        let ep = make_sync({
+            let healthy = healthy.clone();
+            move |_| {
+                if healthy.load(Ordering::SeqCst) {
+                    StatusCode::OK
+                } else {
+                    StatusCode::BAD_GATEWAY
+                }
+            }
+        })
+        .with(
+            CircuitBreaker::new()
+                .minimum_requests(4)
+                .failure_rate(0.5)
+                .open_duration(Duration::from_millis(50)),
+        );
+        let cli = TestClient::new(ep);
+
+        for _ in 0..4 {
+            cli.get("/")
+                .send()
+                .await
+                .assert_status(StatusCode::BAD_GATEWAY);
+        }
+        cli.get("/")
+            .send()
+            .await
+            .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+
+        // the probe fails and the circuit reopens
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        cli.get("/")
+            .send()
+            .await
+            .assert_status(StatusCode::BAD_GATEWAY);
+        cli.get("/")
+            .send()
+            .await
+            .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+
+        // the probe succeeds and the circuit closes
+        healthy.store(true, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        cli.get("/").send().await.assert_status_is_ok();
+        cli.get("/").send().await.assert_status_is_ok();
+    }
+
+    #[test]
+    fn cancelled_calls() {
+        let breaker = Breaker {
+            config: Config {
+                failure_rate: 0.5,
+                window_size: 10,
+                minimum_requests: 1,
+                open_duration: Duration::from_secs(60),
+                half_open_requests: 1,
+            },
+            state: Mutex::new(State::Closed {
+                outcomes: VecDeque::new(),
+            }),
+        };
+
+        // a cancelled call of the closed circuit is not recorded
+        drop(CallGuard {
+            breaker: &breaker,
+            probe: breaker.acquire().unwrap(),
+            completed: false,
+        });
+        assert!(
+            matches!(&*breaker.state.lock(), State::Closed { outcomes } if outcomes.is_empty())
+        );
+
+        // a cancelled probe reopens the circuit
+        *breaker.state.lock() = State::HalfOpen {
+            permits: 0,
+            successes: 0,
+        };
+        drop(CallGuard {
+            breaker: &breaker,
+            probe: breaker.acquire().unwrap(),
+            completed: false,
+        });
+        assert!(matches!(&*breaker.state.lock(), State::Open { .. }));
+    }
+
+    #[test]
+    #[should_panic(expected = "the failure rate must be greater than 0")]
+    fn zero_failure_rate() {
+        let _ = CircuitBreaker::new().failure_rate(0.0);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_failures() {
+        let ep =
+            make_sync(|_| StatusCode::NOT_FOUND).with(CircuitBreaker::new().minimum_requests(1));
+        let cli = TestClient::new(ep);
+
+        for _ in 0..4 {
+            cli.get("/")
+                .send()
+                .await
+                .assert_status(StatusCode::NOT_FOUND);
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::{collections::HashSet, str::FromStr};
 use headers::HeaderMap;
 
 use crate::{
+    endpoint::MiddlewareChain,
     http::header,
     web::{Compress, CompressionAlgo, CompressionLevel},
     Body, Endpoint, IntoResponse, Middleware, Request, Response, Result,
@@ -173,6 +174,10 @@ impl<E: Endpoint> Endpoint for CompressionEndpoint<E> {
             None => Ok(resp.into_response()),
         }
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("Compression", self.ep.middleware_chains())
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
+    endpoint::MiddlewareChain,
     web::cookie::{CookieJar, CookieKey},
     Endpoint, IntoResponse, Middleware, Request, Response, Result,
 };
@@ -64,6 +65,10 @@ impl<E: Endpoint> Endpoint for CookieJarManagerEndpoint<E> {
             self.inner.call(req).await.map(IntoResponse::into_response)
         }
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("CookieJarManager", self.inner.middleware_chains())
+    }
 }
 
 #[cfg(test)]
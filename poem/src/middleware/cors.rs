@@ -1,12 +1,13 @@
 use std::{collections::HashSet, str::FromStr, sync::Arc};
 
 use headers::{
-    AccessControlAllowHeaders, AccessControlAllowMethods, AccessControlExposeHeaders, HeaderMapExt,
+    AccessControlAllowHeaders, AccessControlAllowMethods, AccessControlExposeHeaders, Allow,
+    HeaderMapExt,
 };
 use wildmatch::WildMatch;
 
 use crate::{
-    endpoint::Endpoint,
+    endpoint::{Endpoint, MiddlewareChain},
     error::CorsError,
     http::{
         header,
@@ -16,6 +17,7 @@ use crate::{
     middleware::Middleware,
     request::Request,
     response::Response,
+    route::AllowedMethodsProbe,
     IntoResponse, Result,
 };
 
@@ -44,6 +46,7 @@ pub struct Cors {
     allow_origins_fn: Option<Arc<dyn Fn(&str) -> bool + Send + Sync>>,
     allow_headers: HashSet<HeaderName>,
     allow_methods: HashSet<Method>,
+    allow_methods_from_route: bool,
     expose_headers: HashSet<HeaderName>,
     max_age: i32,
 }
@@ -121,6 +124,58 @@ impl Cors {
             .fold(self, |cors, method| cors.allow_method(method))
     }
 
+    /// Specifies whether to allow the methods of the requested path.
+    /// (defaults to `false`)
+    ///
+    /// The preflight request is routed to the [`RouteMethod`](crate::RouteMethod)
+    /// of the path, which returns the methods registered for it, such as the
+    /// operations of an OpenAPI service, without calling any endpoint. The
+    /// preflight requests of the paths which are not found, or not routed by
+    /// a `RouteMethod`, are rejected, and the endpoints of the paths not
+    /// routed by a `RouteMethod` are called with the preflight request.
+    ///
+    /// It is ignored if the allowed methods are specified with
+    /// [`Cors::allow_method`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{
+    ///     get, handler,
+    ///     http::{header, StatusCode},
+    ///     middleware::Cors,
+    ///     test::TestClient,
+    ///     EndpointExt, Route,
+    /// };
+    ///
+    /// #[handler]
+    /// fn index() -> &'static str {
+    ///     "hello"
+    /// }
+    ///
+    /// let app = Route::new()
+    ///     .at("/", get(index))
+    ///     .with(Cors::new().allow_methods_from_route(true));
+    /// let cli = TestClient::new(app);
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let resp = cli
+    ///     .options("/")
+    ///     .header(header::ORIGIN, "https://example.com")
+    ///     .header(header::ACCESS_CONTROL_REQUEST_METHOD, "DELETE")
+    ///     .send()
+    ///     .await;
+    /// resp.assert_status(StatusCode::FORBIDDEN);
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn allow_methods_from_route(self, enable: bool) -> Self {
+        Self {
+            allow_methods_from_route: enable,
+            ..self
+        }
+    }
+
     /// Add an allow origin.
     ///
     /// NOTE: Default is allow any origin.
@@ -217,6 +272,7 @@ impl<E: Endpoint> Middleware<E> for Cors {
             allow_origins_fn: self.allow_origins_fn.clone(),
             allow_headers: self.allow_headers.clone(),
             allow_methods: self.allow_methods.clone(),
+            allow_methods_from_route: self.allow_methods_from_route,
             expose_headers: self.expose_headers.clone(),
             allow_headers_header: self.allow_headers.clone().into_iter().collect(),
             allow_methods_header: self.allow_methods.clone().into_iter().collect(),
@@ -236,6 +292,7 @@ pub struct CorsEndpoint<E> {
     allow_origins_fn: Option<Arc<dyn Fn(&str) -> bool + Send + Sync>>,
     allow_headers: HashSet<HeaderName>,
     allow_methods: HashSet<Method>,
+    allow_methods_from_route: bool,
     expose_headers: HashSet<HeaderName>,
     allow_headers_header: AccessControlAllowHeaders,
     allow_methods_header: AccessControlAllowMethods,
@@ -277,13 +334,20 @@ impl<E: Endpoint> CorsEndpoint<E> {
         &self,
         origin: &HeaderValue,
         request_headers: Option<&HeaderValue>,
+        route_methods: Option<HashSet<Method>>,
     ) -> Response {
         let mut builder = Response::builder()
             .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin)
             .typed_header(self.expose_headers_header.clone())
             .header(header::ACCESS_CONTROL_MAX_AGE, self.max_age);
 
-        if self.allow_methods.is_empty() {
+        if let Some(route_methods) = route_methods {
+            builder = builder.typed_header(
+                route_methods
+                    .into_iter()
+                    .collect::<AccessControlAllowMethods>(),
+            );
+        } else if self.allow_methods.is_empty() {
             builder = builder.typed_header(
                 [
                     Method::GET,
@@ -367,29 +431,50 @@ impl<E: Endpoint> Endpoint for CorsEndpoint<E> {
         }
 
         if req.method() == Method::OPTIONS {
-            let allow_method = req
+            let request_method = req
                 .headers()
                 .get(header::ACCESS_CONTROL_REQUEST_METHOD)
                 .and_then(|value| value.to_str().ok())
-                .and_then(|value| value.parse::<Method>().ok())
-                .map(|method| {
-                    if self.allow_methods.is_empty() {
-                        true
-                    } else {
-                        self.allow_methods.contains(&method)
-                    }
-                });
+                .and_then(|value| value.parse::<Method>().ok());
+            let (allow_headers, request_headers) = self.check_allow_headers(&req);
+            let request_headers = request_headers.cloned();
+
+            let route_methods = if self.allow_methods_from_route
+                && self.allow_methods.is_empty()
+                && request_method.is_some()
+            {
+                let mut req = req;
+                req.extensions_mut().insert(AllowedMethodsProbe);
+                Some(match self.inner.call(req).await {
+                    Ok(resp) => resp
+                        .into_response()
+                        .headers()
+                        .typed_get::<Allow>()
+                        .map(|allow| allow.iter().collect::<HashSet<_>>())
+                        .unwrap_or_default(),
+                    Err(_) => HashSet::new(),
+                })
+            } else {
+                None
+            };
+
+            let allow_method = request_method.map(|method| match &route_methods {
+                Some(route_methods) => route_methods.contains(&method),
+                None => self.allow_methods.is_empty() || self.allow_methods.contains(&method),
+            });
             if !matches!(allow_method, Some(true)) {
                 return Err(CorsError::MethodNotAllowed.into());
             }
 
-            let (allow_headers, request_headers) = self.check_allow_headers(&req);
-
             if !allow_headers {
                 return Err(CorsError::HeadersNotAllowed.into());
             }
 
-            return Ok(self.build_preflight_response(&origin, request_headers));
+            return Ok(self.build_preflight_response(
+                &origin,
+                request_headers.as_ref(),
+                route_methods,
+            ));
         }
 
         let mut resp = self.inner.get_response(req).await;
@@ -416,10 +501,16 @@ impl<E: Endpoint> Endpoint for CorsEndpoint<E> {
 
         Ok(resp)
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("Cors", self.inner.middleware_chains())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
     use http::StatusCode;
 
     use super::*;
@@ -723,4 +814,61 @@ mod tests {
         resp.assert_status_is_ok();
         resp.assert_header(header::ACCESS_CONTROL_ALLOW_HEADERS, "content-type");
     }
+
+    #[tokio::test]
+    async fn allow_methods_from_route() {
+        static CALLED: AtomicBool = AtomicBool::new(false);
+        let handler = || {
+            make_sync(|_| {
+                CALLED.store(true, Ordering::SeqCst);
+                "a"
+            })
+        };
+
+        let ep = crate::Route::new()
+            .at(
+                "/a",
+                crate::get(handler()).post(handler()).options(handler()),
+            )
+            .at("/b", crate::delete(handler()))
+            .at("/c", handler())
+            .with(
+                Cors::new()
+                    .allow_origin(ALLOW_ORIGIN)
+                    .allow_methods_from_route(true),
+            );
+        let cli = TestClient::new(ep);
+
+        let resp = cli
+            .options("/a")
+            .header(header::ORIGIN, ALLOW_ORIGIN)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_header_csv(
+            header::ACCESS_CONTROL_ALLOW_METHODS,
+            ["GET", "HEAD", "OPTIONS", "POST"],
+        );
+
+        for path in ["/b", "/not-found"] {
+            let resp = cli
+                .options(path)
+                .header(header::ORIGIN, ALLOW_ORIGIN)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                .send()
+                .await;
+            resp.assert_status(StatusCode::FORBIDDEN);
+        }
+
+        // the endpoints of `RouteMethod` are never called by the preflight requests
+        assert!(!CALLED.load(Ordering::SeqCst));
+
+        cli.options("/c")
+            .header(header::ORIGIN, ALLOW_ORIGIN)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+            .send()
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+    }
 }
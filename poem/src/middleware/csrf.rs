@@ -7,6 +7,7 @@ use libcsrf::{
 };
 
 use crate::{
+    endpoint::MiddlewareChain,
     middleware::{CookieJarManager, CookieJarManagerEndpoint},
     web::{
         cookie::{Cookie, SameSite},
@@ -228,6 +229,10 @@ impl<E: Endpoint> Endpoint for CsrfEndpoint<E> {
 
         self.inner.call(req).await
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("Csrf", self.inner.middleware_chains())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,263 @@
+use std::str::FromStr;
+
+use headers::IfNoneMatch;
+use http::{header, Method, StatusCode};
+
+use crate::{
+    endpoint::MiddlewareChain, web::headers::HeaderMapExt, Endpoint, IntoResponse, Middleware,
+    Request, Response, Result,
+};
+
+const DEFAULT_MAX_SIZE: usize = 1024 * 1024;
+
+/// Middleware for generating the `ETag` header of dynamic responses.
+///
+/// For successful `GET` and `HEAD` requests, it computes the entity tag from
+/// the response body, and replies with `304 Not Modified` when it matches the
+/// request `If-None-Match` header.
+///
+/// Only responses whose body is already in memory are handled, streaming
+/// bodies, bodies larger than [`ETag::max_size`] and responses that already
+/// have an `ETag` header are left untouched.
+///
+/// # Example
+///
+/// ```
+/// use http::{header, StatusCode};
+/// use poem::{get, handler, middleware::ETag, test::TestClient, EndpointExt, Route};
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// let app = Route::new().at("/", get(index)).with(ETag::new());
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli.get("/").send().await;
+/// resp.assert_status_is_ok();
+/// let etag = resp.0.headers().get(header::ETAG).cloned().unwrap();
+///
+/// let resp = cli
+///     .get("/")
+///     .header(header::IF_NONE_MATCH, etag)
+///     .send()
+///     .await;
+/// resp.assert_status(StatusCode::NOT_MODIFIED);
+/// # });
+/// ```
+pub struct ETag {
+    max_size: usize,
+    weak: bool,
+}
+
+impl Default for ETag {
+    fn default() -> Self {
+        Self {
+            max_size: DEFAULT_MAX_SIZE,
+            weak: true,
+        }
+    }
+}
+
+impl ETag {
+    /// Creates a new `ETag` middleware.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify the maximum size of the response bodies to be hashed. (default
+    /// to `1MB`)
+    #[must_use]
+    pub fn max_size(self, max_size: usize) -> Self {
+        Self { max_size, ..self }
+    }
+
+    /// Specify whether to generate weak entity tags. (default to `true`)
+    ///
+    /// Weak tags remain valid when the body is later transformed, for example
+    /// by the [`Compression`](crate::middleware::Compression) middleware.
+    #[must_use]
+    pub fn weak(self, weak: bool) -> Self {
+        Self { weak, ..self }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for ETag {
+    type Output = ETagEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        ETagEndpoint {
+            inner: ep,
+            max_size: self.max_size,
+            weak: self.weak,
+        }
+    }
+}
+
+/// Endpoint for ETag middleware.
+pub struct ETagEndpoint<E> {
+    inner: E,
+    max_size: usize,
+    weak: bool,
+}
+
+impl<E: Endpoint> Endpoint for ETagEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let is_get = matches!(*req.method(), Method::GET | Method::HEAD);
+        let if_none_match = req.headers().typed_get::<IfNoneMatch>();
+        let mut resp = self.inner.call(req).await?.into_response();
+
+        if !is_get || resp.status() != StatusCode::OK || resp.headers().contains_key(header::ETAG) {
+            return Ok(resp);
+        }
+
+        let body = resp.take_body();
+        match body.exact_size() {
+            Some(size) if size <= self.max_size as u64 => {}
+            _ => {
+                resp.set_body(body);
+                return Ok(resp);
+            }
+        }
+
+        let data = body.into_bytes().await?;
+        let etag = format!(
+            "{}\"{:x}-{:016x}\"",
+            if self.weak { "W/" } else { "" },
+            data.len(),
+            fnv1a(&data)
+        );
+        resp.headers_mut()
+            .insert(header::ETAG, etag.parse().expect("valid header value"));
+
+        let not_modified = match (if_none_match, headers::ETag::from_str(&etag)) {
+            (Some(if_none_match), Ok(etag)) => !if_none_match.precondition_passes(&etag),
+            _ => false,
+        };
+        if not_modified {
+            resp.set_status(StatusCode::NOT_MODIFIED);
+            resp.headers_mut().remove(header::CONTENT_LENGTH);
+        } else {
+            resp.set_body(data);
+        }
+
+        Ok(resp)
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("ETag", self.inner.middleware_chains())
+    }
+}
+
+/// 64-bit FNV-1a, which is stable across processes and Rust versions so the
+/// generated tags stay valid when the server is restarted or scaled out.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+
+    use super::*;
+    use crate::{
+        endpoint::{make_sync, EndpointExt},
+        test::TestClient,
+        Body,
+    };
+
+    #[tokio::test]
+    async fn etag() {
+        let cli = TestClient::new(make_sync(|_| "hello").with(ETag::new()));
+
+        let resp = cli.get("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_header(header::ETAG, "W/\"5-a430d84680aabd0b\"");
+        resp.assert_text("hello").await;
+
+        let resp = cli
+            .get("/")
+            .header(header::IF_NONE_MATCH, "\"a\", W/\"5-a430d84680aabd0b\"")
+            .send()
+            .await;
+        resp.assert_status(StatusCode::NOT_MODIFIED);
+        resp.assert_text("").await;
+
+        let resp = cli.get("/").header(header::IF_NONE_MATCH, "*").send().await;
+        resp.assert_status(StatusCode::NOT_MODIFIED);
+
+        let resp = cli
+            .get("/")
+            .header(header::IF_NONE_MATCH, "W/\"other\"")
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_text("hello").await;
+
+        let resp = cli.post("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_header_is_not_exist(header::ETAG);
+    }
+
+    #[tokio::test]
+    async fn strong_etag() {
+        let cli = TestClient::new(make_sync(|_| "hello").with(ETag::new().weak(false)));
+
+        let resp = cli.get("/").send().await;
+        resp.assert_header(header::ETAG, "\"5-a430d84680aabd0b\"");
+
+        let resp = cli
+            .get("/")
+            .header(header::IF_NONE_MATCH, "\"5-a430d84680aabd0b\"")
+            .send()
+            .await;
+        resp.assert_status(StatusCode::NOT_MODIFIED);
+    }
+
+    #[tokio::test]
+    async fn skipped_responses() {
+        let cli = TestClient::new(make_sync(|_| "hello").with(ETag::new().max_size(4)));
+        cli.get("/")
+            .send()
+            .await
+            .assert_header_is_not_exist(header::ETAG);
+
+        let cli = TestClient::new(
+            make_sync(|_| {
+                Body::from_bytes_stream(stream::iter(vec![Ok::<_, std::io::Error>(
+                    bytes::Bytes::from_static(b"hello"),
+                )]))
+            })
+            .with(ETag::new()),
+        );
+        let resp = cli.get("/").send().await;
+        resp.assert_header_is_not_exist(header::ETAG);
+        resp.assert_text("hello").await;
+
+        let cli = TestClient::new(
+            make_sync(|_| {
+                "hello"
+                    .with_header(header::ETAG, "\"custom\"")
+                    .with_status(StatusCode::OK)
+            })
+            .with(ETag::new()),
+        );
+        cli.get("/")
+            .send()
+            .await
+            .assert_header(header::ETAG, "\"custom\"");
+
+        let cli = TestClient::new(make_sync(|_| StatusCode::NOT_FOUND).with(ETag::new()));
+        cli.get("/")
+            .send()
+            .await
+            .assert_header_is_not_exist(header::ETAG);
+    }
+}
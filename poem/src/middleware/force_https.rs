@@ -2,7 +2,10 @@ use std::{borrow::Cow, sync::Arc};
 
 use http::{header, uri::Scheme, Uri};
 
-use crate::{web::Redirect, Endpoint, IntoResponse, Middleware, Request, Response, Result};
+use crate::{
+    endpoint::MiddlewareChain, web::Redirect, Endpoint, IntoResponse, Middleware, Request,
+    Response, Result,
+};
 
 type FilterFn = Arc<dyn Fn(&Request) -> bool + Send + Sync>;
 
@@ -86,6 +89,10 @@ where
 
         self.inner.call(req).await.map(IntoResponse::into_response)
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("ForceHttps", self.inner.middleware_chains())
+    }
 }
 
 fn redirect_host(host: &str, https_port: Option<u16>) -> Cow<'_, str> {
@@ -0,0 +1,264 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use bytes::Bytes;
+use http::{header, StatusCode};
+use wildmatch::WildMatch;
+
+use crate::{
+    endpoint::MiddlewareChain, Endpoint, IntoResponse, Middleware, Request, Response, Result,
+};
+
+/// A shared switch to turn the maintenance mode of the [`Maintenance`]
+/// middleware on and off at runtime.
+///
+/// The switch is cheap to clone, and all clones control the same state.
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceSwitch(Arc<AtomicBool>);
+
+impl MaintenanceSwitch {
+    /// Creates a new switch with the maintenance mode disabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables the maintenance mode.
+    pub fn enable(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Disables the maintenance mode.
+    pub fn disable(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the maintenance mode is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Middleware that rejects requests with `503 Service Unavailable` while the
+/// maintenance mode is enabled, so that the traffic can be drained during
+/// migrations.
+///
+/// The requests to the paths added with [`Maintenance::allow_path`], such as
+/// health checks or admin endpoints, are always passed to the endpoint.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use poem::{
+///     get, handler,
+///     http::StatusCode,
+///     middleware::{Maintenance, MaintenanceSwitch},
+///     test::TestClient,
+///     EndpointExt, Route,
+/// };
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// let switch = MaintenanceSwitch::new();
+/// let app = Route::new()
+///     .at("/", get(index))
+///     .at("/health", get(index))
+///     .with(
+///         Maintenance::new(switch.clone())
+///             .retry_after(Duration::from_secs(120))
+///             .body("down for maintenance")
+///             .allow_path("/health"),
+///     );
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// cli.get("/").send().await.assert_status_is_ok();
+///
+/// switch.enable();
+/// let resp = cli.get("/").send().await;
+/// resp.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+/// resp.assert_header("retry-after", "120");
+/// resp.assert_text("down for maintenance").await;
+///
+/// cli.get("/health").send().await.assert_status_is_ok();
+/// # });
+/// ```
+pub struct Maintenance {
+    switch: MaintenanceSwitch,
+    retry_after: Option<Duration>,
+    content_type: Option<String>,
+    body: Bytes,
+    allow_paths: Vec<WildMatch>,
+}
+
+impl Maintenance {
+    /// Creates a new `Maintenance` middleware controlled by the specified
+    /// switch.
+    #[must_use]
+    pub fn new(switch: MaintenanceSwitch) -> Self {
+        Self {
+            switch,
+            retry_after: None,
+            content_type: None,
+            body: Bytes::new(),
+            allow_paths: Vec::new(),
+        }
+    }
+
+    /// Sets the value of the `Retry-After` header, which is rounded to
+    /// seconds.
+    #[must_use]
+    pub fn retry_after(self, retry_after: Duration) -> Self {
+        Self {
+            retry_after: Some(retry_after),
+            ..self
+        }
+    }
+
+    /// Sets the body of the rejected responses. (default to an empty body)
+    #[must_use]
+    pub fn body(self, body: impl Into<Bytes>) -> Self {
+        Self {
+            body: body.into(),
+            ..self
+        }
+    }
+
+    /// Sets the `Content-Type` of the rejected responses.
+    #[must_use]
+    pub fn content_type(self, content_type: impl Into<String>) -> Self {
+        Self {
+            content_type: Some(content_type.into()),
+            ..self
+        }
+    }
+
+    /// Adds a path that is still served in maintenance mode, `*` matches any
+    /// sequence of characters, such as `/admin/*`.
+    #[must_use]
+    pub fn allow_path(mut self, path: impl AsRef<str>) -> Self {
+        self.allow_paths.push(WildMatch::new(path.as_ref()));
+        self
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for Maintenance {
+    type Output = MaintenanceEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        MaintenanceEndpoint {
+            inner: ep,
+            switch: self.switch.clone(),
+            retry_after: self.retry_after,
+            content_type: self.content_type.clone(),
+            body: self.body.clone(),
+            allow_paths: self.allow_paths.clone(),
+        }
+    }
+}
+
+/// Endpoint for Maintenance middleware.
+pub struct MaintenanceEndpoint<E> {
+    inner: E,
+    switch: MaintenanceSwitch,
+    retry_after: Option<Duration>,
+    content_type: Option<String>,
+    body: Bytes,
+    allow_paths: Vec<WildMatch>,
+}
+
+impl<E: Endpoint> Endpoint for MaintenanceEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        if !self.switch.is_enabled()
+            || self
+                .allow_paths
+                .iter()
+                .any(|path| path.matches(req.uri().path()))
+        {
+            return self.inner.call(req).await.map(IntoResponse::into_response);
+        }
+
+        let mut resp = Response::builder().status(StatusCode::SERVICE_UNAVAILABLE);
+        if let Some(retry_after) = self.retry_after {
+            resp = resp.header(header::RETRY_AFTER, retry_after.as_secs());
+        }
+        if let Some(content_type) = &self.content_type {
+            resp = resp.content_type(content_type);
+        }
+        Ok(resp.body(self.body.clone()))
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("Maintenance", self.inner.middleware_chains())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        endpoint::{make_sync, EndpointExt},
+        test::TestClient,
+    };
+
+    #[tokio::test]
+    async fn maintenance() {
+        let switch = MaintenanceSwitch::new();
+        let ep = make_sync(|_| "hello").with(
+            Maintenance::new(switch.clone())
+                .retry_after(Duration::from_millis(30500))
+                .content_type("application/json")
+                .body(r#"{"error":"maintenance"}"#)
+                .allow_path("/health")
+                .allow_path("/admin/*"),
+        );
+        let cli = TestClient::new(ep);
+
+        cli.get("/a").send().await.assert_text("hello").await;
+
+        switch.enable();
+        assert!(switch.is_enabled());
+        let resp = cli.get("/a").send().await;
+        resp.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+        resp.assert_header(header::RETRY_AFTER, "30");
+        resp.assert_content_type("application/json");
+        resp.assert_text(r#"{"error":"maintenance"}"#).await;
+
+        cli.get("/health").send().await.assert_text("hello").await;
+        cli.get("/admin/users")
+            .send()
+            .await
+            .assert_text("hello")
+            .await;
+        cli.get("/health/a")
+            .send()
+            .await
+            .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+
+        switch.disable();
+        cli.get("/a").send().await.assert_text("hello").await;
+    }
+
+    #[tokio::test]
+    async fn maintenance_defaults() {
+        let switch = MaintenanceSwitch::new();
+        switch.enable();
+        let cli = TestClient::new(make_sync(|_| "hello").with(Maintenance::new(switch)));
+
+        let resp = cli.get("/").send().await;
+        resp.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+        resp.assert_header_is_not_exist(header::RETRY_AFTER);
+        resp.assert_text("").await;
+    }
+}
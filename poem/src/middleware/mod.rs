@@ -1,7 +1,10 @@
 //! Commonly used middleware.
 
 mod add_data;
+mod alt_svc;
+mod cache;
 mod catch_panic;
+mod circuit_breaker;
 #[cfg(feature = "compression")]
 mod compression;
 #[cfg(feature = "cookie")]
@@ -9,23 +12,30 @@ mod cookie_jar_manager;
 mod cors;
 #[cfg(feature = "csrf")]
 mod csrf;
+mod etag;
 mod force_https;
+mod maintenance;
 mod normalize_path;
 #[cfg(feature = "opentelemetry")]
 mod opentelemetry_metrics;
 #[cfg(feature = "opentelemetry")]
 mod opentelemetry_tracing;
 mod propagate_header;
+mod request_limits;
 #[cfg(feature = "requestid")]
 mod requestid;
 mod sensitive_header;
 mod set_header;
+mod single_flight;
 mod size_limit;
+mod slow_request_log;
 #[cfg(feature = "tokio-metrics")]
 mod tokio_metrics_mw;
 #[cfg(feature = "tower-compat")]
 mod tower_compat;
 mod tracing_mw;
+#[cfg(feature = "hmac")]
+mod verify_hmac;
 
 #[cfg(feature = "compression")]
 pub use self::compression::{Compression, CompressionEndpoint};
@@ -43,16 +53,26 @@ pub use self::requestid::{ReqId, RequestId, RequestIdEndpoint, ReuseId};
 pub use self::tokio_metrics_mw::{TokioMetrics, TokioMetricsEndpoint};
 #[cfg(feature = "tower-compat")]
 pub use self::tower_compat::TowerLayerCompatExt;
+#[cfg(feature = "hmac")]
+pub use self::verify_hmac::{HmacAlgorithm, SignatureEncoding, VerifyHmac, VerifyHmacEndpoint};
 pub use self::{
     add_data::{AddData, AddDataEndpoint},
+    alt_svc::{AltSvc, AltSvcEndpoint},
+    cache::{Cache, CacheEndpoint, CacheStorage, CachedResponse, MemoryCacheStorage},
     catch_panic::{CatchPanic, CatchPanicEndpoint, PanicHandler},
+    circuit_breaker::{CircuitBreaker, CircuitBreakerEndpoint},
     cors::{Cors, CorsEndpoint},
+    etag::{ETag, ETagEndpoint},
     force_https::ForceHttps,
+    maintenance::{Maintenance, MaintenanceEndpoint, MaintenanceSwitch},
     normalize_path::{NormalizePath, NormalizePathEndpoint, TrailingSlash},
     propagate_header::{PropagateHeader, PropagateHeaderEndpoint},
+    request_limits::{RequestLimits, RequestLimitsEndpoint},
     sensitive_header::{SensitiveHeader, SensitiveHeaderEndpoint},
     set_header::{SetHeader, SetHeaderEndpoint},
+    single_flight::{SingleFlight, SingleFlightEndpoint},
     size_limit::{SizeLimit, SizeLimitEndpoint},
+    slow_request_log::{SlowRequest, SlowRequestLog, SlowRequestLogEndpoint},
     tracing_mw::{Tracing, TracingEndpoint},
 };
 use crate::endpoint::Endpoint;
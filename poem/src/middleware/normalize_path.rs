@@ -3,7 +3,7 @@ use std::str::FromStr;
 use http::{uri::PathAndQuery, Uri};
 use regex::Regex;
 
-use crate::{Endpoint, Middleware, Request, Result};
+use crate::{endpoint::MiddlewareChain, Endpoint, Middleware, Request, Result};
 
 /// Determines the behavior of the [`NormalizePath`] middleware.
 #[derive(Debug, Clone, Copy, Default)]
@@ -117,6 +117,10 @@ impl<E: Endpoint> Endpoint for NormalizePathEndpoint<E> {
 
         self.inner.call(req).await
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("NormalizePath", self.inner.middleware_chains())
+    }
 }
 
 #[cfg(test)]
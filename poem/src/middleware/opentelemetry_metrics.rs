@@ -7,7 +7,10 @@ use libopentelemetry::{
 };
 use opentelemetry_semantic_conventions::trace;
 
-use crate::{route::PathPattern, Endpoint, IntoResponse, Middleware, Request, Response, Result};
+use crate::{
+    endpoint::MiddlewareChain, route::PathPattern, Endpoint, IntoResponse, Middleware, Request,
+    Response, Result,
+};
 
 /// Middleware for metrics with OpenTelemetry.
 #[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
@@ -120,4 +123,8 @@ impl<E: Endpoint> Endpoint for OpenTelemetryMetricsEndpoint<E> {
 
         res
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("OpenTelemetryMetrics", self.inner.middleware_chains())
+    }
 }
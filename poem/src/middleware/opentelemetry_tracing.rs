@@ -9,6 +9,7 @@ use libopentelemetry::{
 use opentelemetry_semantic_conventions::{resource, trace};
 
 use crate::{
+    endpoint::MiddlewareChain,
     route::PathPattern,
     web::{headers::HeaderMapExt, RealIp},
     Endpoint, FromRequest, IntoResponse, Middleware, Request, Response, Result,
@@ -164,4 +165,8 @@ where
         .with_context(Context::current_with_span(span))
         .await
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("OpenTelemetryTracing", self.inner.middleware_chains())
+    }
 }
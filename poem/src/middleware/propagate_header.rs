@@ -2,7 +2,9 @@ use std::collections::HashSet;
 
 use http::{header::HeaderName, HeaderMap};
 
-use crate::{Endpoint, IntoResponse, Middleware, Request, Response, Result};
+use crate::{
+    endpoint::MiddlewareChain, Endpoint, IntoResponse, Middleware, Request, Response, Result,
+};
 
 /// Middleware for propagate a header from the request to the response.
 #[derive(Default)]
@@ -63,6 +65,10 @@ impl<E: Endpoint> Endpoint for PropagateHeaderEndpoint<E> {
         resp.headers_mut().extend(headers);
         Ok(resp)
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("PropagateHeader", self.inner.middleware_chains())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,188 @@
+use crate::{
+    endpoint::MiddlewareChain,
+    error::RequestLimitError,
+    http::{HeaderMap, Uri},
+    Endpoint, Middleware, Request, Result,
+};
+
+/// Middleware for limiting the length of the URI and the number and size of
+/// the headers of the requests.
+///
+/// The limits can also be applied to all the requests of a server with
+/// [`Server::request_limits`](crate::Server::request_limits), which also
+/// raises the limit of the HTTP/1 parser for the number of headers.
+///
+/// # Errors
+///
+/// - [`RequestLimitError`]
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     handler, http::StatusCode, middleware::RequestLimits, test::TestClient, EndpointExt,
+/// };
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// let ep = index.with(RequestLimits::new().max_uri_length(16).max_header_count(8));
+/// let cli = TestClient::new(ep);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// cli.get("/").send().await.assert_status_is_ok();
+/// cli.get("/a-very-long-path")
+///     .query("page", &1)
+///     .send()
+///     .await
+///     .assert_status(StatusCode::URI_TOO_LONG);
+/// # });
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RequestLimits {
+    max_uri_length: Option<usize>,
+    max_header_count: Option<usize>,
+    max_header_bytes: Option<usize>,
+}
+
+impl RequestLimits {
+    /// Create `RequestLimits` middleware without any limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum length of the request URI, longer URIs are rejected
+    /// with `414 URI Too Long`.
+    #[must_use]
+    pub fn max_uri_length(self, len: usize) -> Self {
+        Self {
+            max_uri_length: Some(len),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of the request headers, more headers are
+    /// rejected with `431 Request Header Fields Too Large`.
+    #[must_use]
+    pub fn max_header_count(self, count: usize) -> Self {
+        Self {
+            max_header_count: Some(count),
+            ..self
+        }
+    }
+
+    /// Sets the maximum total size of the names and values of the request
+    /// headers, larger headers are rejected with `431 Request Header Fields
+    /// Too Large`.
+    #[must_use]
+    pub fn max_header_bytes(self, size: usize) -> Self {
+        Self {
+            max_header_bytes: Some(size),
+            ..self
+        }
+    }
+
+    pub(crate) fn header_count(&self) -> Option<usize> {
+        self.max_header_count
+    }
+
+    pub(crate) fn check(&self, uri: &Uri, headers: &HeaderMap) -> Result<(), RequestLimitError> {
+        if let Some(max_len) = self.max_uri_length {
+            let len = uri
+                .path_and_query()
+                .map(|path_and_query| path_and_query.as_str().len())
+                .unwrap_or_default();
+            if len > max_len {
+                return Err(RequestLimitError::UriTooLong);
+            }
+        }
+
+        if let Some(max_count) = self.max_header_count {
+            if headers.len() > max_count {
+                return Err(RequestLimitError::TooManyHeaders);
+            }
+        }
+
+        if let Some(max_bytes) = self.max_header_bytes {
+            let size = headers
+                .iter()
+                .map(|(name, value)| name.as_str().len() + value.len())
+                .sum::<usize>();
+            if size > max_bytes {
+                return Err(RequestLimitError::HeadersTooLarge);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for RequestLimits {
+    type Output = RequestLimitsEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        RequestLimitsEndpoint {
+            inner: ep,
+            limits: *self,
+        }
+    }
+}
+
+/// Endpoint for RequestLimits middleware.
+pub struct RequestLimitsEndpoint<E> {
+    inner: E,
+    limits: RequestLimits,
+}
+
+impl<E: Endpoint> Endpoint for RequestLimitsEndpoint<E> {
+    type Output = E::Output;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        self.limits.check(req.uri(), req.headers())?;
+        self.inner.call(req).await
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("RequestLimits", self.inner.middleware_chains())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+    use crate::{endpoint::make_sync, test::TestClient, EndpointExt};
+
+    #[tokio::test]
+    async fn request_limits() {
+        let ep = make_sync(|_| ()).with(
+            RequestLimits::new()
+                .max_uri_length(10)
+                .max_header_count(2)
+                .max_header_bytes(20),
+        );
+        let cli = TestClient::new(ep);
+
+        cli.get("/abc").send().await.assert_status_is_ok();
+        cli.get("/abc")
+            .query("a", &"123456")
+            .send()
+            .await
+            .assert_status(StatusCode::URI_TOO_LONG);
+        cli.get("/")
+            .header("a", "1")
+            .header("b", "2")
+            .header("c", "3")
+            .send()
+            .await
+            .assert_status(StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE);
+        cli.get("/")
+            .header("a", "1".repeat(20))
+            .send()
+            .await
+            .assert_status(StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE);
+    }
+}
@@ -2,8 +2,8 @@ use tracing::{error, error_span, Instrument};
 use uuid::Uuid;
 
 use crate::{
-    http::StatusCode, Endpoint, Error, FromRequest, IntoResponse, Middleware, Request, Response,
-    Result,
+    endpoint::MiddlewareChain, http::StatusCode, Endpoint, Error, FromRequest, IntoResponse,
+    Middleware, Request, Response, Result,
 };
 
 const X_REQUEST_ID: &str = "x-request-id";
@@ -101,6 +101,10 @@ impl<E: Endpoint> Endpoint for RequestIdEndpoint<E> {
             Err(e) => Err(e),
         }
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("RequestId", self.next.middleware_chains())
+    }
 }
 
 /// A request ID that can be extracted in handler functions.
@@ -2,7 +2,9 @@ use std::collections::HashSet;
 
 use http::{header::HeaderName, HeaderMap};
 
-use crate::{Endpoint, IntoResponse, Middleware, Request, Response, Result};
+use crate::{
+    endpoint::MiddlewareChain, Endpoint, IntoResponse, Middleware, Request, Response, Result,
+};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
 enum AppliedTo {
@@ -107,6 +109,10 @@ impl<E: Endpoint> Endpoint for SensitiveHeaderEndpoint<E> {
 
         Ok(resp)
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("SensitiveHeader", self.inner.middleware_chains())
+    }
 }
 
 #[allow(clippy::mutable_key_type)]
@@ -1,4 +1,5 @@
 use crate::{
+    endpoint::MiddlewareChain,
     http::{header::HeaderName, HeaderValue},
     Endpoint, IntoResponse, Middleware, Request, Response, Result,
 };
@@ -127,6 +128,10 @@ impl<E: Endpoint> Endpoint for SetHeaderEndpoint<E> {
 
         Ok(resp)
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("SetHeader", self.inner.middleware_chains())
+    }
 }
 
 #[cfg(test)]
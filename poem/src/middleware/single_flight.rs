@@ -0,0 +1,296 @@
+use std::{collections::HashMap, sync::Arc};
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderName, Method, StatusCode};
+use parking_lot::Mutex;
+use tokio::sync::watch;
+
+use crate::{
+    endpoint::MiddlewareChain, Endpoint, Error, IntoResponse, Middleware, Request, Response, Result,
+};
+
+/// The result of a request, shared with the requests waiting for it.
+enum Outcome {
+    Response {
+        status: StatusCode,
+        headers: HeaderMap,
+        body: Bytes,
+    },
+    Error {
+        status: StatusCode,
+        message: String,
+    },
+    /// The response has a streaming body, so the waiting requests must call
+    /// the endpoint themselves.
+    Unshared,
+}
+
+type Flights = Mutex<HashMap<String, watch::Receiver<Option<Arc<Outcome>>>>>;
+
+/// Removes the flight when the leading request completes or is cancelled.
+struct FlightGuard<'a> {
+    flights: &'a Flights,
+    key: &'a str,
+}
+
+impl Drop for FlightGuard<'_> {
+    fn drop(&mut self) {
+        self.flights.lock().remove(self.key);
+    }
+}
+
+/// Middleware for coalescing concurrent identical `GET` requests.
+///
+/// While a request is being processed, identical requests do not call the
+/// endpoint but wait for it to complete and receive a copy of its response.
+/// This protects expensive endpoints from bursts of identical requests, for
+/// example when a cached value expires.
+///
+/// Requests are identical when they have the same URI and the same values for
+/// the headers specified with [`SingleFlight::vary_header`]. Since responses
+/// are shared between clients, headers such as `Authorization` must be added
+/// if the response depends on them.
+///
+/// Responses with a streaming body are not shared, the waiting requests call
+/// the endpoint instead.
+///
+/// # Example
+///
+/// ```
+/// use poem::{get, handler, middleware::SingleFlight, EndpointExt, Route};
+///
+/// #[handler]
+/// async fn report() -> String {
+///     // an expensive computation
+///     "report".to_string()
+/// }
+///
+/// let app = Route::new().at(
+///     "/report",
+///     get(report).with(SingleFlight::new().vary_header("authorization")),
+/// );
+/// ```
+#[derive(Default)]
+pub struct SingleFlight {
+    vary_headers: Vec<HeaderName>,
+}
+
+impl SingleFlight {
+    /// Creates a new `SingleFlight` middleware.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a request header whose value must be equal for requests to be
+    /// coalesced.
+    #[must_use]
+    pub fn vary_header<K>(mut self, key: K) -> Self
+    where
+        K: TryInto<HeaderName>,
+    {
+        if let Ok(key) = key.try_into() {
+            self.vary_headers.push(key);
+        }
+        self
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for SingleFlight {
+    type Output = SingleFlightEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        SingleFlightEndpoint {
+            inner: ep,
+            vary_headers: self.vary_headers.clone(),
+            flights: Default::default(),
+        }
+    }
+}
+
+/// Endpoint for SingleFlight middleware.
+pub struct SingleFlightEndpoint<E> {
+    inner: E,
+    vary_headers: Vec<HeaderName>,
+    flights: Flights,
+}
+
+impl<E: Endpoint> SingleFlightEndpoint<E> {
+    fn flight_key(&self, req: &Request) -> String {
+        let mut key = req.uri().to_string();
+        for name in &self.vary_headers {
+            key.push('\n');
+            key.push_str(name.as_str());
+            key.push(':');
+            for (idx, value) in req.headers().get_all(name).iter().enumerate() {
+                if idx > 0 {
+                    key.push(',');
+                }
+                key.push_str(&String::from_utf8_lossy(value.as_bytes()));
+            }
+        }
+        key
+    }
+
+    async fn lead(
+        &self,
+        req: Request,
+        key: &str,
+        tx: watch::Sender<Option<Arc<Outcome>>>,
+    ) -> Result<Response> {
+        let _guard = FlightGuard {
+            flights: &self.flights,
+            key,
+        };
+
+        let mut resp = match self.inner.call(req).await {
+            Ok(resp) => resp.into_response(),
+            Err(err) => {
+                let _ = tx.send(Some(Arc::new(Outcome::Error {
+                    status: err.status(),
+                    message: err.to_string(),
+                })));
+                return Err(err);
+            }
+        };
+
+        let body = resp.take_body();
+        if body.exact_size().is_none() {
+            let _ = tx.send(Some(Arc::new(Outcome::Unshared)));
+            resp.set_body(body);
+            return Ok(resp);
+        }
+        let data = body.into_bytes().await?;
+        let _ = tx.send(Some(Arc::new(Outcome::Response {
+            status: resp.status(),
+            headers: resp.headers().clone(),
+            body: data.clone(),
+        })));
+        resp.set_body(data);
+        Ok(resp)
+    }
+}
+
+impl<E: Endpoint> Endpoint for SingleFlightEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        if req.method() != Method::GET {
+            return self.inner.call(req).await.map(IntoResponse::into_response);
+        }
+
+        let key = self.flight_key(&req);
+        let flight = {
+            let mut flights = self.flights.lock();
+            match flights.get(&key) {
+                Some(rx) => Ok(rx.clone()),
+                None => {
+                    let (tx, rx) = watch::channel(None);
+                    flights.insert(key.clone(), rx);
+                    Err(tx)
+                }
+            }
+        };
+        let mut rx = match flight {
+            Ok(rx) => rx,
+            Err(tx) => return self.lead(req, &key, tx).await,
+        };
+
+        let outcome = loop {
+            if let Some(outcome) = rx.borrow().clone() {
+                break Some(outcome);
+            }
+            if rx.changed().await.is_err() {
+                // the leading request was cancelled
+                break rx.borrow().clone();
+            }
+        };
+
+        match outcome.as_deref() {
+            Some(Outcome::Response {
+                status,
+                headers,
+                body,
+            }) => {
+                let mut resp = Response::builder().status(*status).body(body.clone());
+                *resp.headers_mut() = headers.clone();
+                Ok(resp)
+            }
+            Some(Outcome::Error { status, message }) => {
+                Err(Error::from_string(message.clone(), *status))
+            }
+            Some(Outcome::Unshared) | None => {
+                self.inner.call(req).await.map(IntoResponse::into_response)
+            }
+        }
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("SingleFlight", self.inner.middleware_chains())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use futures_util::future::join_all;
+
+    use super::*;
+    use crate::{handler, test::TestClient, web::Data, EndpointExt};
+
+    #[tokio::test]
+    async fn single_flight() {
+        #[handler(internal)]
+        async fn index(counter: Data<&Arc<AtomicUsize>>) -> String {
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            n.to_string()
+        }
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let cli = TestClient::new(
+            index
+                .with(SingleFlight::new().vary_header("x-user"))
+                .data(counter.clone()),
+        );
+
+        let resps = join_all((0..5).map(|_| cli.get("/").send())).await;
+        for resp in resps {
+            resp.assert_status_is_ok();
+            resp.assert_text("0").await;
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        let resps = join_all([
+            cli.get("/").header("x-user", "a").send(),
+            cli.get("/").header("x-user", "b").send(),
+            cli.get("/a").send(),
+        ])
+        .await;
+        assert_eq!(resps.len(), 3);
+        assert_eq!(counter.load(Ordering::SeqCst), 4);
+
+        join_all((0..3).map(|_| cli.post("/").send())).await;
+        assert_eq!(counter.load(Ordering::SeqCst), 7);
+    }
+
+    #[tokio::test]
+    async fn single_flight_error() {
+        #[handler(internal)]
+        async fn index() -> Result<String> {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Err(Error::from_string("failed", StatusCode::BAD_GATEWAY))
+        }
+
+        let cli = TestClient::new(index.with(SingleFlight::new()));
+        let resps = join_all((0..3).map(|_| cli.get("/").send())).await;
+        for resp in resps {
+            resp.assert_status(StatusCode::BAD_GATEWAY);
+            resp.assert_text("failed").await;
+        }
+    }
+}
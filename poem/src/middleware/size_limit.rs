@@ -1,5 +1,6 @@
 use crate::{
-    error::SizedLimitError, web::headers::HeaderMapExt, Endpoint, Middleware, Request, Result,
+    endpoint::MiddlewareChain, error::SizedLimitError, web::headers::HeaderMapExt, Endpoint,
+    Middleware, Request, Result,
 };
 
 /// Middleware for limit the request payload size.
@@ -53,6 +54,10 @@ impl<E: Endpoint> Endpoint for SizeLimitEndpoint<E> {
 
         self.inner.call(req).await
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("SizeLimit", self.inner.middleware_chains())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,419 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use http::{Method, StatusCode};
+use http_body_util::BodyExt;
+
+use crate::{
+    endpoint::MiddlewareChain,
+    route::{MatchedParams, PathParams, PathPattern},
+    web::QueryParams,
+    Body, Endpoint, IntoResponse, Middleware, Request, Response, Result,
+};
+
+type Redactor = Arc<dyn Fn(&str, &str) -> String + Send + Sync>;
+type SlowRequestHandler = Arc<dyn Fn(&SlowRequest) + Send + Sync>;
+
+const REDACTED: &str = "[redacted]";
+
+/// The information of a slow request reported by the [`SlowRequestLog`]
+/// middleware.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SlowRequest {
+    /// The method of the request.
+    pub method: Method,
+    /// The path of the request.
+    pub path: String,
+    /// The path pattern of the matched route.
+    pub path_pattern: Option<String>,
+    /// The path and query parameters of the request, after redaction.
+    pub params: Vec<(String, String)>,
+    /// The status code of the response.
+    pub status: StatusCode,
+    /// The time spent by the endpoint to create the response.
+    pub handler_duration: Duration,
+    /// The time spent to send the response body, `None` if the endpoint
+    /// returns an error.
+    pub body_duration: Option<Duration>,
+}
+
+impl SlowRequest {
+    /// Returns the total time spent on the request.
+    pub fn total_duration(&self) -> Duration {
+        self.handler_duration + self.body_duration.unwrap_or_default()
+    }
+}
+
+struct DisplayParams<'a>(&'a [(String, String)]);
+
+impl Display for DisplayParams<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (idx, (name, value)) in self.0.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Middleware that emits a `WARN` [`tracing`](https://crates.io/crates/tracing)
+/// event for each request which takes longer than the threshold, including
+/// the time spent by the endpoint and the time spent to send the response
+/// body.
+///
+/// The path pattern and the path parameters of the innermost matched route are
+/// reported, whether this middleware wraps the [`Route`](crate::Route) or the
+/// endpoints of the routes.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use poem::{get, handler, middleware::SlowRequestLog, EndpointExt, Route};
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// let app = Route::new().at(
+///     "/users/:id",
+///     get(index).with(
+///         SlowRequestLog::new(Duration::from_secs(1))
+///             .redact_param("token")
+///             .redact_with(|name, value| {
+///                 if name == "email" {
+///                     format!("{}@***", value.split('@').next().unwrap_or_default())
+///                 } else {
+///                     value.to_string()
+///                 }
+///             }),
+///     ),
+/// );
+/// ```
+pub struct SlowRequestLog {
+    threshold: Duration,
+    redact_params: HashSet<String>,
+    redactor: Option<Redactor>,
+    handler: Option<SlowRequestHandler>,
+}
+
+impl SlowRequestLog {
+    /// Creates a new `SlowRequestLog` middleware that reports the requests
+    /// taking longer than `threshold`.
+    #[must_use]
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            redact_params: HashSet::new(),
+            redactor: None,
+            handler: None,
+        }
+    }
+
+    /// Replaces the value of the path or query parameter with `[redacted]`.
+    #[must_use]
+    pub fn redact_param(mut self, name: impl Into<String>) -> Self {
+        self.redact_params.insert(name.into());
+        self
+    }
+
+    /// Sets a function that returns the reported value of each parameter from
+    /// its name and value.
+    #[must_use]
+    pub fn redact_with<F>(self, f: F) -> Self
+    where
+        F: Fn(&str, &str) -> String + Send + Sync + 'static,
+    {
+        Self {
+            redactor: Some(Arc::new(f)),
+            ..self
+        }
+    }
+
+    /// Sets a function that is called with the slow requests instead of
+    /// emitting the tracing event.
+    #[must_use]
+    pub fn on_slow_request<F>(self, f: F) -> Self
+    where
+        F: Fn(&SlowRequest) + Send + Sync + 'static,
+    {
+        Self {
+            handler: Some(Arc::new(f)),
+            ..self
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for SlowRequestLog {
+    type Output = SlowRequestLogEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        SlowRequestLogEndpoint {
+            inner: ep,
+            config: Arc::new(Config {
+                threshold: self.threshold,
+                redact_params: self.redact_params.clone(),
+                redactor: self.redactor.clone(),
+                handler: self.handler.clone(),
+            }),
+        }
+    }
+}
+
+struct Config {
+    threshold: Duration,
+    redact_params: HashSet<String>,
+    redactor: Option<Redactor>,
+    handler: Option<SlowRequestHandler>,
+}
+
+impl Config {
+    fn params(&self, path_params: &PathParams, query: &QueryParams) -> Vec<(String, String)> {
+        path_params
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .chain(
+                query
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string())),
+            )
+            .map(|(name, value)| {
+                let value = if self.redact_params.contains(&name) {
+                    REDACTED.to_string()
+                } else if let Some(redactor) = &self.redactor {
+                    redactor(&name, &value)
+                } else {
+                    value
+                };
+                (name, value)
+            })
+            .collect()
+    }
+
+    fn report(&self, info: &SlowRequest) {
+        if let Some(handler) = &self.handler {
+            handler(info);
+            return;
+        }
+
+        tracing::warn!(
+            method = %info.method,
+            path = %info.path,
+            path_pattern = info.path_pattern.as_deref(),
+            params = %DisplayParams(&info.params),
+            status = %info.status,
+            handler_duration = ?info.handler_duration,
+            body_duration = ?info.body_duration,
+            total_duration = ?info.total_duration(),
+            "slow request"
+        );
+    }
+}
+
+/// Reports the request when the response body is dropped, which happens after
+/// it has been sent or the connection is closed.
+struct BodyGuard {
+    config: Arc<Config>,
+    info: SlowRequest,
+    start: Instant,
+}
+
+impl Drop for BodyGuard {
+    fn drop(&mut self) {
+        let body_duration = self.start.elapsed();
+        if self.info.handler_duration + body_duration >= self.config.threshold {
+            self.info.body_duration = Some(body_duration);
+            self.config.report(&self.info);
+        }
+    }
+}
+
+/// Endpoint for SlowRequestLog middleware.
+pub struct SlowRequestLogEndpoint<E> {
+    inner: E,
+    config: Arc<Config>,
+}
+
+impl<E: Endpoint> Endpoint for SlowRequestLogEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, mut req: Request) -> Result<Self::Output> {
+        let method = req.method().clone();
+        let path = req.uri().path().to_string();
+        let path_pattern = req
+            .data::<PathPattern>()
+            .map(|pattern| pattern.0.to_string());
+        let query = req.query().clone();
+        let path_params = req.state().match_params.clone();
+        // the path parameters are only known after routing if this wraps a route
+        let matched_params = MatchedParams::default();
+        req.set_data(matched_params.clone());
+
+        let start = Instant::now();
+        let res = self.inner.call(req).await;
+        let handler_duration = start.elapsed();
+
+        let path_params = matched_params.0.lock().take().unwrap_or(path_params);
+        let params = self.config.params(&path_params, &query);
+
+        let (status, response_pattern) = match &res {
+            Ok(_) => (StatusCode::OK, None),
+            Err(err) => (err.status(), err.data::<PathPattern>()),
+        };
+        let mut info = SlowRequest {
+            method,
+            path,
+            path_pattern: path_pattern
+                .or_else(|| response_pattern.map(|pattern| pattern.0.to_string())),
+            params,
+            status,
+            handler_duration,
+            body_duration: None,
+        };
+
+        match res {
+            Ok(resp) => {
+                let resp = resp.into_response();
+                info.status = resp.status();
+                if info.path_pattern.is_none() {
+                    info.path_pattern = resp.data::<PathPattern>().map(|p| p.0.to_string());
+                }
+
+                let guard = BodyGuard {
+                    config: self.config.clone(),
+                    info,
+                    start: Instant::now(),
+                };
+                let (parts, body) = resp.into_parts();
+                let body = Body::from(
+                    body.0
+                        .map_frame(move |frame| {
+                            let _guard = &guard;
+                            frame
+                        })
+                        .boxed(),
+                );
+                Ok(Response::from_parts(parts, body))
+            }
+            Err(err) => {
+                if handler_duration >= self.config.threshold {
+                    self.config.report(&info);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("SlowRequestLog", self.inner.middleware_chains())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::{
+        endpoint::make, error::InternalServerError, get, handler, test::TestClient, EndpointExt,
+        Route,
+    };
+
+    #[tokio::test]
+    async fn slow_request_log() {
+        #[handler(internal)]
+        async fn index() -> &'static str {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            "hello"
+        }
+
+        #[handler(internal)]
+        async fn fast() -> &'static str {
+            "hello"
+        }
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let log = || {
+            let reports = reports.clone();
+            SlowRequestLog::new(Duration::from_millis(30))
+                .redact_param("token")
+                .redact_with(|name, value| {
+                    if name == "id" {
+                        "*".repeat(value.len())
+                    } else {
+                        value.to_string()
+                    }
+                })
+                .on_slow_request(move |info| reports.lock().push(info.clone()))
+        };
+        let app = Route::new()
+            .at("/users/:id", get(index).with(log()))
+            .at("/fast", get(fast).with(log()));
+        let cli = TestClient::new(app);
+
+        let resp = cli
+            .get("/users/100")
+            .query("token", &"secret")
+            .query("page", &2)
+            .send()
+            .await;
+        resp.assert_text("hello").await;
+        cli.get("/fast").send().await.assert_text("hello").await;
+
+        let reports = reports.lock();
+        assert_eq!(reports.len(), 1);
+        let info = &reports[0];
+        assert_eq!(info.method, Method::GET);
+        assert_eq!(info.path, "/users/100");
+        assert_eq!(info.path_pattern.as_deref(), Some("/users/:id"));
+        assert_eq!(info.status, StatusCode::OK);
+        assert_eq!(
+            DisplayParams(&info.params).to_string(),
+            "id=***, token=[redacted], page=2"
+        );
+        assert!(info.handler_duration >= Duration::from_millis(50));
+        assert!(info.body_duration.is_some());
+    }
+
+    #[tokio::test]
+    async fn slow_request_log_error() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let ep = Route::new()
+            .at(
+                "/:id",
+                make(|_| async {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    Err::<(), _>(InternalServerError(std::io::Error::other("failed")))
+                }),
+            )
+            .with(
+                SlowRequestLog::new(Duration::from_millis(30)).on_slow_request({
+                    let reports = reports.clone();
+                    move |info| reports.lock().push(info.clone())
+                }),
+            );
+        let cli = TestClient::new(ep);
+
+        cli.get("/1")
+            .send()
+            .await
+            .assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+
+        let reports = reports.lock();
+        assert_eq!(reports.len(), 1);
+        let info = &reports[0];
+        assert_eq!(info.path_pattern.as_deref(), Some("/:id"));
+        assert_eq!(info.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(DisplayParams(&info.params).to_string(), "id=1");
+        assert!(info.body_duration.is_none());
+    }
+}
@@ -5,7 +5,8 @@ use serde::Serialize;
 use tokio_metrics::{TaskMetrics, TaskMonitor};
 
 use crate::{
-    endpoint::make_sync, Endpoint, IntoResponse, Middleware, Request, Response, Result, RouteMethod,
+    endpoint::{make_sync, MiddlewareChain},
+    Endpoint, IntoResponse, Middleware, Request, Response, Result, RouteMethod,
 };
 
 /// Middleware for metrics with [`tokio-metrics`](https://crates.io/crates/tokio-metrics) crate.
@@ -87,6 +88,10 @@ impl<E: Endpoint> Endpoint for TokioMetricsEndpoint<E> {
             .await?
             .into_response())
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("TokioMetrics", self.inner.middleware_chains())
+    }
 }
 
 #[derive(Serialize, Default)]
@@ -7,7 +7,9 @@ use futures_util::{future::BoxFuture, FutureExt};
 use http::StatusCode;
 use tower::{buffer::Buffer, BoxError, Layer, Service, ServiceExt};
 
-use crate::{Endpoint, Error, IntoResponse, Middleware, Request, Result};
+use crate::{
+    endpoint::MiddlewareChain, Endpoint, Error, IntoResponse, Middleware, Request, Result,
+};
 
 #[doc(hidden)]
 #[derive(Debug, thiserror::Error)]
@@ -51,12 +53,23 @@ where
     type Output = TowerServiceToEndpoint<L::Service>;
 
     fn transform(&self, ep: E) -> Self::Output {
+        // the endpoint is hidden by the layer, so its middleware chains are
+        // collected now
+        let chains = MiddlewareChain::wrap(layer_name::<L>(), ep.middleware_chains());
         let new_svc = self.0.layer(EndpointToTowerService(Arc::new(ep)));
         let buffer = Buffer::new(new_svc, 32);
-        TowerServiceToEndpoint(buffer)
+        TowerServiceToEndpoint(buffer, chains)
     }
 }
 
+/// Returns the name of the layer type without its path and generic
+/// parameters.
+fn layer_name<L>() -> &'static str {
+    let name = std::any::type_name::<L>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
 /// An endpoint to tower service adapter.
 pub struct EndpointToTowerService<E>(Arc<E>);
 
@@ -79,7 +92,10 @@ where
 }
 
 /// An tower service to endpoint adapter.
-pub struct TowerServiceToEndpoint<Svc: Service<Request>>(Buffer<Svc, Request>);
+pub struct TowerServiceToEndpoint<Svc: Service<Request>>(
+    Buffer<Svc, Request>,
+    Vec<MiddlewareChain>,
+);
 
 impl<Svc> Endpoint for TowerServiceToEndpoint<Svc>
 where
@@ -96,6 +112,10 @@ where
         let res = svc.call(req).await.map_err(boxed_err_to_poem_err)?;
         Ok(res)
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.1.clone()
+    }
 }
 
 #[cfg(test)]
@@ -137,7 +157,16 @@ mod tests {
             }
         }
 
-        let ep = make_sync(|_| ()).with(MyServiceLayer.compat());
+        let ep = make_sync(|_| ())
+            .named("index")
+            .with(MyServiceLayer.compat());
+        assert_eq!(
+            ep.middleware_chains()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["/: MyServiceLayer -> index"]
+        );
         let cli = TestClient::new(ep);
         cli.get("/").send().await.assert_status_is_ok();
     }
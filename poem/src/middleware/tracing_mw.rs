@@ -3,8 +3,8 @@ use std::time::Instant;
 use tracing::{Instrument, Level};
 
 use crate::{
-    route::PathPattern, web::RealIp, Endpoint, FromRequest, IntoResponse, Middleware, Request,
-    Response, Result,
+    endpoint::MiddlewareChain, route::PathPattern, web::RealIp, Endpoint, FromRequest,
+    IntoResponse, Middleware, Request, Response, Result,
 };
 
 /// Middleware for [`tracing`](https://crates.io/crates/tracing).
@@ -109,4 +109,8 @@ impl<E: Endpoint> Endpoint for TracingEndpoint<E> {
         .instrument(span)
         .await
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("Tracing", self.inner.middleware_chains())
+    }
 }
@@ -0,0 +1,381 @@
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::hmac;
+
+use crate::{
+    endpoint::MiddlewareChain, error::VerifyHmacError, Endpoint, Middleware, Request, Result,
+};
+
+/// The hash algorithm of the HMAC signatures verified by [`VerifyHmac`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum HmacAlgorithm {
+    /// HMAC-SHA1, only for the legacy webhooks.
+    Sha1,
+    /// HMAC-SHA256.
+    Sha256,
+    /// HMAC-SHA384.
+    Sha384,
+    /// HMAC-SHA512.
+    Sha512,
+}
+
+impl HmacAlgorithm {
+    fn ring_algorithm(self) -> hmac::Algorithm {
+        match self {
+            HmacAlgorithm::Sha1 => hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+            HmacAlgorithm::Sha256 => hmac::HMAC_SHA256,
+            HmacAlgorithm::Sha384 => hmac::HMAC_SHA384,
+            HmacAlgorithm::Sha512 => hmac::HMAC_SHA512,
+        }
+    }
+}
+
+/// The encoding of the HMAC signatures verified by [`VerifyHmac`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SignatureEncoding {
+    /// Lowercase or uppercase hexadecimal.
+    Hex,
+    /// Standard base64 with padding.
+    Base64,
+}
+
+type KeyFn = dyn Fn(&Request) -> Option<Vec<u8>> + Send + Sync;
+
+/// Middleware for verifying webhook style HMAC signatures of the request
+/// body.
+///
+/// The body is read into memory (up to [`VerifyHmac::max_body_size`]) and
+/// verified before the request is passed to the endpoint, the extractors of
+/// the endpoint still see the whole body.
+///
+/// By default, the signature is read from the `X-Signature` header as a
+/// hexadecimal HMAC-SHA256 digest.
+///
+/// # Errors
+///
+/// - [`VerifyHmacError`]
+/// - [`ReadBodyError`](crate::error::ReadBodyError)
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     handler,
+///     http::StatusCode,
+///     middleware::{HmacAlgorithm, VerifyHmac},
+///     test::TestClient,
+///     EndpointExt,
+/// };
+///
+/// #[handler]
+/// fn index(body: String) -> String {
+///     body
+/// }
+///
+/// let ep = index.with(
+///     VerifyHmac::new("It's a Secret to Everybody")
+///         .header("X-Hub-Signature-256")
+///         .algorithm(HmacAlgorithm::Sha256)
+///         .prefix("sha256="),
+/// );
+/// let cli = TestClient::new(ep);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli
+///     .post("/")
+///     .header(
+///         "X-Hub-Signature-256",
+///         "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17",
+///     )
+///     .body("Hello, World!")
+///     .send()
+///     .await;
+/// resp.assert_status_is_ok();
+/// resp.assert_text("Hello, World!").await;
+///
+/// let resp = cli
+///     .post("/")
+///     .header("X-Hub-Signature-256", "sha256=00")
+///     .body("Hello, World!")
+///     .send()
+///     .await;
+/// resp.assert_status(StatusCode::UNAUTHORIZED);
+/// # });
+/// ```
+pub struct VerifyHmac {
+    key_fn: Arc<KeyFn>,
+    header: String,
+    algorithm: HmacAlgorithm,
+    encoding: SignatureEncoding,
+    prefix: Option<String>,
+    max_body_size: usize,
+}
+
+impl VerifyHmac {
+    /// Creates a new `VerifyHmac` middleware with a fixed key.
+    #[must_use]
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        let key = key.into();
+        Self::with_key_fn(move |_| Some(key.clone()))
+    }
+
+    /// Creates a new `VerifyHmac` middleware which looks up the key for each
+    /// request, such as by the path or a tenant header.
+    ///
+    /// The request is rejected if the function returns `None`.
+    #[must_use]
+    pub fn with_key_fn<F>(f: F) -> Self
+    where
+        F: Fn(&Request) -> Option<Vec<u8>> + Send + Sync + 'static,
+    {
+        Self {
+            key_fn: Arc::new(f),
+            header: "x-signature".to_string(),
+            algorithm: HmacAlgorithm::Sha256,
+            encoding: SignatureEncoding::Hex,
+            prefix: None,
+            max_body_size: 1024 * 1024,
+        }
+    }
+
+    /// Sets the name of the header containing the signature. (default to
+    /// `X-Signature`)
+    ///
+    /// If the name is not a valid header name, all requests are rejected with
+    /// [`VerifyHmacError::MissingSignature`].
+    #[must_use]
+    pub fn header(self, name: impl Into<String>) -> Self {
+        Self {
+            header: name.into(),
+            ..self
+        }
+    }
+
+    /// Sets the hash algorithm. (default to [`HmacAlgorithm::Sha256`])
+    #[must_use]
+    pub fn algorithm(self, algorithm: HmacAlgorithm) -> Self {
+        Self { algorithm, ..self }
+    }
+
+    /// Sets the encoding of the signature. (default to
+    /// [`SignatureEncoding::Hex`])
+    #[must_use]
+    pub fn encoding(self, encoding: SignatureEncoding) -> Self {
+        Self { encoding, ..self }
+    }
+
+    /// Sets the prefix before the encoded signature, such as `sha256=`.
+    #[must_use]
+    pub fn prefix(self, prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: Some(prefix.into()),
+            ..self
+        }
+    }
+
+    /// Sets the maximum size of the body, larger bodies are rejected with
+    /// `413 Payload Too Large`. (default to `1MB`)
+    #[must_use]
+    pub fn max_body_size(self, size: usize) -> Self {
+        Self {
+            max_body_size: size,
+            ..self
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for VerifyHmac {
+    type Output = VerifyHmacEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        VerifyHmacEndpoint {
+            inner: ep,
+            key_fn: self.key_fn.clone(),
+            header: self.header.clone(),
+            algorithm: self.algorithm,
+            encoding: self.encoding,
+            prefix: self.prefix.clone(),
+            max_body_size: self.max_body_size,
+        }
+    }
+}
+
+/// Endpoint for VerifyHmac middleware.
+pub struct VerifyHmacEndpoint<E> {
+    inner: E,
+    key_fn: Arc<KeyFn>,
+    header: String,
+    algorithm: HmacAlgorithm,
+    encoding: SignatureEncoding,
+    prefix: Option<String>,
+    max_body_size: usize,
+}
+
+impl<E> VerifyHmacEndpoint<E> {
+    fn signature(&self, req: &Request) -> Result<Vec<u8>, VerifyHmacError> {
+        let value = req
+            .headers()
+            .get(self.header.as_str())
+            .and_then(|value| value.to_str().ok())
+            .ok_or(VerifyHmacError::MissingSignature)?;
+        let value = match &self.prefix {
+            Some(prefix) => value
+                .strip_prefix(prefix.as_str())
+                .ok_or(VerifyHmacError::InvalidSignature)?,
+            None => value,
+        };
+        match self.encoding {
+            SignatureEncoding::Hex => hex::decode(value.trim()).ok(),
+            SignatureEncoding::Base64 => STANDARD.decode(value.trim()).ok(),
+        }
+        .ok_or(VerifyHmacError::InvalidSignature)
+    }
+}
+
+impl<E: Endpoint> Endpoint for VerifyHmacEndpoint<E> {
+    type Output = E::Output;
+
+    async fn call(&self, mut req: Request) -> Result<Self::Output> {
+        let signature = self.signature(&req)?;
+        let key = (self.key_fn)(&req).ok_or(VerifyHmacError::UnknownKey)?;
+        let data = req.take_body().into_bytes_limit(self.max_body_size).await?;
+
+        let key = hmac::Key::new(self.algorithm.ring_algorithm(), &key);
+        hmac::verify(&key, &data, &signature).map_err(|_| VerifyHmacError::InvalidSignature)?;
+
+        req.set_body(data);
+        self.inner.call(req).await
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("VerifyHmac", self.inner.middleware_chains())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+    use crate::{handler, test::TestClient, EndpointExt};
+
+    fn sign(algorithm: HmacAlgorithm, key: &[u8], data: &[u8]) -> Vec<u8> {
+        let key = hmac::Key::new(algorithm.ring_algorithm(), key);
+        hmac::sign(&key, data).as_ref().to_vec()
+    }
+
+    #[handler(internal)]
+    fn index(body: String) -> String {
+        body
+    }
+
+    #[tokio::test]
+    async fn verify_hex() {
+        let cli = TestClient::new(index.with(VerifyHmac::new("secret")));
+        let signature = hex::encode(sign(HmacAlgorithm::Sha256, b"secret", b"hello"));
+
+        let resp = cli
+            .post("/")
+            .header("x-signature", &signature)
+            .body("hello")
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_text("hello").await;
+
+        cli.post("/")
+            .header("x-signature", &signature)
+            .body("hello!")
+            .send()
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+        cli.post("/")
+            .header("x-signature", "not hex")
+            .body("hello")
+            .send()
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+        cli.post("/")
+            .body("hello")
+            .send()
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn verify_base64_with_prefix() {
+        let cli = TestClient::new(
+            index.with(
+                VerifyHmac::new("secret")
+                    .header("x-hook-signature")
+                    .algorithm(HmacAlgorithm::Sha512)
+                    .encoding(SignatureEncoding::Base64)
+                    .prefix("v1="),
+            ),
+        );
+        let signature = STANDARD.encode(sign(HmacAlgorithm::Sha512, b"secret", b"hello"));
+
+        cli.post("/")
+            .header("x-hook-signature", format!("v1={signature}"))
+            .body("hello")
+            .send()
+            .await
+            .assert_text("hello")
+            .await;
+        cli.post("/")
+            .header("x-hook-signature", signature)
+            .body("hello")
+            .send()
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn invalid_header_name() {
+        let cli = TestClient::new(index.with(VerifyHmac::new("secret").header("x signature")));
+        cli.post("/")
+            .header("x-signature", "00")
+            .body("hello")
+            .send()
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn key_fn() {
+        let cli = TestClient::new(index.with(VerifyHmac::with_key_fn(
+            |req| match req.uri().path() {
+                "/a" => Some(b"key-a".to_vec()),
+                _ => None,
+            },
+        )));
+        let signature = hex::encode(sign(HmacAlgorithm::Sha256, b"key-a", b"hello"));
+
+        cli.post("/a")
+            .header("x-signature", &signature)
+            .body("hello")
+            .send()
+            .await
+            .assert_status_is_ok();
+        cli.post("/b")
+            .header("x-signature", &signature)
+            .body("hello")
+            .send()
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn body_too_large() {
+        let cli = TestClient::new(index.with(VerifyHmac::new("secret").max_body_size(4)));
+        let signature = hex::encode(sign(HmacAlgorithm::Sha256, b"secret", b"hello"));
+
+        cli.post("/")
+            .header("x-signature", signature)
+            .body("hello")
+            .send()
+            .await
+            .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
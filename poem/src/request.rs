@@ -4,6 +4,7 @@ use std::{
     io::Error,
     pin::Pin,
     str::FromStr,
+    sync::{Arc, OnceLock},
     task::{Context, Poll},
 };
 
@@ -11,7 +12,7 @@ use http::uri::Scheme;
 use http_body_util::BodyExt;
 use hyper::{body::Incoming, rt::Write as _};
 use parking_lot::Mutex;
-use serde::de::DeserializeOwned;
+use serde::de::{Deserialize, DeserializeOwned};
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 
 #[cfg(feature = "cookie")]
@@ -26,9 +27,9 @@ use crate::{
     route::PathParams,
     web::{
         headers::{Header, HeaderMapExt},
-        LocalAddr, PathDeserializer, RemoteAddr,
+        LocalAddr, PathDeserializer, QueryParams, RemoteAddr, RequestLocal,
     },
-    RequestBody,
+    RequestBody, Response,
 };
 
 pub(crate) struct RequestState {
@@ -37,9 +38,44 @@ pub(crate) struct RequestState {
     pub(crate) scheme: Scheme,
     pub(crate) original_uri: Uri,
     pub(crate) match_params: PathParams,
+    pub(crate) query_params: OnceLock<QueryParams>,
     #[cfg(feature = "cookie")]
     pub(crate) cookie_jar: Option<CookieJar>,
     pub(crate) on_upgrade: Mutex<Option<OnUpgrade>>,
+    pub(crate) response_data: ResponseData,
+    pub(crate) local: RequestLocal,
+}
+
+/// The values inserted with [`Request::set_response_data`], which are moved
+/// to the extensions of the response.
+#[derive(Clone, Default)]
+pub(crate) struct ResponseData(Arc<Mutex<Extensions>>);
+
+impl ResponseData {
+    /// Moves the pending values to the extensions of the response or the
+    /// error, without replacing the values that already exist.
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn apply(&self, res: crate::Result<Response>) -> crate::Result<Response> {
+        fn merge(extensions: &mut Extensions, pending: Extensions) {
+            let existing = std::mem::replace(extensions, pending);
+            extensions.extend(existing);
+        }
+
+        let pending = std::mem::take(&mut *self.0.lock());
+        if pending.is_empty() {
+            return res;
+        }
+        match res {
+            Ok(mut resp) => {
+                merge(resp.extensions_mut(), pending);
+                Ok(resp)
+            }
+            Err(mut err) => {
+                merge(err.extensions_mut(), pending);
+                Err(err)
+            }
+        }
+    }
 }
 
 impl Default for RequestState {
@@ -49,10 +85,13 @@ impl Default for RequestState {
             remote_addr: Default::default(),
             scheme: Scheme::HTTP,
             original_uri: Default::default(),
-            match_params: vec![],
+            match_params: Default::default(),
+            query_params: Default::default(),
             #[cfg(feature = "cookie")]
             cookie_jar: None,
             on_upgrade: Default::default(),
+            response_data: Default::default(),
+            local: Default::default(),
         }
     }
 }
@@ -139,9 +178,12 @@ impl From<(http::Request<Incoming>, LocalAddr, RemoteAddr, Scheme)> for Request
                 scheme,
                 original_uri: parts.uri,
                 match_params: Default::default(),
+                query_params: Default::default(),
                 #[cfg(feature = "cookie")]
                 cookie_jar: None,
                 on_upgrade,
+                response_data: Default::default(),
+                local: Default::default(),
             },
         }
     }
@@ -164,6 +206,14 @@ impl From<Request> for hyper::Request<BoxBody> {
 impl Request {
     /// Creates a new `Request` with the given components parts and body.
     pub fn from_parts(parts: RequestParts, body: Body) -> Self {
+        let mut state = parts.state;
+        if state
+            .query_params
+            .get()
+            .is_some_and(|params| Some(params.source()) != parts.uri.query())
+        {
+            state.query_params = OnceLock::new();
+        }
         Self {
             method: parts.method,
             uri: parts.uri,
@@ -171,7 +221,7 @@ impl Request {
             headers: parts.headers,
             extensions: parts.extensions,
             body,
-            state: parts.state,
+            state,
         }
     }
 
@@ -207,9 +257,18 @@ impl Request {
     /// Returns a mutable reference to the associated URI.
     #[inline]
     pub fn uri_mut(&mut self) -> &mut Uri {
+        self.state.query_params = OnceLock::new();
         &mut self.uri
     }
 
+    /// Sets the URI, the parsed query string is kept if it is unchanged.
+    pub(crate) fn set_uri(&mut self, uri: Uri) {
+        if uri.query() != self.uri.query() {
+            self.state.query_params = OnceLock::new();
+        }
+        self.uri = uri;
+    }
+
     /// Returns a reference to the associated original URI.
     #[inline]
     pub fn original_uri(&self) -> &Uri {
@@ -255,13 +314,77 @@ impl Request {
             .and_then(|value| value.to_str().ok())
     }
 
+    /// Returns all the string values of the specified header.
+    ///
+    /// NOTE: The values which are not valid UTF8 strings are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::Request;
+    ///
+    /// let req = Request::builder()
+    ///     .header("x-forwarded-for", "10.0.0.1")
+    ///     .header("x-forwarded-for", "10.0.0.2")
+    ///     .header("x-retries", "3")
+    ///     .finish();
+    /// assert_eq!(
+    ///     req.header_all("x-forwarded-for"),
+    ///     vec!["10.0.0.1", "10.0.0.2"]
+    /// );
+    /// assert_eq!(req.header_parse::<u32>("x-retries"), Some(3));
+    /// assert_eq!(req.header_parse::<u32>("x-forwarded-for"), None);
+    /// ```
+    pub fn header_all(&self, name: impl AsRef<str>) -> Vec<&str> {
+        self.headers
+            .get_all(name.as_ref())
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect()
+    }
+
+    /// Parses the string value of the specified header with
+    /// [`FromStr`].
+    ///
+    /// NOTE: Returns `None` if the header does not exist or the value cannot
+    /// be parsed.
+    pub fn header_parse<T: FromStr>(&self, name: impl AsRef<str>) -> Option<T> {
+        self.header(name)?.trim().parse().ok()
+    }
+
+    /// Appends a header, the existing values of the header are kept.
+    ///
+    /// NOTE: The header is ignored if the name or value is invalid.
+    pub fn append_header<K, V>(&mut self, key: K, value: V)
+    where
+        K: TryInto<HeaderName>,
+        V: TryInto<HeaderValue>,
+    {
+        if let (Ok(key), Ok(value)) = (key.try_into(), value.try_into()) {
+            self.headers.append(key, value);
+        }
+    }
+
+    /// Sets a header, the existing values of the header are replaced.
+    ///
+    /// NOTE: The header is ignored if the name or value is invalid.
+    pub fn set_header<K, V>(&mut self, key: K, value: V)
+    where
+        K: TryInto<HeaderName>,
+        V: TryInto<HeaderValue>,
+    {
+        if let (Ok(key), Ok(value)) = (key.try_into(), value.try_into()) {
+            self.headers.insert(key, value);
+        }
+    }
+
     /// Returns the raw path parameter with the specified `name`.
     pub fn raw_path_param(&self, name: &str) -> Option<&str> {
         self.state
             .match_params
             .iter()
-            .find(|(key, _)| key == name)
-            .map(|(_, value)| value.as_str())
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
     }
 
     /// Deserialize path parameters.
@@ -293,8 +416,8 @@ impl Request {
     /// resp.assert_text("100:abc").await;
     /// # });
     /// ```
-    pub fn path_params<T: DeserializeOwned>(&self) -> Result<T, ParsePathError> {
-        T::deserialize(PathDeserializer::new(&self.state().match_params))
+    pub fn path_params<'a, T: Deserialize<'a>>(&'a self) -> Result<T, ParsePathError> {
+        T::deserialize(PathDeserializer::new(self.state().match_params.as_ref()))
             .map_err(|_| ParsePathError)
     }
 
@@ -340,9 +463,40 @@ impl Request {
     /// # });
     /// ```
     pub fn params<T: DeserializeOwned>(&self) -> Result<T, ParseQueryError> {
-        Ok(serde_urlencoded::from_str(
-            self.uri().query().unwrap_or_default(),
-        )?)
+        self.query().deserialize()
+    }
+
+    /// Returns the parsed query string.
+    ///
+    /// The query string is parsed the first time this method is called, and
+    /// the result is reused by the extractors of this request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use poem::{handler, test::TestClient, Request};
+    ///
+    /// #[handler]
+    /// fn index(req: &Request) -> String {
+    ///     req.query().get_all("tag").collect::<Vec<_>>().join(",")
+    /// }
+    ///
+    /// let cli = TestClient::new(index);
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let resp = cli
+    ///     .get("/")
+    ///     .query("tag", &"a")
+    ///     .query("tag", &"b")
+    ///     .send()
+    ///     .await;
+    /// resp.assert_text("a,b").await;
+    /// # });
+    /// ```
+    pub fn query(&self) -> &QueryParams {
+        self.state
+            .query_params
+            .get_or_init(|| QueryParams::parse(self.uri.query().unwrap_or_default()))
     }
 
     /// Returns the content type of this request.
@@ -377,6 +531,50 @@ impl Request {
         self.extensions.insert(data);
     }
 
+    /// Returns the [`RequestLocal`] store of this request, which contains the
+    /// values computed for this request.
+    #[inline]
+    pub fn local(&self) -> &RequestLocal {
+        &self.state.local
+    }
+
+    /// Inserts a value which is added to the extensions of the response, so
+    /// that the middlewares can read it with [`Response::data`] after the
+    /// endpoint has been called.
+    ///
+    /// The values are moved to the response by [`Route`](crate::Route) and
+    /// the endpoints created with [`handler`](crate::handler), and do not
+    /// replace the values already set on the response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{handler, test::TestClient, Endpoint, EndpointExt, Request, Response, Route};
+    ///
+    /// #[derive(Clone)]
+    /// struct UserId(u64);
+    ///
+    /// #[handler]
+    /// fn index(req: &Request) -> &'static str {
+    ///     req.set_response_data(UserId(42));
+    ///     "hello"
+    /// }
+    ///
+    /// let app = Route::new().at("/", index).after(|res| async move {
+    ///     let resp: Response = res?;
+    ///     assert_eq!(resp.data::<UserId>().map(|id| id.0), Some(42));
+    ///     Ok(resp)
+    /// });
+    /// let cli = TestClient::new(app);
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// cli.get("/").send().await.assert_status_is_ok();
+    /// # });
+    /// ```
+    pub fn set_response_data(&self, data: impl Clone + Send + Sync + 'static) {
+        self.state.response_data.0.lock().insert(data);
+    }
+
     /// Returns a reference to the remote address.
     #[inline]
     pub fn remote_addr(&self) -> &RemoteAddr {
@@ -609,12 +807,15 @@ impl RequestBuilder {
     pub fn body(self, body: impl Into<Body>) -> Request {
         Request {
             method: self.method,
-            uri: self.uri,
+            uri: self.uri.clone(),
             version: self.version,
             headers: self.headers,
             extensions: self.extensions,
             body: body.into(),
-            state: Default::default(),
+            state: RequestState {
+                original_uri: self.uri,
+                ..Default::default()
+            },
         }
     }
 
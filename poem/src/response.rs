@@ -1,4 +1,7 @@
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    fmt::{self, Debug, Formatter},
+    str::FromStr,
+};
 
 use bytes::Bytes;
 use headers::HeaderMapExt;
@@ -193,6 +196,52 @@ impl Response {
             .and_then(|value| value.to_str().ok())
     }
 
+    /// Returns all the string values of the specified header.
+    ///
+    /// NOTE: The values which are not valid UTF8 strings are skipped.
+    pub fn header_all(&self, name: impl AsRef<str>) -> Vec<&str> {
+        self.headers
+            .get_all(name.as_ref())
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect()
+    }
+
+    /// Parses the string value of the specified header with
+    /// [`FromStr`].
+    ///
+    /// NOTE: Returns `None` if the header does not exist or the value cannot
+    /// be parsed.
+    pub fn header_parse<T: FromStr>(&self, name: impl AsRef<str>) -> Option<T> {
+        self.header(name)?.trim().parse().ok()
+    }
+
+    /// Appends a header, the existing values of the header are kept.
+    ///
+    /// NOTE: The header is ignored if the name or value is invalid.
+    pub fn append_header<K, V>(&mut self, key: K, value: V)
+    where
+        K: TryInto<HeaderName>,
+        V: TryInto<HeaderValue>,
+    {
+        if let (Ok(key), Ok(value)) = (key.try_into(), value.try_into()) {
+            self.headers.append(key, value);
+        }
+    }
+
+    /// Sets a header, the existing values of the header are replaced.
+    ///
+    /// NOTE: The header is ignored if the name or value is invalid.
+    pub fn set_header<K, V>(&mut self, key: K, value: V)
+    where
+        K: TryInto<HeaderName>,
+        V: TryInto<HeaderValue>,
+    {
+        if let (Ok(key), Ok(value)) = (key.try_into(), value.try_into()) {
+            self.headers.insert(key, value);
+        }
+    }
+
     /// Returns the associated version.
     #[inline]
     pub fn version(&self) -> Version {
@@ -364,4 +413,20 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
         assert_eq!(resp.body.into_string().await.unwrap(), "abc");
     }
+
+    #[test]
+    fn headers() {
+        let mut resp = Response::builder().header("x-value", "1").finish();
+        resp.append_header("x-value", "2");
+        resp.append_header("x-value", HeaderValue::from_bytes(b"\xff").unwrap());
+        resp.append_header("invalid name", "3");
+        assert_eq!(resp.header_all("x-value"), vec!["1", "2"]);
+        assert_eq!(resp.header_parse::<i32>("x-value"), Some(1));
+        assert_eq!(resp.headers().len(), 3);
+
+        resp.set_header("x-value", "abc");
+        assert_eq!(resp.header_all("x-value"), vec!["abc"]);
+        assert_eq!(resp.header_parse::<i32>("x-value"), None);
+        assert_eq!(resp.header_parse::<i32>("x-missing"), None);
+    }
 }
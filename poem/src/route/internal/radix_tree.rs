@@ -1,5 +1,7 @@
 use std::{
+    borrow::Cow,
     fmt::{self, Debug, Formatter},
+    ops::Range,
     sync::Arc,
 };
 
@@ -375,20 +377,27 @@ impl<T> Node<T> {
         child.insert_child(segments, data)
     }
 
-    fn matches<'a: 'b, 'b>(
+    fn matches<'a: 'b, 'b, F>(
         &'a self,
         path: &'b [u8],
         params: &mut SmallVec<[(&'b [u8], &'b [u8]); 8]>,
-    ) -> Option<&'a NodeData<T>> {
+        skip: &F,
+    ) -> Option<&'a NodeData<T>>
+    where
+        F: Fn(&NodeData<T>) -> bool,
+    {
+        let not_skipped = |data: &'a Option<NodeData<T>>| data.as_ref().filter(|data| !skip(data));
+
         if path.is_empty() {
-            return if let Some(catch_all_child) = &self.catch_all_child {
-                if !catch_all_child.name.is_empty() {
-                    params.push((&catch_all_child.name, path));
+            if let Some(catch_all_child) = &self.catch_all_child {
+                if let Some(data) = not_skipped(&catch_all_child.data) {
+                    if !catch_all_child.name.is_empty() {
+                        params.push((&catch_all_child.name, path));
+                    }
+                    return Some(data);
                 }
-                catch_all_child.data.as_ref()
-            } else {
-                self.data.as_ref()
-            };
+            }
+            return not_skipped(&self.data);
         }
 
         let num_params = params.len();
@@ -396,7 +405,7 @@ impl<T> Node<T> {
         if let Some(pos) = self.find_static_child(path[0]) {
             let child = &self.children[pos];
             if let Some(tail_path) = path.strip_prefix(child.name.as_slice()) {
-                if let Some(data) = child.matches(tail_path, params) {
+                if let Some(data) = child.matches(tail_path, params, skip) {
                     return Some(data);
                 }
             }
@@ -410,7 +419,7 @@ impl<T> Node<T> {
                 if !regex_children.name.is_empty() {
                     params.push((&regex_children.name, value));
                 }
-                if let Some(data) = regex_children.matches(&path[value.len()..], params) {
+                if let Some(data) = regex_children.matches(&path[value.len()..], params, skip) {
                     return Some(data);
                 }
             }
@@ -424,22 +433,169 @@ impl<T> Node<T> {
                 None => path,
             };
             params.push((&param_children.name, value));
-            if let Some(data) = param_children.matches(&path[value.len()..], params) {
+            if let Some(data) = param_children.matches(&path[value.len()..], params, skip) {
                 return Some(data);
             }
         }
 
         params.truncate(num_params);
         if let Some(catch_all_child) = &self.catch_all_child {
+            let data = not_skipped(&catch_all_child.data)?;
             params.push((&catch_all_child.name, path));
-            return catch_all_child.data.as_ref();
+            return Some(data);
         }
 
         None
     }
 }
 
-pub(crate) type PathParams = Vec<(String, String)>;
+/// The path parameters of a matched route.
+///
+/// All names and values are stored in a single buffer to avoid allocating
+/// strings for each parameter of each request.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) struct PathParams {
+    buf: String,
+    entries: SmallVec<[PathParamEntry; 4]>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct PathParamEntry {
+    name: Range<usize>,
+    value: Range<usize>,
+}
+
+impl PathParams {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: String::with_capacity(capacity),
+            entries: SmallVec::new(),
+        }
+    }
+
+    fn push_decoded(&mut self, name: &str, value: Cow<'_, str>) {
+        let name_start = self.buf.len();
+        self.buf.push_str(name);
+        let value_start = self.buf.len();
+        self.buf.push_str(&value);
+        self.entries.push(PathParamEntry {
+            name: name_start..value_start,
+            value: value_start..self.buf.len(),
+        });
+    }
+
+    /// Appends a parameter.
+    #[inline]
+    pub(crate) fn push(&mut self, name: &str, value: &str) {
+        self.push_decoded(name, Cow::Borrowed(value));
+    }
+
+    /// Removes the last parameter.
+    pub(crate) fn pop(&mut self) {
+        if let Some(entry) = self.entries.pop() {
+            self.buf.truncate(entry.name.start);
+        }
+    }
+
+    /// Appends all parameters of `other`.
+    pub(crate) fn append(&mut self, other: PathParams) {
+        if self.entries.is_empty() {
+            *self = other;
+            return;
+        }
+        for (name, value) in other.iter() {
+            self.push(name, value);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn as_ref(&self) -> PathParamsRef<'_> {
+        PathParamsRef {
+            buf: &self.buf,
+            entries: &self.entries,
+        }
+    }
+
+    #[cfg(test)]
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub(crate) fn last(&self) -> Option<(&str, &str)> {
+        self.as_ref().last()
+    }
+
+    #[cfg(test)]
+    #[inline]
+    pub(crate) fn get(&self, idx: usize) -> Option<(&str, &str)> {
+        self.as_ref().get(idx)
+    }
+
+    #[inline]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.as_ref().iter()
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> FromIterator<(K, V)> for PathParams {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut params = PathParams::default();
+        for (name, value) in iter {
+            params.push(name.as_ref(), value.as_ref());
+        }
+        params
+    }
+}
+
+/// A borrowed view of [`PathParams`].
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct PathParamsRef<'a> {
+    buf: &'a str,
+    entries: &'a [PathParamEntry],
+}
+
+impl<'a> PathParamsRef<'a> {
+    #[inline]
+    fn entry(&self, entry: &PathParamEntry) -> (&'a str, &'a str) {
+        (
+            &self.buf[entry.name.clone()],
+            &self.buf[entry.value.clone()],
+        )
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub(crate) fn get(&self, idx: usize) -> Option<(&'a str, &'a str)> {
+        self.entries.get(idx).map(|entry| self.entry(entry))
+    }
+
+    #[inline]
+    pub(crate) fn last(&self) -> Option<(&'a str, &'a str)> {
+        self.entries.last().map(|entry| self.entry(entry))
+    }
+
+    pub(crate) fn split_first(&self) -> Option<((&'a str, &'a str), PathParamsRef<'a>)> {
+        let (first, tail) = self.entries.split_first()?;
+        Some((
+            self.entry(first),
+            PathParamsRef {
+                buf: self.buf,
+                entries: tail,
+            },
+        ))
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&'a str, &'a str)> {
+        let this = *self;
+        self.entries.iter().map(move |entry| this.entry(entry))
+    }
+}
 
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) struct Matches<'a, T> {
@@ -505,22 +661,55 @@ impl<T> RadixTree<T> {
         }
     }
 
-    pub(crate) fn matches(&self, path: &str) -> Option<Matches<T>> {
+    /// Returns the data of all nodes, sorted by the patterns.
+    pub(crate) fn entries(&self) -> Vec<&NodeData<T>> {
+        fn collect<'a, T>(node: &'a Node<T>, entries: &mut Vec<&'a NodeData<T>>) {
+            entries.extend(&node.data);
+            node.children
+                .iter()
+                .chain(node.param_children.iter().map(AsRef::as_ref))
+                .chain(node.catch_all_child.iter().map(AsRef::as_ref))
+                .chain(node.regex_children.iter().map(AsRef::as_ref))
+                .for_each(|child| collect(child, entries));
+        }
+
+        let mut entries = Vec::new();
+        collect(&self.root, &mut entries);
+        entries.sort_by(|a, b| a.pattern.cmp(&b.pattern));
+        entries
+    }
+
+    pub(crate) fn matches(&self, path: &str) -> Option<Matches<'_, T>> {
+        self.matches_with_skip(path, |_| false)
+    }
+
+    /// Like [`RadixTree::matches`], but the nodes for which `skip` returns
+    /// `true` are treated as if they do not exist.
+    pub(crate) fn matches_with_skip(
+        &self,
+        path: &str,
+        skip: impl Fn(&NodeData<T>) -> bool,
+    ) -> Option<Matches<'_, T>> {
         if path.is_empty() {
             return None;
         }
 
         let mut params = SmallVec::default();
 
-        match self.root.matches(path.as_bytes(), &mut params) {
+        match self.root.matches(path.as_bytes(), &mut params, &skip) {
             Some(data) => {
-                let mut params2 = Vec::with_capacity(params.len());
+                let mut params2 = PathParams::with_capacity(
+                    params
+                        .iter()
+                        .map(|(name, value)| name.len() + value.len())
+                        .sum(),
+                );
                 for (name, value) in params {
                     if let (Ok(name), Ok(value)) = (
                         std::str::from_utf8(name),
                         percent_encoding::percent_decode(value).decode_utf8(),
                     ) {
-                        params2.push((name.to_string(), value.into_owned()));
+                        params2.push_decoded(name, value);
                     }
                 }
                 Some(Matches {
@@ -1093,8 +1282,14 @@ mod tests {
         }
 
         let matches = vec![
-            ("/ab/def", Some((vec![], NodeData::new(1, "/ab/def")))),
-            ("/abc/def", Some((vec![], NodeData::new(2, "/abc/def")))),
+            (
+                "/ab/def",
+                Some((PathParams::default(), NodeData::new(1, "/ab/def"))),
+            ),
+            (
+                "/abc/def",
+                Some((PathParams::default(), NodeData::new(2, "/abc/def"))),
+            ),
             (
                 "/abc/cde",
                 Some((
@@ -1146,7 +1341,7 @@ mod tests {
             ),
             (
                 "/abc/123/def",
-                Some((vec![], NodeData::new(10, "/abc/<\\d+>/def"))),
+                Some((PathParams::default(), NodeData::new(10, "/abc/<\\d+>/def"))),
             ),
             (
                 "/kcd/567",
@@ -1217,6 +1412,25 @@ mod tests {
         assert_eq!(matches.unwrap().data.data, 3);
     }
 
+    #[test]
+    fn test_matches_with_skip() {
+        let mut tree = RadixTree::default();
+        tree.add("/*path", 1).unwrap();
+        tree.add("/a/*path", 2).unwrap();
+        tree.add("/a/:id", 3).unwrap();
+
+        let matches = tree.matches_with_skip("/a/123", |data| data.data == 3);
+        assert_eq!(matches.unwrap().data.data, 2);
+
+        let matches = tree
+            .matches_with_skip("/a/123", |data| data.data != 1)
+            .unwrap();
+        assert_eq!(matches.data.data, 1);
+        assert_eq!(matches.params.get(0), Some(("path", "a/123")));
+
+        assert!(tree.matches_with_skip("/a/123", |_| true).is_none());
+    }
+
     #[test]
     fn test_issue_275() {
         let mut tree = RadixTree::default();
@@ -1226,14 +1440,12 @@ mod tests {
         let matches = tree.matches("/abc/a").unwrap();
         assert_eq!(matches.data.data, 1);
         assert_eq!(matches.params.len(), 1);
-        assert_eq!(matches.params[0].0, "id1");
-        assert_eq!(matches.params[0].1, "abc");
+        assert_eq!(matches.params.get(0), Some(("id1", "abc")));
 
         let matches = tree.matches("/def/b").unwrap();
         assert_eq!(matches.data.data, 2);
         assert_eq!(matches.params.len(), 1);
-        assert_eq!(matches.params[0].0, "id2");
-        assert_eq!(matches.params[0].1, "def");
+        assert_eq!(matches.params.get(0), Some(("id2", "def")));
     }
 
     #[test]
@@ -1243,12 +1455,10 @@ mod tests {
 
         let matches = tree.matches("/a/abc").unwrap();
         assert_eq!(matches.data.data, 1);
-        assert_eq!(matches.params[0].0, "id");
-        assert_eq!(matches.params[0].1, "abc");
+        assert_eq!(matches.params.get(0), Some(("id", "abc")));
 
         let matches = tree.matches("/a/%E4%BD%A0%E5%A5%BD").unwrap();
         assert_eq!(matches.data.data, 1);
-        assert_eq!(matches.params[0].0, "id");
-        assert_eq!(matches.params[0].1, "你好");
+        assert_eq!(matches.params.get(0), Some(("id", "你好")));
     }
 }
@@ -76,6 +76,43 @@ impl<T> Trie<T> {
         }
     }
 
+    /// Returns the patterns and their data, sorted by pattern.
+    pub(crate) fn entries(&self) -> Vec<(String, &T)> {
+        let mut entries = Vec::new();
+        Self::internal_entries(&self.root, &mut Vec::new(), &mut entries);
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        entries
+    }
+
+    fn internal_entries<'a>(
+        node: &'a Node<T>,
+        segments: &mut Vec<&'a str>,
+        entries: &mut Vec<(String, &'a T)>,
+    ) {
+        let pattern = |last: Option<&str>| {
+            last.into_iter()
+                .chain(segments.iter().rev().copied())
+                .collect::<Vec<_>>()
+                .join(".")
+        };
+        if let Some(data) = &node.data {
+            entries.push((pattern(None), data));
+        }
+        if let Some(data) = &node.star_child {
+            entries.push((pattern(Some("*")), data));
+        }
+        if let Some(plus_child) = &node.plus_child {
+            segments.push("+");
+            Self::internal_entries(plus_child, segments, entries);
+            segments.pop();
+        }
+        for (name, child) in &node.named_children {
+            segments.push(name);
+            Self::internal_entries(child, segments, entries);
+            segments.pop();
+        }
+    }
+
     pub(crate) fn matches(&self, domain: &str) -> Option<&T> {
         if domain.is_empty() {
             return self.root.star_child.as_ref();
@@ -114,6 +151,25 @@ impl<T> Trie<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_entries() {
+        let mut tree = Trie::default();
+        tree.add("www.example.com", 1).unwrap();
+        tree.add("+.example.com", 2).unwrap();
+        tree.add("*.com", 3).unwrap();
+        tree.add("*", 4).unwrap();
+
+        assert_eq!(
+            tree.entries(),
+            vec![
+                ("*".to_string(), &4),
+                ("*.com".to_string(), &3),
+                ("+.example.com".to_string(), &2),
+                ("www.example.com".to_string(), &1),
+            ]
+        );
+    }
+
     #[test]
     fn test_add() {
         let mut tree = Trie::default();
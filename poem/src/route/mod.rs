@@ -3,14 +3,19 @@
 mod internal;
 mod router;
 mod router_domain;
+mod router_dynamic;
 mod router_method;
 mod router_scheme;
 
-pub(crate) use internal::radix_tree::PathParams;
+pub(crate) use internal::radix_tree::{PathParams, PathParamsRef};
+pub(crate) use router::MatchedParams;
 pub use router::{PathPattern, Route};
 #[allow(unreachable_pub)]
 pub use router_domain::RouteDomain;
 #[allow(unreachable_pub)]
+pub use router_dynamic::{DynamicRoute, DynamicRouteHandle};
+pub(crate) use router_method::AllowedMethodsProbe;
+#[allow(unreachable_pub)]
 pub use router_method::{
     connect, delete, get, head, options, patch, post, put, trace, RouteMethod,
 };
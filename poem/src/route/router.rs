@@ -1,18 +1,32 @@
 use std::{str::FromStr, sync::Arc};
 
+use parking_lot::Mutex;
 use regex::Regex;
 
 use crate::{
-    endpoint::BoxEndpoint,
+    endpoint::{BoxEndpoint, MiddlewareChain},
     error::{NotFoundError, ParsePathError, RouteError},
-    http::{uri::PathAndQuery, Uri},
-    route::{check_result, internal::radix_tree::RadixTree},
+    http::{uri::PathAndQuery, Extensions, Uri},
+    route::{
+        check_result,
+        internal::radix_tree::{Matches, PathParams, RadixTree},
+    },
     Endpoint, EndpointExt, IntoEndpoint, IntoResponse, Request, Response, Result,
 };
 
 #[derive(Debug, Clone, Copy)]
 struct PathPrefix(usize);
 
+/// The slot to hand back the requests not matched by the nested routes, see
+/// [`Route::fallthrough`].
+#[derive(Clone, Default)]
+struct UnmatchedRequest(Arc<Mutex<Option<Request>>>);
+
+/// The slot to report the path parameters of the innermost matched route to
+/// the middlewares wrapping the route.
+#[derive(Clone, Default)]
+pub(crate) struct MatchedParams(pub(crate) Arc<Mutex<Option<PathParams>>>);
+
 /// Routing object
 ///
 /// You can match the full path or wildcard path, and use the
@@ -131,6 +145,8 @@ struct PathPrefix(usize);
 #[derive(Default)]
 pub struct Route {
     tree: RadixTree<BoxEndpoint<'static>>,
+    data: Extensions,
+    fallthrough: bool,
 }
 
 impl Route {
@@ -139,6 +155,102 @@ impl Route {
         Default::default()
     }
 
+    /// Attaches a data to the requests matched by this routing object, which
+    /// can be extracted with [`Data`](crate::web::Data).
+    ///
+    /// Unlike [`EndpointExt::data`], the returned value is still a `Route`, so
+    /// the data can be attached to a nested scope while defining it. The data
+    /// of an inner scope shadows the data of the same type in an outer one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{handler, test::TestClient, web::Data, EndpointExt, Route};
+    ///
+    /// #[derive(Clone)]
+    /// struct Config(&'static str);
+    ///
+    /// #[handler]
+    /// fn index(Data(config): Data<&Config>) -> &'static str {
+    ///     config.0
+    /// }
+    ///
+    /// let app = Route::new()
+    ///     .at("/", index)
+    ///     .at("/limited", index.data(Config("limited")))
+    ///     .nest(
+    ///         "/admin",
+    ///         Route::new().at("/", index).route_data(Config("admin")),
+    ///     )
+    ///     .route_data(Config("default"));
+    /// let cli = TestClient::new(app);
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// cli.get("/").send().await.assert_text("default").await;
+    /// cli.get("/limited")
+    ///     .send()
+    ///     .await
+    ///     .assert_text("limited")
+    ///     .await;
+    /// cli.get("/admin").send().await.assert_text("admin").await;
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn route_data<T: Clone + Send + Sync + 'static>(mut self, data: T) -> Self {
+        self.data.insert(data);
+        self
+    }
+
+    /// Specifies whether the requests not matched by the routes of a nested
+    /// endpoint, such as a nested `Route` or an `OpenApiService`, are routed
+    /// to the other routes of this route that match the path. (defaults to
+    /// `false`)
+    ///
+    /// Only the requests not matched by the routing tables fall through, a
+    /// [`NotFoundError`] returned by a handler is returned as it is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{handler, test::TestClient, Route};
+    ///
+    /// #[handler]
+    /// fn users() -> &'static str {
+    ///     "users"
+    /// }
+    ///
+    /// #[handler]
+    /// fn spa() -> &'static str {
+    ///     "index.html"
+    /// }
+    ///
+    /// let app = Route::new()
+    ///     .nest("/app", Route::new().at("/users", users))
+    ///     .at("/*path", spa)
+    ///     .fallthrough(true);
+    /// let cli = TestClient::new(app);
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// cli.get("/app/users")
+    ///     .send()
+    ///     .await
+    ///     .assert_text("users")
+    ///     .await;
+    /// cli.get("/app/settings")
+    ///     .send()
+    ///     .await
+    ///     .assert_text("index.html")
+    ///     .await;
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn fallthrough(self, enable: bool) -> Self {
+        Self {
+            fallthrough: enable,
+            ..self
+        }
+    }
+
     /// Add an [Endpoint] to the specified path.
     ///
     /// # Panics
@@ -250,16 +362,15 @@ impl Route {
             async fn call(&self, mut req: Request) -> Result<Self::Output> {
                 if !self.root {
                     let params = &mut req.state_mut().match_params;
-                    if params.last().map(|(name, _)| name.as_str()) != Some("--poem-rest") {
+                    if params.last().map(|(name, _)| name) != Some("--poem-rest") {
                         return Err(ParsePathError.into());
                     }
 
-                    params.pop().expect("can't be empty due to a check above");
+                    params.pop();
                 }
 
                 let new_uri = {
-                    let uri = std::mem::take(req.uri_mut());
-                    let mut uri_parts = uri.into_parts();
+                    let mut uri_parts = req.uri().clone().into_parts();
                     let path =
                         &uri_parts.path_and_query.as_ref().unwrap().as_str()[self.prefix_len..];
                     uri_parts.path_and_query = Some(if !path.starts_with('/') {
@@ -269,11 +380,22 @@ impl Route {
                     });
                     Uri::from_parts(uri_parts).unwrap()
                 };
-                *req.uri_mut() = new_uri;
+                req.set_uri(new_uri);
 
                 req.set_data(PathPrefix(self.prefix_for_path_pattern));
                 Ok(self.inner.call(req).await?.into_response())
             }
+
+            fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+                let mut chains = self.inner.middleware_chains();
+                // the paths of the endpoints nested without stripping contain the prefix
+                for chain in &mut chains {
+                    if let Some(path) = chain.path.get(self.prefix_for_path_pattern..) {
+                        chain.path = path.to_string();
+                    }
+                }
+                chains
+            }
         }
 
         assert!(
@@ -328,54 +450,127 @@ impl Route {
 #[derive(Debug, Clone)]
 pub struct PathPattern(pub Arc<str>);
 
+impl Route {
+    async fn call_matched(
+        &self,
+        mut req: Request,
+        matches: Matches<'_, BoxEndpoint<'static>>,
+    ) -> Result<Response> {
+        req.state_mut().match_params.append(matches.params);
+        if let Some(slot) = req.data::<MatchedParams>() {
+            *slot.0.lock() = Some(req.state().match_params.clone());
+        }
+        if !self.data.is_empty() {
+            req.extensions_mut().extend(self.data.clone());
+        }
+
+        let pattern = match matches.data.pattern.strip_suffix("/*--poem-rest") {
+            Some(pattern) => pattern.into(),
+            None => matches.data.pattern.clone(),
+        };
+
+        let pattern = match (req.data::<PathPattern>(), req.data::<PathPrefix>()) {
+            (Some(parent), Some(prefix)) => {
+                PathPattern(format!("{}{}", parent.0, &pattern[prefix.0..]).into())
+            }
+            (None, Some(prefix)) => PathPattern(pattern[prefix.0..].into()),
+            (None, None) => PathPattern(pattern),
+            (Some(parent), None) => PathPattern(format!("{}{}", parent.0, pattern).into()),
+        };
+        req.set_data(pattern.clone());
+
+        let response_data = req.state().response_data.clone();
+        let result = response_data.apply(matches.data.data.call(req).await);
+
+        // Add PathPattern to the innermost response so that metrics instrumentation
+        // can report the innermost matched pattern.
+        match result {
+            Ok(mut res) => {
+                if res.data::<PathPattern>().is_none() {
+                    res.set_data(pattern);
+                }
+                Ok(res)
+            }
+            Err(mut err) => {
+                if err.data::<PathPattern>().is_none() {
+                    err.set_data(pattern);
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Returns [`NotFoundError`], and hands the request back to the route with
+/// [`Route::fallthrough`] enabled if there is one.
+#[allow(clippy::result_large_err)]
+fn unmatched(req: Request) -> Result<Response> {
+    if let Some(slot) = req.data::<UnmatchedRequest>().cloned() {
+        *slot.0.lock() = Some(req);
+    }
+    Err(NotFoundError.into())
+}
+
 impl Endpoint for Route {
     type Output = Response;
 
     async fn call(&self, mut req: Request) -> Result<Self::Output> {
-        match self.tree.matches(req.uri().path()) {
-            Some(matches) => {
-                req.state_mut().match_params.extend(matches.params);
+        let Some(matches) = self.tree.matches(req.uri().path()) else {
+            return unmatched(req);
+        };
+        if !self.fallthrough {
+            return self.call_matched(req, matches).await;
+        }
 
-                let pattern = match matches.data.pattern.strip_suffix("/*--poem-rest") {
-                    Some(pattern) => pattern.into(),
-                    None => matches.data.pattern.clone(),
+        // restores the request if it is not matched by the nested routes
+        let uri = req.uri().clone();
+        let match_params = req.state().match_params.clone();
+        let extensions = req.extensions().clone();
+        let slot = UnmatchedRequest::default();
+        req.set_data(slot.clone());
+
+        let pattern = matches.data.pattern.clone();
+        match self.call_matched(req, matches).await {
+            Err(err) if err.is::<NotFoundError>() => {
+                let Some(mut req) = slot.0.lock().take() else {
+                    return Err(err);
                 };
+                req.set_uri(uri);
+                req.state_mut().match_params = match_params;
+                *req.extensions_mut() = extensions;
+
+                match self
+                    .tree
+                    .matches_with_skip(req.uri().path(), |data| data.pattern == pattern)
+                {
+                    Some(matches) => self.call_matched(req, matches).await,
+                    None => unmatched(req),
+                }
+            }
+            res => res,
+        }
+    }
 
-                let pattern = match (req.data::<PathPattern>(), req.data::<PathPrefix>()) {
-                    (Some(parent), Some(prefix)) => {
-                        PathPattern(format!("{}{}", parent.0, &pattern[prefix.0..]).into())
-                    }
-                    (None, Some(prefix)) => PathPattern(pattern[prefix.0..].into()),
-                    (None, None) => PathPattern(pattern),
-                    (Some(parent), None) => PathPattern(format!("{}{}", parent.0, pattern).into()),
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        let mut chains = Vec::new();
+        for entry in self.tree.entries() {
+            if entry.pattern.ends_with("/*--poem-rest") {
+                continue;
+            }
+            for mut chain in entry.data.middleware_chains() {
+                chain.path = match (&*entry.pattern, chain.path.as_str()) {
+                    (pattern, "" | "/") => pattern.to_string(),
+                    ("/", path) => path.to_string(),
+                    (pattern, path) => format!("{pattern}{path}"),
                 };
-                req.set_data(pattern.clone());
-
-                let result = matches.data.data.call(req).await;
-
-                // Add PathPattern to the innermost response so that metrics instrumentation
-                // can report the innermost matched pattern.
-                match result {
-                    Ok(mut res) => {
-                        if res.data::<PathPattern>().is_none() {
-                            res.set_data(pattern);
-                        }
-                        Ok(res)
-                    }
-                    Err(mut err) => {
-                        if err.data::<PathPattern>().is_none() {
-                            err.set_data(pattern);
-                        }
-                        Err(err)
-                    }
-                }
+                chains.push(chain);
             }
-            None => Err(NotFoundError.into()),
         }
+        chains
     }
 }
 
-fn normalize_path(path: &str) -> String {
+pub(super) fn normalize_path(path: &str) -> String {
     let re = Regex::new("//+").unwrap();
     let mut path = re.replace_all(path, "/").to_string();
     if !path.starts_with('/') {
@@ -415,6 +610,63 @@ mod tests {
             .unwrap()
     }
 
+    #[tokio::test]
+    async fn fallthrough() {
+        #[handler(internal)]
+        fn not_found() -> Result<()> {
+            Err(NotFoundError.into())
+        }
+
+        let api = || {
+            Route::new()
+                .at("/a", h)
+                .at("/missing", not_found)
+                .nest("/inner", Route::new().at("/b", h))
+        };
+
+        let r = Route::new().nest("/api", api()).at("/*path", h);
+        assert_eq!(get(&r, "/api/a").await, "/a");
+        assert!(r
+            .call(Request::builder().uri(Uri::from_static("/api/c")).finish())
+            .await
+            .unwrap_err()
+            .is::<NotFoundError>());
+
+        let r = Route::new()
+            .nest("/api", api())
+            .at("/*path", h)
+            .fallthrough(true);
+        assert_eq!(get(&r, "/api/a").await, "/a");
+        assert_eq!(get(&r, "/api/inner/b").await, "/b");
+        assert_eq!(get(&r, "/api/c").await, "/api/c");
+        assert_eq!(get(&r, "/api/inner/c").await, "/api/inner/c");
+        assert_eq!(get(&r, "/other").await, "/other");
+        assert!(r
+            .call(
+                Request::builder()
+                    .uri(Uri::from_static("/api/missing"))
+                    .finish()
+            )
+            .await
+            .unwrap_err()
+            .is::<NotFoundError>());
+
+        // falls through the nested routes with fallthrough enabled
+        let r = Route::new()
+            .nest(
+                "/api",
+                Route::new()
+                    .nest("/inner", Route::new().at("/b", h))
+                    .at("/*path", h)
+                    .fallthrough(true),
+            )
+            .at("/*path", h)
+            .fallthrough(true);
+        assert_eq!(get(&r, "/api/inner/b").await, "/b");
+        assert_eq!(get(&r, "/api/inner/c").await, "/inner/c");
+        assert_eq!(get(&r, "/api").await, "/");
+    }
+
     #[tokio::test]
     async fn nested() {
         let r = Route::new().nest(
@@ -784,4 +1036,138 @@ mod tests {
             "/nest_no_strip1/nest_no_strip2/:id"
         );
     }
+
+    #[test]
+    fn middleware_chains() {
+        use crate::{
+            get,
+            middleware::{AddData, SetHeader, Tracing},
+        };
+
+        let ep = || make_sync(|_| ());
+        let app = Route::new()
+            .at("/a", get(ep().with(SetHeader::new())).post(ep()))
+            .nest(
+                "/api",
+                Route::new()
+                    .at("/", ep())
+                    .at("/x", ep().named("x"))
+                    .with(Tracing),
+            )
+            .nest_no_strip("/static", Route::new().at("/static/file", ep()))
+            .with(AddData::new(1));
+
+        assert_eq!(
+            app.middleware_chains()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "GET /a: AddData -> SetHeader",
+                "POST /a: AddData",
+                "/api: AddData -> Tracing",
+                "/api/x: AddData -> Tracing -> x",
+                "/static/file: AddData",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn route_data() {
+        #[derive(Clone)]
+        struct Name(&'static str);
+
+        #[derive(Clone)]
+        struct Level(u8);
+
+        let ep = || {
+            make_sync(|req| {
+                format!(
+                    "{}:{}",
+                    req.data::<Name>().unwrap().0,
+                    req.data::<Level>().map(|level| level.0).unwrap_or_default()
+                )
+            })
+        };
+
+        let app = Route::new()
+            .at("/", ep())
+            .at("/single", ep().data(Name("single")))
+            .nest(
+                "/admin",
+                Route::new()
+                    .at("/a", ep())
+                    .nest("/b", Route::new().at("/c", ep()).route_data(Name("b")))
+                    .route_data(Name("admin"))
+                    .route_data(Level(2)),
+            )
+            .route_data(Name("root"));
+        let cli = TestClient::new(app);
+
+        cli.get("/").send().await.assert_text("root:0").await;
+        cli.get("/single")
+            .send()
+            .await
+            .assert_text("single:0")
+            .await;
+        cli.get("/admin/a")
+            .send()
+            .await
+            .assert_text("admin:2")
+            .await;
+        cli.get("/admin/b/c").send().await.assert_text("b:2").await;
+    }
+
+    #[tokio::test]
+    async fn response_data() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct UserId(u64);
+
+        #[handler(internal)]
+        fn index(req: &Request) -> Result<&'static str> {
+            req.set_response_data(UserId(1));
+            if req.uri().path() == "/err" {
+                return Err(Error::from_status(StatusCode::BAD_REQUEST));
+            }
+            Ok("hello")
+        }
+
+        let check = |res: Result<Response>| async move {
+            let data = match &res {
+                Ok(resp) => resp.data::<UserId>().cloned(),
+                Err(err) => err.data::<UserId>().cloned(),
+            };
+            assert_eq!(data, Some(UserId(1)));
+            res
+        };
+
+        // endpoints created with `handler`
+        let cli = TestClient::new(index.after(check));
+        cli.get("/").send().await.assert_status_is_ok();
+        cli.get("/err")
+            .send()
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        // other endpoints
+        let app = Route::new()
+            .at(
+                "/",
+                make_sync(|req| {
+                    req.set_response_data(UserId(1));
+                    "hello"
+                }),
+            )
+            .at(
+                "/existing",
+                make_sync(|req| {
+                    req.set_response_data(UserId(2));
+                    Response::builder().extension(UserId(1)).finish()
+                }),
+            )
+            .after(check);
+        let cli = TestClient::new(app);
+        cli.get("/").send().await.assert_status_is_ok();
+        cli.get("/existing").send().await.assert_status_is_ok();
+    }
 }
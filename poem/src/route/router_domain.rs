@@ -1,5 +1,5 @@
 use crate::{
-    endpoint::BoxEndpoint,
+    endpoint::{BoxEndpoint, MiddlewareChain},
     error::{NotFoundError, RouteError},
     http::header,
     route::{check_result, internal::trie::Trie},
@@ -101,6 +101,14 @@ impl Endpoint for RouteDomain {
             None => Err(NotFoundError.into()),
         }
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.tree
+            .entries()
+            .into_iter()
+            .flat_map(|(_, ep)| ep.middleware_chains())
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -198,4 +206,24 @@ mod tests {
     fn duplicate_4() {
         let _ = RouteDomain::new().at("*", h).at("*", h);
     }
+
+    #[test]
+    fn middleware_chains() {
+        use crate::{middleware::Tracing, Route};
+
+        let app = RouteDomain::new()
+            .at(
+                "www.example.com",
+                Route::new().at("/a", make_sync(|_| ()).named("a")),
+            )
+            .at("*", make_sync(|_| ()).with(Tracing));
+
+        assert_eq!(
+            app.middleware_chains()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["/: Tracing", "/a: a"]
+        );
+    }
 }
@@ -0,0 +1,271 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use parking_lot::{Mutex, RwLock};
+
+use crate::{
+    endpoint::{BoxEndpoint, MiddlewareChain},
+    error::RouteError,
+    route::router::normalize_path,
+    Endpoint, EndpointExt, IntoEndpoint, Request, Response, Result, Route,
+};
+
+#[derive(Clone)]
+struct Entry {
+    nest: bool,
+    ep: Arc<BoxEndpoint<'static>>,
+}
+
+struct Inner {
+    entries: Mutex<BTreeMap<String, Entry>>,
+    route: RwLock<Arc<Route>>,
+}
+
+impl Inner {
+    fn update(
+        &self,
+        f: impl FnOnce(&mut BTreeMap<String, Entry>) -> Result<bool, RouteError>,
+    ) -> Result<bool, RouteError> {
+        let mut entries = self.entries.lock();
+        let mut new_entries = entries.clone();
+        if !f(&mut new_entries)? {
+            return Ok(false);
+        }
+
+        let mut route = Route::new();
+        for (path, entry) in &new_entries {
+            route = if entry.nest {
+                route.try_nest(path, entry.ep.clone())?
+            } else {
+                route.try_at(path, entry.ep.clone())?
+            };
+        }
+
+        *entries = new_entries;
+        *self.route.write() = Arc::new(route);
+        Ok(true)
+    }
+}
+
+/// Routing object whose routing table can be modified at runtime, with the
+/// [`DynamicRouteHandle`] returned by [`DynamicRoute::handle`].
+///
+/// Each modification rebuilds the routing table, and the requests being
+/// processed are not affected by it.
+///
+/// # Errors
+///
+/// - [`NotFoundError`](crate::error::NotFoundError)
+///
+/// # Example
+///
+/// ```
+/// use poem::{endpoint::make_sync, test::TestClient, DynamicRoute, Route};
+///
+/// let dynamic = DynamicRoute::new();
+/// let handle = dynamic.handle();
+/// let app = Route::new().nest("/hooks", dynamic);
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// cli.get("/hooks/a")
+///     .send()
+///     .await
+///     .assert_status(poem::http::StatusCode::NOT_FOUND);
+///
+/// handle.add("/a", make_sync(|_| "hook a")).unwrap();
+/// cli.get("/hooks/a").send().await.assert_text("hook a").await;
+///
+/// handle.remove("/a");
+/// cli.get("/hooks/a")
+///     .send()
+///     .await
+///     .assert_status(poem::http::StatusCode::NOT_FOUND);
+/// # });
+/// ```
+pub struct DynamicRoute {
+    inner: Arc<Inner>,
+}
+
+impl Default for DynamicRoute {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                entries: Default::default(),
+                route: Default::default(),
+            }),
+        }
+    }
+}
+
+impl DynamicRoute {
+    /// Create a new dynamic routing object.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns a handle to modify the routing table.
+    pub fn handle(&self) -> DynamicRouteHandle {
+        DynamicRouteHandle {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Endpoint for DynamicRoute {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let route = self.inner.route.read().clone();
+        route.call(req).await
+    }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.inner.route.read().middleware_chains()
+    }
+}
+
+/// A cloneable handle to modify the routing table of a [`DynamicRoute`].
+#[derive(Clone)]
+pub struct DynamicRouteHandle {
+    inner: Arc<Inner>,
+}
+
+impl DynamicRouteHandle {
+    /// Adds an [Endpoint] to the specified path.
+    ///
+    /// Returns [`RouteError::Duplicate`] if the path already exists, use
+    /// [`DynamicRouteHandle::replace`] to replace an existing endpoint.
+    pub fn add<E>(&self, path: impl AsRef<str>, ep: E) -> Result<(), RouteError>
+    where
+        E: IntoEndpoint,
+        E::Endpoint: 'static,
+    {
+        self.insert(path.as_ref(), ep, false, false).map(|_| ())
+    }
+
+    /// Nests an [Endpoint] to the specified path and strips the prefix.
+    ///
+    /// Returns [`RouteError::Duplicate`] if the path already exists.
+    pub fn nest<E>(&self, path: impl AsRef<str>, ep: E) -> Result<(), RouteError>
+    where
+        E: IntoEndpoint,
+        E::Endpoint: 'static,
+    {
+        self.insert(path.as_ref(), ep, true, false).map(|_| ())
+    }
+
+    /// Adds an [Endpoint] to the specified path, replacing the existing one.
+    ///
+    /// Returns `true` if an endpoint has been replaced.
+    pub fn replace<E>(&self, path: impl AsRef<str>, ep: E) -> Result<bool, RouteError>
+    where
+        E: IntoEndpoint,
+        E::Endpoint: 'static,
+    {
+        self.insert(path.as_ref(), ep, false, true)
+    }
+
+    fn insert<E>(&self, path: &str, ep: E, nest: bool, replace: bool) -> Result<bool, RouteError>
+    where
+        E: IntoEndpoint,
+        E::Endpoint: 'static,
+    {
+        let path = normalize_path(path);
+        let entry = Entry {
+            nest,
+            ep: Arc::new(ep.map_to_response().boxed()),
+        };
+        let mut replaced = false;
+        self.inner.update(|entries| {
+            if entries.contains_key(&path) && !replace {
+                return Err(RouteError::Duplicate(path));
+            }
+            replaced = entries.insert(path, entry).is_some();
+            Ok(true)
+        })?;
+        Ok(replaced)
+    }
+
+    /// Removes the endpoint at the specified path.
+    ///
+    /// Returns `true` if the endpoint has been removed.
+    pub fn remove(&self, path: impl AsRef<str>) -> bool {
+        let path = normalize_path(path.as_ref());
+        self.inner
+            .update(|entries| Ok(entries.remove(&path).is_some()))
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if there is an endpoint at the specified path.
+    pub fn contains(&self, path: impl AsRef<str>) -> bool {
+        self.inner
+            .entries
+            .lock()
+            .contains_key(&normalize_path(path.as_ref()))
+    }
+
+    /// Returns the paths in the routing table.
+    pub fn paths(&self) -> Vec<String> {
+        self.inner.entries.lock().keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+    use crate::{endpoint::make_sync, handler, test::TestClient, web::Path};
+
+    #[tokio::test]
+    async fn dynamic_route() {
+        let dynamic = DynamicRoute::new();
+        let handle = dynamic.handle();
+        let cli = TestClient::new(Route::new().nest("/api", dynamic));
+
+        cli.get("/api/a")
+            .send()
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+
+        #[handler(internal)]
+        fn hello(Path(name): Path<String>) -> String {
+            format!("hello {name}")
+        }
+
+        handle.add("/a", make_sync(|_| "a")).unwrap();
+        handle.add("/hello/:name", hello).unwrap();
+        handle
+            .nest("/b", Route::new().at("/c", make_sync(|_| "b/c")))
+            .unwrap();
+        assert_eq!(handle.paths(), vec!["/a", "/b", "/hello/:name"]);
+        assert!(matches!(
+            handle.add("/a", make_sync(|_| "a")),
+            Err(RouteError::Duplicate(_))
+        ));
+        assert!(matches!(
+            handle.add("/x/:y<[>", make_sync(|_| "x")),
+            Err(RouteError::InvalidRegex { .. })
+        ));
+        assert!(!handle.contains("/x/:y<[>"));
+
+        cli.get("/api/a").send().await.assert_text("a").await;
+        cli.get("/api/hello/poem")
+            .send()
+            .await
+            .assert_text("hello poem")
+            .await;
+        cli.get("/api/b/c").send().await.assert_text("b/c").await;
+
+        assert!(handle.replace("/a", make_sync(|_| "a2")).unwrap());
+        cli.get("/api/a").send().await.assert_text("a2").await;
+
+        assert!(handle.remove("/a"));
+        assert!(!handle.remove("/a"));
+        cli.get("/api/a")
+            .send()
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        cli.get("/api/b/c").send().await.assert_text("b/c").await;
+    }
+}
@@ -1,10 +1,13 @@
 use std::future::Future;
 
 use futures_util::{future::Either, FutureExt};
+use headers::Allow;
 
 use crate::{
-    endpoint::BoxEndpoint, error::MethodNotAllowedError, http::Method, Endpoint, EndpointExt,
-    IntoEndpoint, Request, Response, Result,
+    endpoint::{BoxEndpoint, MiddlewareChain},
+    error::MethodNotAllowedError,
+    http::{Method, StatusCode},
+    Endpoint, EndpointExt, IntoEndpoint, Request, Response, Result,
 };
 
 /// Routing object for HTTP methods
@@ -51,13 +54,20 @@ use crate::{
 ///     .get_response(Request::builder().method(Method::PUT).finish())
 ///     .await;
 /// assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+///
 /// # });
 /// ```
 #[derive(Default)]
 pub struct RouteMethod {
     methods: Vec<(Method, BoxEndpoint<'static>)>,
+    auto_options: bool,
 }
 
+/// Marks a request to ask [`RouteMethod`] for its allowed methods, which are
+/// returned in the `Allow` header without calling any endpoint.
+#[derive(Clone)]
+pub(crate) struct AllowedMethodsProbe;
+
 impl RouteMethod {
     /// Create a `RouteMethod` object.
     pub fn new() -> Self {
@@ -165,12 +175,74 @@ impl RouteMethod {
     {
         self.method(Method::TRACE, ep)
     }
+
+    /// Specifies whether to answer the `OPTIONS` requests with `204 No
+    /// Content` and an `Allow` header listing the allowed methods if there is
+    /// no endpoint for `OPTIONS`. (defaults to `false`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{
+    ///     handler,
+    ///     http::{Method, StatusCode},
+    ///     Endpoint, Request, RouteMethod,
+    /// };
+    ///
+    /// #[handler]
+    /// fn index() -> &'static str {
+    ///     "hello"
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let route_method = RouteMethod::new().get(index).post(index).auto_options(true);
+    ///
+    /// let resp = route_method
+    ///     .get_response(Request::builder().method(Method::OPTIONS).finish())
+    ///     .await;
+    /// assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    /// assert_eq!(resp.header("allow"), Some("GET, POST, HEAD, OPTIONS"));
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn auto_options(self, enable: bool) -> Self {
+        Self {
+            auto_options: enable,
+            ..self
+        }
+    }
+
+    /// Returns the methods that this object responds to, `HEAD` is included
+    /// if there is an endpoint for `GET`, and `OPTIONS` is included if there
+    /// is an endpoint for it or [`RouteMethod::auto_options`] is enabled.
+    pub fn allowed_methods(&self) -> Vec<Method> {
+        let mut methods: Vec<Method> = self.methods.iter().map(|(m, _)| m.clone()).collect();
+        if methods.contains(&Method::GET) && !methods.contains(&Method::HEAD) {
+            methods.push(Method::HEAD);
+        }
+        if self.auto_options && !methods.contains(&Method::OPTIONS) {
+            methods.push(Method::OPTIONS);
+        }
+        methods
+    }
+
+    fn allow_response(&self) -> Response {
+        Response::builder()
+            .status(StatusCode::NO_CONTENT)
+            .typed_header(self.allowed_methods().into_iter().collect::<Allow>())
+            .finish()
+    }
 }
 
 impl Endpoint for RouteMethod {
     type Output = Response;
 
     fn call(&self, mut req: Request) -> impl Future<Output = Result<Self::Output>> + Send {
+        if req.extensions().get::<AllowedMethodsProbe>().is_some() {
+            let resp = self.allow_response();
+            return Either::Right(Either::Right(Either::Left(std::future::ready(Ok(resp)))));
+        }
+
         match self
             .methods
             .iter()
@@ -189,12 +261,28 @@ impl Endpoint for RouteMethod {
                         }
                         .boxed(),
                     ))
+                } else if self.auto_options && req.method() == Method::OPTIONS {
+                    let resp = self.allow_response();
+                    Either::Right(Either::Right(Either::Left(std::future::ready(Ok(resp)))))
                 } else {
-                    Either::Right(Either::Right(async { Err(MethodNotAllowedError.into()) }))
+                    Either::Right(Either::Right(Either::Right(async {
+                        Err(MethodNotAllowedError.into())
+                    })))
                 }
             }
         }
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        let mut chains = Vec::new();
+        for (method, ep) in &self.methods {
+            for mut chain in ep.middleware_chains() {
+                chain.method = Some(method.clone());
+                chains.push(chain);
+            }
+        }
+        chains
+    }
 }
 
 /// A helper function, similar to `RouteMethod::new().get(ep)`.
@@ -289,6 +377,44 @@ mod tests {
         resp.assert_status(StatusCode::METHOD_NOT_ALLOWED);
     }
 
+    #[tokio::test]
+    async fn auto_options() {
+        #[handler(internal)]
+        fn index() -> &'static str {
+            "hello"
+        }
+
+        TestClient::new(RouteMethod::new().get(index).delete(index))
+            .options("/")
+            .send()
+            .await
+            .assert_status(StatusCode::METHOD_NOT_ALLOWED);
+
+        let resp = TestClient::new(
+            RouteMethod::new()
+                .get(index)
+                .delete(index)
+                .auto_options(true),
+        )
+        .options("/")
+        .send()
+        .await;
+        resp.assert_status(StatusCode::NO_CONTENT);
+        resp.assert_header("allow", "GET, DELETE, HEAD, OPTIONS");
+
+        let resp = TestClient::new(
+            RouteMethod::new()
+                .post(index)
+                .options(index)
+                .auto_options(true),
+        )
+        .options("/")
+        .send()
+        .await;
+        resp.assert_status_is_ok();
+        resp.assert_text("hello").await;
+    }
+
     #[tokio::test]
     async fn route_method() {
         #[handler(internal)]
@@ -1,8 +1,9 @@
 use http::uri::Scheme;
 
 use crate::{
-    endpoint::BoxEndpoint, error::NotFoundError, Endpoint, EndpointExt, IntoEndpoint, Request,
-    Response,
+    endpoint::{BoxEndpoint, MiddlewareChain},
+    error::NotFoundError,
+    Endpoint, EndpointExt, IntoEndpoint, Request, Response,
 };
 
 /// Routing object for request scheme
@@ -89,4 +90,13 @@ impl Endpoint for RouteScheme {
             },
         }
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        self.schemes
+            .iter()
+            .map(|(_, ep)| ep)
+            .chain(&self.fallback)
+            .flat_map(|ep| ep.middleware_chains())
+            .collect()
+    }
 }
@@ -5,28 +5,35 @@ use std::{
     io::IoSlice,
     pin::Pin,
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
     task::{Context, Poll},
 };
 
-use http::uri::Scheme;
-use hyper::body::Incoming;
+use bytes::{BufMut, Bytes, BytesMut};
+use futures_util::{future::BoxFuture, ready, FutureExt};
+use http::{header, uri::Scheme, StatusCode};
+use hyper::body::{Frame, Incoming, SizeHint};
 use hyper_util::server::conn::auto;
 use pin_project_lite::pin_project;
 use tokio::{
     io::{AsyncRead, AsyncWrite, ReadBuf, Result as IoResult},
-    sync::{oneshot, Notify},
+    runtime::Handle,
+    sync::{oneshot, Notify, OwnedSemaphorePermit, Semaphore},
     time::Duration,
 };
 use tokio_util::sync::CancellationToken;
 
 use crate::{
+    body::BoxBody,
     endpoint::{DynEndpoint, ToDynEndpoint},
-    listener::{Acceptor, AcceptorExt, Listener},
-    web::{LocalAddr, RemoteAddr},
-    Endpoint, EndpointExt, IntoEndpoint, Response,
+    listener::{
+        Acceptor, AcceptorExt, AlpnProtocolSlot, BoxAcceptor, BoxIo, Listener, PeerCredentials,
+    },
+    middleware::RequestLimits,
+    web::{GracefulShutdown, LocalAddr, RemoteAddr, TrackedResponse},
+    Endpoint, EndpointExt, Error, IntoEndpoint, IntoResponse, Response,
 };
 
 enum Either<L, A> {
@@ -34,12 +41,30 @@ enum Either<L, A> {
     Acceptor(A),
 }
 
+const DEFAULT_UPGRADE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum number of the connections rejected concurrently, see
+/// [`Server::shed_connections`].
+const MAX_CONCURRENT_REJECTIONS: usize = 64;
+
+type StartHook = Box<dyn FnOnce(Vec<LocalAddr>) -> BoxFuture<'static, IoResult<()>> + Send>;
+type ShutdownHook = Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>;
+
 /// An HTTP Server.
 #[cfg_attr(docsrs, doc(cfg(feature = "server")))]
 pub struct Server<L, A> {
     listener: Either<L, A>,
     name: Option<String>,
-    idle_timeout: Option<Duration>,
+    timeouts: ConnectionTimeouts,
+    runtime: Option<Handle>,
+    max_connections: Option<usize>,
+    shed_connections: bool,
+    connection_gauge: ConnectionGauge,
+    upgrade_shutdown_timeout: Duration,
+    request_limits: Option<RequestLimits>,
+    h2c: bool,
+    on_start: Vec<StartHook>,
+    on_shutdown: Vec<ShutdownHook>,
 }
 
 impl<L: Listener> Server<L, Infallible> {
@@ -48,7 +73,16 @@ impl<L: Listener> Server<L, Infallible> {
         Self {
             listener: Either::Listener(listener),
             name: None,
-            idle_timeout: None,
+            timeouts: ConnectionTimeouts::default(),
+            runtime: None,
+            max_connections: None,
+            shed_connections: false,
+            connection_gauge: ConnectionGauge::default(),
+            upgrade_shutdown_timeout: DEFAULT_UPGRADE_SHUTDOWN_TIMEOUT,
+            request_limits: None,
+            h2c: true,
+            on_start: Vec::new(),
+            on_shutdown: Vec::new(),
         }
     }
 }
@@ -59,7 +93,16 @@ impl<A: Acceptor> Server<Infallible, A> {
         Self {
             listener: Either::Acceptor(acceptor),
             name: None,
-            idle_timeout: None,
+            timeouts: ConnectionTimeouts::default(),
+            runtime: None,
+            max_connections: None,
+            shed_connections: false,
+            connection_gauge: ConnectionGauge::default(),
+            upgrade_shutdown_timeout: DEFAULT_UPGRADE_SHUTDOWN_TIMEOUT,
+            request_limits: None,
+            h2c: true,
+            on_start: Vec::new(),
+            on_shutdown: Vec::new(),
         }
     }
 }
@@ -84,11 +127,363 @@ where
     #[must_use]
     pub fn idle_timeout(self, timeout: Duration) -> Self {
         Self {
-            idle_timeout: Some(timeout),
+            timeouts: ConnectionTimeouts {
+                idle: Some(timeout),
+                ..self.timeouts
+            },
+            ..self
+        }
+    }
+
+    /// Specify how long to wait for the client to send the complete headers
+    /// of an HTTP/1 request, the connection is closed if they are not
+    /// received in time.
+    ///
+    /// Unlike [`Server::idle_timeout`], it cannot be extended by sending the
+    /// headers slowly, which protects the server from the slowloris attacks.
+    #[must_use]
+    pub fn header_read_timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeouts: ConnectionTimeouts {
+                header_read: Some(timeout),
+                ..self.timeouts
+            },
+            ..self
+        }
+    }
+
+    /// Specify how long a connection is kept open without any request in
+    /// flight, including the time before the first request.
+    ///
+    /// When it elapses, the connection is gracefully closed.
+    #[must_use]
+    pub fn keep_alive_timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeouts: ConnectionTimeouts {
+                keep_alive: Some(timeout),
+                ..self.timeouts
+            },
             ..self
         }
     }
 
+    /// Specify the maximum time to produce the response of a request,
+    /// including the time to read the request body.
+    ///
+    /// If the endpoint does not return the response in time, the request is
+    /// cancelled and the client receives `408 Request Timeout`. The response
+    /// body is not limited by this timeout once the response is returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use poem::{handler, listener::TcpListener, Server};
+    ///
+    /// #[handler]
+    /// fn index() -> &'static str {
+    ///     "hello"
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// Server::new(TcpListener::bind("0.0.0.0:3000"))
+    ///     .header_read_timeout(Duration::from_secs(10))
+    ///     .keep_alive_timeout(Duration::from_secs(60))
+    ///     .request_timeout(Duration::from_secs(30))
+    ///     .run(index)
+    ///     .await
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn request_timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeouts: ConnectionTimeouts {
+                request: Some(timeout),
+                ..self.timeouts
+            },
+            ..self
+        }
+    }
+
+    /// Specify the runtime used to spawn the tasks serving the connections.
+    ///
+    /// By default, the tasks are spawned on the runtime that runs the server.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use poem::{handler, listener::TcpListener, Server};
+    ///
+    /// #[handler]
+    /// fn index() -> &'static str {
+    ///     "hello"
+    /// }
+    ///
+    /// let workers = tokio::runtime::Builder::new_multi_thread()
+    ///     .enable_all()
+    ///     .build()
+    ///     .unwrap();
+    /// let runtime = tokio::runtime::Builder::new_current_thread()
+    ///     .enable_all()
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// runtime
+    ///     .block_on(
+    ///         Server::new(TcpListener::bind("0.0.0.0:3000"))
+    ///             .runtime(workers.handle().clone())
+    ///             .run(index),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    pub fn runtime(self, handle: Handle) -> Self {
+        Self {
+            runtime: Some(handle),
+            ..self
+        }
+    }
+
+    /// Specify the maximum number of connections served concurrently.
+    ///
+    /// When the limit is reached, no new connection is accepted until one of
+    /// the connections is closed, unless [`Server::shed_connections`] is
+    /// enabled. By default there is no limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_connections` is zero.
+    #[must_use]
+    pub fn max_connections(self, max_connections: usize) -> Self {
+        assert!(
+            max_connections > 0,
+            "`max_connections` must be greater than zero"
+        );
+        Self {
+            max_connections: Some(max_connections),
+            ..self
+        }
+    }
+
+    /// Specify whether to keep accepting the new connections when the limit
+    /// of [`Server::max_connections`] is reached, and reject their requests
+    /// with `503 Service Unavailable` instead of leaving them in the backlog
+    /// of the listener. (defaults to `false`)
+    ///
+    /// The rejected connections are closed after the first response, or
+    /// within a second. At most 64 connections are rejected concurrently, the
+    /// connections exceeding this are closed immediately without a
+    /// response.
+    #[must_use]
+    pub fn shed_connections(self, enable: bool) -> Self {
+        Self {
+            shed_connections: enable,
+            ..self
+        }
+    }
+
+    /// Specify a [`ConnectionGauge`] to observe the number of the connections
+    /// of this server.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use poem::{handler, listener::TcpListener, ConnectionGauge, Server};
+    ///
+    /// #[handler]
+    /// fn index() -> &'static str {
+    ///     "hello"
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let gauge = ConnectionGauge::new();
+    /// tokio::spawn({
+    ///     let gauge = gauge.clone();
+    ///     async move {
+    ///         loop {
+    ///             tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+    ///             println!("active: {}, rejected: {}", gauge.active(), gauge.rejected());
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// Server::new(TcpListener::bind("0.0.0.0:3000"))
+    ///     .max_connections(1024)
+    ///     .shed_connections(true)
+    ///     .connection_gauge(gauge)
+    ///     .run(index)
+    ///     .await
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn connection_gauge(self, gauge: ConnectionGauge) -> Self {
+        Self {
+            connection_gauge: gauge,
+            ..self
+        }
+    }
+
+    /// Specify how long the graceful shutdown waits for the upgraded
+    /// connections, such as WebSocket connections, and the other connections
+    /// tracked with [`GracefulShutdown`] after the regular connections have
+    /// been closed.
+    ///
+    /// The WebSocket connections are sent a `Close` message and the `SSE`
+    /// event streams end when the graceful shutdown is requested, so they
+    /// usually close well before the timeout.
+    ///
+    /// Default is 10 seconds, use [`Duration::ZERO`] to stop without waiting
+    /// for them.
+    #[must_use]
+    pub fn upgrade_shutdown_timeout(self, timeout: Duration) -> Self {
+        Self {
+            upgrade_shutdown_timeout: timeout,
+            ..self
+        }
+    }
+
+    /// Specify the limits of the request URI and headers for all the requests
+    /// of this server, see [`RequestLimits`].
+    ///
+    /// The requests exceeding the limits are rejected with `414 URI Too Long`
+    /// or `431 Request Header Fields Too Large` before they reach the
+    /// endpoint.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use poem::{handler, listener::TcpListener, middleware::RequestLimits, Server};
+    ///
+    /// #[handler]
+    /// fn index() -> &'static str {
+    ///     "hello"
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// Server::new(TcpListener::bind("0.0.0.0:3000"))
+    ///     .request_limits(
+    ///         RequestLimits::new()
+    ///             .max_uri_length(8 * 1024)
+    ///             .max_header_count(64)
+    ///             .max_header_bytes(16 * 1024),
+    ///     )
+    ///     .run(index)
+    ///     .await
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn request_limits(self, limits: RequestLimits) -> Self {
+        Self {
+            request_limits: Some(limits),
+            ..self
+        }
+    }
+
+    /// Specify whether to accept HTTP/2 over cleartext TCP (h2c). (defaults
+    /// to `true`)
+    ///
+    /// The connections starting with the HTTP/2 connection preface (prior
+    /// knowledge) are served with HTTP/2, and the others with HTTP/1. A
+    /// request without body with the `Upgrade: h2c` header switches the
+    /// connection to HTTP/2, and is answered on the stream 1 of the HTTP/2
+    /// connection. The requests with body are served with HTTP/1.1 without
+    /// upgrading the connection.
+    ///
+    /// When it is disabled, the connections without TLS are always served
+    /// with HTTP/1. The connections with TLS negotiate the protocol with ALPN
+    /// regardless of this option.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use poem::{handler, listener::TcpListener, Server};
+    ///
+    /// #[handler]
+    /// fn index() -> &'static str {
+    ///     "hello"
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// // serve HTTP/1 only
+    /// Server::new(TcpListener::bind("0.0.0.0:3000"))
+    ///     .h2c(false)
+    ///     .run(index)
+    ///     .await
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn h2c(self, enable: bool) -> Self {
+        Self {
+            h2c: enable,
+            ..self
+        }
+    }
+
+    /// Adds a callback that is called after the listener is bound and before
+    /// the server starts accepting connections, with the local addresses of
+    /// the listener.
+    ///
+    /// It can be used to initialize the application, such as warming up the
+    /// caches or announcing the server to the service discovery. The
+    /// connections are not accepted until all the callbacks complete, in the
+    /// order they are added, and the server stops with the error if any of
+    /// them fails. If the shutdown signal completes first, the server stops
+    /// without waiting for the remaining callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use poem::{handler, listener::TcpListener, Server};
+    ///
+    /// #[handler]
+    /// fn index() -> &'static str {
+    ///     "hello"
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// Server::new(TcpListener::bind("0.0.0.0:3000"))
+    ///     .on_start(|addrs| async move {
+    ///         println!("register {:?} to the service discovery", addrs);
+    ///         Ok(())
+    ///     })
+    ///     .on_shutdown(|| async {
+    ///         println!("deregister from the service discovery");
+    ///     })
+    ///     .run(index)
+    ///     .await
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn on_start<F, Fut>(mut self, f: F) -> Self
+    where
+        F: FnOnce(Vec<LocalAddr>) -> Fut + Send + 'static,
+        Fut: Future<Output = IoResult<()>> + Send + 'static,
+    {
+        self.on_start.push(Box::new(move |addrs| f(addrs).boxed()));
+        self
+    }
+
+    /// Adds a callback that is called when the graceful shutdown is
+    /// initiated, after the server stops accepting connections and before
+    /// the remaining connections are drained.
+    ///
+    /// It can be used to withdraw the server from the service discovery or to
+    /// clean up the resources of the application, the callbacks are called
+    /// in the order they are added. They are also called if an
+    /// [`on_start`](Server::on_start) callback fails or the shutdown signal
+    /// completes before the callbacks of `on_start` finish, but not if the
+    /// listener fails to bind.
+    #[must_use]
+    pub fn on_shutdown<F, Fut>(mut self, f: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.on_shutdown.push(Box::new(move || f().boxed()));
+        self
+    }
+
     /// Run this server.
     pub async fn run<E>(self, ep: E) -> IoResult<()>
     where
@@ -100,6 +495,44 @@ where
     }
 
     /// Run this server and a signal to initiate graceful shutdown.
+    ///
+    /// When `signal` completes, the server stops accepting new connections,
+    /// the idle keep-alive connections are closed, and the in-flight requests
+    /// are allowed to complete. If `timeout` is specified, the connections
+    /// that are still open after it elapses are aborted, otherwise the server
+    /// waits until all of them are closed.
+    ///
+    /// The upgraded connections, such as WebSockets, are not aborted by
+    /// `timeout`, see [`Server::upgrade_shutdown_timeout`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use poem::{handler, listener::TcpListener, Server};
+    /// use tokio::sync::oneshot;
+    ///
+    /// #[handler]
+    /// fn index() -> &'static str {
+    ///     "hello"
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let (tx, rx) = oneshot::channel::<()>();
+    /// # drop(tx);
+    /// Server::new(TcpListener::bind("0.0.0.0:3000"))
+    ///     .run_with_graceful_shutdown(
+    ///         index,
+    ///         async move {
+    ///             let _ = rx.await;
+    ///         },
+    ///         Some(Duration::from_secs(10)),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// # });
+    /// ```
     pub async fn run_with_graceful_shutdown<E>(
         self,
         ep: E,
@@ -114,13 +547,26 @@ where
         let Server {
             listener,
             name,
-            idle_timeout,
+            timeouts,
+            runtime,
+            max_connections,
+            shed_connections,
+            connection_gauge,
+            upgrade_shutdown_timeout,
+            request_limits,
+            h2c,
+            on_start,
+            on_shutdown,
         } = self;
+        let runtime = runtime.unwrap_or_else(Handle::current);
+        let connection_limit = max_connections.map(|n| Arc::new(Semaphore::new(n)));
+        let rejections = Arc::new(Semaphore::new(MAX_CONCURRENT_REJECTIONS));
         let name = name.as_deref();
         let alive_connections = Arc::new(AtomicUsize::new(0));
         let notify = Arc::new(Notify::new());
         let timeout_token = CancellationToken::new();
         let server_graceful_shutdown_token = CancellationToken::new();
+        let shutdown = GracefulShutdown::new(server_graceful_shutdown_token.clone());
 
         let mut acceptor = match listener {
             Either::Listener(listener) => listener.into_acceptor().await?.boxed(),
@@ -132,6 +578,30 @@ where
         for addr in acceptor.local_addr() {
             tracing::info!(name = name, addr = %addr, "listening");
         }
+        let local_addrs = acceptor.local_addr();
+        let start = async {
+            for hook in on_start {
+                hook(local_addrs.clone()).await?;
+            }
+            IoResult::Ok(())
+        };
+        let started = tokio::select! {
+            res = start => Some(res),
+            _ = &mut signal => None,
+        };
+        match started {
+            Some(Ok(())) => {}
+            Some(Err(err)) => {
+                run_shutdown_hooks(on_shutdown).await;
+                return Err(err);
+            }
+            None => {
+                tracing::info!(name = name, "shutdown requested before the server started");
+                run_shutdown_hooks(on_shutdown).await;
+                tracing::info!(name = name, "server stopped");
+                return Ok(());
+            }
+        }
         tracing::info!(name = name, "server started");
 
         loop {
@@ -155,52 +625,262 @@ where
                     }
                     break;
                 },
-                res = acceptor.accept() => {
-                    if let Ok((socket, local_addr, remote_addr, scheme)) = res {
-                        alive_connections.fetch_add(1, Ordering::Release);
+                res = accept(&mut acceptor, connection_limit.as_ref(), shed_connections) => {
+                    match res {
+                        Ok(Accepted::Reject(socket)) => {
+                            connection_gauge.rejected.fetch_add(1, Ordering::Relaxed);
+                            // the socket is closed immediately if there are too many rejections
+                            if let Ok(permit) = rejections.clone().try_acquire_owned() {
+                                runtime.spawn(async move {
+                                    let _permit = permit;
+                                    reject_connection(socket).await;
+                                });
+                            }
+                        }
+                        Ok(Accepted::Serve(permit, (socket, local_addr, remote_addr, scheme))) => {
+                            alive_connections.fetch_add(1, Ordering::Release);
+                            connection_gauge.active.fetch_add(1, Ordering::AcqRel);
 
-                        let ep = ep.clone();
-                        let alive_connections = alive_connections.clone();
-                        let notify = notify.clone();
-                        let timeout_token = timeout_token.clone();
-                        let server_graceful_shutdown_token = server_graceful_shutdown_token.clone();
+                            let ep = ep.clone();
+                            let alive_connections = alive_connections.clone();
+                            let connection_gauge = connection_gauge.clone();
+                            let notify = notify.clone();
+                            let timeout_token = timeout_token.clone();
+                            let server_graceful_shutdown_token = server_graceful_shutdown_token.clone();
+                            let shutdown = shutdown.clone();
 
-                        tokio::spawn(async move {
-                            let serve_connection = serve_connection(socket, local_addr, remote_addr, scheme, ep, server_graceful_shutdown_token.clone(), idle_timeout);
+                            runtime.spawn(async move {
+                                let _permit = permit;
+                                let alpn_protocol = BoxAcceptor::alpn_protocol(&socket);
+                                let peer_credentials = BoxAcceptor::peer_credentials(&socket);
+                                let serve_connection = serve_connection(socket, local_addr, remote_addr, scheme, alpn_protocol, peer_credentials, ep, shutdown, timeouts, request_limits, h2c);
 
-                            if timeout.is_some() {
-                                tokio::select! {
-                                    _ = serve_connection => {}
-                                    _ = timeout_token.cancelled() => {}
+                                if timeout.is_some() {
+                                    tokio::select! {
+                                        _ = serve_connection => {}
+                                        _ = timeout_token.cancelled() => {}
+                                    }
+                                } else {
+                                   serve_connection.await;
                                 }
-                            } else {
-                               serve_connection.await;
-                            }
 
-                            if alive_connections.fetch_sub(1, Ordering::Acquire) == 1 {
-                                // notify only if shutdown is initiated, to prevent notification when server is active.
-                                // It's a valid state to have 0 alive connections when server is not shutting down.
-                                if server_graceful_shutdown_token.is_cancelled() {
-                                    notify.notify_one();
+                                connection_gauge.active.fetch_sub(1, Ordering::AcqRel);
+                                if alive_connections.fetch_sub(1, Ordering::Acquire) == 1 {
+                                    // notify only if shutdown is initiated, to prevent notification when server is active.
+                                    // It's a valid state to have 0 alive connections when server is not shutting down.
+                                    if server_graceful_shutdown_token.is_cancelled() {
+                                        notify.notify_one();
+                                    }
                                 }
-                            }
-                        });
+                            });
+                        }
+                        Err(_) => {}
                     }
                 }
             }
         }
 
         drop(acceptor);
+        run_shutdown_hooks(on_shutdown).await;
         if alive_connections.load(Ordering::Acquire) > 0 {
             tracing::info!(name = name, "wait for all connections to close.");
             notify.notified().await;
         }
 
+        let tracked = shutdown.tracked();
+        if tracked > 0 {
+            if upgrade_shutdown_timeout.is_zero() {
+                tracing::info!(
+                    name = name,
+                    tracked = tracked,
+                    "stop without waiting for upgraded connections."
+                );
+            } else {
+                tracing::info!(
+                    name = name,
+                    tracked = tracked,
+                    timeout_in_seconds = upgrade_shutdown_timeout.as_secs_f32(),
+                    "wait for upgraded connections to close.",
+                );
+                if tokio::time::timeout(upgrade_shutdown_timeout, shutdown.wait_tracked())
+                    .await
+                    .is_err()
+                {
+                    tracing::info!(
+                        name = name,
+                        tracked = shutdown.tracked(),
+                        "upgraded connections did not close in time."
+                    );
+                }
+            }
+        }
+
         tracing::info!(name = name, "server stopped");
         Ok(())
     }
 }
 
+async fn run_shutdown_hooks(hooks: Vec<ShutdownHook>) {
+    for hook in hooks {
+        hook().await;
+    }
+}
+
+/// The timeouts applied to each connection.
+#[derive(Debug, Default, Clone, Copy)]
+struct ConnectionTimeouts {
+    idle: Option<Duration>,
+    header_read: Option<Duration>,
+    keep_alive: Option<Duration>,
+    request: Option<Duration>,
+}
+
+/// Tracks the requests in flight of a connection, to close the connection
+/// when it has no request in flight for the keep-alive timeout.
+#[derive(Default)]
+struct KeepAlive {
+    in_flight: AtomicUsize,
+    notify: Notify,
+}
+
+impl KeepAlive {
+    fn start_request(self: &Arc<Self>) -> KeepAliveGuard {
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        self.notify.notify_waiters();
+        KeepAliveGuard(self.clone())
+    }
+
+    /// Completes when there has been no request in flight for `timeout`.
+    async fn idle(&self, timeout: Duration) {
+        loop {
+            // created before checking the counter, so the changes are not missed
+            let notified = self.notify.notified();
+            if self.in_flight.load(Ordering::Acquire) > 0 {
+                notified.await;
+            } else if tokio::time::timeout(timeout, notified).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+struct KeepAliveGuard(Arc<KeepAlive>);
+
+pin_project! {
+    /// A response body which keeps the request in flight until it is dropped.
+    struct KeepAliveBody {
+        #[pin]
+        inner: BoxBody,
+        _guard: KeepAliveGuard,
+    }
+}
+
+impl hyper::body::Body for KeepAliveBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.project().inner.poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for KeepAliveGuard {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::AcqRel);
+        self.0.notify.notify_waiters();
+    }
+}
+
+/// A handle to observe the connections of a [`Server`], see
+/// [`Server::connection_gauge`].
+#[cfg_attr(docsrs, doc(cfg(feature = "server")))]
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionGauge {
+    active: Arc<AtomicUsize>,
+    rejected: Arc<AtomicU64>,
+}
+
+impl ConnectionGauge {
+    /// Create a `ConnectionGauge`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of the connections being served.
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of the connections rejected because the limit of
+    /// [`Server::max_connections`] was reached.
+    pub fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+}
+
+/// An accepted connection.
+enum Accepted {
+    /// Serves the connection, with the permit if the number of connections is
+    /// limited.
+    Serve(
+        Option<OwnedSemaphorePermit>,
+        (BoxIo, LocalAddr, RemoteAddr, Scheme),
+    ),
+    /// Rejects the connection because there is no connection slot.
+    Reject(BoxIo),
+}
+
+/// Waits for a connection slot if the number of connections is limited, and
+/// then accepts a connection. If `shed` is enabled, the connection is accepted
+/// without waiting, and rejected if there is no connection slot.
+async fn accept(
+    acceptor: &mut BoxAcceptor,
+    limit: Option<&Arc<Semaphore>>,
+    shed: bool,
+) -> IoResult<Accepted> {
+    let permit = match limit {
+        Some(limit) if shed => {
+            let conn = acceptor.accept().await?;
+            return Ok(match limit.clone().try_acquire_owned() {
+                Ok(permit) => Accepted::Serve(Some(permit), conn),
+                Err(_) => Accepted::Reject(conn.0),
+            });
+        }
+        Some(limit) => Some(
+            limit
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("the semaphore is never closed"),
+        ),
+        None => None,
+    };
+    Ok(Accepted::Serve(permit, acceptor.accept().await?))
+}
+
+/// Responds to the requests of a rejected connection with `503 Service
+/// Unavailable`.
+async fn reject_connection(socket: BoxIo) {
+    let service = hyper::service::service_fn(|_: http::Request<Incoming>| async {
+        Ok::<http::Response<_>, Infallible>(StatusCode::SERVICE_UNAVAILABLE.into_response().into())
+    });
+    let mut builder = auto::Builder::new(hyper_util::rt::TokioExecutor::new());
+    builder.http1().keep_alive(false);
+    let conn = builder.serve_connection(hyper_util::rt::TokioIo::new(socket), service);
+    let _ = tokio::time::timeout(Duration::from_secs(1), conn).await;
+}
+
 pin_project! {
     struct ClosingInactiveConnection<T> {
         #[pin]
@@ -312,36 +992,92 @@ impl<T> ClosingInactiveConnection<T> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn serve_connection(
     socket: impl AsyncRead + AsyncWrite + Send + Unpin + 'static,
     local_addr: LocalAddr,
     remote_addr: RemoteAddr,
     scheme: Scheme,
+    alpn_protocol: Option<AlpnProtocolSlot>,
+    peer_credentials: Option<PeerCredentials>,
     ep: Arc<dyn DynEndpoint<Output = Response>>,
-    server_graceful_shutdown_token: CancellationToken,
-    idle_connection_close_timeout: Option<Duration>,
+    shutdown: GracefulShutdown,
+    timeouts: ConnectionTimeouts,
+    request_limits: Option<RequestLimits>,
+    h2c: bool,
 ) {
     let connection_shutdown_token = CancellationToken::new();
+    let is_cleartext = scheme == Scheme::HTTP;
+    let keep_alive = Arc::new(KeepAlive::default());
+    let h2c_upgrade = Arc::new(parking_lot::Mutex::new(None::<H2cUpgrade>));
 
     let service = hyper::service::service_fn({
         let remote_addr = remote_addr.clone();
+        let shutdown = shutdown.clone();
+        let keep_alive = keep_alive.clone();
+        let h2c_upgrade = h2c_upgrade.clone();
 
-        move |req: http::Request<Incoming>| {
+        move |mut req: http::Request<Incoming>| {
+            if let Some(protocol) = alpn_protocol.as_ref().and_then(AlpnProtocolSlot::get) {
+                req.extensions_mut().insert(protocol.clone());
+            }
+            if let Some(cred) = peer_credentials {
+                req.extensions_mut().insert(cred);
+            }
+            req.extensions_mut().insert(shutdown.clone());
+            let upgrade = match h2c && is_cleartext {
+                true => h2c_upgrade_frames(&req).map(|frames| {
+                    *h2c_upgrade.lock() = Some(H2cUpgrade {
+                        on_upgrade: hyper::upgrade::on(&mut req),
+                        frames,
+                    });
+                }),
+                false => None,
+            };
             let ep = ep.clone();
             let local_addr = local_addr.clone();
             let remote_addr = remote_addr.clone();
             let scheme = scheme.clone();
+            let shutdown = shutdown.clone();
+            let keep_alive = keep_alive.start_request();
             async move {
-                Ok::<http::Response<_>, Infallible>(
-                    ep.get_response((req, local_addr, remote_addr, scheme).into())
-                        .await
-                        .into(),
-                )
+                let resp = match request_limits
+                    .as_ref()
+                    .map(|limits| limits.check(req.uri(), req.headers()))
+                {
+                    Some(Err(err)) => Error::from(err).into_response(),
+                    // the request is answered with HTTP/2 after the upgrade
+                    _ if upgrade.is_some() => Response::builder()
+                        .status(StatusCode::SWITCHING_PROTOCOLS)
+                        .header(header::CONNECTION, "upgrade")
+                        .header(header::UPGRADE, "h2c")
+                        .finish(),
+                    _ => {
+                        let resp = ep.get_response((req, local_addr, remote_addr, scheme).into());
+                        let mut resp = match timeouts.request {
+                            Some(timeout) => tokio::time::timeout(timeout, resp)
+                                .await
+                                .unwrap_or_else(|_| StatusCode::REQUEST_TIMEOUT.into_response()),
+                            None => resp.await,
+                        };
+                        if resp.extensions().get::<TrackedResponse>().is_some() {
+                            let body = shutdown.track_body(resp.take_body());
+                            resp.set_body(body);
+                        }
+                        resp
+                    }
+                };
+                // the request is in flight until the response body has been sent
+                let resp = hyper::Response::<BoxBody>::from(resp).map(|body| KeepAliveBody {
+                    inner: body,
+                    _guard: keep_alive,
+                });
+                Ok::<http::Response<_>, Infallible>(resp)
             }
         }
     });
 
-    let socket = match idle_connection_close_timeout {
+    let socket = match timeouts.idle {
         Some(timeout) => {
             tokio_util::either::Either::Left(ClosingInactiveConnection::new(socket, timeout, {
                 let connection_shutdown_token = connection_shutdown_token.clone();
@@ -356,24 +1092,1050 @@ async fn serve_connection(
         }
         None => tokio_util::either::Either::Right(socket),
     };
+    let io = hyper_util::rt::TokioIo::new(socket);
+    let max_headers = request_limits
+        .as_ref()
+        .and_then(RequestLimits::header_count)
+        // the rest of the headers are rejected by the parser, also with `431`
+        .map(|count| count.max(1));
+    let close_signal = CloseSignal {
+        remote_addr,
+        connection_shutdown_token,
+        keep_alive,
+        keep_alive_timeout: timeouts.keep_alive,
+        shutdown,
+    };
 
-    let builder = auto::Builder::new(hyper_util::rt::TokioExecutor::new());
-    let conn =
-        builder.serve_connection_with_upgrades(hyper_util::rt::TokioIo::new(socket), service);
-    futures_util::pin_mut!(conn);
+    if !h2c && is_cleartext {
+        // `auto::Builder::http1_only` is ignored by the connections with upgrades
+        let mut builder = hyper::server::conn::http1::Builder::new();
+        if let Some(count) = max_headers {
+            builder.max_headers(count);
+        }
+        if let Some(timeout) = timeouts.header_read {
+            builder
+                .timer(hyper_util::rt::TokioTimer::new())
+                .header_read_timeout(timeout);
+        }
+        let conn = builder.serve_connection(io, service).with_upgrades();
+        futures_util::pin_mut!(conn);
+        tokio::select! {
+            _ = &mut conn => {},
+            _ = close_signal.wait() => {
+                // Init graceful shutdown for connection
+                conn.as_mut().graceful_shutdown();
+                // Continue awaiting after graceful-shutdown is initiated to handle existed requests.
+                let _ = conn.await;
+            }
+        }
+        return;
+    }
 
+    let mut builder = auto::Builder::new(hyper_util::rt::TokioExecutor::new());
+    if let Some(count) = max_headers {
+        builder.http1().max_headers(count);
+    }
+    if let Some(timeout) = timeouts.header_read {
+        builder
+            .http1()
+            .timer(hyper_util::rt::TokioTimer::new())
+            .header_read_timeout(timeout);
+    }
+    let conn = builder.serve_connection_with_upgrades(io, service.clone());
+    futures_util::pin_mut!(conn);
     tokio::select! {
-        _ = &mut conn => {
-            // Connection completed successfully.
-        },
-        _ = connection_shutdown_token.cancelled() => {
-            tracing::info!(remote_addr=%remote_addr, "closing connection due to inactivity");
+        _ = &mut conn => {},
+        _ = close_signal.wait() => {
+            // Init graceful shutdown for connection
+            conn.as_mut().graceful_shutdown();
+            // Continue awaiting after graceful-shutdown is initiated to handle existed requests.
+            let _ = conn.await;
+        }
+    }
+
+    let upgrade = h2c_upgrade.lock().take();
+    if let Some(upgrade) = upgrade {
+        let Ok(upgraded) = upgrade.on_upgrade.await else {
+            return;
+        };
+        let io = H2cUpgraded {
+            inner: hyper_util::rt::TokioIo::new(upgraded),
+            buf: BytesMut::new(),
+            frames: Some(upgrade.frames),
+        };
+        let conn = hyper::server::conn::http2::Builder::new(hyper_util::rt::TokioExecutor::new())
+            .serve_connection(hyper_util::rt::TokioIo::new(io), service);
+        futures_util::pin_mut!(conn);
+        tokio::select! {
+            _ = &mut conn => {},
+            _ = close_signal.wait() => {
+                // Init graceful shutdown for connection
+                conn.as_mut().graceful_shutdown();
+                // Continue awaiting after graceful-shutdown is initiated to handle existed requests.
+                let _ = conn.await;
+            }
+        }
+    }
+}
+
+/// The reasons to close a connection gracefully.
+struct CloseSignal {
+    remote_addr: RemoteAddr,
+    connection_shutdown_token: CancellationToken,
+    keep_alive: Arc<KeepAlive>,
+    keep_alive_timeout: Option<Duration>,
+    shutdown: GracefulShutdown,
+}
+
+impl CloseSignal {
+    /// Completes when the connection is inactive or the server is stopping.
+    fn wait(&self) -> BoxFuture<'static, ()> {
+        let remote_addr = self.remote_addr.clone();
+        let connection_shutdown_token = self.connection_shutdown_token.clone();
+        let keep_alive = self.keep_alive.clone();
+        let keep_alive_timeout = self.keep_alive_timeout;
+        let shutdown = self.shutdown.clone();
+
+        async move {
+            tokio::select! {
+                _ = connection_shutdown_token.cancelled() => {
+                    tracing::info!(remote_addr=%remote_addr, "closing connection due to inactivity");
+                }
+                _ = async {
+                    match keep_alive_timeout {
+                        Some(timeout) => keep_alive.idle(timeout).await,
+                        None => futures_util::future::pending().await,
+                    }
+                } => {
+                    tracing::debug!(remote_addr=%remote_addr, "closing idle keep-alive connection");
+                }
+                _ = shutdown.requested() => {}
+            }
+        }
+        .boxed()
+    }
+}
+
+/// A request which asks to upgrade the connection to HTTP/2 over cleartext
+/// TCP.
+struct H2cUpgrade {
+    on_upgrade: hyper::upgrade::OnUpgrade,
+    frames: Bytes,
+}
+
+/// Returns the request as the frames of the stream 1 of an HTTP/2 connection
+/// if it asks to upgrade the connection to HTTP/2 over cleartext TCP, see
+/// [RFC 7540 section 3.2](https://datatracker.ietf.org/doc/html/rfc7540#section-3.2).
+fn h2c_upgrade_frames(req: &http::Request<Incoming>) -> Option<Bytes> {
+    const MAX_FRAME_SIZE: usize = 16384;
+    const HEADERS: u8 = 0x1;
+    const CONTINUATION: u8 = 0x9;
+    const END_STREAM: u8 = 0x1;
+    const END_HEADERS: u8 = 0x4;
+
+    let has_token = |name: header::HeaderName, token: &str| {
+        req.headers()
+            .get_all(name)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|value| value.trim().eq_ignore_ascii_case(token))
+    };
+    if req.version() != http::Version::HTTP_11
+        || !has_token(header::UPGRADE, "h2c")
+        || !has_token(header::CONNECTION, "upgrade")
+        || !has_token(header::CONNECTION, "http2-settings")
+        || req.headers().get_all("http2-settings").iter().count() != 1
+    {
+        return None;
+    }
+    // the body would have to be sent with DATA frames, such requests are
+    // served with HTTP/1.1
+    if !hyper::body::Body::is_end_stream(req.body()) {
+        return None;
+    }
+
+    let authority = req
+        .uri()
+        .authority()
+        .map(|authority| authority.as_str().as_bytes())
+        .or_else(|| req.headers().get(header::HOST).map(|host| host.as_bytes()));
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|path| path.as_str())
+        .unwrap_or("/");
+    let mut block = BytesMut::new();
+    hpack_literal(&mut block, b":method", req.method().as_str().as_bytes());
+    hpack_literal(&mut block, b":scheme", b"http");
+    if let Some(authority) = authority {
+        hpack_literal(&mut block, b":authority", authority);
+    }
+    hpack_literal(&mut block, b":path", path.as_bytes());
+    for (name, value) in req.headers() {
+        if matches!(
+            name.as_str(),
+            "connection"
+                | "upgrade"
+                | "http2-settings"
+                | "host"
+                | "keep-alive"
+                | "proxy-connection"
+                | "transfer-encoding"
+                | "te"
+        ) {
+            continue;
+        }
+        hpack_literal(&mut block, name.as_str().as_bytes(), value.as_bytes());
+    }
+
+    let mut frames = BytesMut::new();
+    let mut chunks = block.chunks(MAX_FRAME_SIZE).peekable();
+    let (mut ty, mut flags) = (HEADERS, END_STREAM);
+    while let Some(chunk) = chunks.next() {
+        if chunks.peek().is_none() {
+            flags |= END_HEADERS;
+        }
+        frames.put_uint(chunk.len() as u64, 3);
+        frames.put_u8(ty);
+        frames.put_u8(flags);
+        frames.put_u32(1);
+        frames.put_slice(chunk);
+        (ty, flags) = (CONTINUATION, 0);
+    }
+    Some(frames.freeze())
+}
+
+/// Encodes a header field as a literal without indexing and Huffman coding.
+fn hpack_literal(buf: &mut BytesMut, name: &[u8], value: &[u8]) {
+    buf.put_u8(0);
+    for s in [name, value] {
+        let mut len = s.len();
+        if len < 0x7f {
+            buf.put_u8(len as u8);
+        } else {
+            buf.put_u8(0x7f);
+            len -= 0x7f;
+            while len >= 0x80 {
+                buf.put_u8((len & 0x7f) as u8 | 0x80);
+                len >>= 7;
+            }
+            buf.put_u8(len as u8);
+        }
+        buf.put_slice(s);
+    }
+}
+
+pin_project! {
+    /// A connection upgraded to HTTP/2, which reads the request that asked
+    /// for the upgrade as the stream 1, after the SETTINGS frame of the
+    /// client.
+    struct H2cUpgraded<T> {
+        #[pin]
+        inner: T,
+        buf: BytesMut,
+        frames: Option<Bytes>,
+    }
+}
+
+impl<T: AsyncRead> AsyncRead for H2cUpgraded<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        out: &mut ReadBuf<'_>,
+    ) -> Poll<IoResult<()>> {
+        // the connection preface and the header of the SETTINGS frame
+        const PREFIX_LEN: usize = 24 + 9;
+        const MAX_SETTINGS_LEN: usize = 16384;
+
+        let mut this = self.project();
+        loop {
+            if this.frames.is_none() {
+                if this.buf.is_empty() {
+                    return this.inner.poll_read(cx, out);
+                }
+                let len = this.buf.len().min(out.remaining());
+                out.put_slice(&this.buf.split_to(len));
+                return Poll::Ready(Ok(()));
+            }
+
+            if this.buf.len() >= PREFIX_LEN {
+                let len =
+                    u32::from_be_bytes([0, this.buf[24], this.buf[25], this.buf[26]]) as usize;
+                if len > MAX_SETTINGS_LEN {
+                    // not a valid HTTP/2 connection, the server rejects it
+                    *this.frames = None;
+                    continue;
+                }
+                if this.buf.len() >= PREFIX_LEN + len {
+                    let rest = this.buf.split_off(PREFIX_LEN + len);
+                    let frames = this.frames.take().unwrap_or_default();
+                    this.buf.extend_from_slice(&frames);
+                    this.buf.extend_from_slice(&rest);
+                    continue;
+                }
+            }
+
+            let mut data = [0; 1024];
+            let mut data = ReadBuf::new(&mut data);
+            ready!(this.inner.as_mut().poll_read(cx, &mut data))?;
+            if data.filled().is_empty() {
+                *this.frames = None;
+            }
+            this.buf.extend_from_slice(data.filled());
+        }
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for H2cUpgraded<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+        self.project().inner.poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<IoResult<usize>> {
+        self.project().inner.poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    use super::*;
+    use crate::{
+        handler,
+        listener::{Listener, TcpListener},
+    };
+
+    async fn start_server(
+        server: impl FnOnce(BoxAcceptor) -> Server<Infallible, BoxAcceptor>,
+    ) -> std::net::SocketAddr {
+        #[handler(internal)]
+        fn index() -> String {
+            std::thread::current()
+                .name()
+                .unwrap_or_default()
+                .to_string()
+        }
+
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        let server = server(acceptor.boxed());
+        tokio::spawn(async move {
+            let _ = server.run(index).await;
+        });
+        addr
+    }
+
+    async fn request(stream: &mut TcpStream) -> String {
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut buf = vec![0; 1024];
+        let n = stream.read(&mut buf).await.unwrap();
+        String::from_utf8_lossy(&buf[..n]).into_owned()
+    }
+
+    #[tokio::test]
+    async fn runtime() {
+        let workers = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("poem-test-worker")
+            .enable_all()
+            .build()
+            .unwrap();
+        let handle = workers.handle().clone();
+        let addr =
+            start_server(|acceptor| Server::new_with_acceptor(acceptor).runtime(handle)).await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        assert!(request(&mut stream).await.ends_with("poem-test-worker"));
+
+        drop(stream);
+        workers.shutdown_background();
+    }
+
+    #[tokio::test]
+    async fn max_connections() {
+        let addr =
+            start_server(|acceptor| Server::new_with_acceptor(acceptor).max_connections(1)).await;
+
+        let mut stream1 = TcpStream::connect(addr).await.unwrap();
+        assert!(request(&mut stream1).await.starts_with("HTTP/1.1 200"));
+
+        let mut stream2 = TcpStream::connect(addr).await.unwrap();
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), request(&mut stream2))
+                .await
+                .is_err()
+        );
+
+        drop(stream1);
+        let mut buf = vec![0; 1024];
+        let n = tokio::time::timeout(Duration::from_secs(5), stream2.read(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&buf[..n]).starts_with("HTTP/1.1 200"));
+    }
+
+    #[tokio::test]
+    async fn shed_connections() {
+        let gauge = ConnectionGauge::new();
+        let addr = start_server({
+            let gauge = gauge.clone();
+            |acceptor| {
+                Server::new_with_acceptor(acceptor)
+                    .max_connections(1)
+                    .shed_connections(true)
+                    .connection_gauge(gauge)
+            }
+        })
+        .await;
+
+        let mut stream1 = TcpStream::connect(addr).await.unwrap();
+        assert!(request(&mut stream1).await.starts_with("HTTP/1.1 200"));
+        assert_eq!(gauge.active(), 1);
+
+        let mut stream2 = TcpStream::connect(addr).await.unwrap();
+        let resp = tokio::time::timeout(Duration::from_secs(5), request(&mut stream2))
+            .await
+            .unwrap();
+        assert!(resp.starts_with("HTTP/1.1 503"));
+        assert_eq!(gauge.rejected(), 1);
+
+        drop(stream1);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(gauge.active(), 0);
+
+        let mut stream3 = TcpStream::connect(addr).await.unwrap();
+        assert!(request(&mut stream3).await.starts_with("HTTP/1.1 200"));
+    }
+
+    #[tokio::test]
+    async fn shed_connections_limit_rejections() {
+        let gauge = ConnectionGauge::new();
+        let addr = start_server({
+            let gauge = gauge.clone();
+            |acceptor| {
+                Server::new_with_acceptor(acceptor)
+                    .max_connections(1)
+                    .shed_connections(true)
+                    .connection_gauge(gauge)
+            }
+        })
+        .await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        assert!(request(&mut stream).await.starts_with("HTTP/1.1 200"));
+
+        // the rejected connections which send no request are kept for a second
+        let mut rejected = Vec::new();
+        for _ in 0..MAX_CONCURRENT_REJECTIONS {
+            rejected.push(TcpStream::connect(addr).await.unwrap());
+        }
+        while gauge.rejected() < MAX_CONCURRENT_REJECTIONS as u64 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let mut buf = vec![0; 1024];
+        let n = tokio::time::timeout(Duration::from_millis(500), stream.read(&mut buf))
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        assert_eq!(n, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "`max_connections` must be greater than zero")]
+    fn max_connections_zero() {
+        let _ = Server::new(TcpListener::bind("127.0.0.1:0")).max_connections(0);
+    }
+
+    #[tokio::test]
+    async fn upgrade_shutdown_timeout() {
+        #[handler(internal)]
+        fn index(shutdown: GracefulShutdown) {
+            let guard = shutdown.track();
+            tokio::spawn(async move {
+                shutdown.requested().await;
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                drop(guard);
+            });
+        }
+
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        let (tx, rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(
+            Server::new_with_acceptor(acceptor)
+                .upgrade_shutdown_timeout(Duration::from_secs(5))
+                .run_with_graceful_shutdown(
+                    index,
+                    async move {
+                        let _ = rx.await;
+                    },
+                    None,
+                ),
+        );
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        assert!(request(&mut stream).await.starts_with("HTTP/1.1 200"));
+        drop(stream);
+
+        let start = std::time::Instant::now();
+        tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[cfg(feature = "sse")]
+    #[tokio::test]
+    async fn sse_graceful_shutdown() {
+        use crate::web::sse::{Event, SSE};
+
+        #[handler(internal)]
+        fn index() -> SSE {
+            SSE::new(futures_util::StreamExt::chain(
+                futures_util::stream::iter([Event::message("a")]),
+                futures_util::stream::pending(),
+            ))
+        }
+
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        let (tx, rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(
+            Server::new_with_acceptor(acceptor).run_with_graceful_shutdown(
+                index,
+                async move {
+                    let _ = rx.await;
+                },
+                None,
+            ),
+        );
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        assert!(request(&mut stream).await.starts_with("HTTP/1.1 200"));
+
+        // the event stream ends and the server stops without waiting for the
+        // upgrade shutdown timeout
+        tx.send(()).unwrap();
+        let mut data = Vec::new();
+        tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut data))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&data).ends_with("0\r\n\r\n"));
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+    }
+
+    async fn start_slow_server(
+        delay: Duration,
+        timeout: Option<Duration>,
+    ) -> (
+        std::net::SocketAddr,
+        oneshot::Sender<()>,
+        tokio::task::JoinHandle<IoResult<()>>,
+    ) {
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        let (tx, rx) = oneshot::channel::<()>();
+        let ep = crate::endpoint::make(move |_| async move {
+            tokio::time::sleep(delay).await;
+            "done"
+        });
+        let server = tokio::spawn(
+            Server::new_with_acceptor(acceptor).run_with_graceful_shutdown(
+                ep,
+                async move {
+                    let _ = rx.await;
+                },
+                timeout,
+            ),
+        );
+        (addr, tx, server)
+    }
+
+    #[tokio::test]
+    async fn graceful_shutdown_drains_requests() {
+        let (addr, tx, server) =
+            start_slow_server(Duration::from_millis(300), Some(Duration::from_secs(5))).await;
+
+        let mut idle_stream = TcpStream::connect(addr).await.unwrap();
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let resp = tokio::spawn(async move { request(&mut stream).await });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        tx.send(()).unwrap();
+
+        let resp = resp.await.unwrap();
+        assert!(resp.starts_with("HTTP/1.1 200"));
+        assert!(resp.ends_with("done"));
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        let mut buf = vec![0; 16];
+        assert_eq!(idle_stream.read(&mut buf).await.unwrap_or_default(), 0);
+        assert!(TcpStream::connect(addr).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn graceful_shutdown_timeout() {
+        let (addr, tx, server) =
+            start_slow_server(Duration::from_secs(30), Some(Duration::from_millis(200))).await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let start = std::time::Instant::now();
+        tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        let mut buf = vec![0; 16];
+        assert_eq!(stream.read(&mut buf).await.unwrap_or_default(), 0);
+    }
+
+    #[tokio::test]
+    async fn lifecycle_hooks() {
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        let events = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let (tx, rx) = oneshot::channel::<()>();
+
+        let server = tokio::spawn({
+            let start_events = events.clone();
+            let shutdown_events = events.clone();
+            Server::new_with_acceptor(acceptor)
+                .on_start(move |addrs| async move {
+                    assert_eq!(addrs[0].as_socket_addr(), Some(&addr));
+                    // the connections are not accepted until the hook completes
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    start_events.lock().push("start");
+                    Ok(())
+                })
+                .on_shutdown(move || async move {
+                    shutdown_events.lock().push("shutdown");
+                })
+                .run_with_graceful_shutdown(
+                    crate::endpoint::make_sync({
+                        let events = events.clone();
+                        move |_| events.lock().push("request")
+                    }),
+                    async move {
+                        let _ = rx.await;
+                    },
+                    None,
+                )
+        });
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        assert!(request(&mut stream).await.starts_with("HTTP/1.1 200"));
+        drop(stream);
+        tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(*events.lock(), vec!["start", "request", "shutdown"]);
+
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let shutdown_events = events.clone();
+        let err = Server::new_with_acceptor(acceptor)
+            .on_start(|_| async { Err(io::Error::other("failed to warm up")) })
+            .on_shutdown(move || async move {
+                shutdown_events.lock().push("shutdown");
+            })
+            .run(crate::endpoint::make_sync(|_| ()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "failed to warm up");
+        // the shutdown hooks are called even if the server fails to start
+        assert_eq!(
+            *events.lock(),
+            vec!["start", "request", "shutdown", "shutdown"]
+        );
+    }
+
+    #[tokio::test]
+    async fn lifecycle_hooks_with_signal() {
+        let events = Arc::new(parking_lot::Mutex::new(Vec::new()));
+
+        // the signal interrupts the start hooks
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let shutdown_events = events.clone();
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            Server::new_with_acceptor(acceptor)
+                .on_start(|_| futures_util::future::pending())
+                .on_shutdown(move || async move {
+                    shutdown_events.lock().push("shutdown");
+                })
+                .run_with_graceful_shutdown(
+                    crate::endpoint::make_sync(|_| ()),
+                    tokio::time::sleep(Duration::from_millis(100)),
+                    None,
+                ),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(*events.lock(), vec!["shutdown"]);
+        events.lock().clear();
+
+        // the shutdown hooks are called before the connections are drained
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        let (tx, rx) = oneshot::channel::<()>();
+        let server = tokio::spawn({
+            let shutdown_events = events.clone();
+            Server::new_with_acceptor(acceptor)
+                .on_shutdown(move || async move {
+                    shutdown_events.lock().push("shutdown");
+                })
+                .run_with_graceful_shutdown(
+                    crate::endpoint::make({
+                        let events = events.clone();
+                        move |_| {
+                            let events = events.clone();
+                            async move {
+                                tokio::time::sleep(Duration::from_millis(300)).await;
+                                events.lock().push("request");
+                            }
+                        }
+                    }),
+                    async move {
+                        let _ = rx.await;
+                    },
+                    None,
+                )
+        });
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        tx.send(()).unwrap();
+        let mut buf = vec![0; 1024];
+        let n = stream.read(&mut buf).await.unwrap();
+        assert!(String::from_utf8_lossy(&buf[..n]).starts_with("HTTP/1.1 200"));
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(*events.lock(), vec!["shutdown", "request"]);
+    }
+
+    #[tokio::test]
+    async fn request_limits() {
+        let addr = start_server(|acceptor| {
+            Server::new_with_acceptor(acceptor)
+                .request_limits(RequestLimits::new().max_uri_length(8).max_header_count(2))
+        })
+        .await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        assert!(request(&mut stream).await.starts_with("HTTP/1.1 200"));
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /a-long-path HTTP/1.1\r\nhost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut buf = vec![0; 1024];
+        let n = stream.read(&mut buf).await.unwrap();
+        assert!(String::from_utf8_lossy(&buf[..n]).starts_with("HTTP/1.1 414"));
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\na: 1\r\nb: 2\r\n\r\n")
+            .await
+            .unwrap();
+        let n = stream.read(&mut buf).await.unwrap();
+        assert!(String::from_utf8_lossy(&buf[..n]).starts_with("HTTP/1.1 431"));
+    }
+
+    #[tokio::test]
+    async fn header_read_timeout() {
+        let addr = start_server(|acceptor| {
+            Server::new_with_acceptor(acceptor).header_read_timeout(Duration::from_millis(200))
+        })
+        .await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        assert!(request(&mut stream).await.starts_with("HTTP/1.1 200"));
+
+        // the headers are never finished
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\n")
+            .await
+            .unwrap();
+
+        let mut buf = vec![0; 1024];
+        let n = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf))
+            .await
+            .expect("the connection is closed after the timeout")
+            .unwrap_or_default();
+        assert_eq!(n, 0);
+    }
+
+    #[tokio::test]
+    async fn keep_alive_timeout() {
+        let addr = start_server(|acceptor| {
+            Server::new_with_acceptor(acceptor).keep_alive_timeout(Duration::from_millis(200))
+        })
+        .await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(request(&mut stream).await.starts_with("HTTP/1.1 200"));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(request(&mut stream).await.starts_with("HTTP/1.1 200"));
+
+        let mut buf = vec![0; 16];
+        let n = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf))
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        assert_eq!(n, 0);
+    }
+
+    #[tokio::test]
+    async fn keep_alive_timeout_streaming_body() {
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        let ep = crate::endpoint::make(|_| async move {
+            let chunks = futures_util::StreamExt::then(
+                futures_util::stream::iter(["a", "b", "c"]),
+                |chunk| async move {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    Ok::<_, io::Error>(chunk)
+                },
+            );
+            crate::Body::from_bytes_stream(chunks)
+        });
+        tokio::spawn(
+            Server::new_with_acceptor(acceptor)
+                .keep_alive_timeout(Duration::from_millis(150))
+                .run(ep),
+        );
+
+        // the connection is not idle while the response body is being sent
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        for _ in 0..2 {
+            stream
+                .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\n\r\n")
+                .await
+                .unwrap();
+            let mut data = Vec::new();
+            while !data.ends_with(b"0\r\n\r\n") {
+                let mut buf = vec![0; 1024];
+                let n = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf))
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_ne!(n, 0, "the connection is closed");
+                data.extend_from_slice(&buf[..n]);
+            }
+            let data = String::from_utf8_lossy(&data);
+            assert!(data.starts_with("HTTP/1.1 200"));
+            assert!(data.ends_with("1\r\na\r\n1\r\nb\r\n1\r\nc\r\n0\r\n\r\n"));
         }
-        _ = server_graceful_shutdown_token.cancelled() => {}
     }
 
-    // Init graceful shutdown for connection
-    conn.as_mut().graceful_shutdown();
-    // Continue awaiting after graceful-shutdown is initiated to handle existed requests.
-    let _ = conn.await;
+    #[tokio::test]
+    async fn request_timeout() {
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        let ep = crate::endpoint::make(|req| async move {
+            if req.uri().path() == "/slow" {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+            }
+            "done"
+        });
+        tokio::spawn(
+            Server::new_with_acceptor(acceptor)
+                .request_timeout(Duration::from_millis(200))
+                .run(ep),
+        );
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        assert!(request(&mut stream).await.starts_with("HTTP/1.1 200"));
+
+        stream
+            .write_all(b"GET /slow HTTP/1.1\r\nhost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut buf = vec![0; 1024];
+        let n = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&buf[..n]).starts_with("HTTP/1.1 408"));
+    }
+
+    #[tokio::test]
+    async fn h2c() {
+        // the connection preface followed by an empty SETTINGS frame
+        const PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n\0\0\0\x04\0\0\0\0\0";
+
+        let addr = start_server(Server::new_with_acceptor).await;
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(PREFACE).await.unwrap();
+        let mut buf = vec![0; 1024];
+        let n = stream.read(&mut buf).await.unwrap();
+        // the server answers with a SETTINGS frame
+        assert!(n >= 9);
+        assert_eq!(buf[3], 0x04);
+
+        let addr = start_server(|acceptor| Server::new_with_acceptor(acceptor).h2c(false)).await;
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(PREFACE).await.unwrap();
+        let n = stream.read(&mut buf).await.unwrap_or_default();
+        assert!(n == 0 || buf[..n].starts_with(b"HTTP/1.1"));
+    }
+
+    #[tokio::test]
+    async fn h2c_upgrade() {
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        let ep =
+            crate::endpoint::make_sync(|req| format!("{} {:?}", req.uri().path(), req.version()));
+        tokio::spawn(Server::new_with_acceptor(acceptor).run(ep));
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(
+                b"GET /a HTTP/1.1\r\nhost: localhost\r\nconnection: Upgrade, HTTP2-Settings\r\n\
+                  upgrade: h2c\r\nhttp2-settings: AAMAAABkAAQAoAAAAAIAAAAA\r\n\r\n",
+            )
+            .await
+            .unwrap();
+        let mut buf = vec![0; 1024];
+        let mut data = Vec::new();
+        let end = loop {
+            if let Some(pos) = data.windows(4).position(|s| s == b"\r\n\r\n") {
+                break pos + 4;
+            }
+            let n = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_ne!(n, 0);
+            data.extend_from_slice(&buf[..n]);
+        };
+        assert!(data.starts_with(b"HTTP/1.1 101"));
+        // the frames of HTTP/2 may follow the response
+        let mut data = data.split_off(end);
+
+        // the connection preface followed by an empty SETTINGS frame
+        stream
+            .write_all(b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n\0\0\0\x04\0\0\0\0\0")
+            .await
+            .unwrap();
+
+        // the response is sent on the stream 1
+        let mut body = Vec::new();
+        loop {
+            while data.len() < 9
+                || data.len() < 9 + u32::from_be_bytes([0, data[0], data[1], data[2]]) as usize
+            {
+                let n = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf))
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_ne!(n, 0);
+                data.extend_from_slice(&buf[..n]);
+            }
+            let len = u32::from_be_bytes([0, data[0], data[1], data[2]]) as usize;
+            let frame = data.drain(..9 + len).collect::<Vec<_>>();
+            let (ty, flags, stream_id) = (frame[3], frame[4], &frame[5..9]);
+            // a DATA frame of the stream 1
+            if ty == 0x0 && stream_id == [0, 0, 0, 1] {
+                body.extend_from_slice(&frame[9..]);
+                if flags & 0x1 != 0 {
+                    break;
+                }
+            }
+        }
+        assert_eq!(body, b"/a HTTP/2.0");
+    }
 }
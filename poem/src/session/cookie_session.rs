@@ -3,6 +3,7 @@ use std::{collections::BTreeMap, sync::Arc};
 use serde_json::Value;
 
 use crate::{
+    endpoint::MiddlewareChain,
     middleware::{CookieJarManager, CookieJarManagerEndpoint},
     session::{CookieConfig, Session, SessionStatus},
     Endpoint, Middleware, Request, Result,
@@ -72,6 +73,10 @@ impl<E: Endpoint> Endpoint for CookieSessionEndpoint<E> {
 
         Ok(resp)
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("CookieSession", self.inner.middleware_chains())
+    }
 }
 
 #[cfg(test)]
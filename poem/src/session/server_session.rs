@@ -4,6 +4,7 @@ use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use rand::{thread_rng, Rng};
 
 use crate::{
+    endpoint::MiddlewareChain,
     middleware::{CookieJarManager, CookieJarManagerEndpoint},
     session::{session_storage::SessionStorage, CookieConfig, Session, SessionStatus},
     Endpoint, Middleware, Request, Result,
@@ -113,4 +114,8 @@ where
 
         Ok(resp)
     }
+
+    fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        MiddlewareChain::wrap("ServerSession", self.inner.middleware_chains())
+    }
 }
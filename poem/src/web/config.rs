@@ -0,0 +1,385 @@
+use std::{collections::BTreeMap, ffi::OsString, ops::Deref, path::Path, sync::Arc};
+
+use serde::{
+    de::{value::Error as DeError, DeserializeOwned, Error as _, IntoDeserializer, Visitor},
+    forward_to_deserialize_any, Deserializer,
+};
+
+use crate::{
+    error::{ConfigError, GetDataError},
+    FromRequest, Request, RequestBody, Result,
+};
+
+/// A typed configuration which is loaded at startup and shared with the
+/// endpoints as data.
+///
+/// The configuration is loaded from the environment variables, a `JSON`
+/// file or a `TOML` file (requires the `toml` feature), then added to the
+/// application with [`EndpointExt::data`](crate::EndpointExt::data) or
+/// [`AddData`](crate::middleware::AddData). The handlers extract it as
+/// `Config<T>`, which is cheap to clone.
+///
+/// # Errors
+///
+/// - [`GetDataError`]
+///
+/// # Example
+///
+/// ```
+/// use poem::{get, handler, test::TestClient, web::Config, EndpointExt, Route};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct AppConfig {
+///     name: String,
+///     #[serde(default)]
+///     debug: bool,
+/// }
+///
+/// #[handler]
+/// fn index(config: Config<AppConfig>) -> String {
+///     format!("{} {}", config.name, config.debug)
+/// }
+///
+/// let config = Config::<AppConfig>::from_json_str(r#"{"name": "demo"}"#).unwrap();
+/// let app = Route::new().at("/", get(index)).data(config);
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// cli.get("/").send().await.assert_text("demo false").await;
+/// # });
+/// ```
+pub struct Config<T>(pub Arc<T>);
+
+impl<T> Clone for Config<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Deref for Config<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> Config<T> {
+    /// Create a configuration with the specified value.
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+}
+
+impl<T: DeserializeOwned> Config<T> {
+    /// Loads the configuration from the environment variables starting with
+    /// `prefix`.
+    ///
+    /// The prefix is removed and the rest of the name is converted to
+    /// lowercase as the field name, a double underscore separates the fields
+    /// of a nested struct. For example, with the prefix `APP_`, the variable
+    /// `APP_DB__MAX_CONNECTIONS` is the `max_connections` field of the `db`
+    /// field. The values of the sequences are separated by commas.
+    ///
+    /// The variables whose names are not valid unicode are ignored, and the
+    /// invalid unicode in the values is replaced with `U+FFFD`.
+    pub fn from_env(prefix: &str) -> Result<Self, ConfigError> {
+        Self::from_vars(prefix, std::env::vars_os())
+    }
+
+    fn from_vars(
+        prefix: &str,
+        vars: impl IntoIterator<Item = (OsString, OsString)>,
+    ) -> Result<Self, ConfigError> {
+        let mut root = BTreeMap::new();
+        for (name, value) in vars {
+            let Some(name) = name.to_str().and_then(|name| name.strip_prefix(prefix)) else {
+                continue;
+            };
+            let value = value.to_string_lossy().into_owned();
+            let mut path = name.split("__").map(str::to_lowercase).peekable();
+            let mut map = &mut root;
+            while let Some(key) = path.next() {
+                if path.peek().is_none() {
+                    map.insert(key, EnvNode::Value(value));
+                    break;
+                }
+                let node = map
+                    .entry(key)
+                    .or_insert_with(|| EnvNode::Map(BTreeMap::new()));
+                if let EnvNode::Value(_) = node {
+                    *node = EnvNode::Map(BTreeMap::new());
+                }
+                let EnvNode::Map(child) = node else {
+                    unreachable!()
+                };
+                map = child;
+            }
+        }
+
+        T::deserialize(EnvNode::Map(root))
+            .map(Self::new)
+            .map_err(|err| ConfigError::Parse(err.to_string()))
+    }
+
+    /// Loads the configuration from a `JSON` string.
+    pub fn from_json_str(s: &str) -> Result<Self, ConfigError> {
+        serde_json::from_str(s)
+            .map(Self::new)
+            .map_err(|err| ConfigError::Parse(err.to_string()))
+    }
+
+    /// Loads the configuration from a `JSON` file.
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::from_json_str(&std::fs::read_to_string(path)?)
+    }
+
+    /// Loads the configuration from a `TOML` string.
+    #[cfg(feature = "toml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+    pub fn from_toml_str(s: &str) -> Result<Self, ConfigError> {
+        toml::from_str(s)
+            .map(Self::new)
+            .map_err(|err| ConfigError::Parse(err.to_string()))
+    }
+
+    /// Loads the configuration from a `TOML` file.
+    #[cfg(feature = "toml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+    pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::from_toml_str(&std::fs::read_to_string(path)?)
+    }
+}
+
+impl<'a, T: Send + Sync + 'static> FromRequest<'a> for Config<T> {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        Ok(req
+            .data::<Config<T>>()
+            .cloned()
+            .ok_or_else(|| GetDataError(std::any::type_name::<Config<T>>()))?)
+    }
+}
+
+/// A node of the configuration loaded from the environment variables.
+enum EnvNode {
+    Value(String),
+    Map(BTreeMap<String, EnvNode>),
+}
+
+impl<'de> IntoDeserializer<'de, DeError> for EnvNode {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! deserialize_parse {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self {
+                    EnvNode::Value(value) => visitor.$visit(
+                        value
+                            .trim()
+                            .parse()
+                            .map_err(|_| DeError::custom(format!("invalid value `{value}`")))?,
+                    ),
+                    node => node.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for EnvNode {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            EnvNode::Value(value) => visitor.visit_string(value),
+            EnvNode::Map(map) => {
+                visitor.visit_map(serde::de::value::MapDeserializer::new(map.into_iter()))
+            }
+        }
+    }
+
+    deserialize_parse! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            EnvNode::Value(value) if value.is_empty() => visitor.visit_none(),
+            node => visitor.visit_some(node),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            EnvNode::Value(value) => {
+                let items = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| EnvNode::Value(item.to_string()));
+                visitor.visit_seq(serde::de::value::SeqDeserializer::new(items))
+            }
+            node => node.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            EnvNode::Value(value) => visitor.visit_enum(value.into_deserializer()),
+            node => node.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{handler, test::TestClient, EndpointExt};
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Level {
+        Debug,
+        Info,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Database {
+        url: String,
+        max_connections: u32,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct AppConfig {
+        name: String,
+        port: u16,
+        debug: bool,
+        level: Level,
+        hosts: Vec<String>,
+        timeout: Option<f64>,
+        db: Database,
+    }
+
+    #[test]
+    fn from_vars() {
+        let vars = [
+            ("APP_NAME", "123"),
+            ("APP_PORT", "8080"),
+            ("APP_DEBUG", "true"),
+            ("APP_LEVEL", "info"),
+            ("APP_HOSTS", "a.com, b.com"),
+            ("APP_DB__URL", "postgres://localhost"),
+            ("APP_DB__MAX_CONNECTIONS", "10"),
+            ("OTHER_NAME", "other"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.into(), value.into()));
+        let config = Config::<AppConfig>::from_vars("APP_", vars).unwrap();
+        assert_eq!(
+            *config,
+            AppConfig {
+                name: "123".to_string(),
+                port: 8080,
+                debug: true,
+                level: Level::Info,
+                hosts: vec!["a.com".to_string(), "b.com".to_string()],
+                timeout: None,
+                db: Database {
+                    url: "postgres://localhost".to_string(),
+                    max_connections: 10,
+                },
+            }
+        );
+
+        let vars = [("APP_PORT", "abc")]
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()));
+        assert!(matches!(
+            Config::<AppConfig>::from_vars("APP_", vars),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn from_vars_non_unicode() {
+        use std::os::unix::ffi::OsStringExt;
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct NameConfig {
+            name: String,
+        }
+
+        let vars = [
+            (OsString::from_vec(b"OTHER_\xff".to_vec()), "other".into()),
+            ("APP_NAME".into(), OsString::from_vec(b"a\xffb".to_vec())),
+        ];
+        let config = Config::<NameConfig>::from_vars("APP_", vars).unwrap();
+        assert_eq!(config.name, "a\u{fffd}b");
+    }
+
+    #[tokio::test]
+    async fn extractor() {
+        #[handler(internal)]
+        fn index(config: Config<Database>) -> String {
+            config.url.clone()
+        }
+
+        let config = Config::<Database>::from_json_str(
+            r#"{"url": "postgres://localhost", "max_connections": 10}"#,
+        )
+        .unwrap();
+        let cli = TestClient::new(index.data(config));
+        cli.get("/")
+            .send()
+            .await
+            .assert_text("postgres://localhost")
+            .await;
+
+        TestClient::new(index)
+            .get("/")
+            .send()
+            .await
+            .assert_status(http::StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
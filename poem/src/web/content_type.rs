@@ -0,0 +1,209 @@
+use bytes::Bytes;
+
+use crate::Request;
+
+/// Specifies how the [`Json`](crate::web::Json) and
+/// [`Form`](crate::web::Form) extractors handle requests without the
+/// `Content-Type` header, which can be added to the endpoint with
+/// [`EndpointExt::data`](crate::EndpointExt::data).
+///
+/// The `Json` and `Form` payloads of `poem-openapi` follow the same rule.
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     handler,
+///     http::StatusCode,
+///     test::TestClient,
+///     web::{ContentTypeMode, Json},
+///     EndpointExt,
+/// };
+///
+/// #[handler]
+/// fn index(Json(value): Json<i32>) -> String {
+///     value.to_string()
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let cli = TestClient::new(index);
+/// cli.post("/")
+///     .body("100")
+///     .send()
+///     .await
+///     .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+///
+/// let cli = TestClient::new(index.data(ContentTypeMode::Lenient));
+/// cli.post("/")
+///     .body("100")
+///     .send()
+///     .await
+///     .assert_text("100")
+///     .await;
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ContentTypeMode {
+    /// Rejects the requests without the `Content-Type` header. (default)
+    #[default]
+    Strict,
+    /// Parses the body of the requests without the `Content-Type` header as
+    /// the expected type.
+    Lenient,
+}
+
+impl ContentTypeMode {
+    /// Returns the mode configured for the request.
+    pub fn from_request(req: &Request) -> Self {
+        req.data::<ContentTypeMode>().copied().unwrap_or_default()
+    }
+}
+
+/// Returns the charset parameter of the content type if it is not UTF-8.
+fn non_utf8_charset(content_type: Option<&str>) -> Option<String> {
+    let content_type = content_type?.parse::<mime::Mime>().ok()?;
+    let charset = content_type.get_param(mime::CHARSET)?.as_str();
+    if ["utf-8", "utf8", "us-ascii"]
+        .iter()
+        .any(|name| charset.eq_ignore_ascii_case(name))
+    {
+        return None;
+    }
+    Some(charset.to_string())
+}
+
+#[cfg(feature = "charset")]
+fn encoding(charset: &str) -> Result<&'static encoding_rs::Encoding, String> {
+    encoding_rs::Encoding::for_label(charset.as_bytes()).ok_or_else(|| charset.to_string())
+}
+
+/// Converts a text body to UTF-8 according to the charset of the content type.
+///
+/// Returns `Err(charset)` if the charset is not supported. Without the
+/// `charset` feature, the body is returned unchanged.
+#[doc(hidden)]
+pub fn text_body_to_utf8(content_type: Option<&str>, data: Bytes) -> Result<Bytes, String> {
+    let Some(charset) = non_utf8_charset(content_type) else {
+        return Ok(data);
+    };
+
+    #[cfg(feature = "charset")]
+    {
+        let (text, _) = encoding(&charset)?.decode_without_bom_handling(&data);
+        Ok(Bytes::from(text.into_owned()))
+    }
+
+    #[cfg(not(feature = "charset"))]
+    {
+        let _ = charset;
+        Ok(data)
+    }
+}
+
+/// Converts an `application/x-www-form-urlencoded` body to UTF-8 according to
+/// the charset of the content type, the percent-encoded octets are decoded
+/// with the charset.
+///
+/// Returns `Err(charset)` if the charset is not supported. Without the
+/// `charset` feature, the body is returned unchanged.
+#[doc(hidden)]
+pub fn form_body_to_utf8(content_type: Option<&str>, data: Bytes) -> Result<Bytes, String> {
+    let Some(charset) = non_utf8_charset(content_type) else {
+        return Ok(data);
+    };
+
+    #[cfg(feature = "charset")]
+    {
+        let encoding = encoding(&charset)?;
+        let decode = |s: &[u8]| {
+            let s = s
+                .iter()
+                .map(|b| if *b == b'+' { b' ' } else { *b })
+                .collect::<Vec<_>>();
+            let s = percent_encoding::percent_decode(&s).collect::<Vec<_>>();
+            encoding.decode_without_bom_handling(&s).0.into_owned()
+        };
+        let pairs = data
+            .split(|b| *b == b'&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let mut it = pair.splitn(2, |b| *b == b'=');
+                let name = decode(it.next().unwrap_or_default());
+                let value = decode(it.next().unwrap_or_default());
+                (name, value)
+            })
+            .collect::<Vec<_>>();
+        Ok(Bytes::from(
+            serde_urlencoded::to_string(pairs).map_err(|_| charset)?,
+        ))
+    }
+
+    #[cfg(not(feature = "charset"))]
+    {
+        let _ = charset;
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utf8_bodies() {
+        for content_type in [
+            None,
+            Some("application/json"),
+            Some("application/json; charset=utf-8"),
+            Some("application/json; charset=UTF-8"),
+            Some("application/json; charset=\"utf-8\""),
+            Some("application/json; charset=us-ascii"),
+        ] {
+            assert_eq!(
+                text_body_to_utf8(content_type, Bytes::from_static(b"abc")).unwrap(),
+                "abc"
+            );
+        }
+    }
+
+    #[cfg(not(feature = "charset"))]
+    #[test]
+    fn without_charset_feature() {
+        assert_eq!(
+            text_body_to_utf8(
+                Some("application/json; charset=latin1"),
+                Bytes::from_static(b"abc")
+            )
+            .unwrap(),
+            "abc"
+        );
+    }
+
+    #[cfg(feature = "charset")]
+    #[test]
+    fn transcode() {
+        assert_eq!(
+            text_body_to_utf8(
+                Some("application/json; charset=iso-8859-1"),
+                Bytes::from_static(b"\"caf\xe9\"")
+            )
+            .unwrap(),
+            "\"café\""
+        );
+        assert_eq!(
+            form_body_to_utf8(
+                Some("application/x-www-form-urlencoded; charset=iso-8859-1"),
+                Bytes::from_static(b"name=caf%E9&a+b=c+d")
+            )
+            .unwrap(),
+            "name=caf%C3%A9&a+b=c+d"
+        );
+        assert_eq!(
+            text_body_to_utf8(
+                Some("application/json; charset=unknown"),
+                Bytes::from_static(b"abc")
+            ),
+            Err("unknown".to_string())
+        );
+    }
+}
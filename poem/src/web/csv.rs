@@ -0,0 +1,140 @@
+use std::io::Error as IoError;
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+
+use crate::{Body, IntoResponse, Response};
+
+/// A response that streams the records as `text/csv`.
+///
+/// `S` is a [`Stream`] of records, each record must implement
+/// [`serde::Serialize`]. The header row is generated from the field names of
+/// the first record, and the fields are quoted and escaped as needed.
+///
+/// Records are serialized as the body is sent, so a large table never has to
+/// be kept in memory. Use [`futures_util::stream::iter`] to send the records
+/// of an iterator.
+///
+/// # Example
+///
+/// ```
+/// use futures_util::stream;
+/// use poem::{get, handler, test::TestClient, web::Csv, Route};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     id: i32,
+///     name: &'static str,
+/// }
+///
+/// #[handler]
+/// fn export() -> Csv<impl futures_util::Stream<Item = User>> {
+///     Csv(stream::iter(vec![
+///         User {
+///             id: 1,
+///             name: "sunli",
+///         },
+///         User {
+///             id: 2,
+///             name: "foo, bar",
+///         },
+///     ]))
+/// }
+///
+/// let app = Route::new().at("/", get(export));
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli.get("/").send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_content_type("text/csv; charset=utf-8");
+/// resp.assert_text("id,name\n1,sunli\n2,\"foo, bar\"\n").await;
+/// # });
+/// ```
+pub struct Csv<S>(pub S);
+
+fn serialize_record<T: Serialize>(record: &T, has_headers: bool) -> Result<Bytes, IoError> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(has_headers)
+        .from_writer(Vec::new());
+    writer
+        .serialize(record)
+        .map_err(IoError::other)?;
+    let data = writer
+        .into_inner()
+        .map_err(|err| IoError::other(err.to_string()))?;
+    Ok(data.into())
+}
+
+impl<S, T> IntoResponse for Csv<S>
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        let mut has_headers = true;
+        let stream = self.0.map(move |record| {
+            let data = serialize_record(&record, has_headers);
+            has_headers = false;
+            data
+        });
+
+        Response::builder()
+            .content_type("text/csv; charset=utf-8")
+            .body(Body::from_bytes_stream(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{handler, test::TestClient};
+
+    #[derive(Serialize)]
+    struct Record {
+        name: String,
+        value: Option<i32>,
+    }
+
+    #[tokio::test]
+    async fn csv() {
+        #[handler(internal)]
+        fn index() -> Csv<impl Stream<Item = Record>> {
+            Csv(stream::iter(vec![
+                Record {
+                    name: "a".to_string(),
+                    value: Some(1),
+                },
+                Record {
+                    name: "b \"c\"\nd".to_string(),
+                    value: None,
+                },
+            ]))
+        }
+
+        let cli = TestClient::new(index);
+        let resp = cli.get("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_content_type("text/csv; charset=utf-8");
+        resp.assert_text("name,value\na,1\n\"b \"\"c\"\"\nd\",\n")
+            .await;
+    }
+
+    #[tokio::test]
+    async fn csv_empty() {
+        #[handler(internal)]
+        fn index() -> Csv<impl Stream<Item = Record>> {
+            Csv(stream::empty())
+        }
+
+        let cli = TestClient::new(index);
+        let resp = cli.get("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("").await;
+    }
+}
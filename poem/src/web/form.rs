@@ -8,7 +8,10 @@ use crate::{
         header::{self},
         Method,
     },
-    web::RequestBody,
+    web::{
+        content_type::{form_body_to_utf8, ContentTypeMode},
+        RequestBody,
+    },
     FromRequest, Request, Result,
 };
 
@@ -20,6 +23,10 @@ use crate::{
 /// If the `Content-Type` is not `application/x-www-form-urlencoded`, then a
 /// `Bad Request` response will be returned.
 ///
+/// The body in a charset other than UTF-8 is decoded if the `charset` feature
+/// is enabled, and requests without the `Content-Type` header are accepted if
+/// [`ContentTypeMode::Lenient`] is used.
+///
 /// # Errors
 ///
 /// - [`ReadBodyError`](crate::error::ReadBodyError)
@@ -89,24 +96,30 @@ impl<T> DerefMut for Form<T> {
 impl<'a, T: DeserializeOwned> FromRequest<'a> for Form<T> {
     async fn from_request(req: &'a Request, body: &mut RequestBody) -> Result<Self> {
         if req.method() == Method::GET {
-            Ok(
-                serde_urlencoded::from_str(req.uri().query().unwrap_or_default())
-                    .map_err(ParseFormError::UrlDecode)
-                    .map(Self)?,
-            )
+            Ok(req
+                .query()
+                .deserialize()
+                .map_err(|err| ParseFormError::UrlDecode(err.0))
+                .map(Self)?)
         } else {
             let content_type = req
                 .headers()
                 .get(header::CONTENT_TYPE)
-                .and_then(|content_type| content_type.to_str().ok())
-                .ok_or(ParseFormError::ContentTypeRequired)?;
-            if !is_form_content_type(content_type) {
-                return Err(ParseFormError::InvalidContentType(content_type.into()).into());
+                .and_then(|content_type| content_type.to_str().ok());
+            match content_type {
+                Some(content_type) if !is_form_content_type(content_type) => {
+                    return Err(ParseFormError::InvalidContentType(content_type.into()).into());
+                }
+                None if ContentTypeMode::from_request(req) == ContentTypeMode::Strict => {
+                    return Err(ParseFormError::ContentTypeRequired.into());
+                }
+                _ => {}
             }
 
+            let data = form_body_to_utf8(content_type, body.take()?.into_bytes().await?)
+                .map_err(ParseFormError::UnsupportedCharset)?;
             Ok(Self(
-                serde_urlencoded::from_bytes(&body.take()?.into_vec().await?)
-                    .map_err(ParseFormError::UrlDecode)?,
+                serde_urlencoded::from_bytes(&data).map_err(ParseFormError::UrlDecode)?,
             ))
         }
     }
@@ -127,7 +140,7 @@ mod tests {
     use serde::Deserialize;
 
     use super::*;
-    use crate::{handler, test::TestClient};
+    use crate::{handler, test::TestClient, EndpointExt};
 
     #[tokio::test]
     async fn test_form_extractor() {
@@ -164,5 +177,25 @@ mod tests {
             .send()
             .await
             .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        cli.post("/")
+            .content_type("application/x-www-form-urlencoded; charset=utf-8")
+            .body("name=abc&value=100")
+            .send()
+            .await
+            .assert_status_is_ok();
+
+        cli.post("/")
+            .body("name=abc&value=100")
+            .send()
+            .await
+            .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let cli = TestClient::new(index.data(ContentTypeMode::Lenient));
+        cli.post("/")
+            .body("name=abc&value=100")
+            .send()
+            .await
+            .assert_status_is_ok();
     }
 }
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use futures_util::{Stream, StreamExt};
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+use crate::{Body, FromRequest, Request, RequestBody, Result};
+
+struct Inner {
+    token: CancellationToken,
+    upgrades: watch::Sender<usize>,
+}
+
+/// An extractor for the graceful shutdown state of the [`Server`](crate::Server).
+///
+/// The connections upgraded to another protocol (such as WebSocket) and the
+/// long-lived streaming responses (such as SSE) are not finished by the
+/// graceful shutdown of HTTP connections, so they are tracked separately with
+/// [`GracefulShutdown::track`]. After the regular connections have been
+/// closed, the server waits for the tracked ones with the timeout specified by
+/// [`Server::upgrade_shutdown_timeout`](crate::Server::upgrade_shutdown_timeout).
+///
+/// The WebSocket connections accepted with the `WebSocket` extractor are
+/// tracked automatically, and a `Close` message with the `Away` code is sent
+/// to the client when the graceful shutdown is requested, the next time the
+/// `WebSocketStream` is polled. The `SSE` responses are tracked
+/// automatically too, and their event streams end when the graceful shutdown
+/// is requested.
+///
+/// Outside of a server, such as in the tests, the shutdown is never requested.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use poem::{handler, web::GracefulShutdown, Body};
+///
+/// #[handler]
+/// fn ticks(shutdown: GracefulShutdown) -> Body {
+///     let ticks = futures_util::stream::unfold((), |_| async {
+///         tokio::time::sleep(Duration::from_secs(1)).await;
+///         Some((Ok::<_, std::io::Error>("tick\n"), ()))
+///     });
+///     // The stream ends when the graceful shutdown is requested.
+///     Body::from_bytes_stream(shutdown.track_stream(ticks))
+/// }
+/// ```
+#[derive(Clone)]
+pub struct GracefulShutdown {
+    inner: Arc<Inner>,
+}
+
+impl Default for GracefulShutdown {
+    fn default() -> Self {
+        Self::new(CancellationToken::new())
+    }
+}
+
+impl GracefulShutdown {
+    pub(crate) fn new(token: CancellationToken) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                token,
+                upgrades: watch::channel(0).0,
+            }),
+        }
+    }
+
+    /// Returns `true` if the graceful shutdown has been requested.
+    #[inline]
+    pub fn is_requested(&self) -> bool {
+        self.inner.token.is_cancelled()
+    }
+
+    /// Waits until the graceful shutdown is requested.
+    pub async fn requested(&self) {
+        self.inner.token.cancelled().await
+    }
+
+    /// Tracks an upgraded connection or a long-lived response until the
+    /// returned guard is dropped.
+    pub fn track(&self) -> ShutdownGuard {
+        self.inner.upgrades.send_modify(|n| *n += 1);
+        ShutdownGuard(self.clone())
+    }
+
+    /// Tracks a stream, such as the events of an SSE response, which ends
+    /// when the graceful shutdown is requested.
+    pub fn track_stream<S>(&self, stream: S) -> impl Stream<Item = S::Item> + Send + 'static
+    where
+        S: Stream + Send + 'static,
+    {
+        let guard = self.track();
+        let token = self.inner.token.clone();
+        stream.take_until(token.cancelled_owned()).map(move |item| {
+            let _ = &guard;
+            item
+        })
+    }
+
+    /// Tracks a response body, which ends when the graceful shutdown is
+    /// requested.
+    pub(crate) fn track_body(&self, body: Body) -> Body {
+        Body::from_bytes_stream(self.track_stream(body.into_bytes_stream()))
+    }
+
+    /// Returns a future that resolves when the graceful shutdown is
+    /// requested.
+    #[cfg(feature = "websocket")]
+    pub(crate) fn requested_owned(&self) -> tokio_util::sync::WaitForCancellationFutureOwned {
+        self.inner.token.clone().cancelled_owned()
+    }
+
+    /// Returns the number of the tracked connections.
+    pub fn tracked(&self) -> usize {
+        *self.inner.upgrades.borrow()
+    }
+
+    /// Waits for all the tracked connections to be finished.
+    pub(crate) async fn wait_tracked(&self) {
+        let mut rx = self.inner.upgrades.subscribe();
+        let _ = rx.wait_for(|n| *n == 0).await;
+    }
+}
+
+impl<'a> FromRequest<'a> for GracefulShutdown {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        Ok(req.data::<GracefulShutdown>().cloned().unwrap_or_default())
+    }
+}
+
+/// A marker in the extensions of the responses which are tracked by the
+/// server until the body has been sent, see [`GracefulShutdown::track_body`].
+#[derive(Clone, Copy)]
+pub(crate) struct TrackedResponse;
+
+/// A guard returned by [`GracefulShutdown::track`], the connection is tracked
+/// until it is dropped.
+pub struct ShutdownGuard(GracefulShutdown);
+
+impl Drop for ShutdownGuard {
+    fn drop(&mut self) {
+        self.0.inner.upgrades.send_modify(|n| *n -= 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures_util::stream;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn track() {
+        let token = CancellationToken::new();
+        let shutdown = GracefulShutdown::new(token.clone());
+
+        let guard = shutdown.track();
+        let stream = shutdown.track_stream(stream::pending::<()>());
+        assert_eq!(shutdown.tracked(), 2);
+        assert!(!shutdown.is_requested());
+
+        token.cancel();
+        assert!(shutdown.is_requested());
+        shutdown.requested().await;
+        assert_eq!(stream.collect::<Vec<_>>().await, vec![]);
+        assert_eq!(shutdown.tracked(), 1);
+
+        let wait = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.wait_tracked().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!wait.is_finished());
+        drop(guard);
+        tokio::time::timeout(Duration::from_secs(1), wait)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(shutdown.tracked(), 0);
+    }
+}
@@ -4,8 +4,13 @@ use http::StatusCode;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    error::ParseJsonError, http::header, web::RequestBody, FromRequest, IntoResponse, Request,
-    Response, Result,
+    error::ParseJsonError,
+    http::header,
+    web::{
+        content_type::{text_body_to_utf8, ContentTypeMode},
+        RequestBody,
+    },
+    FromRequest, IntoResponse, Request, Response, Result,
 };
 
 /// JSON extractor and response.
@@ -13,6 +18,12 @@ use crate::{
 /// To extract the specified type of JSON from the body, `T` must implement
 /// [`serde::Deserialize`].
 ///
+/// The body in a charset other than UTF-8, such as
+/// `application/json; charset=iso-8859-1`, is decoded if the `charset`
+/// feature is enabled, otherwise it is parsed as is. Requests without the
+/// `Content-Type` header are rejected unless [`ContentTypeMode::Lenient`] is
+/// used.
+///
 /// # Errors
 ///
 /// - [`ReadBodyError`](crate::error::ReadBodyError)
@@ -108,15 +119,21 @@ impl<'a, T: DeserializeOwned> FromRequest<'a> for Json<T> {
         let content_type = req
             .headers()
             .get(header::CONTENT_TYPE)
-            .and_then(|content_type| content_type.to_str().ok())
-            .ok_or(ParseJsonError::ContentTypeRequired)?;
-        if !is_json_content_type(content_type) {
-            return Err(ParseJsonError::InvalidContentType(content_type.into()).into());
+            .and_then(|content_type| content_type.to_str().ok());
+        match content_type {
+            Some(content_type) if !is_json_content_type(content_type) => {
+                return Err(ParseJsonError::InvalidContentType(content_type.into()).into());
+            }
+            None if ContentTypeMode::from_request(req) == ContentTypeMode::Strict => {
+                return Err(ParseJsonError::ContentTypeRequired.into());
+            }
+            _ => {}
         }
 
+        let data = text_body_to_utf8(content_type, body.take()?.into_bytes().await?)
+            .map_err(ParseJsonError::UnsupportedCharset)?;
         Ok(Self(
-            serde_json::from_slice(&body.take()?.into_bytes().await?)
-                .map_err(ParseJsonError::Parse)?,
+            serde_json::from_slice(&data).map_err(ParseJsonError::Parse)?,
         ))
     }
 }
@@ -152,7 +169,7 @@ mod tests {
     use serde_json::json;
 
     use super::*;
-    use crate::{handler, test::TestClient};
+    use crate::{handler, test::TestClient, EndpointExt};
 
     #[derive(Deserialize, Serialize, Debug, Eq, PartialEq)]
     struct CreateResource {
@@ -195,6 +212,58 @@ mod tests {
             .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
     }
 
+    #[tokio::test]
+    async fn test_json_extractor_charset() {
+        #[handler(internal)]
+        async fn index(query: Json<CreateResource>) -> String {
+            query.0.name
+        }
+
+        let cli = TestClient::new(index);
+        cli.post("/")
+            .content_type("application/json; charset=UTF-8")
+            .body(r#"{"name": "abc", "value": 100}"#)
+            .send()
+            .await
+            .assert_text("abc")
+            .await;
+
+        let resp = cli
+            .post("/")
+            .content_type("application/json; charset=iso-8859-1")
+            .body(&b"{\"name\": \"caf\xe9\", \"value\": 100}"[..])
+            .send()
+            .await;
+        if cfg!(feature = "charset") {
+            resp.assert_text("café").await;
+        } else {
+            // the body is not valid UTF-8
+            resp.assert_status(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_json_extractor_lenient() {
+        #[handler(internal)]
+        async fn index(query: Json<CreateResource>) -> String {
+            query.0.name
+        }
+
+        let cli = TestClient::new(index.data(ContentTypeMode::Lenient));
+        cli.post("/")
+            .body(r#"{"name": "abc", "value": 100}"#)
+            .send()
+            .await
+            .assert_text("abc")
+            .await;
+        cli.post("/")
+            .content_type("text/plain")
+            .body(r#"{"name": "abc", "value": 100}"#)
+            .send()
+            .await
+            .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
     #[tokio::test]
     async fn test_json_response() {
         #[handler(internal)]
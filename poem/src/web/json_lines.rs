@@ -0,0 +1,279 @@
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use futures_util::{stream::BoxStream, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error::{ParseJsonError, ReadBodyError},
+    web::RequestBody,
+    BufferPool, FromRequest, PooledBuffer, Request, Result,
+};
+
+/// An extractor that parses the body as a stream of newline delimited JSON
+/// records ([JSON Lines](https://jsonlines.org/), also known as NDJSON).
+///
+/// Records are parsed as the body is received, so the whole body never has to
+/// be kept in memory. Empty lines are skipped, and the last record does not
+/// need to be terminated with a newline.
+///
+/// A line may not be longer than 1MiB by default, which can be changed with
+/// [`JsonLines::max_line_length`].
+///
+/// # Errors
+///
+/// Each item of the stream may fail with:
+///
+/// - [`ReadBodyError`], [`ReadBodyError::PayloadTooLarge`] if a line is longer
+///   than the maximum length
+/// - [`ParseJsonError`]
+///
+/// # Example
+///
+/// ```
+/// use futures_util::TryStreamExt;
+/// use poem::{handler, post, test::TestClient, web::JsonLines, Endpoint, Request, Result, Route};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Record {
+///     value: i32,
+/// }
+///
+/// #[handler]
+/// async fn index(records: JsonLines<Record>) -> Result<String> {
+///     let sum = records
+///         .try_fold(0, |sum, record| async move { Ok(sum + record.value) })
+///         .await?;
+///     Ok(sum.to_string())
+/// }
+///
+/// let app = Route::new().at("/", post(index));
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli
+///     .post("/")
+///     .content_type("application/x-ndjson")
+///     .body("{\"value\": 1}\n{\"value\": 2}\n{\"value\": 3}\n")
+///     .send()
+///     .await;
+/// resp.assert_status_is_ok();
+/// resp.assert_text("6").await;
+/// # });
+/// ```
+pub struct JsonLines<T> {
+    stream: BoxStream<'static, Result<bytes::Bytes, std::io::Error>>,
+    buf: PooledBuffer<'static>,
+    scanned: usize,
+    eof: bool,
+    max_line_length: usize,
+    _mark: PhantomData<fn() -> T>,
+}
+
+const DEFAULT_MAX_LINE_LENGTH: usize = 1024 * 1024;
+
+impl<T> JsonLines<T> {
+    /// Sets the maximum length of a line in bytes, the default is 1MiB.
+    ///
+    /// The stream fails with [`ReadBodyError::PayloadTooLarge`] and ends when a
+    /// longer line is received.
+    #[must_use]
+    pub fn max_line_length(self, max_line_length: usize) -> Self {
+        Self {
+            max_line_length,
+            ..self
+        }
+    }
+
+    fn fail(&mut self, err: ReadBodyError) -> Poll<Option<Result<T>>> {
+        self.eof = true;
+        self.buf.clear();
+        self.scanned = 0;
+        Poll::Ready(Some(Err(err.into())))
+    }
+
+    fn parse_line(&mut self, len: usize) -> Option<Result<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let line = self.buf.split_to(len);
+        self.scanned = 0;
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return None;
+        }
+        Some(serde_json::from_slice(&line).map_err(|err| ParseJsonError::Parse(err).into()))
+    }
+}
+
+impl<'a, T: DeserializeOwned> FromRequest<'a> for JsonLines<T> {
+    async fn from_request(_req: &'a Request, body: &mut RequestBody) -> Result<Self> {
+        Ok(Self {
+            stream: body.take()?.into_bytes_stream().boxed(),
+            buf: BufferPool::global().get(),
+            scanned: 0,
+            eof: false,
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+            _mark: PhantomData,
+        })
+    }
+}
+
+impl<T: DeserializeOwned> Stream for JsonLines<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let scanned = self.scanned;
+            if let Some(pos) = self.buf[scanned..].iter().position(|b| *b == b'\n') {
+                if scanned + pos > self.max_line_length {
+                    return self.fail(ReadBodyError::PayloadTooLarge);
+                }
+                let item = self.parse_line(scanned + pos);
+                self.buf.advance(1);
+                match item {
+                    Some(item) => return Poll::Ready(Some(item)),
+                    None => continue,
+                }
+            }
+
+            if self.buf.len() > self.max_line_length {
+                return self.fail(ReadBodyError::PayloadTooLarge);
+            }
+
+            if self.eof {
+                let len = self.buf.len();
+                return Poll::Ready(self.parse_line(len));
+            }
+
+            match self.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(data))) => {
+                    self.scanned = self.buf.len();
+                    self.buf.extend_from_slice(&data);
+                }
+                Poll::Ready(Some(Err(err))) => return self.fail(ReadBodyError::Io(err)),
+                Poll::Ready(None) => self.eof = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::TryStreamExt;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{handler, test::TestClient};
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Record {
+        value: i32,
+    }
+
+    #[tokio::test]
+    async fn json_lines() {
+        #[handler(internal)]
+        async fn index(records: JsonLines<Record>) -> Result<String> {
+            let records = records.try_collect::<Vec<_>>().await?;
+            Ok(records
+                .iter()
+                .map(|record| record.value.to_string())
+                .collect::<Vec<_>>()
+                .join(","))
+        }
+
+        let cli = TestClient::new(index);
+
+        let resp = cli
+            .post("/")
+            .body("{\"value\": 1}\r\n\n{\"value\": 2}\n  \n{\"value\": 3}")
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_text("1,2,3").await;
+
+        let resp = cli.post("/").body("").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("").await;
+
+        let resp = cli
+            .post("/")
+            .body("{\"value\": 1}\n{\"value\": \"a\"}\n")
+            .send()
+            .await;
+        resp.assert_status(http::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn json_lines_chunked() {
+        let chunks = ["{\"val", "ue\": 1}\n{\"value\"", ": 2}", "\n"];
+        let body =
+            crate::Body::from_bytes_stream(futures_util::stream::iter(chunks.into_iter().map(
+                |chunk| Ok::<_, std::io::Error>(bytes::Bytes::from_static(chunk.as_bytes())),
+            )));
+        let (req, mut body) = Request::builder().body(body).split();
+        let records = JsonLines::<Record>::from_request(&req, &mut body)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(records, vec![Record { value: 1 }, Record { value: 2 }]);
+    }
+
+    #[tokio::test]
+    async fn max_line_length() {
+        #[handler(internal)]
+        async fn index(records: JsonLines<Record>) -> Result<String> {
+            let records = records.max_line_length(16).try_collect::<Vec<_>>().await?;
+            Ok(records.len().to_string())
+        }
+
+        let cli = TestClient::new(index);
+
+        let resp = cli
+            .post("/")
+            .body("{\"value\": 1}\n{\"value\":    2}\n")
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_text("2").await;
+
+        let resp = cli
+            .post("/")
+            .body("{\"value\": 1}\n{\"value\":      2}\n")
+            .send()
+            .await;
+        resp.assert_status(http::StatusCode::PAYLOAD_TOO_LARGE);
+
+        let resp = cli.post("/").body("{\"value\":       1}").send().await;
+        resp.assert_status(http::StatusCode::PAYLOAD_TOO_LARGE);
+
+        let chunks = ["{\"value\"", ": 1", "                 }\n"];
+        let body =
+            crate::Body::from_bytes_stream(futures_util::stream::iter(chunks.into_iter().map(
+                |chunk| Ok::<_, std::io::Error>(bytes::Bytes::from_static(chunk.as_bytes())),
+            )));
+        let (req, mut body) = Request::builder().body(body).split();
+        let mut records = JsonLines::<Record>::from_request(&req, &mut body)
+            .await
+            .unwrap()
+            .max_line_length(16);
+        assert!(matches!(
+            records
+                .next()
+                .await
+                .unwrap()
+                .unwrap_err()
+                .downcast::<ReadBodyError>(),
+            Ok(ReadBodyError::PayloadTooLarge)
+        ));
+        assert!(records.next().await.is_none());
+    }
+}
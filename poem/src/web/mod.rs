@@ -2,25 +2,41 @@
 
 mod accept;
 mod addr;
+#[cfg(feature = "cbor")]
+mod cbor;
 #[cfg(feature = "compression")]
 mod compress;
+mod config;
+pub(crate) mod content_type;
 #[cfg(feature = "cookie")]
 #[cfg_attr(docsrs, doc(cfg(feature = "cookie")))]
 pub mod cookie;
+#[cfg(feature = "csv")]
+mod csv;
 mod data;
 mod form;
+mod graceful_shutdown;
 mod json;
+mod json_lines;
+#[cfg(feature = "msgpack")]
+mod msgpack;
 #[cfg(feature = "multipart")]
 mod multipart;
+mod pagination;
 mod path;
+#[cfg(feature = "protobuf")]
+mod protobuf;
 mod query;
+mod query_params;
 mod real_ip;
 mod redirect;
+mod request_local;
 #[cfg(feature = "sse")]
 #[cfg_attr(docsrs, doc(cfg(feature = "sse")))]
 pub mod sse;
 #[cfg(feature = "static-files")]
 mod static_file;
+mod subdomain;
 #[cfg(feature = "tempfile")]
 mod tempfile;
 #[cfg(feature = "xml")]
@@ -35,6 +51,7 @@ mod typed_header;
 #[cfg(feature = "websocket")]
 #[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
 pub mod websocket;
+mod with_rejection;
 
 use std::{convert::Infallible, fmt::Debug, future::Future};
 
@@ -44,13 +61,20 @@ use bytes::Bytes;
 use futures_util::FutureExt;
 use http::header;
 
+#[cfg(feature = "cbor")]
+pub use self::cbor::Cbor;
 #[cfg(feature = "compression")]
 pub use self::compress::{Compress, CompressionAlgo};
 #[cfg(feature = "csrf")]
 pub use self::csrf::{CsrfToken, CsrfVerifier};
+#[cfg(feature = "csv")]
+pub use self::csv::Csv;
+#[cfg(feature = "msgpack")]
+pub use self::msgpack::MsgPack;
 #[cfg(feature = "multipart")]
-pub use self::multipart::{Field, Multipart};
-pub(crate) use self::path::PathDeserializer;
+pub use self::multipart::{CopyTo, Field, FromMultipartField, Multipart, UploadedFile};
+#[cfg(feature = "protobuf")]
+pub use self::protobuf::Protobuf;
 #[cfg(feature = "static-files")]
 pub use self::static_file::{StaticFileRequest, StaticFileResponse};
 #[cfg(feature = "tempfile")]
@@ -62,15 +86,25 @@ pub use self::yaml::Yaml;
 pub use self::{
     accept::Accept,
     addr::{LocalAddr, RemoteAddr},
+    config::Config,
+    content_type::ContentTypeMode,
     data::Data,
     form::Form,
+    graceful_shutdown::{GracefulShutdown, ShutdownGuard},
     json::Json,
+    json_lines::JsonLines,
+    pagination::{Paginated, Pagination, PaginationConfig},
     path::Path,
     query::Query,
+    query_params::QueryParams,
     real_ip::RealIp,
     redirect::Redirect,
+    request_local::{Local, RequestLocal},
+    subdomain::{BaseDomain, Subdomain},
     typed_header::TypedHeader,
+    with_rejection::WithRejection,
 };
+pub(crate) use self::{graceful_shutdown::TrackedResponse, path::PathDeserializer};
 use crate::{
     body::Body,
     error::{ReadBodyError, Result},
@@ -267,6 +301,10 @@ impl RequestBody {
 ///
 ///     Extracts the matched path pattern from the incoming request.
 ///
+/// - **WithRejection&lt;T, R>**
+///
+///     Extracts `T` and converts its error into the rejection `R`, see [`WithRejection`].
+///
 /// # Create your own extractor
 ///
 /// The following is an example of a custom token extractor, which extracts the
@@ -404,6 +442,21 @@ pub trait FromRequest<'a>: Sized {
 ///    Convert `T` to response and set the specified status code [`StatusCode`],
 /// and then merge the specified [`HeaderMap`].
 ///
+/// - **(StatusCode,)**
+///
+///    Same as [`StatusCode`].
+///
+/// - **(HeaderMap, T)** / **(StatusCode, T, HeaderMap)**
+///
+///    Convert `T` to response, set the status code if specified, and then
+///   merge the specified [`HeaderMap`].
+///
+/// - **([(K, V); N], T)** / **(StatusCode, [(K, V); N], T)**
+///
+///    Convert `T` to response, set the status code if specified, and then
+///   append the header pairs, such as `[("x-request-id", "1")]`. The pairs
+///   with an invalid name or value are ignored.
+///
 /// - **Response**
 ///
 ///    The implementation for [`Response`] always returns itself.
@@ -724,6 +777,58 @@ impl<T: IntoResponse> IntoResponse for (HeaderMap, T) {
     }
 }
 
+impl IntoResponse for (StatusCode,) {
+    fn into_response(self) -> Response {
+        self.0.into_response()
+    }
+}
+
+impl<T: IntoResponse> IntoResponse for (StatusCode, T, HeaderMap) {
+    fn into_response(self) -> Response {
+        let mut resp = self.1.into_response();
+        resp.set_status(self.0);
+        resp.headers_mut().extend(self.2);
+        resp
+    }
+}
+
+fn append_headers<K, V, const N: usize>(resp: &mut Response, headers: [(K, V); N])
+where
+    K: TryInto<HeaderName>,
+    V: TryInto<HeaderValue>,
+{
+    for (key, value) in headers {
+        resp.append_header(key, value);
+    }
+}
+
+impl<K, V, T, const N: usize> IntoResponse for ([(K, V); N], T)
+where
+    K: TryInto<HeaderName> + Send,
+    V: TryInto<HeaderValue> + Send,
+    T: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        let mut resp = self.1.into_response();
+        append_headers(&mut resp, self.0);
+        resp
+    }
+}
+
+impl<K, V, T, const N: usize> IntoResponse for (StatusCode, [(K, V); N], T)
+where
+    K: TryInto<HeaderName> + Send,
+    V: TryInto<HeaderValue> + Send,
+    T: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        let mut resp = self.2.into_response();
+        resp.set_status(self.0);
+        append_headers(&mut resp, self.1);
+        resp
+    }
+}
+
 /// An HTML response.
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
 pub struct Html<T>(pub T);
@@ -904,6 +1009,56 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::CREATED);
         assert!(resp.into_body().into_string().await.unwrap().is_empty());
 
+        // (StatusCode,)
+        let resp = (StatusCode::NO_CONTENT,).into_response();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(resp.into_body().into_string().await.unwrap().is_empty());
+
+        // (StatusCode, T, HeaderMap)
+        let mut headers = HeaderMap::new();
+        headers.append("Value1", HeaderValue::from_static("123"));
+        let resp = (
+            StatusCode::CREATED,
+            Json(serde_json::json!({"id": 1})),
+            headers,
+        )
+            .into_response();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        assert_eq!(resp.content_type(), Some("application/json; charset=utf-8"));
+        assert_eq!(
+            resp.headers().get("Value1"),
+            Some(&HeaderValue::from_static("123"))
+        );
+        assert_eq!(resp.into_body().into_string().await.unwrap(), r#"{"id":1}"#);
+
+        // ([(K, V); N], T)
+        let resp = ([("Value1", "123"), ("Value1", "456")], "abc").into_response();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers()
+                .get_all("Value1")
+                .iter()
+                .map(|value| value.to_str().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["123", "456"]
+        );
+        assert_eq!(resp.into_body().into_string().await.unwrap(), "abc");
+
+        // (StatusCode, [(K, V); N], T)
+        let resp = (
+            StatusCode::ACCEPTED,
+            [("Value1", "123"), ("Invalid Name", "456")],
+            "abc",
+        )
+            .into_response();
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+        assert_eq!(
+            resp.headers().get("Value1"),
+            Some(&HeaderValue::from_static("123"))
+        );
+        assert_eq!(resp.headers().len(), 2);
+        assert_eq!(resp.into_body().into_string().await.unwrap(), "abc");
+
         // Html
         let resp = Html("abc").into_response();
         assert_eq!(resp.status(), StatusCode::OK);
@@ -1048,4 +1203,118 @@ mod tests {
             Bytes::from_static(b"abc")
         );
     }
+
+    #[tokio::test]
+    async fn derive_from_request() {
+        use serde::Deserialize;
+
+        use crate::{handler, test::TestClient, EndpointExt, Route};
+
+        #[derive(Deserialize)]
+        struct Pagination {
+            page: u32,
+        }
+
+        #[derive(crate::FromRequest)]
+        #[from_request(internal)]
+        struct Params<'a> {
+            id: Path<u32>,
+            pagination: Query<Pagination>,
+            prefix: Data<&'a String>,
+            body: String,
+        }
+
+        #[derive(crate::FromRequest)]
+        #[from_request(internal)]
+        struct Tuple(Path<u32>, Option<Query<Pagination>>);
+
+        #[handler(internal)]
+        fn index(params: Params<'_>) -> String {
+            format!(
+                "{}{} {} {}",
+                params.prefix.0, params.id.0, params.pagination.page, params.body
+            )
+        }
+
+        #[handler(internal)]
+        fn tuple(Tuple(id, pagination): Tuple) -> String {
+            format!("{} {}", id.0, pagination.map(|p| p.page).unwrap_or(0))
+        }
+
+        let cli = TestClient::new(
+            Route::new()
+                .at("/:id", index)
+                .at("/tuple/:id", tuple)
+                .data("user-".to_string()),
+        );
+
+        let resp = cli.post("/10").query("page", &2).body("abc").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("user-10 2 abc").await;
+
+        cli.post("/10")
+            .query("page", &"x")
+            .send()
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        let req = Request::builder().uri_str("/?page=x").finish();
+        let err = match Tuple::from_request_without_body(&req).await {
+            Err(err) => err,
+            Ok(_) => panic!("expected an error"),
+        };
+        assert!(err.to_string().starts_with("failed to extract `0`: "));
+
+        let resp = cli.get("/tuple/5").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("5 0").await;
+    }
+
+    #[tokio::test]
+    async fn derive_into_response() {
+        #[derive(crate::IntoResponse)]
+        #[response(internal)]
+        enum MyResponse {
+            #[response(status = 201)]
+            Created(Json<i32>, #[response(header = "Location")] String),
+            #[response(status = 409, content_type = "text/plain; charset=utf-8")]
+            Conflict {
+                message: Vec<u8>,
+                #[response(header = "x-retry-after")]
+                retry_after: u32,
+            },
+            #[response(status = 404)]
+            NotFound,
+            Other(StatusCode),
+        }
+
+        let resp = MyResponse::Created(Json(1), "/users/1".to_string()).into_response();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        assert_eq!(resp.header(header::LOCATION), Some("/users/1"));
+        assert_eq!(
+            resp.header(header::CONTENT_TYPE),
+            Some("application/json; charset=utf-8")
+        );
+        assert_eq!(resp.into_body().into_string().await.unwrap(), "1");
+
+        let resp = MyResponse::Conflict {
+            message: b"conflict".to_vec(),
+            retry_after: 10,
+        }
+        .into_response();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        assert_eq!(resp.header("X-Retry-After"), Some("10"));
+        assert_eq!(
+            resp.header(header::CONTENT_TYPE),
+            Some("text/plain; charset=utf-8")
+        );
+        assert_eq!(resp.into_body().into_string().await.unwrap(), "conflict");
+
+        let resp = MyResponse::NotFound.into_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert!(resp.into_body().into_bytes().await.unwrap().is_empty());
+
+        let resp = MyResponse::Other(StatusCode::ACCEPTED).into_response();
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    }
 }
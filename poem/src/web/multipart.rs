@@ -1,17 +1,21 @@
 use std::{
     fmt::{self, Debug, Formatter},
+    future::{Future, IntoFuture},
     str::FromStr,
 };
 
-use futures_util::TryStreamExt;
+use bytes::Bytes;
+use futures_util::{future::BoxFuture, TryStreamExt};
 use mime::Mime;
 #[cfg(feature = "tempfile")]
 use tokio::fs::File;
-use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 #[cfg(feature = "tempfile")]
 use tokio::io::{AsyncSeekExt, SeekFrom};
 
-use crate::{error::ParseMultipartError, http::header, FromRequest, Request, RequestBody, Result};
+use crate::{
+    error::ParseMultipartError, http::header, BufferPool, FromRequest, Request, RequestBody, Result,
+};
 
 /// A single field in a multipart stream.
 #[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
@@ -58,19 +62,10 @@ impl Field {
 
     /// Get the full data of the field as bytes.
     pub async fn bytes(self) -> Result<Vec<u8>, ParseMultipartError> {
-        let mut data = Vec::new();
-        let mut buf = [0; 2048];
+        let mut data = BufferPool::global().get();
         let mut reader = self.into_async_read();
-        loop {
-            let sz = reader.read(&mut buf[..]).await?;
-            if sz > 0 {
-                data.extend_from_slice(&buf[..sz]);
-            } else {
-                break;
-            }
-        }
-
-        Ok(data)
+        while reader.read_buf(&mut *data).await? > 0 {}
+        Ok(data.into_vec())
     }
 
     /// Get the full field data as text.
@@ -90,15 +85,276 @@ impl Field {
         Ok(file)
     }
 
+    /// Copies the field data into a writer, such as a file or an object
+    /// storage upload, without buffering it in memory.
+    ///
+    /// Returns the number of bytes written. The size of the data can be
+    /// limited with [`CopyTo::limit`], and the progress can be reported with
+    /// [`CopyTo::on_progress`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{error::BadRequest, handler, web::Multipart, Result};
+    /// use tokio::fs::File;
+    ///
+    /// #[handler]
+    /// async fn upload(mut multipart: Multipart) -> Result<String> {
+    ///     let mut total = 0;
+    ///     while let Some(field) = multipart.next_field().await? {
+    ///         let mut file = File::create("upload.bin").await.map_err(BadRequest)?;
+    ///         total += field
+    ///             .copy_to(&mut file)
+    ///             .limit(10 * 1024 * 1024)
+    ///             .on_progress(|written| println!("{written} bytes"))
+    ///             .await?;
+    ///     }
+    ///     Ok(format!("{total} bytes uploaded"))
+    /// }
+    /// ```
+    pub fn copy_to<W>(self, writer: &mut W) -> CopyTo<'_, W>
+    where
+        W: AsyncWrite + Unpin + Send + ?Sized,
+    {
+        CopyTo {
+            field: self,
+            writer,
+            limit: None,
+            on_progress: None,
+        }
+    }
+
     /// Consume this field to return a reader.
     pub fn into_async_read(self) -> impl AsyncRead + Send {
         tokio_util::io::StreamReader::new(
             self.0
-                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string())),
+                .map_err(|err| std::io::Error::other(err.to_string())),
         )
     }
 }
 
+/// A file uploaded in a multipart field, which is read into memory.
+///
+/// Use [`Multipart`] and [`Field::copy_to`] instead to stream large files.
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+#[derive(Debug, Clone)]
+pub struct UploadedFile {
+    file_name: Option<String>,
+    content_type: Option<String>,
+    data: Bytes,
+}
+
+impl UploadedFile {
+    /// The file name found in the `Content-Disposition` header.
+    #[inline]
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    /// Get the content type of the file.
+    #[inline]
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Returns the size of the file.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the content of the file.
+    #[inline]
+    pub fn data(&self) -> &Bytes {
+        &self.data
+    }
+
+    /// Consumes this object to return the content of the file.
+    #[inline]
+    pub fn into_data(self) -> Bytes {
+        self.data
+    }
+}
+
+/// Represents a type that can be parsed from the fields of a struct deriving
+/// [`FromMultipart`](crate::FromMultipart).
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub trait FromMultipartField: Sized + Send {
+    /// Parses the value from a field.
+    fn from_field(field: Field) -> impl Future<Output = Result<Self, ParseMultipartError>> + Send;
+
+    /// Merges a repeated field into the value parsed from the previous
+    /// fields with the same name.
+    ///
+    /// The default implementation rejects the repeated field.
+    fn merge_repeated_field(
+        self,
+        field: Field,
+    ) -> impl Future<Output = Result<Self, ParseMultipartError>> + Send {
+        let name = field.name().unwrap_or_default().to_string();
+        async move { Err(ParseMultipartError::RepeatedField(name)) }
+    }
+
+    /// Returns the value if the field is missing.
+    ///
+    /// The default implementation fails with
+    /// [`ParseMultipartError::MissingField`].
+    fn from_missing(name: &str) -> Result<Self, ParseMultipartError> {
+        Err(ParseMultipartError::MissingField(name.to_string()))
+    }
+}
+
+impl FromMultipartField for String {
+    async fn from_field(field: Field) -> Result<Self, ParseMultipartError> {
+        field.text().await
+    }
+}
+
+impl FromMultipartField for Bytes {
+    async fn from_field(field: Field) -> Result<Self, ParseMultipartError> {
+        Ok(field.bytes().await?.into())
+    }
+}
+
+impl FromMultipartField for UploadedFile {
+    async fn from_field(field: Field) -> Result<Self, ParseMultipartError> {
+        let file_name = field.file_name().map(ToString::to_string);
+        let content_type = field.content_type().map(ToString::to_string);
+        Ok(Self {
+            file_name,
+            content_type,
+            data: field.bytes().await?.into(),
+        })
+    }
+}
+
+impl<T: FromMultipartField> FromMultipartField for Option<T> {
+    async fn from_field(field: Field) -> Result<Self, ParseMultipartError> {
+        T::from_field(field).await.map(Some)
+    }
+
+    async fn merge_repeated_field(self, field: Field) -> Result<Self, ParseMultipartError> {
+        match self {
+            Some(value) => value.merge_repeated_field(field).await.map(Some),
+            None => T::from_field(field).await.map(Some),
+        }
+    }
+
+    fn from_missing(_name: &str) -> Result<Self, ParseMultipartError> {
+        Ok(None)
+    }
+}
+
+impl<T: FromMultipartField> FromMultipartField for Vec<T> {
+    async fn from_field(field: Field) -> Result<Self, ParseMultipartError> {
+        Ok(vec![T::from_field(field).await?])
+    }
+
+    async fn merge_repeated_field(mut self, field: Field) -> Result<Self, ParseMultipartError> {
+        self.push(T::from_field(field).await?);
+        Ok(self)
+    }
+
+    fn from_missing(_name: &str) -> Result<Self, ParseMultipartError> {
+        Ok(Vec::new())
+    }
+}
+
+macro_rules! impl_from_multipart_field_for_from_str {
+    ($($ty:ty),*) => {
+        $(
+        impl FromMultipartField for $ty {
+            async fn from_field(field: Field) -> Result<Self, ParseMultipartError> {
+                let name = field.name().unwrap_or_default().to_string();
+                let text = field.text().await?;
+                text.parse().map_err(|err: <$ty as FromStr>::Err| {
+                    ParseMultipartError::InvalidField {
+                        name,
+                        reason: err.to_string(),
+                    }
+                })
+            }
+        }
+        )*
+    };
+}
+
+impl_from_multipart_field_for_from_str!(
+    bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+/// A future which copies a multipart field into a writer, created by
+/// [`Field::copy_to`].
+///
+/// # Errors
+///
+/// - [`ParseMultipartError`]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub struct CopyTo<'a, W: ?Sized> {
+    field: Field,
+    writer: &'a mut W,
+    limit: Option<u64>,
+    on_progress: Option<Box<dyn FnMut(u64) + Send + 'a>>,
+}
+
+impl<'a, W: ?Sized> CopyTo<'a, W> {
+    /// Fails with [`ParseMultipartError::PayloadTooLarge`] if the field data
+    /// is larger than `limit` bytes.
+    ///
+    /// The data received before the limit was exceeded has already been
+    /// written.
+    #[must_use]
+    pub fn limit(self, limit: u64) -> Self {
+        Self {
+            limit: Some(limit),
+            ..self
+        }
+    }
+
+    /// Sets a callback which is called with the total number of bytes
+    /// written, after each chunk of data.
+    #[must_use]
+    pub fn on_progress(self, f: impl FnMut(u64) + Send + 'a) -> Self {
+        Self {
+            on_progress: Some(Box::new(f)),
+            ..self
+        }
+    }
+}
+
+impl<'a, W> IntoFuture for CopyTo<'a, W>
+where
+    W: AsyncWrite + Unpin + Send + ?Sized,
+{
+    type Output = Result<u64, ParseMultipartError>;
+    type IntoFuture = BoxFuture<'a, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let Self {
+            field: Field(mut field),
+            writer,
+            limit,
+            mut on_progress,
+        } = self;
+
+        Box::pin(async move {
+            let mut written = 0;
+            while let Some(chunk) = field.chunk().await? {
+                written += chunk.len() as u64;
+                if limit.is_some_and(|limit| written > limit) {
+                    return Err(ParseMultipartError::PayloadTooLarge);
+                }
+                writer.write_all(&chunk).await?;
+                if let Some(on_progress) = &mut on_progress {
+                    on_progress(written);
+                }
+            }
+            writer.flush().await?;
+            Ok(written)
+        })
+    }
+}
+
 /// An extractor that parses `multipart/form-data` requests commonly used with
 /// file uploads.
 ///
@@ -131,6 +387,17 @@ pub struct Multipart {
 
 impl<'a> FromRequest<'a> for Multipart {
     async fn from_request(req: &'a Request, body: &mut RequestBody) -> Result<Self> {
+        Self::new(req, body, multer::Constraints::new())
+    }
+}
+
+impl Multipart {
+    #[allow(clippy::result_large_err)]
+    fn new(
+        req: &Request,
+        body: &mut RequestBody,
+        constraints: multer::Constraints,
+    ) -> Result<Self> {
         let content_type = req
             .headers()
             .get(header::CONTENT_TYPE)
@@ -148,15 +415,30 @@ impl<'a> FromRequest<'a> for Multipart {
         let boundary = multer::parse_boundary(content_type.as_ref())
             .map_err(ParseMultipartError::Multipart)?;
         Ok(Self {
-            inner: multer::Multipart::new(
+            inner: multer::Multipart::with_constraints(
                 tokio_util::io::ReaderStream::new(body.take()?.into_async_read()),
                 boundary,
+                constraints,
             ),
         })
     }
-}
 
-impl Multipart {
+    /// Extracts the multipart body from the request like
+    /// [`FromRequest::from_request`], but reading a field fails with a
+    /// `413 Payload Too Large` error once the body is larger than `limit`
+    /// bytes.
+    pub async fn from_request_limit(
+        req: &Request,
+        body: &mut RequestBody,
+        limit: u64,
+    ) -> Result<Self> {
+        Self::new(
+            req,
+            body,
+            multer::Constraints::new().size_limit(multer::SizeLimit::new().whole_stream(limit)),
+        )
+    }
+
     /// Yields the next [`Field`] if available.
     pub async fn next_field(&mut self) -> Result<Option<Field>, ParseMultipartError> {
         match self.inner.next_field().await? {
@@ -217,4 +499,128 @@ mod tests {
             .await;
         resp.assert_status_is_ok();
     }
+
+    #[tokio::test]
+    async fn test_copy_to() {
+        #[handler(internal)]
+        async fn index(mut multipart: Multipart) -> Result<String> {
+            let field = multipart.next_field().await?.unwrap();
+            let mut data = Vec::new();
+            let mut progress = Vec::new();
+            let written = field
+                .copy_to(&mut data)
+                .limit(10)
+                .on_progress(|written| progress.push(written))
+                .await?;
+            assert_eq!(written, 4);
+            assert_eq!(progress.last(), Some(&4));
+            Ok(String::from_utf8(data).unwrap())
+        }
+
+        let cli = TestClient::new(index);
+        let send = |value: &'static str| {
+            cli.post("/")
+                .header("content-type", "multipart/form-data; boundary=X-BOUNDARY")
+                .body(format!(
+                    "--X-BOUNDARY\r\nContent-Disposition: form-data; name=\"file\"\r\n\r\n{value}\r\n--X-BOUNDARY--\r\n"
+                ))
+                .send()
+        };
+
+        let resp = send("abcd").await;
+        resp.assert_status_is_ok();
+        resp.assert_text("abcd").await;
+
+        send("0123456789a")
+            .await
+            .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn derive_from_multipart() {
+        #[derive(crate::FromMultipart)]
+        #[multipart(internal, limit = 1024)]
+        struct Upload {
+            name: String,
+            count: u32,
+            #[multipart(rename = "tag")]
+            tags: Vec<String>,
+            description: Option<String>,
+            #[multipart(default)]
+            public: bool,
+            file: UploadedFile,
+        }
+
+        #[handler(internal)]
+        fn index(upload: Upload) -> String {
+            format!(
+                "{} {} {:?} {:?} {} {:?} {:?} {}",
+                upload.name,
+                upload.count,
+                upload.tags,
+                upload.description,
+                upload.public,
+                upload.file.file_name(),
+                upload.file.content_type(),
+                String::from_utf8_lossy(upload.file.data()),
+            )
+        }
+
+        let cli = TestClient::new(index);
+        let send = |fields: &[(&str, &str)]| {
+            let mut data = String::new();
+            for (name, value) in fields {
+                let disposition = match *name {
+                    "file" => {
+                        "name=\"file\"; filename=\"a.txt\"\r\nContent-Type: text/plain".to_string()
+                    }
+                    name => format!("name=\"{name}\""),
+                };
+                data.push_str(&format!(
+                    "--X-BOUNDARY\r\nContent-Disposition: form-data; {disposition}\r\n\r\n{value}\r\n"
+                ));
+            }
+            data.push_str("--X-BOUNDARY--\r\n");
+            cli.post("/")
+                .header("content-type", "multipart/form-data; boundary=X-BOUNDARY")
+                .body(data)
+                .send()
+        };
+
+        let resp = send(&[
+            ("name", "abc"),
+            ("count", "3"),
+            ("tag", "a"),
+            ("unknown", "x"),
+            ("tag", "b"),
+            ("file", "hello"),
+        ])
+        .await;
+        resp.assert_status_is_ok();
+        resp.assert_text(r#"abc 3 ["a", "b"] None false Some("a.txt") Some("text/plain") hello"#)
+            .await;
+
+        let resp = send(&[("name", "abc"), ("count", "x"), ("file", "hello")]).await;
+        resp.assert_status(StatusCode::BAD_REQUEST);
+        resp.assert_text(
+            ParseMultipartError::InvalidField {
+                name: "count".to_string(),
+                reason: "invalid digit found in string".to_string(),
+            }
+            .to_string(),
+        )
+        .await;
+
+        let resp = send(&[("name", "abc"), ("count", "1")]).await;
+        resp.assert_status(StatusCode::BAD_REQUEST);
+        resp.assert_text("field `file` is required").await;
+
+        let resp = send(&[("name", "a"), ("name", "b"), ("count", "1"), ("file", "")]).await;
+        resp.assert_status(StatusCode::BAD_REQUEST);
+        resp.assert_text("field `name` is repeated").await;
+
+        let file = "x".repeat(1024);
+        let resp = send(&[("name", "abc"), ("count", "1"), ("file", &file)]).await;
+        resp.assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+    }
 }
@@ -0,0 +1,492 @@
+use http::{header, HeaderName, HeaderValue};
+
+use crate::{
+    error::ParsePaginationError, FromRequest, IntoResponse, Request, RequestBody, Response, Result,
+};
+
+const PAGE: &str = "page";
+const PER_PAGE: &str = "per_page";
+const CURSOR: &str = "cursor";
+
+/// The defaults and caps used by the [`Pagination`] extractor, which can be
+/// added to the endpoint with [`EndpointExt::data`](crate::EndpointExt::data).
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     handler,
+///     test::TestClient,
+///     web::{Pagination, PaginationConfig},
+///     EndpointExt,
+/// };
+///
+/// #[handler]
+/// fn index(pagination: Pagination) -> String {
+///     pagination.per_page().to_string()
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let cli = TestClient::new(index.data(PaginationConfig::new().max_per_page(50)));
+/// let resp = cli.get("/").query("per_page", &500).send().await;
+/// resp.assert_text("50").await;
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PaginationConfig {
+    default_per_page: u32,
+    max_per_page: u32,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            default_per_page: 20,
+            max_per_page: 100,
+        }
+    }
+}
+
+impl PaginationConfig {
+    /// Create a new `PaginationConfig` with `20` items per page by default
+    /// and at most `100` items per page.
+    #[must_use]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the number of items per page used when the request does not
+    /// specify `per_page`.
+    #[must_use]
+    pub fn default_per_page(self, per_page: u32) -> Self {
+        Self {
+            default_per_page: per_page.max(1),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of items per page, larger values of
+    /// `per_page` are lowered to it.
+    #[must_use]
+    pub fn max_per_page(self, per_page: u32) -> Self {
+        Self {
+            max_per_page: per_page.max(1),
+            ..self
+        }
+    }
+
+    /// Returns the configuration for the request.
+    pub fn from_request(req: &Request) -> Self {
+        req.data::<PaginationConfig>().copied().unwrap_or_default()
+    }
+}
+
+/// An extractor for the pagination parameters of a list endpoint.
+///
+/// The page is selected either with the `page` (starting from `1`) or the
+/// `cursor` query parameter, if both are present the cursor wins. The number
+/// of items per page is read from `per_page`, the default value and the cap
+/// come from [`PaginationConfig`].
+///
+/// # Errors
+///
+/// - [`ParsePaginationError`]
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     handler,
+///     http::StatusCode,
+///     test::TestClient,
+///     web::{Json, Paginated, Pagination},
+/// };
+///
+/// #[handler]
+/// fn index(pagination: Pagination) -> Paginated<Json<Vec<u64>>> {
+///     let items = (pagination.offset()..pagination.offset() + pagination.limit()).collect();
+///     Paginated::new(Json(items), &pagination).total(100)
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let cli = TestClient::new(index);
+/// let resp = cli
+///     .get("/")
+///     .query("page", &2)
+///     .query("per_page", &3)
+///     .send()
+///     .await;
+/// resp.assert_status_is_ok();
+/// resp.assert_header("x-total-count", "100");
+/// resp.assert_json(&[3, 4, 5]).await;
+///
+/// let resp = cli.get("/").query("page", &0).send().await;
+/// resp.assert_status(StatusCode::BAD_REQUEST);
+/// # });
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Pagination {
+    page: u32,
+    per_page: u32,
+    cursor: Option<String>,
+    path: String,
+    params: Vec<(String, String)>,
+}
+
+impl Pagination {
+    /// Returns the requested page, starting from `1`.
+    ///
+    /// It is always `1` when the request uses a cursor.
+    #[inline]
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    /// Returns the number of items per page.
+    #[inline]
+    pub fn per_page(&self) -> u32 {
+        self.per_page
+    }
+
+    /// Returns the cursor sent by the client, if any.
+    #[inline]
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    /// Returns the number of items to skip.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        (self.page as u64 - 1) * self.per_page as u64
+    }
+
+    /// Returns the maximum number of items to return, it is the same as
+    /// [`Pagination::per_page`].
+    #[inline]
+    pub fn limit(&self) -> u64 {
+        self.per_page as u64
+    }
+
+    fn parse(req: &Request) -> Result<Self, ParsePaginationError> {
+        let config = PaginationConfig::from_request(req);
+        let query = req.query();
+
+        let parse_number = |name: &'static str| -> Result<Option<u32>, ParsePaginationError> {
+            match query.get(name) {
+                Some(value) => match value.parse::<u32>() {
+                    Ok(0) | Err(_) => Err(ParsePaginationError::InvalidNumber {
+                        name,
+                        value: value.to_string(),
+                    }),
+                    Ok(n) => Ok(Some(n)),
+                },
+                None => Ok(None),
+            }
+        };
+
+        let cursor = query
+            .get(CURSOR)
+            .filter(|cursor| !cursor.is_empty())
+            .map(ToString::to_string);
+        let page = match cursor {
+            Some(_) => 1,
+            None => parse_number(PAGE)?.unwrap_or(1),
+        };
+        let per_page = parse_number(PER_PAGE)?
+            .unwrap_or(config.default_per_page)
+            .min(config.max_per_page);
+
+        Ok(Self {
+            page,
+            per_page,
+            cursor,
+            // the links must point to the resource even inside nested routes
+            path: req.original_uri().path().to_string(),
+            params: query
+                .iter()
+                .filter(|(name, _)| ![PAGE, PER_PAGE, CURSOR].contains(name))
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        })
+    }
+
+    /// Returns the uri of the current resource with the specified
+    /// pagination parameters.
+    fn uri(&self, page: Option<u32>, cursor: Option<&str>) -> String {
+        let mut params = self
+            .params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.clone()))
+            .collect::<Vec<_>>();
+        if let Some(page) = page {
+            params.push((PAGE, page.to_string()));
+        }
+        if let Some(cursor) = cursor {
+            params.push((CURSOR, cursor.to_string()));
+        }
+        params.push((PER_PAGE, self.per_page.to_string()));
+        format!(
+            "{}?{}",
+            self.path,
+            serde_urlencoded::to_string(params).unwrap_or_default()
+        )
+    }
+}
+
+impl<'a> FromRequest<'a> for Pagination {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        Ok(Self::parse(req)?)
+    }
+}
+
+/// A response for a page of items, which adds the `Link` header with the
+/// `first`, `prev`, `next` and `last` relations and the `X-Total-Count`
+/// header to the response of the items.
+///
+/// The `next` and `last` relations of a numbered page are only available
+/// when the total number of items is set with [`Paginated::total`], a
+/// cursor based page only has the `next` relation when the next cursor is set
+/// with [`Paginated::next_cursor`].
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     handler,
+///     test::TestClient,
+///     web::{Json, Paginated, Pagination},
+/// };
+///
+/// #[handler]
+/// fn index(pagination: Pagination) -> Paginated<Json<Vec<String>>> {
+///     let after = pagination.cursor().unwrap_or("a");
+///     Paginated::new(Json(vec![after.to_string()]), &pagination).next_cursor("b")
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let cli = TestClient::new(index);
+/// let resp = cli.get("/items").query("cursor", &"a").send().await;
+/// resp.assert_header(
+///     "link",
+///     r#"</items?per_page=20>; rel="first", </items?cursor=b&per_page=20>; rel="next""#,
+/// );
+/// # });
+/// ```
+pub struct Paginated<T> {
+    items: T,
+    pagination: Pagination,
+    total: Option<u64>,
+    next_cursor: Option<String>,
+}
+
+impl<T> Paginated<T> {
+    /// Create a page with the items of the request specified by
+    /// `pagination`.
+    pub fn new(items: T, pagination: &Pagination) -> Self {
+        Self {
+            items,
+            pagination: pagination.clone(),
+            total: None,
+            next_cursor: None,
+        }
+    }
+
+    /// Sets the total number of items.
+    #[must_use]
+    pub fn total(self, total: u64) -> Self {
+        Self {
+            total: Some(total),
+            ..self
+        }
+    }
+
+    /// Sets the cursor of the next page.
+    #[must_use]
+    pub fn next_cursor(self, cursor: impl Into<String>) -> Self {
+        Self {
+            next_cursor: Some(cursor.into()),
+            ..self
+        }
+    }
+
+    fn links(&self) -> Vec<(String, &'static str)> {
+        let pagination = &self.pagination;
+        let mut links = Vec::new();
+
+        if pagination.cursor.is_some() || self.next_cursor.is_some() {
+            links.push((pagination.uri(None, None), "first"));
+            if let Some(cursor) = &self.next_cursor {
+                links.push((pagination.uri(None, Some(cursor)), "next"));
+            }
+            return links;
+        }
+
+        let page = pagination.page;
+        let last_page = self.total.map(|total| {
+            total
+                .div_ceil(pagination.per_page as u64)
+                .clamp(1, u32::MAX as u64) as u32
+        });
+
+        links.push((pagination.uri(Some(1), None), "first"));
+        if page > 1 {
+            let prev = last_page.map_or(page - 1, |last_page| (page - 1).min(last_page));
+            links.push((pagination.uri(Some(prev), None), "prev"));
+        }
+        if let Some(last_page) = last_page {
+            if page < last_page {
+                links.push((pagination.uri(Some(page + 1), None), "next"));
+            }
+            links.push((pagination.uri(Some(last_page), None), "last"));
+        }
+        links
+    }
+}
+
+impl<T: IntoResponse> IntoResponse for Paginated<T> {
+    fn into_response(self) -> Response {
+        let links = self
+            .links()
+            .into_iter()
+            .map(|(uri, rel)| format!("<{uri}>; rel=\"{rel}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut resp = self.items.into_response();
+
+        if let Ok(value) = HeaderValue::from_str(&links) {
+            resp.headers_mut().insert(header::LINK, value);
+        }
+        if let Some(total) = self.total {
+            resp.headers_mut().insert(
+                HeaderName::from_static("x-total-count"),
+                HeaderValue::from(total),
+            );
+        }
+        resp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get, handler, http::StatusCode, test::TestClient, web::Json, EndpointExt, Route};
+
+    #[tokio::test]
+    async fn extract() {
+        #[handler(internal)]
+        fn index(pagination: Pagination) -> String {
+            format!(
+                "{}:{}:{}",
+                pagination.page(),
+                pagination.per_page(),
+                pagination.cursor().unwrap_or_default()
+            )
+        }
+
+        let cli = TestClient::new(index.data(PaginationConfig::new().default_per_page(10)));
+
+        cli.get("/").send().await.assert_text("1:10:").await;
+        cli.get("/")
+            .query("page", &3)
+            .query("per_page", &1000)
+            .send()
+            .await
+            .assert_text("3:100:")
+            .await;
+        cli.get("/")
+            .query("page", &3)
+            .query("cursor", &"abc")
+            .send()
+            .await
+            .assert_text("1:10:abc")
+            .await;
+
+        for (name, value) in [("page", "0"), ("page", "a"), ("per_page", "0")] {
+            cli.get("/")
+                .query(name, &value)
+                .send()
+                .await
+                .assert_status(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn page_links() {
+        #[handler(internal)]
+        fn index(pagination: Pagination) -> Paginated<Json<Vec<i32>>> {
+            Paginated::new(Json(vec![]), &pagination).total(25)
+        }
+
+        let cli = TestClient::new(index);
+
+        let resp = cli
+            .get("/items")
+            .query("q", &"a b")
+            .query("page", &2)
+            .query("per_page", &10)
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_header("x-total-count", "25");
+        resp.assert_header(
+            "link",
+            [
+                r#"</items?q=a+b&page=1&per_page=10>; rel="first""#,
+                r#"</items?q=a+b&page=1&per_page=10>; rel="prev""#,
+                r#"</items?q=a+b&page=3&per_page=10>; rel="next""#,
+                r#"</items?q=a+b&page=3&per_page=10>; rel="last""#,
+            ]
+            .join(", "),
+        );
+
+        let resp = cli.get("/items").query("page", &3).send().await;
+        resp.assert_header(
+            "link",
+            [
+                r#"</items?page=1&per_page=20>; rel="first""#,
+                r#"</items?page=2&per_page=20>; rel="prev""#,
+                r#"</items?page=2&per_page=20>; rel="last""#,
+            ]
+            .join(", "),
+        );
+    }
+
+    #[tokio::test]
+    async fn page_without_total() {
+        #[handler(internal)]
+        fn index(pagination: Pagination) -> Paginated<Json<Vec<i32>>> {
+            Paginated::new(Json(vec![]), &pagination)
+        }
+
+        let resp = TestClient::new(index)
+            .get("/")
+            .query("page", &2)
+            .send()
+            .await;
+        resp.assert_header_is_not_exist("x-total-count");
+        resp.assert_header(
+            "link",
+            r#"</?page=1&per_page=20>; rel="first", </?page=1&per_page=20>; rel="prev""#,
+        );
+    }
+
+    #[tokio::test]
+    async fn nested_route_links() {
+        #[handler(internal)]
+        fn index(pagination: Pagination) -> Paginated<Json<Vec<i32>>> {
+            Paginated::new(Json(vec![]), &pagination)
+        }
+
+        let app = Route::new().nest("/api", Route::new().at("/items", get(index)));
+        let resp = TestClient::new(app)
+            .get("/api/items")
+            .query("page", &2)
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_header(
+            "link",
+            r#"</api/items?page=1&per_page=20>; rel="first", </api/items?page=1&per_page=20>; rel="prev""#,
+        );
+    }
+}
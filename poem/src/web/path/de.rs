@@ -5,6 +5,8 @@ use serde::{
     forward_to_deserialize_any, Deserializer,
 };
 
+use crate::route::PathParamsRef;
+
 /// This type represents errors that can occur when deserializing.
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) struct PathDeserializerError(pub(crate) String);
@@ -62,10 +64,10 @@ macro_rules! parse_single_value {
                 ));
             }
 
-            let value = self.url_params[0].1.parse().map_err(|_| {
+            let value = self.value().parse().map_err(|_| {
                 PathDeserializerError::custom(format!(
                     "can not parse `{:?}` to a `{}`",
-                    self.url_params[0].1.as_str(),
+                    self.value(),
                     $tp
                 ))
             })?;
@@ -75,14 +77,22 @@ macro_rules! parse_single_value {
 }
 
 pub(crate) struct PathDeserializer<'de> {
-    url_params: &'de [(String, String)],
+    url_params: PathParamsRef<'de>,
 }
 
 impl<'de> PathDeserializer<'de> {
     #[inline]
-    pub(crate) fn new(url_params: &'de [(String, String)]) -> Self {
+    pub(crate) fn new(url_params: PathParamsRef<'de>) -> Self {
         PathDeserializer { url_params }
     }
+
+    #[inline]
+    fn value(&self) -> &'de str {
+        self.url_params
+            .get(0)
+            .map(|(_, value)| value)
+            .unwrap_or_default()
+    }
 }
 
 impl<'de> Deserializer<'de> for PathDeserializer<'de> {
@@ -119,7 +129,7 @@ impl<'de> Deserializer<'de> for PathDeserializer<'de> {
                 self.url_params.len()
             )));
         }
-        visitor.visit_str(&self.url_params[0].1)
+        visitor.visit_borrowed_str(self.value())
     }
 
     fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -242,13 +252,13 @@ impl<'de> Deserializer<'de> for PathDeserializer<'de> {
         }
 
         visitor.visit_enum(EnumDeserializer {
-            value: &self.url_params[0].1,
+            value: self.value(),
         })
     }
 }
 
 struct MapDeserializer<'de> {
-    params: &'de [(String, String)],
+    params: PathParamsRef<'de>,
     value: Option<&'de str>,
 }
 
@@ -290,7 +300,7 @@ macro_rules! parse_key {
         where
             V: Visitor<'de>,
         {
-            visitor.visit_str(self.key)
+            visitor.visit_borrowed_str(self.key)
         }
     };
 }
@@ -518,7 +528,7 @@ impl<'de> VariantAccess<'de> for UnitVariant {
 }
 
 struct SeqDeserializer<'de> {
-    params: &'de [(String, String)],
+    params: PathParamsRef<'de>,
 }
 
 impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
@@ -541,7 +551,7 @@ impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
 #[cfg(test)]
 #[allow(clippy::float_cmp)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{borrow::Cow, collections::HashMap};
 
     use serde::Deserialize;
 
@@ -580,7 +590,7 @@ mod tests {
             #[allow(clippy::bool_assert_comparison)]
             {
                 let url_params = create_url_params(vec![("value", $value_str)]);
-                let deserializer = PathDeserializer::new(&url_params);
+                let deserializer = PathDeserializer::new(url_params.as_ref());
                 assert_eq!(<$ty>::deserialize(deserializer).unwrap(), $value);
             }
         };
@@ -605,13 +615,13 @@ mod tests {
 
         let url_params = create_url_params(vec![("a", "B")]);
         assert_eq!(
-            MyEnum::deserialize(PathDeserializer::new(&url_params)).unwrap(),
+            MyEnum::deserialize(PathDeserializer::new(url_params.as_ref())).unwrap(),
             MyEnum::B
         );
 
         let url_params = create_url_params(vec![("a", "1"), ("b", "2")]);
         assert_eq!(
-            i32::deserialize(PathDeserializer::new(&url_params)).unwrap_err(),
+            i32::deserialize(PathDeserializer::new(url_params.as_ref())).unwrap_err(),
             PathDeserializerError::custom("wrong number of parameters: 2 expected 1".to_string())
         );
     }
@@ -620,26 +630,26 @@ mod tests {
     fn test_parse_seq() {
         let url_params = create_url_params(vec![("a", "1"), ("b", "true"), ("c", "abc")]);
         assert_eq!(
-            <(i32, bool, String)>::deserialize(PathDeserializer::new(&url_params)).unwrap(),
+            <(i32, bool, String)>::deserialize(PathDeserializer::new(url_params.as_ref())).unwrap(),
             (1, true, "abc".to_string())
         );
 
         #[derive(Debug, Deserialize, Eq, PartialEq)]
         struct TupleStruct(i32, bool, String);
         assert_eq!(
-            TupleStruct::deserialize(PathDeserializer::new(&url_params)).unwrap(),
+            TupleStruct::deserialize(PathDeserializer::new(url_params.as_ref())).unwrap(),
             TupleStruct(1, true, "abc".to_string())
         );
 
         let url_params = create_url_params(vec![("a", "1"), ("b", "2"), ("c", "3")]);
         assert_eq!(
-            <Vec<i32>>::deserialize(PathDeserializer::new(&url_params)).unwrap(),
+            <Vec<i32>>::deserialize(PathDeserializer::new(url_params.as_ref())).unwrap(),
             vec![1, 2, 3]
         );
 
         let url_params = create_url_params(vec![("a", "c"), ("a", "B")]);
         assert_eq!(
-            <Vec<MyEnum>>::deserialize(PathDeserializer::new(&url_params)).unwrap(),
+            <Vec<MyEnum>>::deserialize(PathDeserializer::new(url_params.as_ref())).unwrap(),
             vec![MyEnum::C, MyEnum::B]
         );
     }
@@ -648,7 +658,7 @@ mod tests {
     fn test_parse_struct() {
         let url_params = create_url_params(vec![("a", "1"), ("b", "true"), ("c", "abc")]);
         assert_eq!(
-            Struct::deserialize(PathDeserializer::new(&url_params)).unwrap(),
+            Struct::deserialize(PathDeserializer::new(url_params.as_ref())).unwrap(),
             Struct {
                 c: "abc".to_string(),
                 b: true,
@@ -661,11 +671,38 @@ mod tests {
     fn test_parse_map() {
         let url_params = create_url_params(vec![("a", "1"), ("b", "true"), ("c", "abc")]);
         assert_eq!(
-            <HashMap<String, String>>::deserialize(PathDeserializer::new(&url_params)).unwrap(),
+            <HashMap<String, String>>::deserialize(PathDeserializer::new(url_params.as_ref()))
+                .unwrap(),
             [("a", "1"), ("b", "true"), ("c", "abc")]
                 .iter()
                 .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
                 .collect()
         );
     }
+
+    #[test]
+    fn test_parse_borrowed() {
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        struct BorrowedStruct<'a> {
+            a: &'a str,
+            #[serde(borrow)]
+            b: Cow<'a, str>,
+        }
+
+        let url_params = create_url_params(vec![("value", "abc")]);
+        assert_eq!(
+            <&str>::deserialize(PathDeserializer::new(url_params.as_ref())).unwrap(),
+            "abc"
+        );
+
+        let url_params = create_url_params(vec![("a", "abc"), ("b", "def")]);
+        let value =
+            BorrowedStruct::deserialize(PathDeserializer::new(url_params.as_ref())).unwrap();
+        assert_eq!(value.a, "abc");
+        assert!(matches!(value.b, Cow::Borrowed("def")));
+
+        let value =
+            <(&str, &str)>::deserialize(PathDeserializer::new(url_params.as_ref())).unwrap();
+        assert_eq!(value, ("abc", "def"));
+    }
 }
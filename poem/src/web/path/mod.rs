@@ -3,7 +3,7 @@ mod de;
 use std::ops::{Deref, DerefMut};
 
 pub(crate) use de::PathDeserializer;
-use serde::de::DeserializeOwned;
+use serde::de::Deserialize;
 
 use crate::{error::ParsePathError, FromRequest, Request, RequestBody, Result};
 
@@ -66,6 +66,27 @@ use crate::{error::ParsePathError, FromRequest, Request, RequestBody, Result};
 /// # });
 /// ```
 ///
+/// Parameters can also be borrowed from the request as `&str` or
+/// `Cow<str>`, which avoids allocating a `String` for each of them.
+///
+/// ```
+/// use poem::{get, handler, test::TestClient, web::Path, Route};
+///
+/// #[handler]
+/// async fn user_info(Path(user_id): Path<&str>) -> String {
+///     user_id.to_uppercase()
+/// }
+///
+/// let app = Route::new().at("/users/:user_id", get(user_info));
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli.get("/users/abc").send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_text("ABC").await;
+/// # });
+/// ```
+///
 /// Path segments also can be deserialized into any type that implements [`serde::Deserialize`](https://docs.rs/serde/1.0.127/serde/trait.Deserialize.html).
 /// Path segment labels will be matched with struct field names.
 ///
@@ -116,16 +137,16 @@ impl<T> DerefMut for Path<T> {
     }
 }
 
-impl<T: DeserializeOwned> Path<T> {
-    async fn internal_from_request(req: &Request) -> Result<Self, ParsePathError> {
+impl<'a, T: Deserialize<'a>> Path<T> {
+    async fn internal_from_request(req: &'a Request) -> Result<Self, ParsePathError> {
         Ok(Path(
-            T::deserialize(de::PathDeserializer::new(&req.state().match_params))
+            T::deserialize(de::PathDeserializer::new(req.state().match_params.as_ref()))
                 .map_err(|_| ParsePathError)?,
         ))
     }
 }
 
-impl<'a, T: DeserializeOwned> FromRequest<'a> for Path<T> {
+impl<'a, T: Deserialize<'a>> FromRequest<'a> for Path<T> {
     async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
         Self::internal_from_request(req).await.map_err(Into::into)
     }
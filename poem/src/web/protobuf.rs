@@ -0,0 +1,208 @@
+use std::ops::{Deref, DerefMut};
+
+use prost::Message;
+
+use crate::{
+    error::ParseProtobufError, http::header, web::RequestBody, FromRequest, IntoResponse, Request,
+    Response, Result,
+};
+
+/// Protocol Buffers extractor and response.
+///
+/// To extract the specified type of message from the body, `T` must implement
+/// [`prost::Message`] and [`Default`].
+///
+/// # Errors
+///
+/// - [`ReadBodyError`](crate::error::ReadBodyError)
+/// - [`ParseProtobufError`]
+///
+/// ```
+/// use poem::{handler, post, test::TestClient, web::Protobuf, Route};
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// struct User {
+///     #[prost(string, tag = "1")]
+///     name: String,
+/// }
+///
+/// #[handler]
+/// async fn index(Protobuf(user): Protobuf<User>) -> String {
+///     format!("welcome {}!", user.name)
+/// }
+///
+/// let app = Route::new().at("/", post(index));
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli
+///     .post("/")
+///     .body_protobuf(&User {
+///         name: "foo".to_string(),
+///     })
+///     .send()
+///     .await;
+/// resp.assert_status_is_ok();
+/// resp.assert_text("welcome foo!").await;
+/// # });
+/// ```
+///
+/// # Response
+///
+/// To encode the specified type, `T` must implement [`prost::Message`].
+///
+/// ```
+/// use poem::{get, handler, test::TestClient, web::Protobuf, Route};
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// struct User {
+///     #[prost(string, tag = "1")]
+///     name: String,
+/// }
+///
+/// #[handler]
+/// async fn index() -> Protobuf<User> {
+///     Protobuf(User {
+///         name: "foo".to_string(),
+///     })
+/// }
+///
+/// let app = Route::new().at("/", get(index));
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli.get("/").send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_content_type("application/protobuf");
+/// # });
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct Protobuf<T>(pub T);
+
+impl<T> Deref for Protobuf<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Protobuf<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'a, T: Message + Default> FromRequest<'a> for Protobuf<T> {
+    async fn from_request(req: &'a Request, body: &mut RequestBody) -> Result<Self> {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .ok_or(ParseProtobufError::ContentTypeRequired)?;
+        if !is_protobuf_content_type(content_type) {
+            return Err(ParseProtobufError::InvalidContentType(content_type.into()).into());
+        }
+
+        let data = body.take()?.into_bytes().await?;
+        Ok(Self(T::decode(data).map_err(ParseProtobufError::Parse)?))
+    }
+}
+
+fn is_protobuf_content_type(content_type: &str) -> bool {
+    matches!(content_type.parse::<mime::Mime>(),
+        Ok(content_type) if content_type.type_() == "application"
+        && (matches!(content_type.subtype().as_str(), "protobuf" | "x-protobuf")
+        || content_type
+            .suffix()
+            .is_some_and(|v| v == "proto" || v == "protobuf")))
+}
+
+impl<T: Message> IntoResponse for Protobuf<T> {
+    fn into_response(self) -> Response {
+        Response::builder()
+            .header(header::CONTENT_TYPE, "application/protobuf")
+            .body(self.0.encode_to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+    use crate::{handler, test::TestClient};
+
+    #[derive(Clone, PartialEq, Message)]
+    struct CreateResource {
+        #[prost(string, tag = "1")]
+        name: String,
+        #[prost(int32, tag = "2")]
+        value: i32,
+    }
+
+    #[tokio::test]
+    async fn protobuf_extractor() {
+        #[handler(internal)]
+        async fn index(query: Protobuf<CreateResource>) {
+            assert_eq!(query.name, "abc");
+            assert_eq!(query.value, 100);
+        }
+
+        let cli = TestClient::new(index);
+        let resource = CreateResource {
+            name: "abc".to_string(),
+            value: 100,
+        };
+        cli.post("/")
+            .body_protobuf(&resource)
+            .send()
+            .await
+            .assert_status_is_ok();
+
+        cli.post("/")
+            .content_type("application/x-protobuf")
+            .body(resource.encode_to_vec())
+            .send()
+            .await
+            .assert_status_is_ok();
+
+        cli.post("/")
+            .content_type("application/octet-stream")
+            .body(resource.encode_to_vec())
+            .send()
+            .await
+            .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        cli.post("/")
+            .content_type("application/protobuf")
+            .body("abc")
+            .send()
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn protobuf_response() {
+        #[handler(internal)]
+        async fn index() -> Protobuf<CreateResource> {
+            Protobuf(CreateResource {
+                name: "abc".to_string(),
+                value: 100,
+            })
+        }
+
+        let cli = TestClient::new(index);
+        let resp = cli.get("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_content_type("application/protobuf");
+        let data = resp.0.into_body().into_bytes().await.unwrap();
+        assert_eq!(
+            CreateResource::decode(data).unwrap(),
+            CreateResource {
+                name: "abc".to_string(),
+                value: 100,
+            }
+        );
+    }
+}
@@ -66,7 +66,7 @@ impl<T> DerefMut for Query<T> {
 
 impl<T: DeserializeOwned> Query<T> {
     async fn internal_from_request(req: &Request) -> Result<Self, ParseQueryError> {
-        Ok(serde_urlencoded::from_str(req.uri().query().unwrap_or_default()).map(Self)?)
+        req.query().deserialize().map(Self)
     }
 }
 
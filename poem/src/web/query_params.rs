@@ -0,0 +1,266 @@
+use serde::{
+    de::{self, value::MapDeserializer, Error as _, IntoDeserializer},
+    forward_to_deserialize_any, Deserialize,
+};
+
+use crate::error::ParseQueryError;
+
+/// The parsed query string of a request.
+///
+/// It is parsed once per request, when it is first accessed with
+/// [`Request::query`](crate::Request::query), and shared by all the
+/// extractors using the query string.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct QueryParams {
+    source: String,
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryParams {
+    pub(crate) fn parse(query: &str) -> Self {
+        Self {
+            source: query.to_string(),
+            pairs: serde_urlencoded::from_str(query).unwrap_or_default(),
+        }
+    }
+
+    pub(crate) fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the first value with the specified name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.get_all(name).next()
+    }
+
+    /// Returns all values with the specified name.
+    pub fn get_all<'a, 'b: 'a>(&'b self, name: &'a str) -> impl Iterator<Item = &'b str> + 'a {
+        self.iter()
+            .filter(move |(n, _)| *n == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns `true` if there is a parameter with the specified name.
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Returns an iterator over all parameters, in the order they appear in
+    /// the query string.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Returns the number of parameters.
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Returns `true` if there are no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Deserialize the parameters as `T`, with the same rules as
+    /// `serde_urlencoded`.
+    pub fn deserialize<'a, T: Deserialize<'a>>(&'a self) -> Result<T, ParseQueryError> {
+        Ok(T::deserialize(QueryDeserializer {
+            inner: MapDeserializer::new(self.iter().map(|(name, value)| (Part(name), Part(value)))),
+        })?)
+    }
+}
+
+type Error = de::value::Error;
+
+struct QueryDeserializer<'de, I: Iterator<Item = (Part<'de>, Part<'de>)>> {
+    inner: MapDeserializer<'de, I, Error>,
+}
+
+impl<'de, I: Iterator<Item = (Part<'de>, Part<'de>)>> de::Deserializer<'de>
+    for QueryDeserializer<'de, I>
+{
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(self.inner)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(self.inner)
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.inner.end()?;
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string option bytes
+        byte_buf unit_struct newtype_struct tuple_struct struct identifier tuple
+        enum ignored_any
+    }
+}
+
+struct Part<'de>(&'de str);
+
+impl<'de> IntoDeserializer<'de, Error> for Part<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! forward_parsed_value {
+    ($($ty:ident => $method:ident,)*) => {
+        $(
+            fn $method<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self.0.parse::<$ty>() {
+                    Ok(value) => value.into_deserializer().$method(visitor),
+                    Err(err) => Err(Error::custom(err)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Part<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_str(self.0)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        char str string unit bytes byte_buf unit_struct tuple_struct struct
+        identifier tuple ignored_any seq map
+    }
+
+    forward_parsed_value! {
+        bool => deserialize_bool,
+        u8 => deserialize_u8,
+        u16 => deserialize_u16,
+        u32 => deserialize_u32,
+        u64 => deserialize_u64,
+        i8 => deserialize_i8,
+        i16 => deserialize_i16,
+        i32 => deserialize_i32,
+        i64 => deserialize_i64,
+        f32 => deserialize_f32,
+        f64 => deserialize_f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::Request;
+
+    #[test]
+    fn query_params() {
+        let params = QueryParams::parse("a=1&b=hello%20world&a=2&c");
+        assert_eq!(params.len(), 4);
+        assert_eq!(params.get("a"), Some("1"));
+        assert_eq!(params.get_all("a").collect::<Vec<_>>(), vec!["1", "2"]);
+        assert_eq!(params.get("b"), Some("hello world"));
+        assert_eq!(params.get("c"), Some(""));
+        assert!(!params.contains_key("d"));
+    }
+
+    #[test]
+    fn request_query() {
+        let mut req = Request::builder().uri_str("/a?x=1").finish();
+        assert_eq!(req.query().get("x"), Some("1"));
+
+        req.set_uri("/b?x=1".parse().unwrap());
+        assert_eq!(req.query().get("x"), Some("1"));
+
+        *req.uri_mut() = "/b?x=2".parse().unwrap();
+        assert_eq!(req.query().get("x"), Some("2"));
+
+        let (mut parts, body) = req.into_parts();
+        parts.uri = "/b?x=3".parse().unwrap();
+        let req = Request::from_parts(parts, body);
+        assert_eq!(req.query().get("x"), Some("3"));
+    }
+
+    #[test]
+    fn deserialize() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(rename_all = "lowercase")]
+        enum Kind {
+            Foo,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Params<'a> {
+            a: i32,
+            b: Option<bool>,
+            c: &'a str,
+            kind: Kind,
+            d: Option<String>,
+        }
+
+        let params = QueryParams::parse("a=1&b=true&c=abc&kind=foo");
+        assert_eq!(
+            params.deserialize::<Params>().unwrap(),
+            Params {
+                a: 1,
+                b: Some(true),
+                c: "abc",
+                kind: Kind::Foo,
+                d: None,
+            }
+        );
+        assert!(QueryParams::parse("a=x&c=abc&kind=foo")
+            .deserialize::<Params>()
+            .is_err());
+
+        assert_eq!(
+            QueryParams::parse("a=1&a=2")
+                .deserialize::<Vec<(String, String)>>()
+                .unwrap(),
+            vec![
+                ("a".to_string(), "1".to_string()),
+                ("a".to_string(), "2".to_string())
+            ]
+        );
+        assert_eq!(
+            QueryParams::parse("x=1")
+                .deserialize::<HashMap<String, i32>>()
+                .unwrap(),
+            HashMap::from([("x".to_string(), 1)])
+        );
+    }
+}
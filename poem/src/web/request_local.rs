@@ -0,0 +1,170 @@
+use std::{ops::Deref, sync::Arc};
+
+use parking_lot::Mutex;
+
+use crate::{error::GetLocalError, http::Extensions, FromRequest, Request, RequestBody, Result};
+
+/// A store for the values computed while processing a request, such as the
+/// authenticated principal, the tenant or the locale.
+///
+/// Unlike the extensions of the request, which also carry the application
+/// wide [`Data`](crate::web::Data), this store only contains the values
+/// inserted for the current request. It uses interior mutability, so the
+/// values can be inserted through a shared reference, and cloning it returns
+/// a handle to the same store.
+///
+/// # Example
+///
+/// ```
+/// use poem::{handler, test::TestClient, web::Local, EndpointExt, Request, Route};
+///
+/// #[derive(Clone)]
+/// struct Tenant(String);
+///
+/// #[handler]
+/// fn index(Local(Tenant(tenant)): Local<Tenant>) -> String {
+///     tenant
+/// }
+///
+/// let app = Route::new()
+///     .at("/", index)
+///     .before(|req: Request| async move {
+///         if let Some(tenant) = req.header("x-tenant") {
+///             req.local().insert(Tenant(tenant.to_string()));
+///         }
+///         Ok(req)
+///     });
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli.get("/").header("x-tenant", "acme").send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_text("acme").await;
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct RequestLocal(Arc<Mutex<Extensions>>);
+
+impl RequestLocal {
+    /// Inserts a value into the store, returns the previous value of the
+    /// same type.
+    pub fn insert<T: Clone + Send + Sync + 'static>(&self, value: T) -> Option<T> {
+        self.0.lock().insert(value)
+    }
+
+    /// Returns a clone of the value of type `T`.
+    pub fn get<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        self.0.lock().get::<T>().cloned()
+    }
+
+    /// Returns the value of type `T`, inserts the value returned by `f` if it
+    /// does not exist.
+    pub fn get_or_insert_with<T, F>(&self, f: F) -> T
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> T,
+    {
+        self.0.lock().get_or_insert_with(f).clone()
+    }
+
+    /// Returns `true` if the store contains a value of type `T`.
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.0.lock().get::<T>().is_some()
+    }
+
+    /// Removes the value of type `T` from the store.
+    pub fn remove<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        self.0.lock().remove::<T>()
+    }
+}
+
+impl<'a> FromRequest<'a> for RequestLocal {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        Ok(req.local().clone())
+    }
+}
+
+/// An extractor that clones a value from the [`RequestLocal`] store.
+///
+/// # Errors
+///
+/// - [`GetLocalError`]
+pub struct Local<T>(pub T);
+
+impl<T> Deref for Local<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, T: Clone + Send + Sync + 'static> FromRequest<'a> for Local<T> {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        Ok(Local(req.local().get::<T>().ok_or_else(|| {
+            GetLocalError(std::any::type_name::<T>())
+        })?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+    use crate::{handler, test::TestClient, Endpoint, EndpointExt};
+
+    #[test]
+    fn store() {
+        let local = RequestLocal::default();
+        assert!(!local.contains::<i32>());
+        assert_eq!(local.insert(1i32), None);
+        assert_eq!(local.clone().insert(2i32), Some(1));
+        assert_eq!(local.get::<i32>(), Some(2));
+        assert_eq!(local.get_or_insert_with(|| 3i64), 3);
+        assert_eq!(local.get_or_insert_with(|| 4i64), 3);
+        assert_eq!(local.remove::<i32>(), Some(2));
+        assert!(!local.contains::<i32>());
+    }
+
+    #[tokio::test]
+    async fn extractor() {
+        #[handler(internal)]
+        fn index(value: Local<i32>, local: RequestLocal) -> String {
+            local.insert(value.0 + 1);
+            value.0.to_string()
+        }
+
+        let ep = index.around(|ep, req| async move {
+            let local = req.local().clone();
+            if req.header("x-value").is_some() {
+                local.insert(10i32);
+            }
+            let resp = ep.call(req).await?;
+            assert_eq!(local.get::<i32>(), Some(11));
+            Ok(resp)
+        });
+        let cli = TestClient::new(ep);
+
+        cli.get("/")
+            .header("x-value", "1")
+            .send()
+            .await
+            .assert_text("10")
+            .await;
+
+        let cli = TestClient::new(index);
+        cli.get("/")
+            .send()
+            .await
+            .assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn isolated_from_data() {
+        let req = Request::builder().extension(1i32).finish();
+        assert_eq!(req.local().get::<i32>(), None);
+        req.local().insert(2i32);
+        assert_eq!(req.data::<i32>(), Some(&1));
+    }
+}
@@ -5,10 +5,14 @@ use futures_util::{stream::BoxStream, Stream, StreamExt};
 use tokio::time::Duration;
 
 use super::Event;
-use crate::{Body, IntoResponse, Response};
+use crate::{web::TrackedResponse, Body, IntoResponse, Response};
 
 /// An SSE response.
 ///
+/// When served by the [`Server`](crate::Server), the response is tracked by
+/// the graceful shutdown and the event stream ends when the graceful shutdown
+/// is requested, see [`GracefulShutdown`](crate::web::GracefulShutdown).
+///
 /// # Example
 ///
 /// ```
@@ -85,6 +89,7 @@ impl IntoResponse for SSE {
             .content_type("text/event-stream")
             .header("X-Accel-Buffering", "no")
             .header("Cache-Control", "no-cache")
+            .extension(TrackedResponse)
             .body(Body::from_async_read(tokio_util::io::StreamReader::new(
                 stream,
             )))
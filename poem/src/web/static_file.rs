@@ -1,7 +1,7 @@
 use std::{
     collections::Bound,
     fs::Metadata,
-    io::{Seek, SeekFrom},
+    io::{Read, Seek, SeekFrom},
     path::Path,
     str::FromStr,
     time::{SystemTime, UNIX_EPOCH},
@@ -260,9 +260,9 @@ impl StaticFileRequest {
 
             content_length = end - start;
             file.seek(SeekFrom::Start(start))?;
-            Body::from_async_read(File::from_std(file).take(end - start))
+            file_body(file, content_length)?
         } else {
-            Body::from_async_read(File::from_std(file))
+            file_body(file, content_length)?
         };
 
         Ok(StaticFileResponse::Ok {
@@ -284,6 +284,30 @@ impl StaticFileRequest {
     }
 }
 
+/// The size of the chunks read from the files, each read of a `tokio` file
+/// is a blocking task, so the small [`Body::DEFAULT_CHUNK_SIZE`] makes large
+/// files stream slowly.
+const FILE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Creates the body with `len` bytes from the current position of the file.
+///
+/// The files no larger than one chunk are read into memory, the larger ones
+/// are streamed with a buffer of [`FILE_CHUNK_SIZE`].
+fn file_body(mut file: std::fs::File, len: u64) -> std::io::Result<Body> {
+    if len <= FILE_CHUNK_SIZE as u64 {
+        let mut data = Vec::with_capacity(len as usize);
+        (&mut file).take(len).read_to_end(&mut data)?;
+        return Ok(Body::from_vec(data));
+    }
+
+    let mut file = File::from_std(file);
+    file.set_max_buf_size(FILE_CHUNK_SIZE);
+    Ok(Body::from_async_read_with_chunk_size(
+        file.take(len),
+        FILE_CHUNK_SIZE,
+    ))
+}
+
 fn equiv_utf8_text(ct: Mime) -> Mime {
     if ct == mime::APPLICATION_JAVASCRIPT {
         return mime::APPLICATION_JAVASCRIPT_UTF_8;
@@ -560,4 +584,52 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_large_file() {
+        let data = (0..FILE_CHUNK_SIZE * 3 + 100)
+            .map(|i| (i % 251) as u8)
+            .collect::<Vec<_>>();
+        let path = std::env::temp_dir().join(format!("poem-static-file-{}", std::process::id()));
+        std::fs::write(&path, &data).unwrap();
+
+        let static_file = StaticFileRequest::from_request_without_body(&Request::default())
+            .await
+            .unwrap();
+        match static_file.create_response(&path, false).unwrap() {
+            StaticFileResponse::Ok {
+                body,
+                content_length,
+                ..
+            } => {
+                assert_eq!(content_length, data.len() as u64);
+                assert_eq!(body.into_vec().await.unwrap(), data);
+            }
+            StaticFileResponse::NotModified => panic!(),
+        }
+
+        let static_file = StaticFileRequest::from_request_without_body(
+            &Request::builder()
+                .typed_header(Range::bytes(100..(FILE_CHUNK_SIZE * 2) as u64).unwrap())
+                .finish(),
+        )
+        .await
+        .unwrap();
+        match static_file.create_response(&path, false).unwrap() {
+            StaticFileResponse::Ok {
+                body,
+                content_length,
+                ..
+            } => {
+                assert_eq!(content_length, (FILE_CHUNK_SIZE * 2 - 100) as u64);
+                assert_eq!(
+                    body.into_vec().await.unwrap(),
+                    &data[100..FILE_CHUNK_SIZE * 2]
+                );
+            }
+            StaticFileResponse::NotModified => panic!(),
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
 }
@@ -0,0 +1,157 @@
+use std::ops::Deref;
+
+use http::header;
+
+use crate::{error::SubdomainError, FromRequest, Request, RequestBody, Result};
+
+/// The base domain used by the [`Subdomain`] extractor, which must be added
+/// to the endpoint with [`EndpointExt::data`](crate::EndpointExt::data).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BaseDomain(String);
+
+impl BaseDomain {
+    /// Create a `BaseDomain`.
+    pub fn new(domain: impl AsRef<str>) -> Self {
+        Self(domain.as_ref().trim_matches('.').to_ascii_lowercase())
+    }
+}
+
+/// An extractor that extracts the subdomain from the `Host` header of the
+/// request, relative to the configured [`BaseDomain`].
+///
+/// For example, with the base domain `app.example.com`, the subdomain of
+/// `tenant1.app.example.com` is `tenant1`, and the subdomain of
+/// `eu.tenant1.app.example.com` is `eu.tenant1`.
+///
+/// # Errors
+///
+/// - [`SubdomainError`]
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     get, handler,
+///     http::{header, StatusCode},
+///     test::TestClient,
+///     web::{BaseDomain, Subdomain},
+///     EndpointExt, Route,
+/// };
+///
+/// #[handler]
+/// fn index(subdomain: Subdomain) -> String {
+///     format!("tenant: {}", subdomain.0)
+/// }
+///
+/// let app = Route::new()
+///     .at("/", get(index))
+///     .data(BaseDomain::new("app.example.com"));
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli
+///     .get("/")
+///     .header(header::HOST, "tenant1.app.example.com")
+///     .send()
+///     .await;
+/// resp.assert_status_is_ok();
+/// resp.assert_text("tenant: tenant1").await;
+///
+/// let resp = cli
+///     .get("/")
+///     .header(header::HOST, "app.example.com")
+///     .send()
+///     .await;
+/// resp.assert_status(StatusCode::NOT_FOUND);
+/// # });
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Subdomain(pub String);
+
+impl Subdomain {
+    /// Returns the labels of the subdomain, from left to right.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.0.split('.')
+    }
+}
+
+impl Deref for Subdomain {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> FromRequest<'a> for Subdomain {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        let base_domain = req
+            .data::<BaseDomain>()
+            .ok_or(SubdomainError::BaseDomainNotConfigured)?;
+        let host = req
+            .headers()
+            .get(header::HOST)
+            .and_then(|value| value.to_str().ok())
+            .or_else(|| req.uri().host())
+            .ok_or(SubdomainError::MissingHost)?;
+        let host = match host.rsplit_once(':') {
+            Some((host, port)) if port.bytes().all(|b| b.is_ascii_digit()) => host,
+            _ => host,
+        };
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+
+        host.strip_suffix(base_domain.0.as_str())
+            .and_then(|prefix| prefix.strip_suffix('.'))
+            .filter(|subdomain| !subdomain.is_empty())
+            .map(|subdomain| Subdomain(subdomain.to_string()))
+            .ok_or_else(|| SubdomainError::NoSubdomain.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+    use crate::{handler, test::TestClient, EndpointExt};
+
+    #[tokio::test]
+    async fn subdomain() {
+        #[handler(internal)]
+        fn index(subdomain: Subdomain) -> String {
+            subdomain.labels().collect::<Vec<_>>().join(",")
+        }
+
+        let cli = TestClient::new(index.data(BaseDomain::new(".App.Example.com")));
+
+        for (host, labels) in [
+            ("tenant1.app.example.com", "tenant1"),
+            ("Tenant1.App.Example.com:8080", "tenant1"),
+            ("eu.tenant1.app.example.com.", "eu,tenant1"),
+        ] {
+            let resp = cli.get("/").header(header::HOST, host).send().await;
+            resp.assert_status_is_ok();
+            resp.assert_text(labels).await;
+        }
+
+        for host in ["app.example.com", "example.com", "tenant1.xapp.example.com"] {
+            cli.get("/")
+                .header(header::HOST, host)
+                .send()
+                .await
+                .assert_status(StatusCode::NOT_FOUND);
+        }
+
+        cli.get("/")
+            .send()
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        TestClient::new(index)
+            .get("/")
+            .header(header::HOST, "tenant1.app.example.com")
+            .send()
+            .await
+            .assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -11,11 +11,15 @@ use crate::{
         header::{self, HeaderValue},
         Method, StatusCode,
     },
+    web::GracefulShutdown,
     Body, FromRequest, IntoResponse, OnUpgrade, Request, RequestBody, Response, Result,
 };
 
 /// An extractor that can accept websocket connections.
 ///
+/// The upgraded connections are tracked by the graceful shutdown of the
+/// server, see [`GracefulShutdown`].
+///
 /// # Errors
 ///
 /// - [`WebSocketError`]
@@ -24,6 +28,7 @@ pub struct WebSocket {
     on_upgrade: OnUpgrade,
     protocols: Option<Box<[Cow<'static, str>]>>,
     sec_websocket_protocol: Option<HeaderValue>,
+    shutdown: Option<GracefulShutdown>,
 }
 
 impl WebSocket {
@@ -62,6 +67,7 @@ impl WebSocket {
             on_upgrade: req.take_upgrade()?,
             protocols: None,
             sec_websocket_protocol,
+            shutdown: req.data::<GracefulShutdown>().cloned(),
         })
     }
 }
@@ -172,6 +178,7 @@ where
                     .split(',')
                     .map(|req_p| req_p.trim())
                     .find(|req_p| protocols.iter().any(|p| p == req_p))
+                    .map(ToString::to_string)
             });
 
         let mut builder = Response::builder()
@@ -183,7 +190,7 @@ where
                 sign(self.websocket.key.as_bytes()),
             );
 
-        if let Some(protocol) = protocol {
+        if let Some(protocol) = &protocol {
             builder = builder.header(
                 header::SEC_WEBSOCKET_PROTOCOL,
                 HeaderValue::from_str(protocol).unwrap(),
@@ -193,6 +200,11 @@ where
         let resp = builder.body(Body::empty());
 
         tokio::spawn(async move {
+            let _guard = self
+                .websocket
+                .shutdown
+                .as_ref()
+                .map(GracefulShutdown::track);
             let upgraded = match self.websocket.on_upgrade.await {
                 Ok(upgraded) => upgraded,
                 Err(_) => return,
@@ -201,7 +213,8 @@ where
             let stream =
                 tokio_tungstenite::WebSocketStream::from_raw_socket(upgraded, Role::Server, None)
                     .await;
-            (self.callback)(WebSocketStream::new(stream)).await;
+            let stream = WebSocketStream::new(stream, protocol, self.websocket.shutdown.as_ref());
+            (self.callback)(stream).await;
         });
 
         resp
@@ -25,11 +25,13 @@
 mod extractor;
 mod message;
 mod stream;
+mod typed;
 mod utils;
 
 pub use extractor::{BoxWebSocketUpgraded, WebSocket, WebSocketUpgraded};
 pub use message::{CloseCode, Message};
 pub use stream::WebSocketStream;
+pub use typed::{TypedWebSocketError, TypedWebSocketStream, WebSocketCodec};
 
 #[cfg(test)]
 mod tests {
@@ -37,6 +39,7 @@ mod tests {
 
     use futures_util::{SinkExt, StreamExt};
     use http::{header, HeaderValue};
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 
     use super::*;
     use crate::{
@@ -160,4 +163,137 @@ mod tests {
 
         handle.abort();
     }
+
+    #[tokio::test]
+    async fn test_typed_websocket() {
+        #[handler(internal)]
+        async fn index(ws: WebSocket) -> impl IntoResponse {
+            ws.protocols(["calc+json"]).on_upgrade(|socket| async move {
+                assert_eq!(socket.protocol(), Some("calc+json"));
+                let mut socket = socket.typed::<Vec<i32>, i32>();
+                assert_eq!(socket.codec(), WebSocketCodec::Json);
+                while let Some(Ok(numbers)) = socket.recv().await {
+                    if socket.send(&numbers.iter().sum()).await.is_err() {
+                        break;
+                    }
+                }
+            })
+        }
+
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = acceptor
+            .local_addr()
+            .remove(0)
+            .as_socket_addr()
+            .cloned()
+            .unwrap();
+        let server = Server::new_with_acceptor(acceptor);
+
+        let handle = tokio::spawn(async move {
+            let _ = server.run(index).await;
+        });
+
+        let mut request = format!("ws://{addr}").into_client_request().unwrap();
+        request.headers_mut().insert(
+            header::SEC_WEBSOCKET_PROTOCOL,
+            HeaderValue::from_static("calc+json"),
+        );
+        let (mut client_stream, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+
+        client_stream
+            .send(tokio_tungstenite::tungstenite::Message::Text(
+                "[1, 2, 3]".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            client_stream.next().await.unwrap().unwrap(),
+            tokio_tungstenite::tungstenite::Message::Text("6".to_string())
+        );
+
+        client_stream
+            .send(tokio_tungstenite::tungstenite::Message::Text(
+                "not json".to_string(),
+            ))
+            .await
+            .unwrap();
+        match client_stream.next().await.unwrap().unwrap() {
+            tokio_tungstenite::tungstenite::Message::Close(Some(frame)) => {
+                assert_eq!(u16::from(frame.code), 1007);
+            }
+            msg => panic!("unexpected message: {msg:?}"),
+        }
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_graceful_shutdown() {
+        #[handler(internal)]
+        async fn index(ws: WebSocket) -> impl IntoResponse {
+            ws.on_upgrade(|mut stream| async move {
+                while let Some(Ok(msg)) = stream.next().await {
+                    if let Message::Text(text) = msg {
+                        if stream.send(Message::Text(text)).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            })
+        }
+
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = acceptor
+            .local_addr()
+            .remove(0)
+            .as_socket_addr()
+            .cloned()
+            .unwrap();
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(
+            Server::new_with_acceptor(acceptor).run_with_graceful_shutdown(
+                index,
+                async move {
+                    let _ = rx.await;
+                },
+                None,
+            ),
+        );
+
+        let (mut client_stream, _) = tokio_tungstenite::connect_async(format!("ws://{addr}"))
+            .await
+            .unwrap();
+        client_stream
+            .send(tokio_tungstenite::tungstenite::Message::Text(
+                "abc".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            client_stream.next().await.unwrap().unwrap(),
+            tokio_tungstenite::tungstenite::Message::Text("abc".to_string())
+        );
+
+        tx.send(()).unwrap();
+        match client_stream.next().await.unwrap().unwrap() {
+            tokio_tungstenite::tungstenite::Message::Close(Some(frame)) => {
+                assert_eq!(u16::from(frame.code), 1001);
+            }
+            msg => panic!("unexpected message: {msg:?}"),
+        }
+        // the close handshake is completed by the client
+        assert!(client_stream.next().await.is_none());
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+    }
 }
@@ -1,23 +1,94 @@
 use std::{
+    future::Future,
     io::{Error as IoError, Result as IoResult},
     pin::Pin,
     task::{Context, Poll},
 };
 
 use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use tokio_util::sync::WaitForCancellationFutureOwned;
 
-use super::{utils::tungstenite_error_to_io_error, Message};
-use crate::Upgraded;
+use super::{
+    utils::tungstenite_error_to_io_error, CloseCode, Message, TypedWebSocketStream, WebSocketCodec,
+};
+use crate::{web::GracefulShutdown, Upgraded};
 
 /// A `WebSocket` stream, which implements [`Stream<Message>`] and
 /// [`Sink<Message>`].
+///
+/// When the graceful shutdown of the server is requested, a `Close` message
+/// with the [`CloseCode::Away`] code is sent the next time the stream is
+/// polled, and the stream ends when the client replies.
 pub struct WebSocketStream {
     inner: tokio_tungstenite::WebSocketStream<Upgraded>,
+    protocol: Option<String>,
+    shutdown: Option<Pin<Box<WaitForCancellationFutureOwned>>>,
+    send_close: bool,
 }
 
 impl WebSocketStream {
-    pub(crate) fn new(inner: tokio_tungstenite::WebSocketStream<Upgraded>) -> Self {
-        Self { inner }
+    pub(crate) fn new(
+        inner: tokio_tungstenite::WebSocketStream<Upgraded>,
+        protocol: Option<String>,
+        shutdown: Option<&GracefulShutdown>,
+    ) -> Self {
+        Self {
+            inner,
+            protocol,
+            shutdown: shutdown.map(|shutdown| Box::pin(shutdown.requested_owned())),
+            send_close: false,
+        }
+    }
+
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        if let Some(shutdown) = &mut self.shutdown {
+            if shutdown.as_mut().poll(cx).is_ready() {
+                self.shutdown = None;
+                self.send_close = true;
+            }
+        }
+
+        if self.send_close {
+            futures_util::ready!(self.inner.poll_ready_unpin(cx))
+                .map_err(tungstenite_error_to_io_error)?;
+            self.send_close = false;
+            self.inner
+                .start_send_unpin(
+                    Message::close_with(CloseCode::Away, "server is shutting down").into(),
+                )
+                .map_err(tungstenite_error_to_io_error)?;
+            // the message is flushed when the stream is polled
+            let _ = self.inner.poll_flush_unpin(cx);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Returns the subprotocol negotiated with
+    /// [`WebSocket::protocols`](super::WebSocket::protocols).
+    #[inline]
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+
+    /// Converts this stream into a [`TypedWebSocketStream`] which receives
+    /// messages of type `T` and sends messages of type `U`.
+    ///
+    /// The codec is selected by the negotiated subprotocol (see
+    /// [`WebSocketCodec::from_protocol`]), and defaults to
+    /// [`WebSocketCodec::Json`].
+    pub fn typed<T, U>(self) -> TypedWebSocketStream<T, U> {
+        let codec = self
+            .protocol()
+            .and_then(WebSocketCodec::from_protocol)
+            .unwrap_or_default();
+        TypedWebSocketStream::new(self, codec)
+    }
+
+    /// Converts this stream into a [`TypedWebSocketStream`] with the
+    /// specified codec.
+    pub fn typed_with<T, U>(self, codec: WebSocketCodec) -> TypedWebSocketStream<T, U> {
+        TypedWebSocketStream::new(self, codec)
     }
 }
 
@@ -25,6 +96,10 @@ impl Stream for WebSocketStream {
     type Item = IoResult<Message>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Err(err) = futures_util::ready!(self.poll_shutdown(cx)) {
+            return Poll::Ready(Some(Err(err)));
+        }
+
         match self.inner.poll_next_unpin(cx) {
             Poll::Ready(Some(Ok(msg))) => Poll::Ready(Some(Ok(msg.into()))),
             Poll::Ready(Some(Err(err))) => {
@@ -0,0 +1,306 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io::Error as IoError,
+    marker::PhantomData,
+};
+
+use bytes::BufMut;
+use futures_util::{SinkExt, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{CloseCode, Message, WebSocketStream};
+use crate::BufferPool;
+
+/// The codec used by [`TypedWebSocketStream`] to encode and decode messages.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum WebSocketCodec {
+    /// JSON, sent as text messages.
+    #[default]
+    Json,
+    /// MessagePack, sent as binary messages.
+    #[cfg(feature = "msgpack")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+    MsgPack,
+}
+
+impl WebSocketCodec {
+    /// Returns the codec for a subprotocol name, `json` or `msgpack`.
+    ///
+    /// A subprotocol with a suffix, such as `chat.v1+json`, is also
+    /// recognized.
+    pub fn from_protocol(protocol: &str) -> Option<Self> {
+        let name = protocol.rsplit(['+', '.']).next().unwrap_or(protocol);
+        match name {
+            "json" => Some(WebSocketCodec::Json),
+            #[cfg(feature = "msgpack")]
+            "msgpack" => Some(WebSocketCodec::MsgPack),
+            _ => None,
+        }
+    }
+
+    /// Returns the subprotocol name of this codec.
+    pub fn protocol(&self) -> &'static str {
+        match self {
+            WebSocketCodec::Json => "json",
+            #[cfg(feature = "msgpack")]
+            WebSocketCodec::MsgPack => "msgpack",
+        }
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Message, String> {
+        let mut buf = BufferPool::global().get();
+        match self {
+            WebSocketCodec::Json => {
+                serde_json::to_writer((&mut *buf).writer(), value)
+                    .map_err(|err| err.to_string())?;
+                String::from_utf8(buf.into_vec())
+                    .map(Message::Text)
+                    .map_err(|err| err.to_string())
+            }
+            #[cfg(feature = "msgpack")]
+            WebSocketCodec::MsgPack => {
+                rmp_serde::encode::write_named(&mut (&mut *buf).writer(), value)
+                    .map_err(|err| err.to_string())?;
+                Ok(Message::Binary(buf.into_vec()))
+            }
+        }
+    }
+
+    fn decode<T: DeserializeOwned>(&self, msg: &Message) -> Result<T, String> {
+        match (self, msg) {
+            (WebSocketCodec::Json, Message::Text(text)) => {
+                serde_json::from_str(text).map_err(|err| err.to_string())
+            }
+            (WebSocketCodec::Json, Message::Binary(data)) => {
+                serde_json::from_slice(data).map_err(|err| err.to_string())
+            }
+            #[cfg(feature = "msgpack")]
+            (WebSocketCodec::MsgPack, Message::Binary(data)) => {
+                rmp_serde::from_slice(data).map_err(|err| err.to_string())
+            }
+            _ => Err("unexpected message type".to_string()),
+        }
+    }
+}
+
+/// An error returned by [`TypedWebSocketStream`].
+#[derive(Debug)]
+pub enum TypedWebSocketError {
+    /// An IO error of the underlying stream.
+    Io(IoError),
+    /// The received message could not be decoded.
+    Decode(String),
+    /// The message could not be encoded.
+    Encode(String),
+}
+
+impl Display for TypedWebSocketError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypedWebSocketError::Io(err) => write!(f, "io error: {err}"),
+            TypedWebSocketError::Decode(err) => write!(f, "failed to decode message: {err}"),
+            TypedWebSocketError::Encode(err) => write!(f, "failed to encode message: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for TypedWebSocketError {}
+
+impl From<IoError> for TypedWebSocketError {
+    fn from(err: IoError) -> Self {
+        TypedWebSocketError::Io(err)
+    }
+}
+
+/// A typed layer over [`WebSocketStream`], which receives messages of type
+/// `T` and sends messages of type `U` with a [`WebSocketCodec`].
+///
+/// `Ping` and `Pong` messages are skipped, and a `Close` message ends the
+/// stream. By default, the connection is closed with
+/// [`CloseCode::Invalid`] when a received message cannot be decoded, see
+/// [`TypedWebSocketStream::close_on_decode_error`].
+///
+/// # Example
+///
+/// ```
+/// use poem::{handler, web::websocket::WebSocket, IntoResponse};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize)]
+/// #[serde(tag = "type")]
+/// enum ClientMessage {
+///     Join { room: String },
+///     Say { text: String },
+/// }
+///
+/// #[derive(Serialize)]
+/// #[serde(tag = "type")]
+/// enum ServerMessage {
+///     Joined { room: String },
+///     Said { text: String },
+/// }
+///
+/// #[handler]
+/// async fn chat(ws: WebSocket) -> impl IntoResponse {
+///     ws.protocols(["chat+json"]).on_upgrade(|socket| async move {
+///         let mut socket = socket.typed::<ClientMessage, ServerMessage>();
+///         while let Some(Ok(msg)) = socket.recv().await {
+///             let reply = match msg {
+///                 ClientMessage::Join { room } => ServerMessage::Joined { room },
+///                 ClientMessage::Say { text } => ServerMessage::Said { text },
+///             };
+///             if socket.send(&reply).await.is_err() {
+///                 break;
+///             }
+///         }
+///     })
+/// }
+/// ```
+pub struct TypedWebSocketStream<T, U> {
+    inner: WebSocketStream,
+    codec: WebSocketCodec,
+    close_on_decode_error: bool,
+    closed: bool,
+    _mark: PhantomData<fn(U) -> T>,
+}
+
+impl<T, U> TypedWebSocketStream<T, U> {
+    pub(crate) fn new(inner: WebSocketStream, codec: WebSocketCodec) -> Self {
+        Self {
+            inner,
+            codec,
+            close_on_decode_error: true,
+            closed: false,
+            _mark: PhantomData,
+        }
+    }
+
+    /// Specifies whether to close the connection when a received message
+    /// cannot be decoded. (defaults to `true`)
+    ///
+    /// If it is `false`, the decode error is returned and the next message
+    /// can still be received.
+    #[must_use]
+    pub fn close_on_decode_error(self, enable: bool) -> Self {
+        Self {
+            close_on_decode_error: enable,
+            ..self
+        }
+    }
+
+    /// Returns the codec of this stream.
+    #[inline]
+    pub fn codec(&self) -> WebSocketCodec {
+        self.codec
+    }
+
+    /// Returns the inner [`WebSocketStream`].
+    #[inline]
+    pub fn into_inner(self) -> WebSocketStream {
+        self.inner
+    }
+
+    /// Closes the connection with the specified code and reason.
+    pub async fn close(
+        &mut self,
+        code: CloseCode,
+        reason: impl Into<String>,
+    ) -> Result<(), IoError> {
+        self.closed = true;
+        self.inner.send(Message::close_with(code, reason)).await
+    }
+}
+
+impl<T: DeserializeOwned, U> TypedWebSocketStream<T, U> {
+    /// Receives the next message.
+    ///
+    /// Returns `None` if the connection is closed.
+    pub async fn recv(&mut self) -> Option<Result<T, TypedWebSocketError>> {
+        if self.closed {
+            return None;
+        }
+
+        loop {
+            let msg = match self.inner.next().await? {
+                Ok(msg) => msg,
+                Err(err) => return Some(Err(err.into())),
+            };
+            match msg {
+                Message::Text(_) | Message::Binary(_) => {
+                    return match self.codec.decode(&msg) {
+                        Ok(value) => Some(Ok(value)),
+                        Err(err) => {
+                            if self.close_on_decode_error {
+                                // the reason of a close frame is limited to 123 bytes
+                                let mut reason = err.clone();
+                                let mut len = reason.len().min(123);
+                                while !reason.is_char_boundary(len) {
+                                    len -= 1;
+                                }
+                                reason.truncate(len);
+                                let _ = self.close(CloseCode::Invalid, reason).await;
+                            }
+                            Some(Err(TypedWebSocketError::Decode(err)))
+                        }
+                    };
+                }
+                Message::Ping(_) | Message::Pong(_) => continue,
+                Message::Close(_) => {
+                    self.closed = true;
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+impl<T, U: Serialize> TypedWebSocketStream<T, U> {
+    /// Sends a message.
+    pub async fn send(&mut self, value: &U) -> Result<(), TypedWebSocketError> {
+        let msg = self
+            .codec
+            .encode(value)
+            .map_err(TypedWebSocketError::Encode)?;
+        self.inner.send(msg).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_protocol() {
+        assert_eq!(
+            WebSocketCodec::from_protocol("json"),
+            Some(WebSocketCodec::Json)
+        );
+        assert_eq!(
+            WebSocketCodec::from_protocol("chat.v1+json"),
+            Some(WebSocketCodec::Json)
+        );
+        assert_eq!(WebSocketCodec::from_protocol("graphql-ws"), None);
+        #[cfg(feature = "msgpack")]
+        assert_eq!(
+            WebSocketCodec::from_protocol("chat+msgpack"),
+            Some(WebSocketCodec::MsgPack)
+        );
+    }
+
+    #[test]
+    fn decode() {
+        let codec = WebSocketCodec::Json;
+        assert_eq!(
+            codec.decode::<Vec<i32>>(&Message::Text("[1,2]".to_string())),
+            Ok(vec![1, 2])
+        );
+        assert!(codec
+            .decode::<Vec<i32>>(&Message::Text("{}".to_string()))
+            .is_err());
+        assert_eq!(
+            codec.encode(&vec![1, 2]),
+            Ok(Message::Text("[1,2]".to_string()))
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use futures_util::FutureExt;
+
+use crate::{Error, FromRequest, IntoResponse, Request, RequestBody, Result};
+
+/// An extractor that wraps another extractor `T`, and converts its error into
+/// the rejection `R`, so that a handler can respond with a custom error when
+/// a specific extractor fails.
+///
+/// The rejection is created from the original [`Error`] with
+/// `R::from(err)`, and is converted into the response with
+/// [`IntoResponse`].
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     get, handler,
+///     http::StatusCode,
+///     test::TestClient,
+///     web::{Json, Query, WithRejection},
+///     Error, IntoResponse, Response, Route,
+/// };
+/// use serde::Deserialize;
+///
+/// struct MyRejection(String);
+///
+/// impl From<Error> for MyRejection {
+///     fn from(err: Error) -> Self {
+///         Self(err.to_string())
+///     }
+/// }
+///
+/// impl IntoResponse for MyRejection {
+///     fn into_response(self) -> Response {
+///         (
+///             StatusCode::UNPROCESSABLE_ENTITY,
+///             Json(serde_json::json!({ "error": self.0 })),
+///         )
+///             .into_response()
+///     }
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Params {
+///     page: u32,
+/// }
+///
+/// #[handler]
+/// fn index(WithRejection(params, _): WithRejection<Query<Params>, MyRejection>) -> String {
+///     format!("page: {}", params.page)
+/// }
+///
+/// let app = Route::new().at("/", get(index));
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli.get("/").query("page", &1).send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_text("page: 1").await;
+///
+/// let resp = cli.get("/").send().await;
+/// resp.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+/// # });
+/// ```
+pub struct WithRejection<T, R>(pub T, pub PhantomData<R>);
+
+impl<T, R> WithRejection<T, R> {
+    /// Consumes this object and returns the inner extractor.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Debug, R> Debug for WithRejection<T, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WithRejection").field(&self.0).finish()
+    }
+}
+
+impl<T, R> Deref for WithRejection<T, R> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, R> DerefMut for WithRejection<T, R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'a, T, R> FromRequest<'a> for WithRejection<T, R>
+where
+    T: FromRequest<'a>,
+    R: From<Error> + IntoResponse,
+{
+    async fn from_request(req: &'a Request, body: &mut RequestBody) -> Result<Self> {
+        // FIXME: remove the unnecessary boxed
+        // https://github.com/rust-lang/rust/issues/100013
+        match T::from_request(req, body).boxed().await {
+            Ok(value) => Ok(Self(value, PhantomData)),
+            Err(err) => Err(Error::from_response(R::from(err).into_response())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+    use crate::{handler, test::TestClient, web::Path, Response, Route};
+
+    struct MyRejection(StatusCode);
+
+    impl From<Error> for MyRejection {
+        fn from(err: Error) -> Self {
+            Self(err.status())
+        }
+    }
+
+    impl IntoResponse for MyRejection {
+        fn into_response(self) -> Response {
+            (
+                StatusCode::IM_A_TEAPOT,
+                format!("rejected: {}", self.0.as_u16()),
+            )
+                .into_response()
+        }
+    }
+
+    #[tokio::test]
+    async fn with_rejection() {
+        #[handler(internal)]
+        fn index(id: WithRejection<Path<u32>, MyRejection>) -> String {
+            format!("id: {}", id.0 .0)
+        }
+
+        let cli = TestClient::new(Route::new().at("/:id", index));
+
+        let resp = cli.get("/10").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("id: 10").await;
+
+        let resp = cli.get("/abc").send().await;
+        resp.assert_status(StatusCode::IM_A_TEAPOT);
+        resp.assert_text("rejected: 400").await;
+    }
+}
@@ -8,7 +8,10 @@ use crate::{
     Response, Result,
 };
 
-/// JSON extractor and response.
+/// YAML extractor and response.
+///
+/// The `application/yaml`, `application/x-yaml`, `text/yaml` and `text/x-yaml`
+/// content types are accepted.
 ///
 /// To extract the specified type of YAML from the body, `T` must implement
 /// [`serde::Deserialize`].
@@ -127,8 +130,8 @@ impl<'a, T: DeserializeOwned> FromRequest<'a> for Yaml<T> {
 
 fn is_yaml_content_type(content_type: &str) -> bool {
     matches!(content_type.parse::<mime::Mime>(),
-        Ok(content_type) if content_type.type_() == "application"
-        && (content_type.subtype() == "yaml"
+        Ok(content_type) if matches!(content_type.type_().as_str(), "application" | "text")
+        && (matches!(content_type.subtype().as_str(), "yaml" | "x-yaml")
         || content_type
             .suffix()
             .map_or(false, |v| v == "yaml")))
@@ -181,6 +184,38 @@ mod tests {
             .await
             .assert_status_is_ok();
     }
+    #[tokio::test]
+    async fn test_yaml_extractor_content_types() {
+        #[handler(internal)]
+        async fn index(query: Yaml<CreateResource>) {
+            assert_eq!(query.name, "abc");
+            assert_eq!(query.value, 100);
+        }
+
+        let cli = TestClient::new(index);
+        for content_type in [
+            "application/yaml",
+            "application/x-yaml",
+            "text/yaml",
+            "text/x-yaml; charset=utf-8",
+            "application/vnd.config+yaml",
+        ] {
+            cli.post("/")
+                .content_type(content_type)
+                .body("name: abc\nvalue: 100\n")
+                .send()
+                .await
+                .assert_status_is_ok();
+        }
+
+        cli.post("/")
+            .content_type("text/plain")
+            .body("name: abc\nvalue: 100\n")
+            .send()
+            .await
+            .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
     #[tokio::test]
     async fn test_yaml_extractor_fail() {
         #[handler(internal)]